@@ -3,11 +3,15 @@ fn main() {
       "load",
       "execute",
       "execute_transaction",
+      "import_jsonl",
+      "table_exists",
+      "backup_database",
       "begin_interruptible_transaction",
       "transaction_continue",
       "transaction_read",
       "fetch_all",
       "fetch_one",
+      "fetch_scalar",
       "fetch_page",
       "close",
       "close_all",
@@ -16,6 +20,7 @@ fn main() {
       "observe",
       "subscribe",
       "unsubscribe",
+      "unsubscribe_all",
       "unobserve",
    ])
    .build();
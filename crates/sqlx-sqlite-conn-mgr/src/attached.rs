@@ -21,6 +21,29 @@ pub struct AttachedSpec {
    pub mode: AttachedMode,
 }
 
+impl AttachedSpec {
+   /// Create a spec, rejecting an invalid schema name up front
+   ///
+   /// Returns `Error::InvalidSchemaName` for names that attaching would reject
+   /// anyway, so the mistake surfaces where the spec is built rather than on
+   /// first use.
+   pub fn new(
+      database: Arc<SqliteDatabase>,
+      schema_name: impl Into<String>,
+      mode: AttachedMode,
+   ) -> Result<Self> {
+      let schema_name = schema_name.into();
+      if !is_valid_schema_name(&schema_name) {
+         return Err(Error::InvalidSchemaName(schema_name));
+      }
+      Ok(Self {
+         database,
+         schema_name,
+         mode,
+      })
+   }
+}
+
 /// Mode for attaching a database
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AttachedMode {
@@ -45,31 +68,35 @@ pub struct AttachedReadConnection {
    /// Locks are automatically released when this guard is dropped.
    #[allow(dead_code)]
    held_writers: Vec<WriteGuard>,
-   /// Schema names of attached databases, retained for debugging utility.
-   #[allow(dead_code)]
-   schema_names: Vec<String>,
+   /// Schema name and mode of each attached database
+   attached: Vec<(String, AttachedMode)>,
 }
 
 impl AttachedReadConnection {
    pub(crate) fn new(
       conn: PoolConnection<Sqlite>,
       held_writers: Vec<WriteGuard>,
-      schema_names: Vec<String>,
+      attached: Vec<(String, AttachedMode)>,
    ) -> Self {
       Self {
          conn,
          held_writers,
-         schema_names,
+         attached,
       }
    }
 
+   /// Mode the database attached as `schema` was attached with, if any.
+   pub fn attached_mode(&self, schema: &str) -> Option<AttachedMode> {
+      find_mode(&self.attached, schema)
+   }
+
    /// Explicitly detach all attached databases.
    ///
    /// This method should be called before dropping the connection to ensure
    /// attached databases are properly cleaned up. Without calling this,
    /// attached databases may persist when the connection is returned to the pool.
    pub async fn detach_all(mut self) -> Result<()> {
-      for schema_name in &self.schema_names {
+      for (schema_name, _) in &self.attached {
          let detach_sql = format!("DETACH DATABASE \"{}\"", schema_name);
          sqlx::query(&detach_sql).execute(&mut *self.conn).await?;
       }
@@ -115,31 +142,38 @@ pub struct AttachedWriteGuard {
    /// Locks are automatically released when this guard is dropped.
    #[allow(dead_code)]
    held_writers: Vec<WriteGuard>,
-   /// Schema names of attached databases, retained for debugging utility.
-   #[allow(dead_code)]
-   schema_names: Vec<String>,
+   /// Schema name and mode of each attached database
+   attached: Vec<(String, AttachedMode)>,
 }
 
 impl AttachedWriteGuard {
    pub(crate) fn new(
       writer: WriteGuard,
       held_writers: Vec<WriteGuard>,
-      schema_names: Vec<String>,
+      attached: Vec<(String, AttachedMode)>,
    ) -> Self {
       Self {
          writer,
          held_writers,
-         schema_names,
+         attached,
       }
    }
 
+   /// Mode the database attached as `schema` was attached with, if any.
+   ///
+   /// `ReadOnly` schemas reject writes; only `ReadWrite` schemas (whose writers
+   /// this guard holds) can be modified through it.
+   pub fn attached_mode(&self, schema: &str) -> Option<AttachedMode> {
+      find_mode(&self.attached, schema)
+   }
+
    /// Explicitly detach all attached databases.
    ///
    /// This method should be called before dropping the connection to ensure
    /// attached databases are properly cleaned up. Without calling this,
    /// attached databases may persist when the connection is returned to the pool.
    pub async fn detach_all(mut self) -> Result<()> {
-      for schema_name in &self.schema_names {
+      for (schema_name, _) in &self.attached {
          let detach_sql = format!("DETACH DATABASE \"{}\"", schema_name);
          sqlx::query(&detach_sql).execute(&mut *self.writer).await?;
       }
@@ -170,6 +204,38 @@ impl Drop for AttachedWriteGuard {
    }
 }
 
+fn find_mode(attached: &[(String, AttachedMode)], schema: &str) -> Option<AttachedMode> {
+   attached
+      .iter()
+      .find(|(name, _)| name == schema)
+      .map(|&(_, mode)| mode)
+}
+
+/// The filename to pass to `ATTACH DATABASE` for a spec on a writable connection
+///
+/// Without a flag, a database attached to the writer is writable. `ReadOnly`
+/// file databases are therefore attached through a `mode=ro` URI (connections
+/// are opened with URI filenames enabled) so writes to them fail with
+/// `SQLITE_READONLY`. In-memory databases are already named by a URI and are
+/// attached unchanged.
+fn writer_attach_target(path: &str, mode: AttachedMode) -> String {
+   if mode == AttachedMode::ReadWrite || path.starts_with("file:") || path == ":memory:" {
+      return path.to_string();
+   }
+
+   let mut uri = String::from("file:");
+   for c in path.chars() {
+      match c {
+         '%' => uri.push_str("%25"),
+         '?' => uri.push_str("%3f"),
+         '#' => uri.push_str("%23"),
+         _ => uri.push(c),
+      }
+   }
+   uri.push_str("?mode=ro");
+   uri
+}
+
 /// Validates that a schema name is a valid SQLite identifier
 ///
 /// A valid schema name:
@@ -235,7 +301,7 @@ pub async fn acquire_reader_with_attached(
       }
    }
 
-   let mut schema_names = Vec::new();
+   let mut attached = Vec::new();
 
    for spec in specs {
       // Validate schema name to prevent SQL injection
@@ -258,10 +324,10 @@ pub async fn acquire_reader_with_attached(
       );
       sqlx::query(&attach_sql).execute(&mut *conn).await?;
 
-      schema_names.push(spec.schema_name);
+      attached.push((spec.schema_name, spec.mode));
    }
 
-   Ok(AttachedReadConnection::new(conn, Vec::new(), schema_names))
+   Ok(AttachedReadConnection::new(conn, Vec::new(), attached))
 }
 
 /// Acquire a write connection with attached database(s)
@@ -270,7 +336,8 @@ pub async fn acquire_reader_with_attached(
 /// 1. Acquires the write connection from the main database
 /// 2. For each attached spec:
 ///    - If read-write mode: acquires the attached database's writer first
-///    - Executes ATTACH DATABASE statement
+///    - Executes ATTACH DATABASE statement, read-only for `ReadOnly` specs
+///      (see [`AttachedWriteGuard::attached_mode`])
 /// 3. Returns an `AttachedWriteGuard` that auto-detaches on drop
 ///
 /// Acquiring attached database writers first ensures proper locking order and
@@ -347,10 +414,10 @@ pub async fn acquire_writer_with_attached(
    let held_writers = all_writers;
 
    // Execute ATTACH commands
-   let mut schema_names = Vec::new();
+   let mut attached = Vec::new();
 
    for spec in specs {
-      let path = spec.database.path_str();
+      let path = writer_attach_target(&spec.database.path_str(), spec.mode);
       let escaped_path = path.replace("'", "''");
       let attach_sql = format!(
          "ATTACH DATABASE '{}' AS \"{}\"",
@@ -358,10 +425,10 @@ pub async fn acquire_writer_with_attached(
       );
       sqlx::query(&attach_sql).execute(&mut *writer).await?;
 
-      schema_names.push(spec.schema_name);
+      attached.push((spec.schema_name, spec.mode));
    }
 
-   Ok(AttachedWriteGuard::new(writer, held_writers, schema_names))
+   Ok(AttachedWriteGuard::new(writer, held_writers, attached))
 }
 
 #[cfg(test)]
@@ -476,6 +543,71 @@ mod tests {
       assert_eq!(value, "new_data");
    }
 
+   #[tokio::test]
+   async fn test_attach_mixed_modes_to_writer() {
+      let temp_dir = TempDir::new().unwrap();
+      let main_db = create_test_db("main.db", &temp_dir).await;
+      let ro_db = create_test_db("ro.db", &temp_dir).await;
+      let rw_db = create_test_db("rw.db", &temp_dir).await;
+
+      let specs = vec![
+         AttachedSpec {
+            database: ro_db.clone(),
+            schema_name: "ro".to_string(),
+            mode: AttachedMode::ReadOnly,
+         },
+         AttachedSpec {
+            database: rw_db.clone(),
+            schema_name: "rw".to_string(),
+            mode: AttachedMode::ReadWrite,
+         },
+      ];
+
+      let mut conn = acquire_writer_with_attached(&main_db, specs).await.unwrap();
+      assert_eq!(conn.attached_mode("ro"), Some(AttachedMode::ReadOnly));
+      assert_eq!(conn.attached_mode("rw"), Some(AttachedMode::ReadWrite));
+      assert_eq!(conn.attached_mode("main"), None);
+
+      sqlx::query("INSERT INTO rw.rw (value) VALUES ('written')")
+         .execute(&mut *conn)
+         .await
+         .unwrap();
+
+      let err = sqlx::query("INSERT INTO ro.ro (value) VALUES ('rejected')")
+         .execute(&mut *conn)
+         .await
+         .unwrap_err();
+      assert!(
+         err.to_string().contains("readonly"),
+         "unexpected error: {err}"
+      );
+
+      // Read-only schemas are still readable
+      let row = sqlx::query("SELECT COUNT(*) FROM ro.ro")
+         .fetch_one(&mut *conn)
+         .await
+         .unwrap();
+      assert_eq!(row.get::<i64, _>(0), 1);
+
+      conn.detach_all().await.unwrap();
+   }
+
+   #[test]
+   fn test_writer_attach_target() {
+      assert_eq!(
+         writer_attach_target("/data/a?b%.db", AttachedMode::ReadOnly),
+         "file:/data/a%3fb%25.db?mode=ro"
+      );
+      assert_eq!(
+         writer_attach_target("/data/a.db", AttachedMode::ReadWrite),
+         "/data/a.db"
+      );
+      assert_eq!(
+         writer_attach_target("file:mem?mode=memory&cache=shared", AttachedMode::ReadOnly),
+         "file:mem?mode=memory&cache=shared"
+      );
+   }
+
    #[tokio::test]
    async fn test_attach_readwrite_to_reader_fails() {
       let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,159 @@
+//! Online backup of a database into a new file
+//!
+//! Uses SQLite's [online backup API](https://www.sqlite.org/backup.html) to copy
+//! the database a batch of pages at a time, yielding to the runtime between
+//! batches. Unlike `VACUUM INTO`, this reports progress as it goes.
+
+use std::ffi::CStr;
+use std::path::Path;
+use std::ptr::NonNull;
+use std::time::Duration;
+
+use libsqlite3_sys::{
+   SQLITE_BUSY, SQLITE_DONE, SQLITE_LOCKED, SQLITE_OK, sqlite3, sqlite3_backup,
+   sqlite3_backup_finish, sqlite3_backup_init, sqlite3_backup_pagecount, sqlite3_backup_remaining,
+   sqlite3_backup_step, sqlite3_errmsg,
+};
+use serde::Serialize;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{ConnectOptions, Connection};
+
+use crate::Result;
+use crate::error::Error;
+
+/// Pages copied per backup step; progress is reported after each step
+const PAGES_PER_STEP: i32 = 64;
+
+/// Delay before retrying a step that found the source or destination locked
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Progress of an online backup, reported after each batch of pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BackupProgress {
+   /// Pages still to be copied
+   pub remaining: u32,
+   /// Total pages in the source database
+   pub total: u32,
+}
+
+/// An initialized `sqlite3_backup`, finished on drop
+struct Backup(NonNull<sqlite3_backup>);
+
+// SAFETY: The backup object is only used by the task that owns it, one call at a
+// time. Both connections it refers to are held exclusively by that same task, so
+// sqlx never touches them while a backup call is in progress.
+unsafe impl Send for Backup {}
+
+impl Backup {
+   /// Finish the backup, returning SQLite's result code for the whole operation
+   fn finish(self) -> i32 {
+      let this = std::mem::ManuallyDrop::new(self);
+      // SAFETY: the pointer came from sqlite3_backup_init; ManuallyDrop skips the
+      // second finish in Drop
+      unsafe { sqlite3_backup_finish(this.0.as_ptr()) }
+   }
+}
+
+impl Drop for Backup {
+   fn drop(&mut self) {
+      // SAFETY: the pointer came from sqlite3_backup_init and has not been finished
+      unsafe {
+         sqlite3_backup_finish(self.0.as_ptr());
+      }
+   }
+}
+
+/// Raw handle of a connection held for the duration of the backup
+#[derive(Clone, Copy)]
+struct RawDb(NonNull<sqlite3>);
+
+// SAFETY: See `Backup`; the owning `SqliteConnection` outlives every use.
+unsafe impl Send for RawDb {}
+
+impl RawDb {
+   async fn of(conn: &mut SqliteConnection) -> Result<Self> {
+      Ok(Self(conn.lock_handle().await?.as_raw_handle()))
+   }
+
+   fn error(self, context: &str) -> Error {
+      // SAFETY: the handle is valid; errmsg returns a NUL-terminated string owned by SQLite
+      let message = unsafe { CStr::from_ptr(sqlite3_errmsg(self.0.as_ptr())) };
+      Error::Backup(format!("{context}: {}", message.to_string_lossy()))
+   }
+}
+
+/// Copy the database open on `source` into a new file at `dest`
+pub(crate) async fn backup(
+   source: &mut SqliteConnection,
+   dest: &Path,
+   mut on_progress: impl FnMut(BackupProgress),
+) -> Result<()> {
+   if dest.exists() {
+      return Err(Error::Io(std::io::Error::new(
+         std::io::ErrorKind::AlreadyExists,
+         format!("backup destination already exists: {}", dest.display()),
+      )));
+   }
+
+   let mut dest_conn = SqliteConnectOptions::new()
+      .filename(dest)
+      .create_if_missing(true)
+      .connect()
+      .await?;
+
+   let src_db = RawDb::of(source).await?;
+   let dest_db = RawDb::of(&mut dest_conn).await?;
+
+   // SAFETY: both handles are valid open connections, distinct from each other
+   let raw = unsafe {
+      sqlite3_backup_init(
+         dest_db.0.as_ptr(),
+         c"main".as_ptr(),
+         src_db.0.as_ptr(),
+         c"main".as_ptr(),
+      )
+   };
+   let backup = Backup(NonNull::new(raw).ok_or_else(|| dest_db.error("backup init failed"))?);
+
+   loop {
+      // SAFETY: backup is a live backup object (not yet finished)
+      let rc = unsafe { sqlite3_backup_step(backup.0.as_ptr(), PAGES_PER_STEP) };
+
+      match rc {
+         SQLITE_OK | SQLITE_DONE => {
+            // SAFETY: as above
+            let (remaining, total) = unsafe {
+               (
+                  sqlite3_backup_remaining(backup.0.as_ptr()),
+                  sqlite3_backup_pagecount(backup.0.as_ptr()),
+               )
+            };
+            on_progress(BackupProgress {
+               remaining: remaining.max(0) as u32,
+               total: total.max(0) as u32,
+            });
+
+            if rc == SQLITE_DONE {
+               break;
+            }
+            // Let other tasks (and readers of the source) run between batches
+            tokio::task::yield_now().await;
+         }
+         SQLITE_BUSY | SQLITE_LOCKED => tokio::time::sleep(BUSY_RETRY_DELAY).await,
+         _ => {
+            let err = dest_db.error("backup step failed");
+            drop(backup);
+            drop(dest_conn);
+            let _ = std::fs::remove_file(dest);
+            return Err(err);
+         }
+      }
+   }
+
+   if backup.finish() != SQLITE_OK {
+      return Err(dest_db.error("backup finish failed"));
+   }
+
+   dest_conn.close().await?;
+   Ok(())
+}
@@ -0,0 +1,117 @@
+//! Custom busy handlers for pooled connections
+//!
+//! [`SqliteDatabase::set_busy_handler`](crate::SqliteDatabase::set_busy_handler)
+//! stores a callback here; the pools install it with `sqlite3_busy_handler` on
+//! every connection they open and on every idle connection they hand out, so
+//! connections opened before the call pick it up too.
+
+use std::ffi::{c_int, c_void};
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use libsqlite3_sys::sqlite3_busy_handler;
+use sqlx::pool::PoolConnectionMetadata;
+use sqlx::sqlite::SqliteConnection;
+
+type HookFuture<'c, T> = Pin<Box<dyn Future<Output = sqlx::Result<T>> + Send + 'c>>;
+
+type Handler = Box<dyn Fn(i32) -> bool + Send + Sync>;
+
+/// Busy handlers set on one database, shared with its pools' connect hooks
+#[derive(Default)]
+pub(crate) struct BusyHandlers {
+   /// Every handler ever set, the current one last
+   ///
+   /// Replaced handlers are kept because a connection that was checked out when
+   /// the handler changed still points at the old one until it is acquired again.
+   handlers: Mutex<Vec<Arc<Handler>>>,
+}
+
+impl BusyHandlers {
+   pub(crate) fn set(&self, handler: Handler) {
+      self
+         .handlers
+         .lock()
+         .unwrap_or_else(|e| e.into_inner())
+         .push(Arc::new(handler));
+   }
+
+   fn current(&self) -> Option<Arc<Handler>> {
+      self
+         .handlers
+         .lock()
+         .unwrap_or_else(|e| e.into_inner())
+         .last()
+         .cloned()
+   }
+
+   /// Install the current handler on `conn`, if one has been set
+   ///
+   /// Returns a `sqlx::Error` so it can be used from pool hooks directly.
+   pub(crate) async fn install(&self, conn: &mut SqliteConnection) -> sqlx::Result<()> {
+      let Some(handler) = self.current() else {
+         return Ok(());
+      };
+
+      let mut handle = conn.lock_handle().await?;
+      // SAFETY: the handle is valid while locked. The handler is kept alive by
+      // `self.handlers`, which the pools' hooks (and so every connection) share.
+      // This also clears the connection's `busy_timeout`; it only fails on a
+      // null handle.
+      unsafe {
+         sqlite3_busy_handler(
+            handle.as_raw_handle().as_ptr(),
+            Some(call_handler),
+            Arc::as_ptr(&handler) as *mut c_void,
+         );
+      }
+      Ok(())
+   }
+}
+
+impl std::fmt::Debug for BusyHandlers {
+   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+      f.debug_struct("BusyHandlers")
+         .field("is_set", &self.current().is_some())
+         .finish()
+   }
+}
+
+/// `after_connect` hook installing the current handler on new connections
+pub(crate) fn install_busy_handler(
+   handlers: &Arc<BusyHandlers>,
+) -> impl for<'c> Fn(&'c mut SqliteConnection, PoolConnectionMetadata) -> HookFuture<'c, ()>
++ Send
++ Sync
++ 'static {
+   let handlers = Arc::clone(handlers);
+   move |conn, _meta| {
+      let handlers = Arc::clone(&handlers);
+      Box::pin(async move { handlers.install(conn).await })
+   }
+}
+
+/// `before_acquire` hook installing the current handler on idle connections,
+/// so a handler set after they were opened still reaches them
+pub(crate) fn reinstall_busy_handler(
+   handlers: &Arc<BusyHandlers>,
+) -> impl for<'c> Fn(&'c mut SqliteConnection, PoolConnectionMetadata) -> HookFuture<'c, bool>
++ Send
++ Sync
++ 'static {
+   let handlers = Arc::clone(handlers);
+   move |conn, _meta| {
+      let handlers = Arc::clone(&handlers);
+      Box::pin(async move { handlers.install(conn).await.map(|()| true) })
+   }
+}
+
+/// Busy callback passed to SQLite; returns non-zero to keep waiting
+extern "C" fn call_handler(data: *mut c_void, count: c_int) -> c_int {
+   // SAFETY: `data` points at a `Handler` kept alive by `BusyHandlers`
+   let handler = unsafe { &*(data as *const Handler) };
+   // A panic must not unwind into SQLite; treat it as giving up
+   catch_unwind(AssertUnwindSafe(|| handler(count))).unwrap_or(false) as c_int
+}
@@ -0,0 +1,43 @@
+//! Manual WAL checkpoints
+
+use serde::Serialize;
+
+/// How aggressively [`SqliteDatabase::checkpoint`](crate::SqliteDatabase::checkpoint)
+/// copies the WAL back into the database file
+///
+/// See <https://www.sqlite.org/pragma.html#pragma_wal_checkpoint>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointMode {
+   /// Checkpoint as many frames as possible without waiting for readers
+   Passive,
+   /// Wait for readers, then checkpoint every frame
+   Full,
+   /// Like `Full`, then wait until readers are done with the WAL so the next
+   /// writer restarts it from the beginning
+   Restart,
+   /// Like `Restart`, and also truncate the WAL file to zero bytes
+   Truncate,
+}
+
+impl CheckpointMode {
+   pub(crate) fn as_sql(self) -> &'static str {
+      match self {
+         CheckpointMode::Passive => "PASSIVE",
+         CheckpointMode::Full => "FULL",
+         CheckpointMode::Restart => "RESTART",
+         CheckpointMode::Truncate => "TRUNCATE",
+      }
+   }
+}
+
+/// Result of a `PRAGMA wal_checkpoint`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CheckpointStats {
+   /// A `Full`, `Restart` or `Truncate` checkpoint could not finish because a
+   /// reader or the busy timeout got in the way
+   pub busy: bool,
+   /// Frames in the WAL (-1 if the database is not in WAL mode)
+   pub log_frames: i64,
+   /// Frames copied back into the database file (-1 if not in WAL mode)
+   pub checkpointed_frames: i64,
+}
@@ -1,6 +1,11 @@
 //! Configuration for SQLite database connection pools
 
 use serde::{Deserialize, Serialize};
+use sqlx::sqlite::{SqliteAutoVacuum, SqliteConnectOptions};
+use tracing::warn;
+
+use crate::Result;
+use crate::error::Error;
 
 /// Configuration for SqliteDatabase connection pools
 ///
@@ -16,6 +21,13 @@ use serde::{Deserialize, Serialize};
 /// let config = SqliteDatabaseConfig {
 ///     max_read_connections: 3,
 ///     idle_timeout_secs: 60,
+///     max_lifetime_secs: None,
+///     optimize_on_release: true,
+///     optimize_interval_secs: 600,
+///     read_uncommitted: false,
+///     foreign_keys: true,
+///     pragmas: vec![("mmap_size".into(), "268435456".into())],
+///     ..Default::default()
 /// };
 ///
 /// // Override just one field
@@ -24,13 +36,22 @@ use serde::{Deserialize, Serialize};
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SqliteDatabaseConfig {
    /// Maximum number of concurrent read connections
    ///
    /// This controls the size of the read-only connection pool.
    /// Higher values allow more concurrent read queries but consume more resources.
    ///
+   /// `0` opens no read pool: `read_pool()` returns the write pool, so the whole
+   /// database uses one connection. Reads then wait for writes and vice versa,
+   /// and reading through `read_pool()` while holding a `WriteGuard` in the same
+   /// task never completes. The connection is `query_only` unless checked out
+   /// by `acquire_writer()`, and WAL mode is enabled at connect rather than on
+   /// the first write. Not allowed with `connect_readonly`
+   /// (`Error::InvalidConfig`).
+   ///
    /// Default: 6
    pub max_read_connections: u32,
 
@@ -41,6 +62,149 @@ pub struct SqliteDatabaseConfig {
    ///
    /// Default: 30
    pub idle_timeout_secs: u64,
+
+   /// Maximum lifetime of read and write connections (in seconds)
+   ///
+   /// Connections older than this are closed and replaced when next released or
+   /// acquired, even if they are in constant use. On mobile this keeps long-lived
+   /// connections from holding file descriptors after the OS reclaimed resources
+   /// while the app was backgrounded. `None` keeps connections until they go idle.
+   ///
+   /// Default: None
+   pub max_lifetime_secs: Option<u64>,
+
+   /// Run `PRAGMA optimize` when the write connection is released back to its pool
+   ///
+   /// Keeps query planner statistics fresh without manual maintenance, following
+   /// SQLite's recommended periodic-optimize practice. Only the write connection
+   /// runs it (read connections are read-only and cannot persist statistics), and
+   /// runs are throttled by `optimize_interval_secs` so that frequent short writes
+   /// do not pay the cost on every release.
+   ///
+   /// Default: false
+   pub optimize_on_release: bool,
+
+   /// Minimum interval between `PRAGMA optimize` runs triggered by
+   /// `optimize_on_release` (in seconds)
+   ///
+   /// The first release after the database is opened always runs the optimize;
+   /// releases within this window of the previous run skip it.
+   ///
+   /// Default: 600 (10 minutes)
+   pub optimize_interval_secs: u64,
+
+   /// Set `PRAGMA read_uncommitted` on read pool connections
+   ///
+   /// Lets readers skip shared-cache table locks, so they do not wait on the writer
+   /// but may see its uncommitted changes. This is only meaningful for shared-cache
+   /// in-memory databases (`file:name?mode=memory&cache=shared`); file databases
+   /// use WAL, where readers never block on the writer and only see committed
+   /// data, so the pragma has no effect there.
+   ///
+   /// Default: false
+   pub read_uncommitted: bool,
+
+   /// Grant the writer to `acquire_writer` callers in the order they asked
+   ///
+   /// The write pool alone does not promise FIFO order among waiters, so a caller
+   /// can be overtaken repeatedly under contention. With this set, `acquire_writer`
+   /// first waits its turn in a FIFO queue, which suits ordered job processors.
+   /// Every write path queues, including `write_transaction` and
+   /// `run_migrations`; only the final WAL checkpoint in `close` takes the write
+   /// connection directly.
+   ///
+   /// Default: false
+   pub fair_writes: bool,
+
+   /// How many times `write_transaction` retries after `Error::Busy`
+   ///
+   /// Each retry re-acquires the writer and reruns the whole transaction, after a
+   /// backoff that starts at 10ms and doubles up to 1s. Only lock contention from
+   /// other processes or connections outside this manager produces `Busy`; writers
+   /// of the same `SqliteDatabase` already wait for each other. Other methods,
+   /// including `acquire_writer`, never retry.
+   ///
+   /// Default: 0
+   pub retry_on_busy: u32,
+
+   /// Set `PRAGMA foreign_keys` on every read and write connection
+   ///
+   /// SQLite itself leaves foreign key constraints unenforced unless each
+   /// connection opts in, so the pragma is applied whenever a pool opens a
+   /// connection, including ones reopened after an idle timeout. Set to `false`
+   /// to allow rows that reference missing parents (e.g. during bulk imports that
+   /// load children first).
+   ///
+   /// Default: true
+   pub foreign_keys: bool,
+
+   /// Extra `PRAGMA name = value` statements for tuning and database identity
+   ///
+   /// Connection-scoped pragmas, for tuning such as `cache_size`, `mmap_size` or
+   /// `temp_store`, are applied to every new connection of both pools, since
+   /// every connection has its own value. Database-scoped pragmas that are
+   /// stored in the file (`application_id`, `auto_vacuum`, `page_size`,
+   /// `user_version`) are applied once, by the write connection, when the
+   /// database is opened, so a writer reconnecting later does not reset a value
+   /// the app has changed since; `page_size` and `auto_vacuum` only take effect
+   /// on a database that has no tables yet (or after `VACUUM`).
+   ///
+   /// `journal_mode` and `synchronous` are managed by WAL initialization and are
+   /// skipped. Other pragmas must be in a fixed allowlist of tuning pragmas
+   /// (`writable_schema`, `locking_mode` or `query_only`, for example, are not),
+   /// and values plain numbers or keywords; anything else fails `connect` with
+   /// `Error::InvalidPragma`.
+   ///
+   /// Default: empty
+   pub pragmas: Vec<(String, String)>,
+
+   /// `PRAGMA auto_vacuum` mode set on the write connection
+   ///
+   /// Applied before WAL initialization, whenever the writer connects. SQLite
+   /// only changes the mode of a database that has no tables yet; on an existing
+   /// database a change from or to `None` takes effect at the next full `VACUUM`.
+   /// `None` leaves the file's mode (SQLite's default is no auto-vacuum) alone.
+   ///
+   /// Default: None
+   pub auto_vacuum: Option<AutoVacuumMode>,
+
+   /// SQLCipher key issued as `PRAGMA key` before anything else on every connection
+   ///
+   /// Encrypts the database at rest. Requires SQLite to be SQLCipher, e.g. by
+   /// enabling `libsqlite3-sys`'s `bundled-sqlcipher` feature in the final
+   /// binary; with plain SQLite the pragma is silently ignored and the file stays
+   /// unencrypted. The key is passed as a passphrase string literal, so raw
+   /// `x'...'` keys are not supported. Only applies to this database: attached
+   /// databases and `backup_to` destinations are opened without it. Never
+   /// serialized, and redacted from `Debug` output.
+   ///
+   /// Default: None
+   #[cfg(feature = "sqlcipher")]
+   #[serde(skip_serializing)]
+   pub encryption_key: Option<String>,
+}
+
+impl std::fmt::Debug for SqliteDatabaseConfig {
+   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+      let mut s = f.debug_struct("SqliteDatabaseConfig");
+      s.field("max_read_connections", &self.max_read_connections)
+         .field("idle_timeout_secs", &self.idle_timeout_secs)
+         .field("max_lifetime_secs", &self.max_lifetime_secs)
+         .field("optimize_on_release", &self.optimize_on_release)
+         .field("optimize_interval_secs", &self.optimize_interval_secs)
+         .field("read_uncommitted", &self.read_uncommitted)
+         .field("fair_writes", &self.fair_writes)
+         .field("retry_on_busy", &self.retry_on_busy)
+         .field("foreign_keys", &self.foreign_keys)
+         .field("pragmas", &self.pragmas)
+         .field("auto_vacuum", &self.auto_vacuum);
+      #[cfg(feature = "sqlcipher")]
+      s.field(
+         "encryption_key",
+         &self.encryption_key.as_ref().map(|_| "<redacted>"),
+      );
+      s.finish()
+   }
 }
 
 impl Default for SqliteDatabaseConfig {
@@ -48,6 +212,138 @@ impl Default for SqliteDatabaseConfig {
       Self {
          max_read_connections: 6,
          idle_timeout_secs: 30,
+         max_lifetime_secs: None,
+         optimize_on_release: false,
+         optimize_interval_secs: 600,
+         read_uncommitted: false,
+         fair_writes: false,
+         retry_on_busy: 0,
+         foreign_keys: true,
+         auto_vacuum: None,
+         pragmas: Vec::new(),
+         #[cfg(feature = "sqlcipher")]
+         encryption_key: None,
+      }
+   }
+}
+
+/// Value of `PRAGMA auto_vacuum`
+///
+/// See <https://www.sqlite.org/pragma.html#pragma_auto_vacuum>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutoVacuumMode {
+   /// Freed pages stay in the file until a `VACUUM`
+   None,
+   /// Freed pages are returned to the OS at every commit
+   Full,
+   /// Freed pages are tracked and returned by `PRAGMA incremental_vacuum`
+   Incremental,
+}
+
+impl AutoVacuumMode {
+   pub(crate) fn to_sqlx(self) -> SqliteAutoVacuum {
+      match self {
+         AutoVacuumMode::None => SqliteAutoVacuum::None,
+         AutoVacuumMode::Full => SqliteAutoVacuum::Full,
+         AutoVacuumMode::Incremental => SqliteAutoVacuum::Incremental,
+      }
+   }
+}
+
+/// Pragmas managed internally by WAL initialization
+const MANAGED_PRAGMAS: &[&str] = &["journal_mode", "synchronous"];
+
+/// Pragmas stored in the database file, which read-only connections cannot set
+const DATABASE_SCOPED_PRAGMAS: &[&str] =
+   &["application_id", "auto_vacuum", "page_size", "user_version"];
+
+/// Connection-scoped pragmas [`SqliteDatabaseConfig::pragmas`] may set
+///
+/// Pragmas that would break the pools' assumptions (`locking_mode`,
+/// `query_only`) or the schema's integrity (`writable_schema`) are left out.
+const CONNECTION_PRAGMAS: &[&str] = &[
+   "analysis_limit",
+   "automatic_index",
+   "busy_timeout",
+   "cache_size",
+   "cache_spill",
+   "cell_size_check",
+   "journal_size_limit",
+   "max_page_count",
+   "mmap_size",
+   "recursive_triggers",
+   "reverse_unordered_selects",
+   "secure_delete",
+   "temp_store",
+   "threads",
+   "trusted_schema",
+   "wal_autocheckpoint",
+];
+
+/// A validated entry of [`SqliteDatabaseConfig::pragmas`]
+pub(crate) struct StartupPragma<'a> {
+   pub name: &'a str,
+   pub value: &'a str,
+   /// Whether the pragma is stored in the file, and so only set once, by the
+   /// writer, when the database is opened
+   pub database_scoped: bool,
+}
+
+impl SqliteDatabaseConfig {
+   /// Validate `pragmas`, dropping the ones managed internally
+   pub(crate) fn startup_pragmas(&self) -> Result<Vec<StartupPragma<'_>>> {
+      let mut pragmas = Vec::new();
+
+      for (name, value) in &self.pragmas {
+         let valid_name = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+         let valid_value = !value.is_empty()
+            && value
+               .chars()
+               .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+' | '.'));
+         if !valid_name || !valid_value {
+            return Err(Error::InvalidPragma(format!("{name} = {value}")));
+         }
+
+         let lowercase = name.to_ascii_lowercase();
+         if MANAGED_PRAGMAS.contains(&lowercase.as_str()) {
+            warn!("Skipping PRAGMA {}: managed by WAL initialization", name);
+            continue;
+         }
+
+         let database_scoped = DATABASE_SCOPED_PRAGMAS.contains(&lowercase.as_str());
+         if !database_scoped && !CONNECTION_PRAGMAS.contains(&lowercase.as_str()) {
+            return Err(Error::InvalidPragma(format!(
+               "{name} is not a pragma that can be set at startup"
+            )));
+         }
+
+         pragmas.push(StartupPragma {
+            name,
+            value,
+            database_scoped,
+         });
+      }
+
+      Ok(pragmas)
+   }
+
+   /// Add `PRAGMA key` to `options` when an encryption key is configured
+   ///
+   /// sqlx always issues `key` ahead of every other pragma, so the database is
+   /// unlocked before anything reads it.
+   pub(crate) fn apply_encryption_key(
+      &self,
+      options: SqliteConnectOptions,
+   ) -> SqliteConnectOptions {
+      #[cfg(feature = "sqlcipher")]
+      if let Some(key) = &self.encryption_key {
+         return options.pragma("key", format!("'{}'", key.replace('\'', "''")));
       }
+      options
    }
 }
@@ -0,0 +1,107 @@
+//! Streaming content fingerprint for tables and databases
+
+use crate::Result;
+use crate::error::Error;
+use futures_util::TryStreamExt;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+
+/// 64-bit FNV-1a, chosen over `DefaultHasher` because its output is fixed and
+/// does not change between Rust releases
+struct Fnv1a64(u64);
+
+impl Fnv1a64 {
+   fn new() -> Self {
+      Self(0xcbf2_9ce4_8422_2325)
+   }
+
+   fn write(&mut self, bytes: &[u8]) {
+      for byte in bytes {
+         self.0 ^= u64::from(*byte);
+         self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+      }
+   }
+
+   /// Write a length-prefixed field so adjacent values cannot run together
+   fn write_field(&mut self, bytes: &[u8]) {
+      self.write(&(bytes.len() as u64).to_le_bytes());
+      self.write(bytes);
+   }
+}
+
+fn quote_name(name: &str) -> String {
+   format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Hash the contents of `table`, or of every user table, inside one read transaction
+pub(crate) async fn content_hash(conn: &mut SqliteConnection, table: Option<&str>) -> Result<u64> {
+   let mut tx = conn.begin().await?;
+
+   let tables: Vec<String> = match table {
+      Some(name) => vec![name.to_string()],
+      None => {
+         sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' \
+             AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\' AND name <> '_sqlx_migrations' \
+             ORDER BY name",
+         )
+         .fetch_all(&mut *tx)
+         .await?
+      }
+   };
+
+   let mut hasher = Fnv1a64::new();
+
+   for name in &tables {
+      let columns: Vec<String> =
+         sqlx::query_scalar("SELECT name FROM pragma_table_info(?) ORDER BY cid")
+            .bind(name)
+            .fetch_all(&mut *tx)
+            .await?;
+
+      if columns.is_empty() {
+         return Err(Error::TableNotFound(name.clone()));
+      }
+
+      hasher.write_field(name.as_bytes());
+      for column in &columns {
+         hasher.write_field(column.as_bytes());
+      }
+
+      // quote() renders each value as a typed SQL literal ('a', 1, 1.5, X'00',
+      // NULL), which both distinguishes storage classes and gives a total order
+      let select_list = columns
+         .iter()
+         .map(|c| format!("quote({})", quote_name(c)))
+         .collect::<Vec<_>>()
+         .join(", ");
+      let order_by = (1..=columns.len())
+         .map(|i| i.to_string())
+         .collect::<Vec<_>>()
+         .join(", ");
+      let sql = format!(
+         "SELECT {} FROM {} ORDER BY {}",
+         select_list,
+         quote_name(name),
+         order_by
+      );
+
+      let mut row_count: u64 = 0;
+      let mut rows = sqlx::query(&sql).fetch(&mut *tx);
+      while let Some(row) = rows.try_next().await? {
+         for i in 0..columns.len() {
+            let literal: String = row.try_get(i)?;
+            hasher.write_field(literal.as_bytes());
+         }
+         row_count += 1;
+      }
+      drop(rows);
+
+      // Terminate the table so its rows cannot be confused with the next table
+      hasher.write(&row_count.to_le_bytes());
+   }
+
+   tx.rollback().await?;
+
+   Ok(hasher.0)
+}
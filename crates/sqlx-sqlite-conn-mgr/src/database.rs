@@ -1,22 +1,79 @@
 //! SQLite database with connection pooling and optional write access
 
 use crate::Result;
+use crate::busy::{BusyHandlers, install_busy_handler, reinstall_busy_handler};
+use crate::checkpoint::{CheckpointMode, CheckpointStats};
 use crate::config::SqliteDatabaseConfig;
 use crate::error::Error;
-use crate::registry::{get_or_open_database, is_memory_database, uncache_database};
+use crate::metrics::PoolSnapshot;
+use crate::registry::{
+   get_or_open_database, is_memory_database, is_shared_memory_database, uncache_database,
+};
 use crate::write_guard::WriteGuard;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-use sqlx::{ConnectOptions, Pool, Sqlite};
+use futures_util::Stream;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection, SqlitePoolOptions};
+use sqlx::{ConnectOptions, Connection, Pool, Sqlite};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tracing::error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tracing::{error, warn};
+
+/// First `write_transaction` busy backoff; doubles per retry up to `BUSY_BACKOFF_MAX`
+const BUSY_BACKOFF_BASE: Duration = Duration::from_millis(10);
+const BUSY_BACKOFF_MAX: Duration = Duration::from_secs(1);
 
 /// Analysis limit for PRAGMA optimize on close.
 /// SQLite recommends 100-1000 for older versions; 3.46.0+ handles automatically.
 /// See: https://www.sqlite.org/lang_analyze.html#recommended_usage_pattern
 const OPTIMIZE_ANALYSIS_LIMIT: u32 = 400;
 
+/// Throttle for `PRAGMA optimize` runs triggered by releasing the write connection
+///
+/// Shared by the write pool's `after_release` hook so that at most one optimize
+/// runs per interval, no matter how often the writer is acquired and released.
+#[derive(Debug)]
+struct OptimizeThrottle {
+   interval: Duration,
+   last_run: Mutex<Option<Instant>>,
+}
+
+impl OptimizeThrottle {
+   fn new(interval: Duration) -> Self {
+      Self {
+         interval,
+         last_run: Mutex::new(None),
+      }
+   }
+
+   /// Returns `true` and records `now` as the last run if an optimize is due
+   fn try_claim(&self, now: Instant) -> bool {
+      let mut last_run = self.last_run.lock().unwrap_or_else(|e| e.into_inner());
+
+      match *last_run {
+         Some(last) if now.saturating_duration_since(last) < self.interval => false,
+         _ => {
+            *last_run = Some(now);
+            true
+         }
+      }
+   }
+}
+
+/// Switch the database to WAL mode with the synchronous level WAL recommends
+async fn initialize_wal(conn: &mut SqliteConnection) -> Result<()> {
+   sqlx::query("PRAGMA journal_mode = WAL")
+      .execute(&mut *conn)
+      .await?;
+
+   // https://www.sqlite.org/wal.html#performance_considerations
+   sqlx::query("PRAGMA synchronous = NORMAL")
+      .execute(&mut *conn)
+      .await?;
+   Ok(())
+}
+
 /// SQLite database with connection pooling for concurrent reads and optional exclusive writes.
 ///
 /// Once the database is opened it can be used for read-only operations by calling `read_pool()`.
@@ -51,10 +108,23 @@ const OPTIMIZE_ANALYSIS_LIMIT: u32 = 400;
 #[derive(Debug)]
 pub struct SqliteDatabase {
    /// Pool of read-only connections (defaults to max_connections=6) for concurrent reads
+   ///
+   /// A clone of the write pool when `max_read_connections` is 0.
    read_pool: Pool<Sqlite>,
 
+   /// Whether `read_pool` is the write pool (`max_read_connections` is 0)
+   reads_via_writer: bool,
+
    /// Single read-write connection pool (max_connections=1) for serialized writes
-   write_conn: Pool<Sqlite>,
+   ///
+   /// `None` for databases opened with `connect_readonly`.
+   write_conn: Option<Pool<Sqlite>>,
+
+   /// FIFO queue in front of the write pool when `fair_writes` is set
+   write_queue: Option<Arc<Semaphore>>,
+
+   /// Retries of `write_transaction` after `Error::Busy`
+   retry_on_busy: u32,
 
    /// Tracks if WAL mode has been initialized (set on first write)
    wal_initialized: AtomicBool,
@@ -64,13 +134,28 @@ pub struct SqliteDatabase {
 
    /// Path to database file (used for cleanup and registry lookups)
    path: PathBuf,
+
+   /// Connection held open for shared-cache in-memory databases
+   ///
+   /// SQLite frees a shared in-memory database when its last connection closes. Pooled
+   /// connections are reaped when idle, so this connection pins the backing store for
+   /// the lifetime of the `SqliteDatabase`. `None` for file-backed databases.
+   keepalive: Mutex<Option<SqliteConnection>>,
+
+   /// Handler set by `set_busy_handler`, installed by both pools' hooks
+   busy_handlers: Arc<BusyHandlers>,
 }
 
 impl SqliteDatabase {
+   /// Get the database file path, exactly as passed to `connect`
+   pub fn path(&self) -> &Path {
+      &self.path
+   }
+
    /// Get the database file path as a string
    ///
-   /// Used internally (crate-private) for ATTACH DATABASE statements
-   pub(crate) fn path_str(&self) -> String {
+   /// Non-UTF-8 characters are replaced. Also used for ATTACH DATABASE statements.
+   pub fn path_str(&self) -> String {
       self.path.to_string_lossy().to_string()
    }
 
@@ -111,6 +196,7 @@ impl SqliteDatabase {
    /// let custom_config = SqliteDatabaseConfig {
    ///    max_read_connections: 10,
    ///    idle_timeout_secs: 60,
+   ///    ..Default::default()
    /// };
    /// let db = SqliteDatabase::connect("test.db", Some(custom_config)).await?;
    /// # Ok(())
@@ -119,9 +205,99 @@ impl SqliteDatabase {
    pub async fn connect(
       path: impl AsRef<Path>,
       custom_config: Option<SqliteDatabaseConfig>,
+   ) -> Result<Arc<Self>> {
+      Self::open(path.as_ref(), custom_config, false).await
+   }
+
+   /// Connect to a named in-memory database shared by the read pool and writer
+   ///
+   /// Opens `file:{name}?mode=memory&cache=shared`, so every connection of this
+   /// instance sees the same data. The instance is cached like a file database:
+   /// connecting again with the same name returns it until it is closed. A
+   /// dedicated connection keeps the data alive while the instance is open; it is
+   /// freed by [`close`](Self::close) or when the last reference is dropped.
+   ///
+   /// `name` may contain only ASCII letters, digits, `_` and `-`; anything else
+   /// fails with `Error::InvalidConfig`. The data is never written to disk.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect_memory("cache", None).await?;
+   /// let mut writer = db.acquire_writer().await?;
+   /// sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+   ///     .execute(&mut *writer)
+   ///     .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn connect_memory(
+      name: &str,
+      custom_config: Option<SqliteDatabaseConfig>,
+   ) -> Result<Arc<Self>> {
+      if name.is_empty()
+         || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+      {
+         return Err(Error::InvalidConfig(format!(
+            "invalid in-memory database name '{name}'"
+         )));
+      }
+
+      Self::open(
+         Path::new(&format!("file:{name}?mode=memory&cache=shared")),
+         custom_config,
+         false,
+      )
+      .await
+   }
+
+   /// Connect to an existing SQLite database without write access
+   ///
+   /// For bundled reference databases, e.g. on read-only media. Unlike
+   /// [`connect`](Self::connect), the file is never created and no write pool is
+   /// built: only [`read_pool`](Self::read_pool) is usable, and
+   /// [`acquire_writer`](Self::acquire_writer) (and everything built on it, such as
+   /// migrations, checkpoints and read-write attachments) returns
+   /// `Error::ReadOnlyDatabase`.
+   ///
+   /// Fails with `Error::Io` (`NotFound`) if the file does not exist. Like
+   /// `connect`, this returns the cached instance if the path is already open, so
+   /// a database previously opened with `connect` stays writable.
+   ///
+   /// A WAL-mode database needs its `-shm` file to be creatable next to it; ship
+   /// read-only databases in rollback-journal mode.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect_readonly("/media/reference.db", None).await?;
+   /// let rows = sqlx::query("SELECT * FROM terms")
+   ///     .fetch_all(db.read_pool()?)
+   ///     .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn connect_readonly(
+      path: impl AsRef<Path>,
+      custom_config: Option<SqliteDatabaseConfig>,
+   ) -> Result<Arc<Self>> {
+      Self::open(path.as_ref(), custom_config, true).await
+   }
+
+   async fn open(
+      path: &Path,
+      custom_config: Option<SqliteDatabaseConfig>,
+      read_only: bool,
    ) -> Result<Arc<Self>> {
       let config = custom_config.unwrap_or_default();
-      let path = path.as_ref();
 
       // Validate path is not empty
       if path.as_os_str().is_empty() {
@@ -131,12 +307,27 @@ impl SqliteDatabase {
          )));
       }
 
+      if read_only && config.max_read_connections == 0 {
+         return Err(Error::InvalidConfig(
+            "a read-only database needs at least one read connection".to_string(),
+         ));
+      }
+
       let path = path.to_path_buf();
+      let pragmas = config.startup_pragmas()?;
 
       get_or_open_database(&path, || async {
          // Check if database file exists
          let db_exists = path.exists();
 
+         // Read-only databases are never created
+         if read_only && !db_exists && !is_memory_database(&path) {
+            return Err(Error::Io(std::io::Error::new(
+               std::io::ErrorKind::NotFound,
+               format!("Database file not found: {}", path.display()),
+            )));
+         }
+
          // If database doesn't exist and not :memory:, create it with a temporary connection
          // We don't keep this connection - WAL mode will be set later in acquire_writer()
          //
@@ -146,52 +337,188 @@ impl SqliteDatabase {
          // for example. That would fail because the read pool connections are read-only and cannot
          // create the file
          if !db_exists && !is_memory_database(&path) {
-            let create_options = SqliteConnectOptions::new()
-               .filename(&path)
-               .create_if_missing(true)
-               .read_only(false);
+            let create_options = config.apply_encryption_key(
+               SqliteConnectOptions::new()
+                  .filename(&path)
+                  .create_if_missing(true)
+                  .read_only(false),
+            );
 
             // Create database file with a temporary connection
             let conn = create_options.connect().await?;
             drop(conn); // Close immediately after creating the file
          }
 
+         // Pin shared in-memory databases before the pools open their (reapable) connections
+         let keepalive = if is_shared_memory_database(&path) {
+            let keepalive_options = config
+               .apply_encryption_key(SqliteConnectOptions::new().filename(&path).read_only(false));
+
+            Some(keepalive_options.connect().await?)
+         } else {
+            None
+         };
+
+         let busy_handlers = Arc::new(BusyHandlers::default());
+
          // Create read pool with read-only connections
-         let read_options = SqliteConnectOptions::new()
-            .filename(&path)
-            .read_only(true)
-            .optimize_on_close(true, OPTIMIZE_ANALYSIS_LIMIT);
+         let mut read_options = config.apply_encryption_key(
+            SqliteConnectOptions::new()
+               .filename(&path)
+               .read_only(true)
+               .foreign_keys(config.foreign_keys)
+               .optimize_on_close(true, OPTIMIZE_ANALYSIS_LIMIT),
+         );
+         if config.read_uncommitted {
+            read_options = read_options.pragma("read_uncommitted", "ON");
+         }
+         for pragma in pragmas.iter().filter(|p| !p.database_scoped) {
+            read_options = read_options.pragma(pragma.name.to_string(), pragma.value.to_string());
+         }
 
-         let read_pool = SqlitePoolOptions::new()
-            .max_connections(config.max_read_connections)
-            .min_connections(0)
-            .idle_timeout(Some(std::time::Duration::from_secs(
-               config.idle_timeout_secs,
-            )))
-            .connect_with(read_options)
-            .await?;
+         // With no read connections, reads share the write pool (set up below)
+         let reads_via_writer = config.max_read_connections == 0;
+         let read_pool = if reads_via_writer {
+            None
+         } else {
+            Some(
+               SqlitePoolOptions::new()
+                  .max_connections(config.max_read_connections)
+                  .min_connections(0)
+                  .idle_timeout(Some(std::time::Duration::from_secs(
+                     config.idle_timeout_secs,
+                  )))
+                  .max_lifetime(config.max_lifetime_secs.map(Duration::from_secs))
+                  .after_connect(install_busy_handler(&busy_handlers))
+                  .before_acquire(reinstall_busy_handler(&busy_handlers))
+                  .connect_with(read_options)
+                  .await?,
+            )
+         };
 
          // Create write pool with a single read-write connection
-         let write_options = SqliteConnectOptions::new()
-            .filename(&path)
-            .read_only(false)
-            .optimize_on_close(true, OPTIMIZE_ANALYSIS_LIMIT);
+         let mut write_options = config.apply_encryption_key(
+            SqliteConnectOptions::new()
+               .filename(&path)
+               .read_only(false)
+               .foreign_keys(config.foreign_keys)
+               .optimize_on_close(true, OPTIMIZE_ANALYSIS_LIMIT),
+         );
+         // sqlx issues auto_vacuum ahead of journal_mode, as SQLite requires
+         if let Some(mode) = config.auto_vacuum {
+            write_options = write_options.auto_vacuum(mode.to_sqlx());
+         }
+         for pragma in pragmas.iter().filter(|p| !p.database_scoped) {
+            write_options = write_options.pragma(pragma.name.to_string(), pragma.value.to_string());
+         }
+         // A write connection doubling as the read pool is read-only while idle;
+         // `acquire_writer` lifts this and releasing the writer restores it
+         if reads_via_writer {
+            write_options = write_options.pragma("query_only", "ON");
+         }
 
-         let write_conn = SqlitePoolOptions::new()
+         let mut write_pool_options = SqlitePoolOptions::new()
             .max_connections(1)
             .min_connections(0)
             .idle_timeout(Some(std::time::Duration::from_secs(
                config.idle_timeout_secs,
             )))
-            .connect_with(write_options)
-            .await?;
+            .max_lifetime(config.max_lifetime_secs.map(Duration::from_secs))
+            .after_connect(install_busy_handler(&busy_handlers))
+            .before_acquire(reinstall_busy_handler(&busy_handlers));
+
+         // Periodically refresh query planner statistics when the writer is released.
+         // Throttled so that frequent short writes don't pay the optimize cost each time.
+         let throttle = config.optimize_on_release.then(|| {
+            Arc::new(OptimizeThrottle::new(Duration::from_secs(
+               config.optimize_interval_secs,
+            )))
+         });
+
+         if throttle.is_some() || reads_via_writer {
+            write_pool_options = write_pool_options.after_release(move |conn, _meta| {
+               let throttle = throttle.clone();
+
+               Box::pin(async move {
+                  if let Some(throttle) = throttle
+                     && throttle.try_claim(Instant::now())
+                  {
+                     let limit = format!("PRAGMA analysis_limit = {OPTIMIZE_ANALYSIS_LIMIT}");
+
+                     if reads_via_writer {
+                        // Released by a read, so still query_only
+                        sqlx::query("PRAGMA query_only = OFF")
+                           .execute(&mut *conn)
+                           .await?;
+                     }
+                     if let Err(e) = sqlx::query(&limit).execute(&mut *conn).await {
+                        warn!("Failed to set analysis limit before optimize: {}", e);
+                     } else if let Err(e) = sqlx::query("PRAGMA optimize").execute(&mut *conn).await
+                     {
+                        warn!("PRAGMA optimize on write connection release failed: {}", e);
+                     }
+                  }
+
+                  // The next checkout may be a read; failing here closes the
+                  // connection rather than hand it out writable
+                  if reads_via_writer {
+                     sqlx::query("PRAGMA query_only = ON")
+                        .execute(&mut *conn)
+                        .await?;
+                  }
+
+                  // Otherwise keep the connection; a failed optimize is not a broken connection
+                  Ok(true)
+               })
+            });
+         }
+
+         let write_conn = if read_only {
+            None
+         } else {
+            Some(write_pool_options.connect_with(write_options).await?)
+         };
+
+         // Pragmas stored in the file are set once here, ahead of WAL
+         // initialization, rather than by every writer reconnect
+         if let Some(write_conn) = &write_conn {
+            let mut conn = write_conn.acquire().await?;
+            if reads_via_writer {
+               sqlx::query("PRAGMA query_only = OFF")
+                  .execute(&mut *conn)
+                  .await?;
+            }
+            for pragma in pragmas.iter().filter(|p| p.database_scoped) {
+               sqlx::query(&format!("PRAGMA {} = {}", pragma.name, pragma.value))
+                  .execute(&mut *conn)
+                  .await?;
+            }
+            // Reads run on this connection and may come before any writer, so
+            // they must not see the database before it is switched to WAL
+            if reads_via_writer {
+               initialize_wal(&mut conn).await?;
+            }
+         }
+         let read_pool = match (read_pool, &write_conn) {
+            (Some(read_pool), _) => read_pool,
+            (None, Some(write_conn)) => write_conn.clone(),
+            (None, None) => unreachable!("read-only databases require read connections"),
+         };
+
+         let write_queue =
+            (config.fair_writes && write_conn.is_some()).then(|| Arc::new(Semaphore::new(1)));
 
          Ok(Self {
             read_pool,
+            reads_via_writer,
             write_conn,
-            wal_initialized: AtomicBool::new(false),
+            write_queue,
+            retry_on_busy: config.retry_on_busy,
+            wal_initialized: AtomicBool::new(reads_via_writer),
             closed: AtomicBool::new(false),
             path: path.clone(),
+            keepalive: Mutex::new(keepalive),
+            busy_handlers,
          })
       })
       .await
@@ -202,6 +529,12 @@ impl SqliteDatabase {
    /// Use this for concurrent read operations. Multiple readers can access
    /// the pool simultaneously.
    ///
+   /// With `max_read_connections: 0` this is the write pool, so reads wait for
+   /// the `WriteGuard` and vice versa. Do not read through it while holding a
+   /// `WriteGuard` in the same task; that waits forever. Use the guard instead.
+   /// Connections handed out by it are `query_only`, as a read pool's would be
+   /// read-only, so writes still have to go through `acquire_writer()`.
+   ///
    /// # Example
    ///
    /// ```no_run
@@ -254,8 +587,31 @@ impl SqliteDatabase {
          return Err(Error::DatabaseClosed);
       }
 
+      let Some(write_conn) = &self.write_conn else {
+         return Err(Error::ReadOnlyDatabase);
+      };
+
+      // Tokio's semaphore grants permits in FIFO order
+      let permit = match &self.write_queue {
+         Some(queue) => Some(
+            Arc::clone(queue)
+               .acquire_owned()
+               .await
+               .map_err(|_| Error::DatabaseClosed)?,
+         ),
+         None => None,
+      };
+
       // Acquire connection from pool (max=1 ensures exclusive access)
-      let mut conn = self.write_conn.acquire().await?;
+      let mut conn = write_conn.acquire().await?;
+
+      // Released reads-via-writer connections are query_only until a writer
+      // takes them
+      if self.reads_via_writer {
+         sqlx::query("PRAGMA query_only = OFF")
+            .execute(&mut *conn)
+            .await?;
+      }
 
       // Initialize WAL mode on first use (atomic check-and-set)
       if self
@@ -263,18 +619,354 @@ impl SqliteDatabase {
          .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
          .is_ok()
       {
-         sqlx::query("PRAGMA journal_mode = WAL")
-            .execute(&mut *conn)
-            .await?;
-
-         // https://www.sqlite.org/wal.html#performance_considerations
-         sqlx::query("PRAGMA synchronous = NORMAL")
-            .execute(&mut *conn)
-            .await?;
+         initialize_wal(&mut conn).await?;
       }
 
       // Return WriteGuard wrapping the pool connection
-      Ok(WriteGuard::new(conn))
+      Ok(WriteGuard::new(conn, permit))
+   }
+
+   /// Acquire exclusive write access, giving up after `timeout`
+   ///
+   /// Like [`acquire_writer`](Self::acquire_writer), but returns
+   /// `Error::WriterAcquireTimeout` with the time waited if the writer is not
+   /// released within `timeout`, instead of waiting for it indefinitely. Useful
+   /// to surface a writer that is held by mistake, e.g. a guard kept across an
+   /// await that never completes.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::{Error, SqliteDatabase};
+   /// use std::time::Duration;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect("test.db", None).await?;
+   /// match db.acquire_writer_timeout(Duration::from_secs(5)).await {
+   ///    Ok(_writer) => { /* write */ }
+   ///    Err(Error::WriterAcquireTimeout(waited)) => eprintln!("writer busy for {waited:?}"),
+   ///    Err(e) => return Err(e),
+   /// }
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn acquire_writer_timeout(&self, timeout: std::time::Duration) -> Result<WriteGuard> {
+      tokio::time::timeout(timeout, self.acquire_writer())
+         .await
+         .map_err(|_| Error::WriterAcquireTimeout(timeout))?
+   }
+
+   /// Acquire the writer and run `f` in a transaction, retrying on lock contention
+   ///
+   /// Like [`WriteGuard::transaction`], but when an attempt fails with
+   /// `Error::Busy`, the transaction is rolled back, the writer released, and the
+   /// whole attempt (acquiring the writer included) is run again, up to
+   /// `retry_on_busy` times (see [`SqliteDatabaseConfig::retry_on_busy`]) with
+   /// exponential backoff. `f` may therefore run more than once and must not have
+   /// side effects outside the transaction. Other errors are returned right away.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::{SqliteDatabase, SqliteDatabaseConfig};
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let config = SqliteDatabaseConfig {
+   ///    retry_on_busy: 5,
+   ///    ..Default::default()
+   /// };
+   /// let db = SqliteDatabase::connect("shared.db", Some(config)).await?;
+   /// db.write_transaction(async |conn| {
+   ///    sqlx::query("UPDATE counters SET n = n + 1").execute(&mut *conn).await?;
+   ///    Ok(())
+   /// })
+   /// .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn write_transaction<T, F>(&self, mut f: F) -> Result<T>
+   where
+      F: AsyncFnMut(&mut SqliteConnection) -> Result<T>,
+   {
+      let mut retries = 0;
+      loop {
+         let result = match self.acquire_writer().await {
+            Ok(mut writer) => writer.transaction(async |conn| f(conn).await).await,
+            Err(err) => Err(err),
+         };
+         match result {
+            Err(err) if err.is_retryable() && retries < self.retry_on_busy => {
+               let delay = BUSY_BACKOFF_BASE
+                  .saturating_mul(1 << retries.min(16))
+                  .min(BUSY_BACKOFF_MAX);
+               retries += 1;
+               warn!(
+                  "Write transaction busy, retry {}/{} in {:?}: {}",
+                  retries, self.retry_on_busy, delay, err
+               );
+               tokio::time::sleep(delay).await;
+            }
+            result => return result,
+         }
+      }
+   }
+
+   /// Checkpoint the WAL without closing the database
+   ///
+   /// Long-running apps can call this periodically (e.g. with `Truncate`) to keep
+   /// the `-wal` file from growing between opening and `close()`. The write
+   /// connection is held for the duration, so writers wait; `Full` and stronger
+   /// modes also wait on readers up to the busy timeout, reporting `busy` if they
+   /// could not finish.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::{CheckpointMode, SqliteDatabase};
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect("app.db", None).await?;
+   /// let stats = db.checkpoint(CheckpointMode::Truncate).await?;
+   /// if stats.busy {
+   ///    println!("checkpoint blocked by an active reader");
+   /// }
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn checkpoint(&self, mode: CheckpointMode) -> Result<CheckpointStats> {
+      let mut writer = self.acquire_writer().await?;
+
+      let sql = format!("PRAGMA wal_checkpoint({})", mode.as_sql());
+      let (busy, log_frames, checkpointed_frames): (i64, i64, i64) =
+         sqlx::query_as(&sql).fetch_one(&mut *writer).await?;
+
+      Ok(CheckpointStats {
+         busy: busy != 0,
+         log_frames,
+         checkpointed_frames,
+      })
+   }
+
+   /// Read the database's current journal mode (e.g. `"wal"`, `"delete"`, `"memory"`)
+   ///
+   /// Queries `PRAGMA journal_mode` on a read connection, so it reports the mode
+   /// actually in effect in the database file. WAL mode is only enabled on the
+   /// first `acquire_writer()`, so a database that has not been written to
+   /// through this instance (for example one that is only read) may still report
+   /// its previous mode. The mode is returned in lowercase, as SQLite reports it.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect("test.db", None).await?;
+   /// if db.journal_mode().await? != "wal" {
+   ///    println!("reads will block while a write is in progress");
+   /// }
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn journal_mode(&self) -> Result<String> {
+      let mut conn = self.read_pool()?.acquire().await?;
+
+      // A connection only notices that the file switched to WAL when it next opens
+      // a read transaction; the PRAGMA alone would report its stale mode.
+      sqlx::query("SELECT 1 FROM sqlite_schema LIMIT 1")
+         .fetch_optional(&mut *conn)
+         .await?;
+      let mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+         .fetch_one(&mut *conn)
+         .await?;
+      Ok(mode.to_lowercase())
+   }
+
+   /// Sample the current state of the read and write pools
+   ///
+   /// Reads pool counters and the size of the `-wal` file; no connection is
+   /// acquired. Checked-out connections are `size - idle`, so a read pool at
+   /// `read_max_connections` with no idle connections is exhausted. The read
+   /// fields are all zero when `max_read_connections` is 0, since reads then
+   /// use the write connection. Returns `Error::DatabaseClosed` after `close()`.
+   pub fn pool_snapshot(&self) -> Result<PoolSnapshot> {
+      if self.closed.load(Ordering::SeqCst) {
+         return Err(Error::DatabaseClosed);
+      }
+
+      let read_pool = (!self.reads_via_writer).then_some(&self.read_pool);
+      let write_pool = self.write_conn.as_ref();
+      Ok(PoolSnapshot {
+         read_size: read_pool.map_or(0, |pool| pool.size()),
+         read_idle: read_pool.map_or(0, |pool| pool.num_idle()),
+         read_max_connections: read_pool.map_or(0, |pool| pool.options().get_max_connections()),
+         write_size: write_pool.map_or(0, |pool| pool.size()),
+         write_idle: write_pool.map_or(0, |pool| pool.num_idle()),
+         write_max_connections: write_pool.map_or(0, |pool| pool.options().get_max_connections()),
+         wal_size: crate::metrics::wal_size(&self.path),
+      })
+   }
+
+   /// Replace SQLite's busy timeout with a custom busy handler on every connection
+   ///
+   /// When a connection finds the database locked, SQLite calls `handler` with the
+   /// number of times it has already been called for this lock (starting at 0).
+   /// Return `true` to retry or `false` to give up and fail with `SQLITE_BUSY`.
+   /// SQLite retries immediately, so sleep in the handler to back off; it runs on
+   /// sqlx's connection worker thread, never inside the async runtime.
+   ///
+   /// This replaces the default 5 second `busy_timeout` on every connection of
+   /// both pools, including ones already open: idle connections get the handler
+   /// when next acquired, and connections checked out right now once they are
+   /// acquired again. Calling it again replaces the previous handler.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   /// use std::time::Duration;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect("test.db", None).await?;
+   ///
+   /// // Exponential backoff, giving up after 10 retries
+   /// db.set_busy_handler(|retries| {
+   ///    std::thread::sleep(Duration::from_millis(1 << retries.min(8)));
+   ///    retries < 10
+   /// });
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub fn set_busy_handler(&self, handler: impl Fn(i32) -> bool + Send + Sync + 'static) {
+      self.busy_handlers.set(Box::new(handler));
+   }
+
+   /// Stream a [`PoolSnapshot`] every `interval`, for live dashboards
+   ///
+   /// The first snapshot is taken immediately. The stream holds only a weak
+   /// reference to the database and ends once it is closed or dropped. Sampling
+   /// is cheap (a few atomic loads and one `stat` of the WAL file per tick) and
+   /// only happens while the stream is polled; a slow consumer delays the next
+   /// sample rather than queueing them.
+   ///
+   /// # Panics
+   ///
+   /// Panics if `interval` is zero.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use futures_util::StreamExt;
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   /// use std::time::Duration;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect("app.db", None).await?;
+   /// let mut metrics = Box::pin(db.metrics_stream(Duration::from_secs(1)));
+   /// while let Some(snapshot) = metrics.next().await {
+   ///    println!("{} readers open, WAL is {} bytes", snapshot.read_size, snapshot.wal_size);
+   /// }
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub fn metrics_stream(
+      self: &Arc<Self>,
+      interval: Duration,
+   ) -> impl Stream<Item = PoolSnapshot> + Send + 'static {
+      crate::metrics::stream(self, interval)
+   }
+
+   /// Compute a content fingerprint of one table or of the whole database
+   ///
+   /// Hashes every row of `table` (or of every user table when `None`) so two
+   /// databases can be compared for identical contents without diffing them row
+   /// by row. Rows are ordered by their values, so insertion order and rowids do
+   /// not affect the result; table and column names do. SQLite internal tables and
+   /// `_sqlx_migrations` (which records install times) are skipped.
+   ///
+   /// The hash is stable across processes and platforms, but it is not
+   /// cryptographic. This reads every row (O(n) in database size) on a single read
+   /// connection inside one transaction, so it sees a consistent snapshot.
+   ///
+   /// Returns `Error::TableNotFound` if `table` does not exist.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let local = SqliteDatabase::connect("local.db", None).await?;
+   /// let synced = SqliteDatabase::connect("synced.db", None).await?;
+   /// let same = local.content_hash(None).await? == synced.content_hash(None).await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn content_hash(&self, table: Option<&str>) -> Result<u64> {
+      let mut conn = self.read_pool()?.acquire().await?;
+      crate::content_hash::content_hash(&mut conn, table).await
+   }
+
+   /// Warm the page cache and OS file cache by reading hot tables up front
+   ///
+   /// Grows a read connection's page cache to fit the database (capped at 64 MiB)
+   /// and reads every row of `tables`, or of every user table when `None`, so
+   /// early queries after startup do not pay for cold disk reads. This is a
+   /// best-effort warmup: the page cache belongs to the one pooled connection that
+   /// ran it (and is lost when that connection idles out), while the OS file cache
+   /// benefits every connection until memory pressure evicts it. Only table pages
+   /// are read, not index pages.
+   ///
+   /// Returns `Error::TableNotFound` if one of `tables` does not exist.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect("app.db", None).await?;
+   /// db.preload_into_cache(Some(&["contacts", "messages"])).await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn preload_into_cache(&self, tables: Option<&[&str]>) -> Result<()> {
+      let mut conn = self.read_pool()?.acquire().await?;
+      crate::preload::preload(&mut conn, tables).await
+   }
+
+   /// Copy the database into a new file using SQLite's online backup API
+   ///
+   /// Pages are copied in small batches on a read connection, so other reads
+   /// continue while the backup runs and writers are only briefly held up between
+   /// batches. `on_progress` is called after each batch with the number of pages
+   /// remaining and the total. If a write lands mid-backup, SQLite restarts the
+   /// copy, so `remaining` can go back up.
+   ///
+   /// Fails with `Error::Io` (`AlreadyExists`) if `dest` already exists. On a
+   /// failed step the partially written file is removed.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect("app.db", None).await?;
+   /// db.backup_to("app-backup.db", |p| {
+   ///    println!("{} of {} pages left", p.remaining, p.total);
+   /// })
+   /// .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn backup_to(
+      &self,
+      dest: impl AsRef<Path>,
+      on_progress: impl FnMut(crate::BackupProgress),
+   ) -> Result<()> {
+      let mut conn = self.read_pool()?.acquire().await?;
+      crate::backup::backup(&mut conn, dest.as_ref(), on_progress).await
    }
 
    /// Run database migrations using the provided migrator
@@ -306,14 +998,179 @@ impl SqliteDatabase {
    /// # }
    /// ```
    pub async fn run_migrations(&self, migrator: &sqlx::migrate::Migrator) -> Result<()> {
-      // Ensure WAL mode is initialized via acquire_writer
-      // (WriteGuard dropped immediately, returning connection to pool)
-      {
-         let _writer = self.acquire_writer().await?;
+      // Run on the writer, which initializes WAL mode on first use
+      let mut writer = self.acquire_writer().await?;
+      migrator.run_direct(&mut *writer).await?;
+
+      Ok(())
+   }
+
+   /// Run database migrations, reporting progress before and after each one
+   ///
+   /// Like [`run_migrations`](Self::run_migrations), but `on_progress` is called
+   /// with [`MigrationPhase::Started`](crate::MigrationPhase::Started) before each
+   /// pending migration and [`MigrationPhase::Applied`](crate::MigrationPhase::Applied)
+   /// once it is recorded, so apps can show "applying 3 of 7". Already applied
+   /// migrations are not reported; `total` counts only pending ones. The writer is
+   /// held for the whole run.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+   /// static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+   ///
+   /// let db = SqliteDatabase::connect("test.db", None).await?;
+   /// db.run_migrations_with_progress(&MIGRATOR, |p| {
+   ///    println!("{:?} {} of {}: {}", p.phase, p.index, p.total, p.description);
+   /// })
+   /// .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn run_migrations_with_progress(
+      &self,
+      migrator: &sqlx::migrate::Migrator,
+      on_progress: impl FnMut(crate::MigrationProgress),
+   ) -> Result<()> {
+      let mut writer = self.acquire_writer().await?;
+      crate::migration::run_with_progress(&mut writer, migrator, on_progress).await?;
+      Ok(())
+   }
+
+   /// List the migrations `run_migrations` would apply, without applying anything
+   ///
+   /// Returns, in migrator order, every up migration missing from
+   /// `_sqlx_migrations` as [`PendingMigration::Pending`](crate::PendingMigration::Pending),
+   /// and every applied migration whose SQL has changed since as
+   /// [`PendingMigration::Modified`](crate::PendingMigration::Modified); the latter
+   /// would make `run_migrations` fail. An empty list means the database is up to
+   /// date. Runs on the read pool, so it never waits for the writer and also works
+   /// on databases opened with `connect_readonly`.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::{PendingMigration, SqliteDatabase};
+   ///
+   /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+   /// static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+   ///
+   /// let db = SqliteDatabase::connect("test.db", None).await?;
+   /// for migration in db.pending_migrations(&MIGRATOR).await? {
+   ///    if let PendingMigration::Modified { version, .. } = migration {
+   ///       eprintln!("migration {version} was edited after it was applied");
+   ///    }
+   /// }
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn pending_migrations(
+      &self,
+      migrator: &sqlx::migrate::Migrator,
+   ) -> Result<Vec<crate::PendingMigration>> {
+      let mut conn = self.read_pool()?.acquire().await?;
+      Ok(crate::migration::pending(&mut conn, migrator).await?)
+   }
+
+   /// Revert applied migrations using their down scripts
+   ///
+   /// Undoes every applied migration newer than `target_version`, newest first, via
+   /// `Migrator::undo`. With `None`, only the most recently applied migration is
+   /// reverted. Each revert runs in its own transaction and removes the migration's
+   /// `_sqlx_migrations` row.
+   ///
+   /// Returns `Error::IrreversibleMigration` before reverting anything if one of
+   /// those migrations has no down script, so a revert never stops halfway across
+   /// a migration it cannot undo. Migrations need `.up.sql`/`.down.sql` pairs to be
+   /// reversible.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+   /// static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+   ///
+   /// let db = SqliteDatabase::connect("test.db", None).await?;
+   /// // Undo the latest migration
+   /// db.revert_migrations(&MIGRATOR, None).await?;
+   /// // Undo everything after version 3
+   /// db.revert_migrations(&MIGRATOR, Some(3)).await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn revert_migrations(
+      &self,
+      migrator: &sqlx::migrate::Migrator,
+      target_version: Option<i64>,
+   ) -> Result<()> {
+      use sqlx::migrate::Migrate;
+
+      let mut writer = self.acquire_writer().await?;
+      writer.ensure_migrations_table().await?;
+
+      let mut applied: Vec<i64> = writer
+         .list_applied_migrations()
+         .await?
+         .into_iter()
+         .map(|m| m.version)
+         .collect();
+      applied.sort_unstable();
+
+      // One step back: keep everything up to the second-newest applied migration
+      let target =
+         target_version.unwrap_or_else(|| applied.iter().rev().nth(1).copied().unwrap_or(i64::MIN));
+
+      if let Some(&version) = applied.iter().filter(|&&v| v > target).find(|&&v| {
+         !migrator
+            .iter()
+            .any(|m| m.version == v && m.migration_type.is_down_migration())
+      }) {
+         return Err(Error::IrreversibleMigration(version));
       }
 
-      // Migrator acquires its own connection from the write pool
-      migrator.run(&self.write_conn).await?;
+      migrator.undo(&mut *writer, target).await?;
+      Ok(())
+   }
+
+   /// Run database migrations while holding an exclusive lock on the database file
+   ///
+   /// Like [`run_migrations`](Self::run_migrations), but the whole run happens inside
+   /// a `BEGIN EXCLUSIVE` transaction. When several processes open the same file at
+   /// startup (e.g. two app instances), only one runs migrations; the others block on
+   /// the lock and then find every migration already applied, instead of racing to
+   /// apply the same migration twice.
+   ///
+   /// This serializes startup across processes: a waiting runner blocks for up to the
+   /// connection's busy timeout, after which it fails with `SQLITE_BUSY`. Each migration
+   /// is applied in a savepoint, so a failed migration rolls back the entire run.
+   /// Migrations marked `-- no-transaction` (e.g. `VACUUM`) cannot be run this way.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+   /// static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+   ///
+   /// let db = SqliteDatabase::connect("shared.db", None).await?;
+   /// db.run_migrations_locked(&MIGRATOR).await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn run_migrations_locked(&self, migrator: &sqlx::migrate::Migrator) -> Result<()> {
+      let mut writer = self.acquire_writer().await?;
+
+      // The exclusive transaction is the advisory lock; the migrator's own
+      // per-migration transactions nest inside it as savepoints
+      let mut tx = writer.begin_with("BEGIN EXCLUSIVE").await?;
+      migrator.run(&mut *tx).await?;
+      tx.commit().await?;
 
       Ok(())
    }
@@ -339,28 +1196,52 @@ impl SqliteDatabase {
    /// # }
    /// ```
    pub async fn close(self: Arc<Self>) -> Result<()> {
-      // Mark as closed
+      // Mark as closed, failing queued writers
       self.closed.store(true, Ordering::SeqCst);
+      if let Some(queue) = &self.write_queue {
+         queue.close();
+      }
 
       // Remove from registry
       if let Err(e) = uncache_database(&self.path).await {
          error!("Failed to remove database from cache: {}", e);
       }
 
-      // This will await all readers to be returned
-      self.read_pool.close().await;
+      // This will await all readers to be returned; a write pool doubling as the
+      // read pool is closed below, after the checkpoint
+      if !self.reads_via_writer {
+         self.read_pool.close().await;
+      }
 
       // Checkpoint WAL before closing the write connection to flush changes and truncate WAL file
       // Only attempt if WAL was initialized (write connection was used)
-      if self.wal_initialized.load(Ordering::SeqCst)
-         && let Ok(mut conn) = self.write_conn.acquire().await
-      {
-         let _ = sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
-            .execute(&mut *conn)
-            .await;
+      if let Some(write_conn) = &self.write_conn {
+         if self.wal_initialized.load(Ordering::SeqCst)
+            && let Ok(mut conn) = write_conn.acquire().await
+         {
+            if self.reads_via_writer {
+               let _ = sqlx::query("PRAGMA query_only = OFF")
+                  .execute(&mut *conn)
+                  .await;
+            }
+            let _ = sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+               .execute(&mut *conn)
+               .await;
+         }
+
+         write_conn.close().await;
       }
 
-      self.write_conn.close().await;
+      // Release the shared in-memory backing store, if any
+      let keepalive = self
+         .keepalive
+         .lock()
+         .unwrap_or_else(|e| e.into_inner())
+         .take();
+
+      if let Some(conn) = keepalive {
+         let _ = conn.close().await;
+      }
 
       Ok(())
    }
@@ -414,3 +1295,36 @@ impl SqliteDatabase {
       Ok(())
    }
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn test_optimize_throttle_runs_first_claim() {
+      let throttle = OptimizeThrottle::new(Duration::from_secs(600));
+
+      assert!(throttle.try_claim(Instant::now()));
+   }
+
+   #[test]
+   fn test_optimize_throttle_runs_at_most_once_within_window() {
+      let throttle = OptimizeThrottle::new(Duration::from_secs(600));
+      let start = Instant::now();
+
+      assert!(throttle.try_claim(start));
+      assert!(!throttle.try_claim(start));
+      assert!(!throttle.try_claim(start + Duration::from_secs(1)));
+      assert!(!throttle.try_claim(start + Duration::from_secs(599)));
+   }
+
+   #[test]
+   fn test_optimize_throttle_runs_again_after_window() {
+      let throttle = OptimizeThrottle::new(Duration::from_secs(600));
+      let start = Instant::now();
+
+      assert!(throttle.try_claim(start));
+      assert!(throttle.try_claim(start + Duration::from_secs(600)));
+      assert!(!throttle.try_claim(start + Duration::from_secs(601)));
+   }
+}
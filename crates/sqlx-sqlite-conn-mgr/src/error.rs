@@ -10,18 +10,34 @@ pub enum Error {
    #[error("IO error: {0}")]
    Io(#[from] std::io::Error),
 
-   /// Error from the sqlx library. Standard sqlx errors are converted to this variant
+   /// Error from the sqlx library. Standard sqlx errors are converted to this variant,
+   /// except lock contention, which becomes [`Error::Busy`]
    #[error("Sqlx error: {0}")]
-   Sqlx(#[from] sqlx::Error),
+   Sqlx(sqlx::Error),
+
+   /// Another connection holds a conflicting lock (`SQLITE_BUSY` or `SQLITE_LOCKED`),
+   /// past the busy timeout
+   ///
+   /// Transient: running the same operation again later usually succeeds.
+   #[error("Database is busy: {0}")]
+   Busy(sqlx::Error),
 
    /// Migration error from the sqlx migrate framework
    #[error("Migration error: {0}")]
    Migration(#[from] sqlx::migrate::MigrateError),
 
+   /// An applied migration that a revert would undo has no down script
+   #[error("Migration {0} has no down script and cannot be reverted")]
+   IrreversibleMigration(i64),
+
    /// Database has been closed and cannot be used
    #[error("Database has been closed")]
    DatabaseClosed,
 
+   /// The database was opened with `connect_readonly` and has no write connection
+   #[error("Database was opened read-only")]
+   ReadOnlyDatabase,
+
    /// Cannot attach a database as read-write to a read-only connection
    #[error("Cannot attach database as read-write to a read-only connection")]
    CannotAttachReadWriteToReader,
@@ -37,4 +53,63 @@ pub enum Error {
       "Database '{0}' appears multiple times in attached database list (would cause deadlock)"
    )]
    DuplicateAttachedDatabase(String),
+
+   /// `acquire_writer_timeout` gave up waiting for the write connection
+   #[error("Timed out after {0:?} waiting for the write connection")]
+   WriterAcquireTimeout(std::time::Duration),
+
+   /// A `WriteGuard::transaction` closure failed and the rollback failed too
+   #[error("Transaction failed: {transaction_error}; rollback also failed: {rollback_error}")]
+   TransactionRollbackFailed {
+      transaction_error: String,
+      rollback_error: String,
+   },
+
+   /// Table does not exist in the database
+   #[error("Table not found: {0}")]
+   TableNotFound(String),
+
+   /// A configured startup pragma has an invalid name or value
+   #[error("Invalid pragma: {0}")]
+   InvalidPragma(String),
+
+   /// The configuration or arguments passed to `connect*` are not usable together
+   #[error("Invalid configuration: {0}")]
+   InvalidConfig(String),
+
+   /// The SQLite online backup API reported an error
+   #[error("Backup failed: {0}")]
+   Backup(String),
+}
+
+impl From<sqlx::Error> for Error {
+   fn from(err: sqlx::Error) -> Self {
+      if is_busy(&err) {
+         Error::Busy(err)
+      } else {
+         Error::Sqlx(err)
+      }
+   }
+}
+
+impl Error {
+   /// Whether the operation failed on lock contention and may succeed if retried
+   pub fn is_retryable(&self) -> bool {
+      matches!(self, Error::Busy(_))
+   }
+}
+
+/// Whether `err` is a database error with primary code `SQLITE_BUSY` or `SQLITE_LOCKED`
+fn is_busy(err: &sqlx::Error) -> bool {
+   let Some(code) = err
+      .as_database_error()
+      .and_then(|e| e.code())
+      .and_then(|code| code.parse::<i32>().ok())
+   else {
+      return false;
+   };
+   matches!(
+      code & 0xff,
+      libsqlite3_sys::SQLITE_BUSY | libsqlite3_sys::SQLITE_LOCKED
+   )
 }
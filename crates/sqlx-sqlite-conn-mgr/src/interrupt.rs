@@ -0,0 +1,69 @@
+//! Deadlines for statements running on a connection
+//!
+//! Dropping a sqlx query future does not stop the statement: the connection's
+//! worker thread keeps stepping it, holding the connection (and any locks) until
+//! it finishes. [`with_interrupt_timeout`] calls `sqlite3_interrupt` instead,
+//! which makes the running statement fail with `SQLITE_INTERRUPT`.
+
+use std::future::Future;
+use std::time::Duration;
+
+use libsqlite3_sys::{sqlite3, sqlite3_interrupt};
+use sqlx::sqlite::SqliteConnection;
+
+use crate::Result;
+
+/// How often the interrupt is repeated until the query future completes
+///
+/// `sqlite3_interrupt` only affects statements that are already running, so an
+/// interrupt sent while the statement is still queued for the worker is lost.
+const INTERRUPT_RETRY: Duration = Duration::from_millis(10);
+
+/// Raw handle of a connection borrowed by [`with_interrupt_timeout`]
+struct RawHandle(*mut sqlite3);
+
+// SAFETY: the handle is only passed to `sqlite3_interrupt`, which SQLite allows
+// from any thread, and only while the connection is mutably borrowed (so open).
+unsafe impl Send for RawHandle {}
+
+impl RawHandle {
+   fn interrupt(&self) {
+      // SAFETY: see the `Send` impl
+      unsafe { sqlite3_interrupt(self.0) }
+   }
+}
+
+/// Run `query` on `conn`, interrupting it if it has not finished after `timeout`
+///
+/// Returns `Ok(None)` if the deadline passed. In that case the query is
+/// interrupted and awaited until it has actually stopped, so the connection is
+/// idle again when this returns; whatever the query returned is discarded.
+pub async fn with_interrupt_timeout<'c, T, F>(
+   conn: &'c mut SqliteConnection,
+   timeout: Duration,
+   query: impl FnOnce(&'c mut SqliteConnection) -> F,
+) -> Result<Option<T>>
+where
+   F: Future<Output = T>,
+{
+   let handle = {
+      let mut locked = conn.lock_handle().await?;
+      RawHandle(locked.as_raw_handle().as_ptr())
+   };
+
+   let fut = query(conn);
+   tokio::pin!(fut);
+   if let Ok(result) = tokio::time::timeout(timeout, &mut fut).await {
+      return Ok(Some(result));
+   }
+
+   loop {
+      handle.interrupt();
+      if tokio::time::timeout(INTERRUPT_RETRY, &mut fut)
+         .await
+         .is_ok()
+      {
+         return Ok(None);
+      }
+   }
+}
@@ -61,9 +61,17 @@
 //! - WAL mode is enabled lazily only when writes are needed
 //!
 mod attached;
+mod backup;
+mod busy;
+mod checkpoint;
 mod config;
+mod content_hash;
 mod database;
 mod error;
+mod interrupt;
+mod metrics;
+mod migration;
+mod preload;
 mod registry;
 mod write_guard;
 
@@ -72,9 +80,14 @@ pub use attached::{
    AttachedMode, AttachedReadConnection, AttachedSpec, AttachedWriteGuard,
    acquire_reader_with_attached, acquire_writer_with_attached,
 };
-pub use config::SqliteDatabaseConfig;
+pub use backup::BackupProgress;
+pub use checkpoint::{CheckpointMode, CheckpointStats};
+pub use config::{AutoVacuumMode, SqliteDatabaseConfig};
 pub use database::SqliteDatabase;
 pub use error::Error;
+pub use interrupt::with_interrupt_timeout;
+pub use metrics::PoolSnapshot;
+pub use migration::{MigrationPhase, MigrationProgress, PendingMigration};
 pub use write_guard::WriteGuard;
 
 // Re-export sqlx migrate types for convenience
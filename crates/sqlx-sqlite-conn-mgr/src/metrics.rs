@@ -0,0 +1,62 @@
+//! Periodic sampling of connection pool state
+//!
+//! [`SqliteDatabase::metrics_stream`] yields a [`PoolSnapshot`] per interval so a
+//! dashboard can be fed without polling. Each sample reads pool counters and
+//! stats the WAL file; it never touches a connection.
+
+use std::path::Path;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use futures_util::Stream;
+use serde::Serialize;
+use tokio::time::MissedTickBehavior;
+
+use crate::database::SqliteDatabase;
+
+/// Connection pool state at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PoolSnapshot {
+   /// Open read connections, idle or in use
+   pub read_size: u32,
+   /// Read connections currently idle in the pool
+   pub read_idle: usize,
+   /// Most read connections the pool will open (0 when reads use the writer)
+   pub read_max_connections: u32,
+   /// Open write connections (0 or 1)
+   pub write_size: u32,
+   /// Write connections currently idle in the pool (0 or 1)
+   pub write_idle: usize,
+   /// Most write connections the pool will open (1, or 0 for `connect_readonly`)
+   pub write_max_connections: u32,
+   /// Size of the `-wal` file in bytes (0 if it does not exist)
+   pub wal_size: u64,
+}
+
+/// Size of the WAL file next to `path`, or 0 if there is none
+pub(crate) fn wal_size(path: &Path) -> u64 {
+   let mut wal = path.as_os_str().to_owned();
+   wal.push("-wal");
+   std::fs::metadata(wal).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Stream of snapshots of `db`, one per `interval`
+///
+/// Holds only a weak reference, so the stream ends once the database is closed
+/// or dropped.
+pub(crate) fn stream(
+   db: &Arc<SqliteDatabase>,
+   interval: Duration,
+) -> impl Stream<Item = PoolSnapshot> + Send + 'static {
+   let mut ticker = tokio::time::interval(interval);
+   ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+   futures_util::stream::unfold(
+      (Arc::downgrade(db), ticker),
+      |(db, mut ticker): (Weak<SqliteDatabase>, _)| async move {
+         ticker.tick().await;
+         let snapshot = db.upgrade()?.pool_snapshot().ok()?;
+         Some((snapshot, (db, ticker)))
+      },
+   )
+}
@@ -0,0 +1,139 @@
+//! Migration runner reporting progress per migration, and a dry-run listing
+//! of what it would apply
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+use sqlx::sqlite::SqliteConnection;
+
+/// Whether a [`MigrationProgress`] is reported before or after its migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MigrationPhase {
+   /// About to apply the migration
+   Started,
+   /// The migration was applied and recorded in `_sqlx_migrations`
+   Applied,
+}
+
+/// Progress through the pending migrations of a
+/// [`run_migrations_with_progress`](crate::SqliteDatabase::run_migrations_with_progress) run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationProgress {
+   /// Position of this migration among the pending ones, starting at 1
+   pub index: usize,
+   /// Number of pending migrations in this run
+   pub total: usize,
+   /// Version of the migration
+   pub version: i64,
+   /// Description of the migration
+   pub description: String,
+   /// Whether this is reported before or after applying it
+   pub phase: MigrationPhase,
+}
+
+/// A migration that `run_migrations` would apply or reject, reported by
+/// [`pending_migrations`](crate::SqliteDatabase::pending_migrations)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum PendingMigration {
+   /// Not applied yet
+   Pending { version: i64, description: String },
+   /// Applied, but its SQL changed since; running migrations fails on it
+   Modified { version: i64, description: String },
+}
+
+/// List the up migrations of `migrator` that are not applied, or whose
+/// checksum differs from the applied one, in migrator order
+pub(crate) async fn pending(
+   conn: &mut SqliteConnection,
+   migrator: &Migrator,
+) -> Result<Vec<PendingMigration>, sqlx::Error> {
+   let has_table: bool = sqlx::query_scalar(
+      "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+   )
+   .fetch_one(&mut *conn)
+   .await?;
+   let applied: Vec<(i64, Vec<u8>)> = if has_table {
+      sqlx::query_as("SELECT version, checksum FROM _sqlx_migrations")
+         .fetch_all(&mut *conn)
+         .await?
+   } else {
+      Vec::new()
+   };
+
+   Ok(migrator
+      .iter()
+      .filter(|m| !m.migration_type.is_down_migration())
+      .filter_map(|m| {
+         let version = m.version;
+         let description = m.description.to_string();
+         match applied.iter().find(|(v, _)| *v == version) {
+            None => Some(PendingMigration::Pending {
+               version,
+               description,
+            }),
+            Some((_, checksum)) if **checksum != *m.checksum => Some(PendingMigration::Modified {
+               version,
+               description,
+            }),
+            Some(_) => None,
+         }
+      })
+      .collect())
+}
+
+/// Apply pending migrations of `migrator`, reporting before and after each
+///
+/// Same checks and bookkeeping as `Migrator::run`: a dirty or modified applied
+/// migration fails the run before anything is applied, and each migration runs
+/// in its own transaction unless marked `-- no-transaction`.
+pub(crate) async fn run_with_progress(
+   conn: &mut SqliteConnection,
+   migrator: &Migrator,
+   mut on_progress: impl FnMut(MigrationProgress),
+) -> Result<(), MigrateError> {
+   conn.ensure_migrations_table().await?;
+   if let Some(version) = conn.dirty_version().await? {
+      return Err(MigrateError::Dirty(version));
+   }
+
+   let applied = conn.list_applied_migrations().await?;
+   let known: HashSet<i64> = migrator.iter().map(|m| m.version).collect();
+   if !migrator.ignore_missing
+      && let Some(missing) = applied.iter().find(|m| !known.contains(&m.version))
+   {
+      return Err(MigrateError::VersionMissing(missing.version));
+   }
+
+   let mut pending = Vec::new();
+   for migration in migrator.iter() {
+      if migration.migration_type.is_down_migration() {
+         continue;
+      }
+      match applied.iter().find(|m| m.version == migration.version) {
+         Some(applied) if applied.checksum != migration.checksum => {
+            return Err(MigrateError::VersionMismatch(migration.version));
+         }
+         Some(_) => {}
+         None => pending.push(migration),
+      }
+   }
+
+   let total = pending.len();
+   for (index, migration) in (1..).zip(pending) {
+      let progress = |phase| MigrationProgress {
+         index,
+         total,
+         version: migration.version,
+         description: migration.description.to_string(),
+         phase,
+      };
+      on_progress(progress(MigrationPhase::Started));
+      conn.apply(migration).await?;
+      on_progress(progress(MigrationPhase::Applied));
+   }
+
+   Ok(())
+}
@@ -0,0 +1,72 @@
+//! Best-effort warmup of the page cache and OS file cache
+
+use crate::Result;
+use crate::error::Error;
+use futures_util::TryStreamExt;
+use sqlx::sqlite::SqliteConnection;
+
+/// Upper bound on the page cache a warmup grows a connection to (in KiB)
+const MAX_PRELOAD_CACHE_KIB: i64 = 64 * 1024;
+
+fn quote_name(name: &str) -> String {
+   format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Size the connection's page cache to fit the database (up to a cap), then read
+/// every row of `tables`, or of every user table, so their pages are cached
+pub(crate) async fn preload(conn: &mut SqliteConnection, tables: Option<&[&str]>) -> Result<()> {
+   let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+      .fetch_one(&mut *conn)
+      .await?;
+   let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+      .fetch_one(&mut *conn)
+      .await?;
+   let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size")
+      .fetch_one(&mut *conn)
+      .await?;
+
+   // Positive cache sizes are in pages, negative ones in KiB
+   let current_kib = if cache_size < 0 {
+      -cache_size
+   } else {
+      cache_size * page_size / 1024
+   };
+   let wanted_kib = (page_count * page_size / 1024).min(MAX_PRELOAD_CACHE_KIB);
+   if wanted_kib > current_kib {
+      sqlx::query(&format!("PRAGMA cache_size = -{wanted_kib}"))
+         .execute(&mut *conn)
+         .await?;
+   }
+
+   let tables: Vec<String> = match tables {
+      Some(names) => {
+         for name in names {
+            let exists: Option<i64> =
+               sqlx::query_scalar("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?")
+                  .bind(name)
+                  .fetch_optional(&mut *conn)
+                  .await?;
+            if exists.is_none() {
+               return Err(Error::TableNotFound(name.to_string()));
+            }
+         }
+         names.iter().map(|name| name.to_string()).collect()
+      }
+      None => {
+         sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' \
+             AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\' ORDER BY name",
+         )
+         .fetch_all(&mut *conn)
+         .await?
+      }
+   };
+
+   for name in &tables {
+      let sql = format!("SELECT * FROM {}", quote_name(name));
+      let mut rows = sqlx::query(&sql).fetch(&mut *conn);
+      while rows.try_next().await?.is_some() {}
+   }
+
+   Ok(())
+}
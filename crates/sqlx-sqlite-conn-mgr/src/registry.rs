@@ -26,25 +26,47 @@ pub fn is_memory_database(path: &Path) -> bool {
       || path_str.contains("mode=memory")
 }
 
+/// Check if a path is a shared-cache in-memory database URI
+/// (e.g. `file:name?mode=memory&cache=shared`)
+///
+/// Unlike a plain `:memory:` database, every connection opened with such a URI sees
+/// the same backing store, which lets the read pool and the writer share it.
+pub(crate) fn is_shared_memory_database(path: &Path) -> bool {
+   let path_str = path.to_str().unwrap_or("");
+   is_memory_database(path) && path_str.contains("cache=shared")
+}
+
+/// Registry key for `path`: the URI itself for shared in-memory databases,
+/// otherwise the canonical file path
+fn registry_key(path: &Path) -> std::io::Result<PathBuf> {
+   if is_shared_memory_database(path) {
+      Ok(path.to_path_buf())
+   } else {
+      canonicalize_path(path)
+   }
+}
+
 /// Get or open a SQLite database connection
 ///
 /// If a database is already connected, returns the cached instance.
 /// Otherwise, calls the provided factory function to create a new connection.
 ///
-/// Special case: `:memory:` databases should not be cached (each is unique)
+/// Special case: private in-memory databases (`:memory:`, or memory URIs without
+/// `cache=shared`) are not cached, since each is unique. Shared-cache ones are
+/// cached under their URI.
 pub async fn get_or_open_database<F, Fut>(path: &Path, factory: F) -> Result<Arc<SqliteDatabase>>
 where
    F: FnOnce() -> Fut,
    Fut: Future<Output = Result<SqliteDatabase>>,
 {
-   // Skip registry for in-memory databases - always create new
-   if is_memory_database(path) {
+   // Skip registry for private in-memory databases - always create new
+   if is_memory_database(path) && !is_shared_memory_database(path) {
       let db = factory().await?;
       return Ok(Arc::new(db));
    }
 
    // Canonicalize the path for consistent lookups
-   let canonical_path = canonicalize_path(path)?;
+   let canonical_path = registry_key(path)?;
 
    // Try to get existing database with read lock (allows concurrent reads)
    {
@@ -122,17 +144,17 @@ fn canonicalize_path(path: &Path) -> std::io::Result<PathBuf> {
 
 /// Remove a database from the cache
 ///
-/// Special case: `:memory:` databases are never in the registry
+/// Special case: private in-memory databases are never in the registry
 ///
 /// Returns an error if the path cannot be canonicalized
 pub async fn uncache_database(path: &Path) -> std::io::Result<()> {
-   // Skip registry for in-memory databases
-   if is_memory_database(path) {
+   // Skip registry for private in-memory databases
+   if is_memory_database(path) && !is_shared_memory_database(path) {
       return Ok(());
    }
 
    // Canonicalize path
-   let canonical_path = canonicalize_path(path)?;
+   let canonical_path = registry_key(path)?;
 
    let mut registry = registry().write().await;
    registry.remove(&canonical_path);
@@ -1,9 +1,12 @@
 //! WriteGuard for exclusive write access to the database
 
-use sqlx::Sqlite;
+use crate::Result;
+use crate::error::Error;
 use sqlx::pool::PoolConnection;
 use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Sqlite};
 use std::ops::{Deref, DerefMut};
+use tokio::sync::OwnedSemaphorePermit;
 
 /// RAII guard for exclusive write access to a database connection
 ///
@@ -35,12 +38,78 @@ use std::ops::{Deref, DerefMut};
 #[derive(Debug)]
 pub struct WriteGuard {
    conn: PoolConnection<Sqlite>,
+   /// Turn in the `fair_writes` queue, released after `conn` is dropped
+   _permit: Option<OwnedSemaphorePermit>,
 }
 
 impl WriteGuard {
    /// Create a new WriteGuard by taking ownership of a pool connection
-   pub(crate) fn new(conn: PoolConnection<Sqlite>) -> Self {
-      Self { conn }
+   pub(crate) fn new(conn: PoolConnection<Sqlite>, permit: Option<OwnedSemaphorePermit>) -> Self {
+      Self {
+         conn,
+         _permit: permit,
+      }
+   }
+
+   /// Run `f` inside `BEGIN IMMEDIATE`, committing if it returns `Ok`
+   ///
+   /// An `Err` from `f` rolls the transaction back and is returned as is; if the
+   /// rollback fails as well, `Error::TransactionRollbackFailed` carries both
+   /// errors. If `f` panics or the returned future is dropped before finishing,
+   /// the transaction is rolled back before the connection is used again.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use sqlx_sqlite_conn_mgr::SqliteDatabase;
+   ///
+   /// # async fn example() -> Result<(), sqlx_sqlite_conn_mgr::Error> {
+   /// let db = SqliteDatabase::connect("test.db", None).await?;
+   /// let mut writer = db.acquire_writer().await?;
+   /// let id: i64 = writer
+   ///     .transaction(async |conn| {
+   ///         sqlx::query("UPDATE accounts SET balance = balance - 10 WHERE id = 1")
+   ///             .execute(&mut *conn)
+   ///             .await?;
+   ///         let row: (i64,) = sqlx::query_as("INSERT INTO ledger (amount) VALUES (10) RETURNING id")
+   ///             .fetch_one(&mut *conn)
+   ///             .await?;
+   ///         Ok(row.0)
+   ///     })
+   ///     .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn transaction<T, F>(&mut self, f: F) -> Result<T>
+   where
+      F: AsyncFnOnce(&mut SqliteConnection) -> Result<T>,
+   {
+      // Dropping an unfinished `Transaction` queues a ROLLBACK on the connection,
+      // which covers panics and cancellation
+      let mut tx = self.conn.begin_with("BEGIN IMMEDIATE").await?;
+      match f(&mut tx).await {
+         Ok(value) => {
+            tx.commit().await?;
+            Ok(value)
+         }
+         Err(err) => match tx.rollback().await {
+            Ok(()) => Err(err),
+            Err(rollback_err) => Err(Error::TransactionRollbackFailed {
+               transaction_error: err.to_string(),
+               rollback_error: rollback_err.to_string(),
+            }),
+         },
+      }
+   }
+
+   /// Close the connection when this guard is dropped instead of returning it
+   /// to the pool
+   ///
+   /// Use this when the connection was left in a state that can't be undone,
+   /// e.g. a setting that could not be restored. The pool opens a fresh
+   /// connection, configured as usual, for the next writer.
+   pub fn close_on_drop(&mut self) {
+      self.conn.close_on_drop();
    }
 }
 
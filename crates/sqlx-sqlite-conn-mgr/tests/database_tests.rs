@@ -197,6 +197,7 @@ async fn test_custom_config() {
    let custom_config = SqliteDatabaseConfig {
       max_read_connections: 10,
       idle_timeout_secs: 60,
+      ..Default::default()
    };
 
    // Verify custom config is accepted and connection works
@@ -207,39 +208,1044 @@ async fn test_custom_config() {
    db.remove().await.unwrap();
 }
 
+#[tokio::test]
+async fn test_max_lifetime_replaces_connections() {
+   use std::time::Duration;
+
+   let temp_dir = TempDir::new().unwrap();
+   let config = SqliteDatabaseConfig {
+      max_read_connections: 1,
+      max_lifetime_secs: Some(1),
+      pragmas: vec![("cache_size".into(), "-4096".into())],
+      ..Default::default()
+   };
+   let db = SqliteDatabase::connect(temp_dir.path().join("lifetime.db"), Some(config))
+      .await
+      .unwrap();
+
+   // Change the pragma on the one read connection, so a replacement is visible
+   let mut conn = db.read_pool().unwrap().acquire().await.unwrap();
+   sqlx::query("PRAGMA cache_size = -1234")
+      .execute(&mut *conn)
+      .await
+      .unwrap();
+   drop(conn);
+
+   let mut conn = db.read_pool().unwrap().acquire().await.unwrap();
+   let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size")
+      .fetch_one(&mut *conn)
+      .await
+      .unwrap();
+   assert_eq!(
+      cache_size, -1234,
+      "connection is reused within its lifetime"
+   );
+   drop(conn);
+
+   tokio::time::sleep(Duration::from_millis(1100)).await;
+
+   // sqlx only checks the lifetime when a connection is released (or on its
+   // periodic reap), so cycle the idle connection once before checking
+   drop(db.read_pool().unwrap().acquire().await.unwrap());
+
+   let mut conn = db.read_pool().unwrap().acquire().await.unwrap();
+   let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size")
+      .fetch_one(&mut *conn)
+      .await
+      .unwrap();
+   assert_eq!(cache_size, -4096, "expired connection is replaced");
+   drop(conn);
+
+   db.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_busy_handler_invoked_under_contention() {
+   use sqlx::ConnectOptions;
+   use sqlx::sqlite::SqliteConnectOptions;
+   use std::sync::atomic::{AtomicI32, Ordering};
+
+   let temp_dir = TempDir::new().unwrap();
+   let path = temp_dir.path().join("busy.db");
+   let db = SqliteDatabase::connect(&path, None).await.unwrap();
+
+   // Open the writer (and enable WAL) before the handler is set
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+
+   let calls = Arc::new(AtomicI32::new(0));
+   let counter = Arc::clone(&calls);
+   db.set_busy_handler(move |retries| {
+      counter.fetch_add(1, Ordering::SeqCst);
+      retries < 3
+   });
+
+   // Hold the write lock from a connection outside the manager
+   let mut other = SqliteConnectOptions::new()
+      .filename(&path)
+      .connect()
+      .await
+      .unwrap();
+   sqlx::query("BEGIN IMMEDIATE")
+      .execute(&mut other)
+      .await
+      .unwrap();
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   let err = sqlx::query("INSERT INTO t DEFAULT VALUES")
+      .execute(&mut *writer)
+      .await
+      .unwrap_err();
+   assert!(
+      err.to_string().contains("locked"),
+      "unexpected error: {err}"
+   );
+   // Called for retries 0, 1, 2 (keep waiting) and 3 (give up)
+   assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+   sqlx::query("ROLLBACK").execute(&mut other).await.unwrap();
+   sqlx::query("INSERT INTO t DEFAULT VALUES")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+
+   db.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_zero_read_connections_read_through_writer() {
+   use std::time::Duration;
+
+   let temp_dir = TempDir::new().unwrap();
+   let path = temp_dir.path().join("single.db");
+   let config = SqliteDatabaseConfig {
+      max_read_connections: 0,
+      ..Default::default()
+   };
+   let db = SqliteDatabase::connect(&path, Some(config.clone()))
+      .await
+      .unwrap();
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO t DEFAULT VALUES")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+
+   // Reads wait for the writer while it is held
+   let pool = db.read_pool().unwrap().clone();
+   let read = tokio::spawn(async move {
+      sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM t")
+         .fetch_one(&pool)
+         .await
+   });
+   tokio::time::sleep(Duration::from_millis(100)).await;
+   assert!(!read.is_finished(), "read ran while the writer was held");
+
+   drop(writer);
+   assert_eq!(read.await.unwrap().unwrap(), 1);
+
+   // The shared connection only writes through a WriteGuard
+   let err = sqlx::query("INSERT INTO t DEFAULT VALUES")
+      .execute(db.read_pool().unwrap())
+      .await
+      .unwrap_err();
+   assert!(err.to_string().contains("readonly"), "{err}");
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("INSERT INTO t DEFAULT VALUES")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+   let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM t")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(count, 2);
+
+   let snapshot = db.pool_snapshot().unwrap();
+   assert_eq!(snapshot.read_max_connections, 0);
+   assert_eq!(snapshot.write_max_connections, 1);
+
+   db.close().await.unwrap();
+
+   // A read-only database cannot route reads through a writer
+   let err = SqliteDatabase::connect_readonly(&path, Some(config))
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::InvalidConfig(_)));
+}
+
+#[tokio::test]
+async fn test_path_accessors() {
+   let temp_dir = TempDir::new().unwrap();
+   let path = temp_dir.path().join("path.db");
+   let db = SqliteDatabase::connect(&path, None).await.unwrap();
+
+   assert_eq!(db.path(), path);
+   assert_eq!(db.path_str(), path.to_string_lossy());
+
+   db.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_foreign_keys() {
+   let temp_dir = TempDir::new().unwrap();
+
+   for enabled in [true, false] {
+      let config = SqliteDatabaseConfig {
+         foreign_keys: enabled,
+         ..Default::default()
+      };
+      let db = SqliteDatabase::connect(
+         temp_dir.path().join(format!("fk_{enabled}.db")),
+         Some(config),
+      )
+      .await
+      .unwrap();
+
+      let mut writer = db.acquire_writer().await.unwrap();
+      sqlx::query("CREATE TABLE parent (id INTEGER PRIMARY KEY)")
+         .execute(&mut *writer)
+         .await
+         .unwrap();
+      sqlx::query(
+         "CREATE TABLE child (id INTEGER PRIMARY KEY, parent_id INTEGER REFERENCES parent(id))",
+      )
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+
+      let orphan = sqlx::query("INSERT INTO child (parent_id) VALUES (42)")
+         .execute(&mut *writer)
+         .await;
+      assert_eq!(
+         orphan.is_err(),
+         enabled,
+         "foreign_keys = {enabled}: {orphan:?}"
+      );
+      drop(writer);
+
+      // Read connections get the pragma too
+      let pragma: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+         .fetch_one(db.read_pool().unwrap())
+         .await
+         .unwrap();
+      assert_eq!(pragma, enabled as i64);
+
+      db.remove().await.unwrap();
+   }
+}
+
+#[tokio::test]
+async fn test_auto_vacuum_mode_applied_to_new_database() {
+   use sqlx_sqlite_conn_mgr::AutoVacuumMode;
+
+   let temp_dir = TempDir::new().unwrap();
+   let config = SqliteDatabaseConfig {
+      auto_vacuum: Some(AutoVacuumMode::Incremental),
+      ..Default::default()
+   };
+   let db = SqliteDatabase::connect(temp_dir.path().join("vacuum.db"), Some(config))
+      .await
+      .unwrap();
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   // 2 = incremental
+   let mode: i64 = sqlx::query_scalar("PRAGMA auto_vacuum")
+      .fetch_one(&mut *writer)
+      .await
+      .unwrap();
+   assert_eq!(mode, 2);
+   drop(writer);
+   assert_eq!(db.journal_mode().await.unwrap(), "wal");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_startup_pragmas() {
+   let temp_dir = TempDir::new().unwrap();
+   let config = SqliteDatabaseConfig {
+      pragmas: vec![
+         ("cache_size".into(), "-4000".into()),
+         ("user_version".into(), "7".into()),
+         ("journal_mode".into(), "DELETE".into()),
+      ],
+      ..Default::default()
+   };
+   let db = SqliteDatabase::connect(temp_dir.path().join("pragmas.db"), Some(config))
+      .await
+      .unwrap();
+
+   // Connection-scoped pragmas reach fresh read connections
+   let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(cache_size, -4000);
+
+   // Database-scoped pragmas are set by the writer; journal_mode is left to WAL init
+   let mut writer = db.acquire_writer().await.unwrap();
+   let user_version: i64 = sqlx::query_scalar("PRAGMA user_version")
+      .fetch_one(&mut *writer)
+      .await
+      .unwrap();
+   assert_eq!(user_version, 7);
+   drop(writer);
+   assert_eq!(db.journal_mode().await.unwrap(), "wal");
+   db.remove().await.unwrap();
+
+   for (name, value) in [
+      ("cache_size; DROP TABLE t", "1"),
+      ("cache_size", "1; DROP TABLE t"),
+      ("writable_schema", "ON"),
+      ("locking_mode", "EXCLUSIVE"),
+   ] {
+      let config = SqliteDatabaseConfig {
+         pragmas: vec![(name.into(), value.into())],
+         ..Default::default()
+      };
+      let result = SqliteDatabase::connect(temp_dir.path().join("invalid.db"), Some(config)).await;
+      assert!(matches!(result, Err(Error::InvalidPragma(_))));
+   }
+}
+
+#[tokio::test]
+async fn test_database_scoped_pragmas_not_reapplied_on_reconnect() {
+   use std::time::Duration;
+
+   let temp_dir = TempDir::new().unwrap();
+   let config = SqliteDatabaseConfig {
+      max_lifetime_secs: Some(1),
+      pragmas: vec![
+         ("cache_size".into(), "-4096".into()),
+         ("user_version".into(), "7".into()),
+      ],
+      ..Default::default()
+   };
+   let db = SqliteDatabase::connect(temp_dir.path().join("reconnect.db"), Some(config))
+      .await
+      .unwrap();
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   let user_version: i64 = sqlx::query_scalar("PRAGMA user_version")
+      .fetch_one(&mut *writer)
+      .await
+      .unwrap();
+   assert_eq!(user_version, 7);
+   // The app manages user_version from here on
+   sqlx::query("PRAGMA user_version = 9")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("PRAGMA cache_size = -1234")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+
+   tokio::time::sleep(Duration::from_millis(1100)).await;
+   // Cycle the expired writer so the next acquire opens a new connection
+   drop(db.acquire_writer().await.unwrap());
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size")
+      .fetch_one(&mut *writer)
+      .await
+      .unwrap();
+   assert_eq!(cache_size, -4096, "writer reconnected with tuning pragmas");
+   let user_version: i64 = sqlx::query_scalar("PRAGMA user_version")
+      .fetch_one(&mut *writer)
+      .await
+      .unwrap();
+   assert_eq!(user_version, 9, "reconnect kept the app's user_version");
+   drop(writer);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_connect_readonly() {
+   let temp_dir = TempDir::new().unwrap();
+   let path = temp_dir.path().join("reference.db");
+
+   let missing = SqliteDatabase::connect_readonly(&path, None).await;
+   assert!(matches!(missing, Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound));
+   assert!(!path.exists());
+
+   // Build the reference database, then reopen it read-only
+   let db = SqliteDatabase::connect(&path, None).await.unwrap();
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("PRAGMA journal_mode = DELETE")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("CREATE TABLE terms (word TEXT)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO terms VALUES ('sqlite')")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+   db.close().await.unwrap();
+
+   let db = SqliteDatabase::connect_readonly(&path, None).await.unwrap();
+   let (word,): (String,) = sqlx::query_as("SELECT word FROM terms")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(word, "sqlite");
+
+   assert!(matches!(
+      db.acquire_writer().await,
+      Err(Error::ReadOnlyDatabase)
+   ));
+   let write = sqlx::query("INSERT INTO terms VALUES ('rejected')")
+      .execute(db.read_pool().unwrap())
+      .await;
+   assert!(write.is_err());
+   assert_eq!(db.pool_snapshot().unwrap().write_size, 0);
+
+   db.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_optimize_on_release() {
+   let temp_dir = TempDir::new().unwrap();
+   let test_path = temp_dir.path().join("test_optimize_on_release.db");
+
+   let config = SqliteDatabaseConfig {
+      optimize_on_release: true,
+      ..Default::default()
+   };
+
+   let db = SqliteDatabase::connect(&test_path, Some(config))
+      .await
+      .unwrap();
+
+   // Repeatedly acquire and release the writer; releases within the throttle
+   // window must not interfere with subsequent writes
+   for i in 0..5 {
+      let mut writer = db.acquire_writer().await.unwrap();
+      sqlx::query("CREATE TABLE IF NOT EXISTS items (id INTEGER PRIMARY KEY, value INTEGER)")
+         .execute(&mut *writer)
+         .await
+         .unwrap();
+      sqlx::query("INSERT INTO items (value) VALUES (?)")
+         .bind(i)
+         .execute(&mut *writer)
+         .await
+         .unwrap();
+   }
+
+   let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(count, 5);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_connect_memory_shares_data_between_writer_and_readers() {
+   let db = SqliteDatabase::connect_memory("test_connect_memory", None)
+      .await
+      .unwrap();
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO items (id) VALUES (1), (2)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+
+   let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(count, 2);
+
+   // Same name returns the cached instance
+   let again = SqliteDatabase::connect_memory("test_connect_memory", None)
+      .await
+      .unwrap();
+   assert!(Arc::ptr_eq(&db, &again));
+   drop(again);
+
+   // Other names are separate databases
+   let other = SqliteDatabase::connect_memory("test_connect_memory_other", None)
+      .await
+      .unwrap();
+   let exists: bool =
+      sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE name = 'items')")
+         .fetch_one(other.read_pool().unwrap())
+         .await
+         .unwrap();
+   assert!(!exists);
+   other.close().await.unwrap();
+
+   let err = SqliteDatabase::connect_memory("a?mode=rw", None)
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::InvalidConfig(_)));
+
+   db.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fair_writes_grants_writer_in_arrival_order() {
+   let temp_dir = TempDir::new().unwrap();
+   let config = SqliteDatabaseConfig {
+      fair_writes: true,
+      ..Default::default()
+   };
+   let db = SqliteDatabase::connect(temp_dir.path().join("fair.db"), Some(config))
+      .await
+      .unwrap();
+
+   let holder = db.acquire_writer().await.unwrap();
+   let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+   let mut waiters = Vec::new();
+   for i in 0..5 {
+      let db = Arc::clone(&db);
+      let order = Arc::clone(&order);
+      waiters.push(tokio::spawn(async move {
+         let _writer = db.acquire_writer().await.unwrap();
+         order.lock().unwrap().push(i);
+         tokio::task::yield_now().await;
+      }));
+      // Let this waiter join the queue before the next one arrives
+      tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+   }
+
+   drop(holder);
+   for waiter in waiters {
+      waiter.await.unwrap();
+   }
+   assert_eq!(*order.lock().unwrap(), [0, 1, 2, 3, 4]);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_write_transaction_retries_on_busy() {
+   use sqlx::ConnectOptions;
+   use sqlx::sqlite::SqliteConnectOptions;
+   use std::sync::atomic::{AtomicU32, Ordering};
+
+   let temp_dir = TempDir::new().unwrap();
+   let path = temp_dir.path().join("retry.db");
+   let config = SqliteDatabaseConfig {
+      retry_on_busy: 10,
+      ..Default::default()
+   };
+   let db = SqliteDatabase::connect(&path, Some(config)).await.unwrap();
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+
+   // Fail with SQLITE_BUSY at once instead of waiting out the busy timeout
+   db.set_busy_handler(|_| false);
+
+   // Hold the write lock from a connection outside the manager
+   let mut other = SqliteConnectOptions::new()
+      .filename(&path)
+      .connect()
+      .await
+      .unwrap();
+   sqlx::query("BEGIN IMMEDIATE")
+      .execute(&mut other)
+      .await
+      .unwrap();
+
+   // Without retries the contention surfaces as a retryable Busy error
+   let mut writer = db.acquire_writer().await.unwrap();
+   let err = writer.transaction(async |_| Ok(())).await.unwrap_err();
+   assert!(matches!(err, Error::Busy(_)), "unexpected error: {err}");
+   assert!(err.is_retryable());
+   drop(writer);
+
+   let release = tokio::spawn(async move {
+      tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+      sqlx::query("ROLLBACK").execute(&mut other).await.unwrap();
+   });
+
+   let runs = AtomicU32::new(0);
+   db.write_transaction(async |conn| {
+      runs.fetch_add(1, Ordering::SeqCst);
+      sqlx::query("INSERT INTO t DEFAULT VALUES")
+         .execute(&mut *conn)
+         .await?;
+      Ok(())
+   })
+   .await
+   .unwrap();
+   release.await.unwrap();
+   assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+   let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM t")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(count, 1);
+
+   db.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_acquire_writer_timeout() {
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("timeout.db"), None)
+      .await
+      .unwrap();
+   let timeout = std::time::Duration::from_millis(50);
+
+   let (held_tx, held_rx) = tokio::sync::oneshot::channel();
+   let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+   let holder = {
+      let db = Arc::clone(&db);
+      tokio::spawn(async move {
+         let _writer = db.acquire_writer().await.unwrap();
+         held_tx.send(()).unwrap();
+         let _ = release_rx.await;
+      })
+   };
+   held_rx.await.unwrap();
+
+   let started = std::time::Instant::now();
+   let err = db.acquire_writer_timeout(timeout).await.unwrap_err();
+   assert!(matches!(err, Error::WriterAcquireTimeout(waited) if waited == timeout));
+   assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+   release_tx.send(()).unwrap();
+   holder.await.unwrap();
+   let writer = db.acquire_writer_timeout(timeout).await.unwrap();
+   drop(writer);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_write_guard_transaction_commits_and_rolls_back() {
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("tx.db"), None)
+      .await
+      .unwrap();
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+
+   // Commit on Ok
+   let inserted = writer
+      .transaction(async |conn| {
+         let result = sqlx::query("INSERT INTO items (id) VALUES (1), (2)")
+            .execute(&mut *conn)
+            .await?;
+         Ok(result.rows_affected())
+      })
+      .await
+      .unwrap();
+   assert_eq!(inserted, 2);
+
+   // Roll back on Err, returning the closure's error
+   let err = writer
+      .transaction(async |conn| {
+         sqlx::query("INSERT INTO items (id) VALUES (3)")
+            .execute(&mut *conn)
+            .await?;
+         Err::<(), _>(Error::TableNotFound("boom".into()))
+      })
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::TableNotFound(ref t) if t == "boom"));
+   drop(writer);
+
+   // Roll back when the closure panics
+   let panicking = {
+      let db = Arc::clone(&db);
+      tokio::spawn(async move {
+         let mut writer = db.acquire_writer().await.unwrap();
+         writer
+            .transaction(async |conn| {
+               sqlx::query("INSERT INTO items (id) VALUES (4)")
+                  .execute(&mut *conn)
+                  .await?;
+               panic!("closure panicked");
+               #[allow(unreachable_code)]
+               Ok(())
+            })
+            .await
+      })
+   };
+   assert!(panicking.await.unwrap_err().is_panic());
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   let ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM items ORDER BY id")
+      .fetch_all(&mut *writer)
+      .await
+      .unwrap();
+   assert_eq!(ids, [1, 2]);
+   drop(writer);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_read_uncommitted() {
+   let config = SqliteDatabaseConfig {
+      read_uncommitted: true,
+      ..Default::default()
+   };
+
+   // Shared-cache memory database: readers can see the writer's open transaction
+   let db = SqliteDatabase::connect(
+      "file:test_read_uncommitted?mode=memory&cache=shared",
+      Some(config.clone()),
+   )
+   .await
+   .unwrap();
+   let pragma: i64 = sqlx::query_scalar("PRAGMA read_uncommitted")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(pragma, 1);
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("BEGIN").execute(&mut *writer).await.unwrap();
+   sqlx::query("INSERT INTO items (id) VALUES (1)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(count, 1);
+   sqlx::query("ROLLBACK").execute(&mut *writer).await.unwrap();
+   drop(writer);
+   db.close().await.unwrap();
+
+   // File database: the pragma is set but reads behave as usual
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("file.db"), Some(config))
+      .await
+      .unwrap();
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("BEGIN").execute(&mut *writer).await.unwrap();
+   sqlx::query("INSERT INTO items (id) VALUES (1)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(count, 0);
+   sqlx::query("COMMIT").execute(&mut *writer).await.unwrap();
+   drop(writer);
+
+   db.remove().await.unwrap();
+}
+
 #[tokio::test]
 async fn test_wal_mode_initialization() {
    let test_path = std::env::current_dir().unwrap().join("test_wal_mode.db");
    let db = SqliteDatabase::connect(&test_path, None).await.unwrap();
 
-   // Before first write, acquire writer which should initialize WAL
+   // Before first write, acquire writer which should initialize WAL
+   let mut writer = db.acquire_writer().await.unwrap();
+
+   // Check journal mode
+   let (mode,): (String,) = sqlx::query_as("PRAGMA journal_mode")
+      .fetch_one(&mut *writer)
+      .await
+      .unwrap();
+
+   assert_eq!(
+      mode.to_lowercase(),
+      "wal",
+      "Journal mode should be WAL after first acquire_writer"
+   );
+
+   // Check sync setting
+   let (sync,): (i32,) = sqlx::query_as("PRAGMA synchronous")
+      .fetch_one(&mut *writer)
+      .await
+      .unwrap();
+
+   assert_eq!(
+      sync, 1,
+      "Sync mode should be NORMAL after first acquire_writer"
+   );
+
+   drop(writer);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_checkpoint_truncates_wal() {
+   use sqlx_sqlite_conn_mgr::CheckpointMode;
+
+   let temp_dir = TempDir::new().unwrap();
+   let path = temp_dir.path().join("checkpoint.db");
+   let wal_path = temp_dir.path().join("checkpoint.db-wal");
+   let db = SqliteDatabase::connect(&path, None).await.unwrap();
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY, payload BLOB)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   for _ in 0..50 {
+      sqlx::query("INSERT INTO t (payload) VALUES (randomblob(4096))")
+         .execute(&mut *writer)
+         .await
+         .unwrap();
+   }
+   drop(writer);
+   assert!(std::fs::metadata(&wal_path).unwrap().len() > 0);
+
+   let stats = db.checkpoint(CheckpointMode::Truncate).await.unwrap();
+   assert!(!stats.busy);
+   assert_eq!(stats.log_frames, 0);
+   assert_eq!(stats.checkpointed_frames, 0);
+   assert_eq!(std::fs::metadata(&wal_path).unwrap().len(), 0);
+
+   let stats = db.checkpoint(CheckpointMode::Passive).await.unwrap();
+   assert!(!stats.busy);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_journal_mode() {
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("journal.db"), None)
+      .await
+      .unwrap();
+
+   // Fresh file: SQLite's default rollback journal until the first write
+   assert_eq!(db.journal_mode().await.unwrap(), "delete");
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+
+   assert_eq!(db.journal_mode().await.unwrap(), "wal");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_metrics_stream() {
+   use futures_util::StreamExt;
+   use std::time::Duration;
+
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("metrics.db"), None)
+      .await
+      .unwrap();
+
    let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   let reader = db.read_pool().unwrap().acquire().await.unwrap();
 
-   // Check journal mode
-   let (mode,): (String,) = sqlx::query_as("PRAGMA journal_mode")
-      .fetch_one(&mut *writer)
+   let snapshots: Vec<_> = db
+      .metrics_stream(Duration::from_millis(10))
+      .take(3)
+      .collect()
+      .await;
+   assert_eq!(snapshots.len(), 3);
+   for snapshot in &snapshots {
+      assert_eq!(snapshot.write_size, 1);
+      assert_eq!(snapshot.write_idle, 0);
+      assert!(snapshot.read_size >= 1);
+      assert!(snapshot.read_idle < snapshot.read_size as usize);
+      assert!(snapshot.wal_size > 0);
+   }
+
+   // Connections are returned to their pools asynchronously
+   drop(reader);
+   drop(writer);
+   tokio::time::sleep(Duration::from_millis(50)).await;
+   let snapshot = db.pool_snapshot().unwrap();
+   assert_eq!(snapshot.write_idle, 1);
+   assert_eq!(snapshot.read_idle, snapshot.read_size as usize);
+
+   // The stream ends once the database is closed
+   let metrics = db.metrics_stream(Duration::from_millis(10));
+   db.clone().close().await.unwrap();
+   assert_eq!(metrics.collect::<Vec<_>>().await.len(), 0);
+   assert!(matches!(db.pool_snapshot(), Err(Error::DatabaseClosed)));
+}
+
+#[tokio::test]
+async fn test_pool_snapshot_connection_counts() {
+   use std::time::Duration;
+
+   let temp_dir = TempDir::new().unwrap();
+   let config = SqliteDatabaseConfig {
+      max_read_connections: 4,
+      ..Default::default()
+   };
+   let db = SqliteDatabase::connect(temp_dir.path().join("stats.db"), Some(config))
       .await
       .unwrap();
 
-   assert_eq!(
-      mode.to_lowercase(),
-      "wal",
-      "Journal mode should be WAL after first acquire_writer"
-   );
+   let snapshot = db.pool_snapshot().unwrap();
+   assert_eq!(snapshot.read_max_connections, 4);
+   assert_eq!(snapshot.write_max_connections, 1);
+
+   let first = db.read_pool().unwrap().acquire().await.unwrap();
+   let second = db.read_pool().unwrap().acquire().await.unwrap();
+   let snapshot = db.pool_snapshot().unwrap();
+   assert!(snapshot.read_size >= 2);
+   assert_eq!(snapshot.read_size as usize - snapshot.read_idle, 2);
+
+   // Connections are returned to their pools asynchronously
+   drop(first);
+   drop(second);
+   tokio::time::sleep(Duration::from_millis(50)).await;
+   let snapshot = db.pool_snapshot().unwrap();
+   assert_eq!(snapshot.read_idle, snapshot.read_size as usize);
+   assert_eq!(snapshot.read_idle, 2);
+
+   let writer = db.acquire_writer().await.unwrap();
+   let snapshot = db.pool_snapshot().unwrap();
+   assert_eq!((snapshot.write_size, snapshot.write_idle), (1, 0));
+   drop(writer);
 
-   // Check sync setting
-   let (sync,): (i32,) = sqlx::query_as("PRAGMA synchronous")
-      .fetch_one(&mut *writer)
+   db.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_preload_into_cache() {
+   use std::time::{Duration, Instant};
+
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("preload.db"), None)
       .await
       .unwrap();
 
-   assert_eq!(
-      sync, 1,
-      "Sync mode should be NORMAL after first acquire_writer"
-   );
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE hot (id INTEGER PRIMARY KEY, payload TEXT)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query(
+      "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 5000) \
+       INSERT INTO hot (payload) SELECT hex(randomblob(100)) FROM n",
+   )
+   .execute(&mut *writer)
+   .await
+   .unwrap();
+   drop(writer);
+
+   let start = Instant::now();
+   db.preload_into_cache(Some(&["hot"])).await.unwrap();
+   let preload = start.elapsed();
+   db.preload_into_cache(None).await.unwrap();
+
+   // Lenient: a warm scan should not be dramatically slower than the warmup itself
+   let start = Instant::now();
+   let count: i64 = sqlx::query_scalar("SELECT COUNT(payload) FROM hot")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(count, 5000);
+   assert!(start.elapsed() <= preload * 4 + Duration::from_millis(100));
+
+   assert!(matches!(
+      db.preload_into_cache(Some(&["missing"])).await,
+      Err(Error::TableNotFound(_))
+   ));
 
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_backup_to_reports_progress() {
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("source.db"), None)
+      .await
+      .unwrap();
+
+   let mut writer = db.acquire_writer().await.unwrap();
+   sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY, data TEXT)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   // Enough data for several backup steps
+   sqlx::query(
+      "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 2000)
+       INSERT INTO t SELECT i, hex(randomblob(256)) FROM n",
+   )
+   .execute(&mut *writer)
+   .await
+   .unwrap();
    drop(writer);
 
+   let dest = temp_dir.path().join("backup.db");
+   let mut progress = Vec::new();
+   db.backup_to(&dest, |p| progress.push(p)).await.unwrap();
+
+   assert!(progress.len() > 1, "expected several progress reports");
+   let last = progress.last().unwrap();
+   assert_eq!(last.remaining, 0);
+   assert!(last.total > 0);
+
+   let backup = SqliteDatabase::connect(&dest, None).await.unwrap();
+   let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM t")
+      .fetch_one(backup.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(count, 2000);
+
+   // Never overwrites an existing file
+   assert!(db.backup_to(&dest, |_| {}).await.is_err());
+
+   backup.remove().await.unwrap();
    db.remove().await.unwrap();
 }
 
@@ -443,6 +1449,255 @@ async fn test_run_migrations_creates_schema() {
    db.remove().await.unwrap();
 }
 
+#[tokio::test]
+async fn test_run_migrations_with_progress_reports_each_migration() {
+   use sqlx_sqlite_conn_mgr::MigrationPhase;
+
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("progress.db"), None)
+      .await
+      .unwrap();
+   let (_dir, migrator) = create_migrations(&[
+      (
+         "create_users",
+         "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+      ),
+      (
+         "create_posts",
+         "CREATE TABLE posts (id INTEGER PRIMARY KEY);",
+      ),
+      ("create_tags", "CREATE TABLE tags (id INTEGER PRIMARY KEY);"),
+   ])
+   .await;
+
+   let mut events = Vec::new();
+   db.run_migrations_with_progress(&migrator, |p| {
+      events.push((p.index, p.total, p.description, p.phase))
+   })
+   .await
+   .unwrap();
+
+   let expected: Vec<_> = ["create users", "create posts", "create tags"]
+      .into_iter()
+      .zip(1..)
+      .flat_map(|(description, index)| {
+         [MigrationPhase::Started, MigrationPhase::Applied]
+            .map(|phase| (index, 3, description.to_string(), phase))
+      })
+      .collect();
+   assert_eq!(events, expected);
+
+   // Nothing pending on a second run
+   let mut calls = 0;
+   db.run_migrations_with_progress(&migrator, |_| calls += 1)
+      .await
+      .unwrap();
+   assert_eq!(calls, 0);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pending_migrations_lists_unapplied_and_modified() {
+   use sqlx_sqlite_conn_mgr::PendingMigration;
+
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("pending.db"), None)
+      .await
+      .unwrap();
+   let users = (
+      "create_users",
+      "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+   );
+   let posts = (
+      "create_posts",
+      "CREATE TABLE posts (id INTEGER PRIMARY KEY);",
+   );
+
+   // Nothing applied yet, not even the migrations table
+   let (_dir, migrator) = create_migrations(&[users, posts]).await;
+   let pending = db.pending_migrations(&migrator).await.unwrap();
+   assert_eq!(
+      pending,
+      [
+         PendingMigration::Pending {
+            version: 1,
+            description: "create users".into()
+         },
+         PendingMigration::Pending {
+            version: 2,
+            description: "create posts".into()
+         },
+      ]
+   );
+
+   // Some pending
+   let (_first_dir, first) = create_migrations(&[users]).await;
+   db.run_migrations(&first).await.unwrap();
+   let pending = db.pending_migrations(&migrator).await.unwrap();
+   assert_eq!(
+      pending,
+      [PendingMigration::Pending {
+         version: 2,
+         description: "create posts".into()
+      }]
+   );
+
+   // All applied, and nothing was applied by the checks
+   db.run_migrations(&migrator).await.unwrap();
+   assert!(db.pending_migrations(&migrator).await.unwrap().is_empty());
+
+   // Tampered checksum
+   let (_edited_dir, edited) = create_migrations(&[
+      (
+         "create_users",
+         "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);",
+      ),
+      posts,
+   ])
+   .await;
+   let pending = db.pending_migrations(&edited).await.unwrap();
+   assert_eq!(
+      pending,
+      [PendingMigration::Modified {
+         version: 1,
+         description: "create users".into()
+      }]
+   );
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_revert_migrations_drops_latest_table() {
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("revert.db"), None)
+      .await
+      .unwrap();
+
+   let dir = TempDir::new().unwrap();
+   for (file, sql) in [
+      (
+         "0001_users.up.sql",
+         "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+      ),
+      ("0001_users.down.sql", "DROP TABLE users;"),
+      (
+         "0002_posts.up.sql",
+         "CREATE TABLE posts (id INTEGER PRIMARY KEY);",
+      ),
+      ("0002_posts.down.sql", "DROP TABLE posts;"),
+   ] {
+      std::fs::write(dir.path().join(file), sql).unwrap();
+   }
+   let migrator = Migrator::new(dir.path()).await.unwrap();
+   db.run_migrations(&migrator).await.unwrap();
+
+   let tables = |db: Arc<SqliteDatabase>| async move {
+      let names: Vec<String> = sqlx::query_scalar(
+         "SELECT name FROM sqlite_master WHERE type = 'table' AND name IN ('users', 'posts') ORDER BY name",
+      )
+      .fetch_all(db.read_pool().unwrap())
+      .await
+      .unwrap();
+      names
+   };
+
+   db.revert_migrations(&migrator, None).await.unwrap();
+   assert_eq!(tables(Arc::clone(&db)).await, ["users"]);
+   let versions: Vec<i64> = sqlx::query_scalar("SELECT version FROM _sqlx_migrations")
+      .fetch_all(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(versions, [1]);
+
+   db.run_migrations(&migrator).await.unwrap();
+   db.revert_migrations(&migrator, Some(0)).await.unwrap();
+   assert!(tables(Arc::clone(&db)).await.is_empty());
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_revert_migrations_rejects_missing_down_script() {
+   let temp_dir = TempDir::new().unwrap();
+   let db = SqliteDatabase::connect(temp_dir.path().join("irreversible.db"), None)
+      .await
+      .unwrap();
+   let (_dir, migrator) = create_migrations(&[(
+      "create_users",
+      "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+   )])
+   .await;
+   db.run_migrations(&migrator).await.unwrap();
+
+   let err = db.revert_migrations(&migrator, None).await.unwrap_err();
+   assert!(matches!(err, Error::IrreversibleMigration(1)));
+   let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(count, 0);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_run_migrations_locked_concurrent() {
+   use sqlx::{ConnectOptions, Connection};
+
+   let path = std::env::current_dir()
+      .unwrap()
+      .join("test_migrations_locked.db");
+
+   let db = SqliteDatabase::connect(&path, None).await.unwrap();
+
+   let (_dir, migrator) = create_migrations(&[
+      (
+         "create_users",
+         "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+      ),
+      ("seed_users", "INSERT INTO users (id) VALUES (1), (2);"),
+   ])
+   .await;
+
+   // A second "app instance" using its own connection to the same file and
+   // the same locking protocol
+   let other_instance = async {
+      let mut conn = sqlx::sqlite::SqliteConnectOptions::new()
+         .filename(&path)
+         .connect()
+         .await
+         .unwrap();
+      let mut tx = conn.begin_with("BEGIN EXCLUSIVE").await.unwrap();
+      migrator.run(&mut *tx).await?;
+      tx.commit().await.unwrap();
+      conn.close().await.unwrap();
+      Ok::<(), sqlx::migrate::MigrateError>(())
+   };
+
+   let (ours, theirs) = tokio::join!(db.run_migrations_locked(&migrator), other_instance);
+   ours.expect("locked migration run should succeed");
+   theirs.expect("concurrent migration run should succeed");
+
+   let (applied,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_migrations")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(applied, 2, "each migration applied exactly once");
+
+   let (users,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(users, 2, "seed migration must not run twice");
+
+   // Re-running is a no-op
+   db.run_migrations_locked(&migrator).await.unwrap();
+
+   db.remove().await.unwrap();
+}
+
 #[tokio::test]
 async fn test_run_migrations_idempotent() {
    let path = std::env::current_dir()
@@ -541,3 +1796,130 @@ async fn test_run_migrations_with_invalid_sql_fails() {
 
    db.remove().await.unwrap();
 }
+
+#[tokio::test]
+async fn test_content_hash() {
+   let dir = TempDir::new().unwrap();
+
+   let mut dbs = Vec::new();
+   for (name, rows) in [
+      ("a.db", ["(1, 'x')", "(2, NULL)"]),
+      // Same rows, different insertion order
+      ("b.db", ["(2, NULL)", "(1, 'x')"]),
+   ] {
+      let db = SqliteDatabase::connect(dir.path().join(name), None)
+         .await
+         .unwrap();
+      let mut writer = db.acquire_writer().await.unwrap();
+      sqlx::query("CREATE TABLE items (id INTEGER, label TEXT)")
+         .execute(&mut *writer)
+         .await
+         .unwrap();
+      sqlx::query("CREATE TABLE tags (name TEXT)")
+         .execute(&mut *writer)
+         .await
+         .unwrap();
+      for row in rows {
+         sqlx::query(&format!("INSERT INTO items VALUES {row}"))
+            .execute(&mut *writer)
+            .await
+            .unwrap();
+      }
+      drop(writer);
+      dbs.push(db);
+   }
+   let (a, b) = (&dbs[0], &dbs[1]);
+
+   assert_eq!(
+      a.content_hash(None).await.unwrap(),
+      b.content_hash(None).await.unwrap()
+   );
+   assert_eq!(
+      a.content_hash(Some("items")).await.unwrap(),
+      b.content_hash(Some("items")).await.unwrap()
+   );
+
+   // Changing a value's type ('1' vs 1) or content changes the hash
+   let before = b.content_hash(None).await.unwrap();
+   let mut writer = b.acquire_writer().await.unwrap();
+   sqlx::query("UPDATE items SET label = '1' WHERE id = 2")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+   assert_ne!(before, b.content_hash(None).await.unwrap());
+   assert_ne!(
+      a.content_hash(Some("items")).await.unwrap(),
+      b.content_hash(Some("items")).await.unwrap()
+   );
+   // Untouched table still matches
+   assert_eq!(
+      a.content_hash(Some("tags")).await.unwrap(),
+      b.content_hash(Some("tags")).await.unwrap()
+   );
+
+   assert!(matches!(
+      a.content_hash(Some("missing")).await,
+      Err(Error::TableNotFound(_))
+   ));
+
+   for db in dbs {
+      db.remove().await.unwrap();
+   }
+}
+
+#[cfg(feature = "sqlcipher")]
+#[tokio::test]
+async fn test_encryption_key_rejects_wrong_key() {
+   let temp_dir = TempDir::new().unwrap();
+   let path = temp_dir.path().join("encrypted.db");
+   let config = |key: &str| SqliteDatabaseConfig {
+      encryption_key: Some(key.to_string()),
+      ..Default::default()
+   };
+
+   // A key with a quote checks the escaping
+   let db = SqliteDatabase::connect(&path, Some(config("it's secret")))
+      .await
+      .unwrap();
+   let mut writer = db.acquire_writer().await.unwrap();
+   let cipher_version: Option<String> = sqlx::query_scalar("PRAGMA cipher_version")
+      .fetch_optional(&mut *writer)
+      .await
+      .unwrap();
+   if cipher_version.is_none() {
+      eprintln!("skipping: SQLite is not linked against SQLCipher");
+      drop(writer);
+      db.remove().await.unwrap();
+      return;
+   }
+   sqlx::query("CREATE TABLE secrets (value TEXT)")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO secrets VALUES ('hidden')")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+   db.close().await.unwrap();
+
+   let db = SqliteDatabase::connect(&path, Some(config("wrong")))
+      .await
+      .unwrap();
+   let read = sqlx::query_scalar::<_, String>("SELECT value FROM secrets")
+      .fetch_one(db.read_pool().unwrap())
+      .await;
+   assert!(read.is_err());
+   db.close().await.unwrap();
+
+   let db = SqliteDatabase::connect(&path, Some(config("it's secret")))
+      .await
+      .unwrap();
+   let value: String = sqlx::query_scalar("SELECT value FROM secrets")
+      .fetch_one(db.read_pool().unwrap())
+      .await
+      .unwrap();
+   assert_eq!(value, "hidden");
+   db.remove().await.unwrap();
+}
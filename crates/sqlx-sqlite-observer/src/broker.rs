@@ -34,15 +34,57 @@
 //! to subscribers. On rollback, they are discarded without notification.
 
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, Weak};
+use std::time::{Instant, SystemTime};
 
 use parking_lot::{Mutex, RwLock};
-use tokio::sync::broadcast;
-use tracing::{debug, error, trace};
+use sqlx::{Pool, Sqlite};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, trace, warn};
 
-use crate::change::{ChangeOperation, ColumnValue, TableChange, TableInfo};
+use crate::change::{
+   ChangeOperation, ColumnValue, ObserverEvent, OpCounts, SchemaChange, TableChange,
+   TableChangeEvent, TableInfo, TransactionBatch, TransactionSummary,
+};
+use crate::compacting::{CompactingLog, CompactingState};
 use crate::hooks::{PreUpdateEvent, SqliteValue};
+use crate::latency::{LatencyStats, LatencyWindow};
+use crate::throttle::Throttle;
+
+/// Changes of the open transaction.
+#[derive(Default)]
+struct TxBuffer {
+   events: Vec<PreUpdateEvent>,
+   /// Set once the transaction outgrew `max_buffered`; rows are then counted
+   /// instead of kept
+   overflow: Option<Overflow>,
+}
+
+struct Overflow {
+   /// Capture time of the transaction's first change
+   started: Instant,
+   tables: HashMap<String, OpCounts>,
+}
+
+/// Position in the open transaction's buffer, taken when a savepoint is opened.
+///
+/// Returned by [`ObservationBroker::savepoint_mark`]; pass it to
+/// [`ObservationBroker::rollback_to_mark`] when the savepoint is rolled back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferMark {
+   events: usize,
+   schema_changes: usize,
+}
+
+impl TxBuffer {
+   fn len(&self) -> usize {
+      self.events.len()
+         + self.overflow.as_ref().map_or(0, |overflow| {
+            overflow.tables.values().map(OpCounts::total).sum()
+         })
+   }
+}
 
 /// Transaction-aware observation broker.
 ///
@@ -50,11 +92,58 @@ use crate::hooks::{PreUpdateEvent, SqliteValue};
 /// subscribers only after successful commit. Rolled-back transactions
 /// have their buffered changes discarded.
 pub struct ObservationBroker {
-   buffer: Mutex<Vec<PreUpdateEvent>>,
+   buffer: Mutex<TxBuffer>,
+   /// Changes buffered per transaction before it degrades to counts
+   max_buffered: AtomicUsize,
    change_tx: broadcast::Sender<TableChange>,
+   batch_tx: broadcast::Sender<TransactionBatch>,
+   summary_tx: broadcast::Sender<TransactionSummary>,
+   /// Whether commits are summarized on `summary_tx`
+   emit_summaries: AtomicBool,
+   /// Whether hooks install the authorizer that reports DDL
+   capture_schema: AtomicBool,
+   event_tx: broadcast::Sender<ObserverEvent>,
+   schema_tx: broadcast::Sender<SchemaChange>,
+   /// DDL seen by the authorizer in the current transaction
+   schema_buffer: Mutex<Vec<SchemaChange>>,
+   /// Sequence number of the next published change
+   next_seq: AtomicU64,
+   channel_capacity: usize,
+   /// Changes the broadcast channel failed to deliver to every subscriber
+   dropped: AtomicU64,
+   /// Set by `close()`; hooks ignore everything afterwards
+   closed: AtomicBool,
+   reliable_subscribers: Mutex<Vec<ReliableSubscriber>>,
+   compacting_logs: Mutex<Vec<Weak<Mutex<CompactingState>>>>,
    observed_tables: RwLock<HashSet<String>>,
+   /// Whether every non-internal table is observed, listed or not
+   observe_all: AtomicBool,
    table_info: RwLock<HashMap<String, TableInfo>>,
+   /// Tables whose cached `TableInfo` no longer matches their schema
+   stale_tables: Mutex<HashSet<String>>,
+   /// Tables altered by committed DDL since their `TableInfo` was cached
+   altered_tables: Mutex<HashSet<String>>,
+   commit_latencies: Mutex<LatencyWindow>,
+   /// Rate limit on broadcast changes, if set
+   throttle: Mutex<Option<Throttle>>,
+   throttle_flusher: OnceLock<()>,
    capture_values: bool,
+   /// Queue to the generated column resolver, when started; committed changes
+   /// are published from there instead of the commit hook.
+   resolver: OnceLock<mpsc::UnboundedSender<Vec<TableChange>>>,
+}
+
+/// Lossless subscriber registered via [`ObservationBroker::subscribe_reliable`].
+struct ReliableSubscriber {
+   /// Tables to deliver; empty means all observed tables.
+   tables: HashSet<String>,
+   tx: mpsc::Sender<TableChangeEvent>,
+}
+
+impl ReliableSubscriber {
+   fn wants(&self, table: &str) -> bool {
+      self.tables.is_empty() || self.tables.contains(table)
+   }
 }
 
 impl ObservationBroker {
@@ -70,18 +159,63 @@ impl ObservationBroker {
       // already validates before reaching this point.
       assert!(channel_capacity > 0, "channel_capacity must be at least 1");
       let (change_tx, _) = broadcast::channel(channel_capacity);
+      let (batch_tx, _) = broadcast::channel(channel_capacity);
+      let (summary_tx, _) = broadcast::channel(channel_capacity);
+      let (event_tx, _) = broadcast::channel(channel_capacity);
+      let (schema_tx, _) = broadcast::channel(channel_capacity);
       Arc::new(Self {
-         buffer: Mutex::new(Vec::new()),
+         buffer: Mutex::new(TxBuffer::default()),
+         max_buffered: AtomicUsize::new(usize::MAX),
          change_tx,
+         batch_tx,
+         summary_tx,
+         emit_summaries: AtomicBool::new(false),
+         capture_schema: AtomicBool::new(false),
+         event_tx,
+         schema_tx,
+         schema_buffer: Mutex::new(Vec::new()),
+         next_seq: AtomicU64::new(0),
+         channel_capacity,
+         dropped: AtomicU64::new(0),
+         closed: AtomicBool::new(false),
+         reliable_subscribers: Mutex::new(Vec::new()),
+         compacting_logs: Mutex::new(Vec::new()),
          observed_tables: RwLock::new(HashSet::new()),
+         observe_all: AtomicBool::new(false),
          table_info: RwLock::new(HashMap::new()),
+         stale_tables: Mutex::new(HashSet::new()),
+         altered_tables: Mutex::new(HashSet::new()),
+         commit_latencies: Mutex::new(LatencyWindow::default()),
+         throttle: Mutex::new(None),
+         throttle_flusher: OnceLock::new(),
          capture_values,
+         resolver: OnceLock::new(),
       })
    }
 
    /// Checks if a table is being observed.
+   ///
+   /// In all-tables mode, every table except internal `sqlite_%` and `_sqlx_%`
+   /// ones is observed.
    pub fn is_table_observed(&self, table: &str) -> bool {
-      self.observed_tables.read().contains(table)
+      (self.observes_all() && !crate::schema::is_internal_table(table))
+         || self.observed_tables.read().contains(table)
+   }
+
+   /// Observes every table, including ones created later, instead of only the
+   /// registered ones.
+   ///
+   /// Schema info is not queried from the hooks: tables are listed and their
+   /// `TableInfo` queried on the next acquire. Until then, changes to a table
+   /// created after that acquire are published without primary key values or
+   /// column names.
+   pub fn set_observe_all(&self, observe_all: bool) {
+      self.observe_all.store(observe_all, Ordering::Relaxed);
+   }
+
+   /// Checks if every non-internal table is observed.
+   pub fn observes_all(&self) -> bool {
+      self.observe_all.load(Ordering::Relaxed)
    }
 
    /// Registers a table for observation with its schema information.
@@ -125,6 +259,19 @@ impl ObservationBroker {
       }
    }
 
+   /// Stops observing a table.
+   ///
+   /// Subscribers are unaffected; they simply stop receiving changes for this
+   /// table. Changes already buffered in an open transaction are still published
+   /// on commit. Returns `true` if the table was being observed.
+   pub fn unobserve_table(&self, table: &str) -> bool {
+      trace!(table = %table, "Unobserving table");
+      self.table_info.write().remove(table);
+      self.stale_tables.lock().remove(table);
+      self.altered_tables.lock().remove(table);
+      self.observed_tables.write().remove(table)
+   }
+
    /// Sets the schema information for an observed table.
    ///
    /// This information is used to extract primary key values and determine
@@ -132,6 +279,8 @@ impl ObservationBroker {
    pub fn set_table_info(&self, table: &str, info: TableInfo) {
       trace!(table = %table, pk_columns = ?info.pk_columns, without_rowid = info.without_rowid, "Setting table info");
       self.table_info.write().insert(table.to_string(), info);
+      self.stale_tables.lock().remove(table);
+      self.altered_tables.lock().remove(table);
    }
 
    /// Gets the schema information for an observed table.
@@ -139,22 +288,249 @@ impl ObservationBroker {
       self.table_info.read().get(table).cloned()
    }
 
+   /// Checks if the schema information for `table` is missing, has drifted
+   /// from the live schema or was altered by DDL, so it should be (re-)queried.
+   pub fn needs_table_info(&self, table: &str) -> bool {
+      !self.table_info.read().contains_key(table)
+         || self.stale_tables.lock().contains(table)
+         || self.altered_tables.lock().contains(table)
+   }
+
    /// Returns a list of all observed tables.
    pub fn get_observed_tables(&self) -> Vec<String> {
       self.observed_tables.read().iter().cloned().collect()
    }
 
+   /// Starts the background task that fills in virtual generated column values
+   /// by re-reading changed rows from `pool` after commit.
+   ///
+   /// Does nothing if the resolver is already running or values are not captured.
+   /// Must be called from within a Tokio runtime.
+   pub(crate) fn start_generated_column_resolver(self: &Arc<Self>, pool: Pool<Sqlite>) {
+      if self.capture_values {
+         self
+            .resolver
+            .get_or_init(|| crate::generated::spawn_resolver(self, pool));
+      }
+   }
+
+   /// Limits broadcast change notifications to `limit` per second, or removes
+   /// the limit with `None`.
+   ///
+   /// Changes over the limit are not sent to [`subscribe`](Self::subscribe)
+   /// receivers; each one-second window that dropped changes is summarized by
+   /// one [`ObserverEvent::Throttled`]. Reliable subscribers, batches and
+   /// compacting logs still receive every change.
+   pub fn set_max_notifications_per_sec(&self, limit: Option<u32>) {
+      *self.throttle.lock() = limit.map(Throttle::new);
+   }
+
+   /// Enables or disables sending a [`TransactionSummary`] per commit.
+   pub fn set_emit_transaction_summary(&self, emit: bool) {
+      self.emit_summaries.store(emit, Ordering::Relaxed);
+   }
+
+   /// Enables or disables DDL detection on connections registered afterwards.
+   pub fn set_capture_schema_changes(&self, capture: bool) {
+      self.capture_schema.store(capture, Ordering::Relaxed);
+   }
+
+   /// Returns whether hooks should install the DDL-detecting authorizer.
+   pub fn captures_schema_changes(&self) -> bool {
+      self.capture_schema.load(Ordering::Relaxed)
+   }
+
+   /// Starts the background task that reports throttled windows once writes
+   /// stop, if a rate limit is set.
+   ///
+   /// Does nothing if the task is already running. Must be called from within
+   /// a Tokio runtime.
+   pub(crate) fn start_throttle_flusher(self: &Arc<Self>) {
+      if self.throttle.lock().is_some() {
+         self
+            .throttle_flusher
+            .get_or_init(|| crate::throttle::spawn_flusher(self));
+      }
+   }
+
+   /// Sends the summary of an ended window that dropped changes, if any.
+   pub(crate) fn flush_throttle(&self) {
+      let summary = self
+         .throttle
+         .lock()
+         .as_mut()
+         .and_then(|throttle| throttle.flush(Instant::now()));
+      if let Some(summary) = summary {
+         let _ = self.event_tx.send(summary);
+      }
+   }
+
+   /// Sends `change` to broadcast subscribers, subject to the rate limit.
+   fn broadcast(&self, change: TableChange) {
+      let (admitted, summary) = match self.throttle.lock().as_mut() {
+         Some(throttle) => throttle.admit(&change.table, Instant::now()),
+         None => (true, None),
+      };
+      if let Some(summary) = summary {
+         let _ = self.event_tx.send(summary);
+      }
+      if admitted {
+         // A send into a full channel evicts the oldest change, which some
+         // receiver has not seen yet and will miss as `Lagged`
+         let full = self.change_tx.len() >= self.channel_capacity;
+         if self.change_tx.send(change).is_err() || full {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+         }
+      }
+   }
+
+   /// Returns the number of changes the broadcast channel did not deliver to
+   /// every subscriber.
+   ///
+   /// Counts changes sent while no one was subscribed, and changes evicted
+   /// from a full channel before a slow receiver read them (which that receiver
+   /// sees as `RecvError::Lagged`). Changes withheld by
+   /// [`set_max_notifications_per_sec`](Self::set_max_notifications_per_sec)
+   /// are reported through [`ObserverEvent::Throttled`] instead.
+   pub fn dropped_count(&self) -> u64 {
+      self.dropped.load(Ordering::Relaxed)
+   }
+
+   /// Returns the number of changes recorded for the open transaction, waiting
+   /// for its commit or rollback, including ones only counted past
+   /// [`set_max_buffered_changes`](Self::set_max_buffered_changes).
+   pub fn pending_buffer_len(&self) -> usize {
+      self.buffer.lock().len()
+   }
+
+   /// Limits the changes buffered per transaction to `limit` (`None` for no
+   /// limit).
+   ///
+   /// A transaction exceeding it discards its buffered rows and only counts
+   /// further changes per table; on commit, one
+   /// [`ObserverEvent::BufferOverflowed`] is sent per table instead of the rows.
+   pub fn set_max_buffered_changes(&self, limit: Option<usize>) {
+      self
+         .max_buffered
+         .store(limit.unwrap_or(usize::MAX), Ordering::Relaxed);
+   }
+
+   /// Stops publishing and discards the buffer of any uncommitted transaction.
+   ///
+   /// Hooks still registered on connections become no-ops: later changes,
+   /// commits and schema changes are ignored. Subscribers keep their receivers
+   /// but receive nothing new.
+   pub fn close(&self) {
+      self.closed.store(true, Ordering::Release);
+      self.schema_buffer.lock().clear();
+      let count = std::mem::take(&mut *self.buffer.lock()).len();
+      debug!(discarded = count, "Observation broker closed");
+   }
+
+   /// Checks if [`close`](Self::close) has been called.
+   pub fn is_closed(&self) -> bool {
+      self.closed.load(Ordering::Acquire)
+   }
+
    /// Called by preupdate_hook - buffers the event for later processing.
    ///
    /// Events are held in the buffer until either `on_commit()` (publish)
    /// or `on_rollback()` (discard) is called.
+   ///
+   /// If the event's column count differs from the table's cached schema, the
+   /// table is marked for a schema re-query and [`ObserverEvent::SchemaDrift`]
+   /// is sent, once per drift. Past the buffer limit, the event is only counted.
    pub fn on_preupdate(&self, event: PreUpdateEvent) {
+      if self.is_closed() {
+         return;
+      }
       trace!(
           table = %event.table,
           operation = ?event.operation,
           "Buffering preupdate event"
       );
-      self.buffer.lock().push(event);
+      if self.has_drifted(&event) && self.stale_tables.lock().insert(event.table.clone()) {
+         warn!(
+            table = %event.table,
+            column_count = event.column_count,
+            "Observed table schema changed; table info will be re-queried"
+         );
+         let _ = self.event_tx.send(ObserverEvent::SchemaDrift {
+            table: event.table.clone(),
+         });
+      }
+
+      let limit = self.max_buffered.load(Ordering::Relaxed);
+      let mut buffer = self.buffer.lock();
+      if let Some(overflow) = &mut buffer.overflow {
+         overflow
+            .tables
+            .entry(event.table)
+            .or_default()
+            .add(Some(event.operation));
+      } else if buffer.events.len() < limit {
+         buffer.events.push(event);
+      } else {
+         warn!(
+            limit,
+            "Transaction exceeded max_buffered_changes; counting changes instead of buffering"
+         );
+         let events = std::mem::take(&mut buffer.events);
+         let mut overflow = Overflow {
+            started: events
+               .first()
+               .map_or(event.timestamp, |first| first.timestamp),
+            tables: HashMap::new(),
+         };
+         for event in events.into_iter().chain(std::iter::once(event)) {
+            overflow
+               .tables
+               .entry(event.table)
+               .or_default()
+               .add(Some(event.operation));
+         }
+         buffer.overflow = Some(overflow);
+      }
+   }
+
+   /// Checks if the event's column count contradicts the cached `TableInfo`.
+   ///
+   /// Only `main` is checked, since that is the schema `TableInfo` is read from.
+   fn has_drifted(&self, event: &PreUpdateEvent) -> bool {
+      event.schema == "main"
+         && self
+            .table_info
+            .read()
+            .get(&event.table)
+            .is_some_and(|info| info.column_count != 0 && info.column_count != event.column_count)
+   }
+
+   /// Called by the authorizer - buffers a DDL statement being prepared.
+   ///
+   /// Published by `on_commit()` or discarded by `on_rollback()`, like row
+   /// changes.
+   pub fn on_schema_change(&self, change: SchemaChange) {
+      if self.is_closed() {
+         return;
+      }
+      trace!(kind = ?change.kind, object = %change.object, "Buffering schema change");
+      self.schema_buffer.lock().push(change);
+   }
+
+   /// Publishes buffered schema changes, marking the cached `TableInfo` of
+   /// each affected observed table stale so the next acquire re-queries it.
+   fn flush_schema_changes(&self) {
+      let changes = std::mem::take(&mut *self.schema_buffer.lock());
+      for change in changes {
+         if change.kind.affects_table()
+            && change.schema == "main"
+            && self.is_table_observed(&change.object)
+         {
+            debug!(table = %change.object, kind = ?change.kind, "Observed table schema changed");
+            self.altered_tables.lock().insert(change.object.clone());
+         }
+         let _ = self.schema_tx.send(change);
+      }
    }
 
    /// Called by commit_hook - flushes buffered events to subscribers.
@@ -162,26 +538,187 @@ impl ObservationBroker {
    /// Converts all buffered `PreUpdateEvent`s to `TableChange`s and sends
    /// them through the broadcast channel. The buffer is cleared afterward.
    pub fn on_commit(&self) {
-      let events: Vec<PreUpdateEvent> = {
-         let mut buffer = self.buffer.lock();
-         std::mem::take(&mut *buffer)
-      };
+      if self.is_closed() {
+         return;
+      }
+      self.flush_schema_changes();
+
+      let TxBuffer { events, overflow } = std::mem::take(&mut *self.buffer.lock());
+      if let Some(overflow) = overflow {
+         self.publish_overflow(overflow);
+         return;
+      }
 
       if events.is_empty() {
          return;
       }
 
+      // Events are buffered in order, so the first one started the transaction's
+      // observed work
+      self
+         .commit_latencies
+         .lock()
+         .record(events[0].timestamp.elapsed());
+
       debug!(count = events.len(), "Flushing buffered changes on commit");
 
-      for event in events {
-         match self.event_to_change(event) {
-            Ok(table_change) => {
-               let _ = self.change_tx.send(table_change);
-            }
-            Err(e) => {
-               error!(error = %e, "Failed to convert event to change");
-            }
+      let changes: Vec<TableChange> = events
+         .into_iter()
+         .map(|event| self.event_to_change(event))
+         .collect();
+
+      let changes = match self.resolver.get() {
+         Some(resolver) => match resolver.send(changes) {
+            Ok(()) => return,
+            // Resolver task is gone (runtime shut down); publish unresolved
+            Err(mpsc::error::SendError(changes)) => changes,
+         },
+         None => changes,
+      };
+
+      self.publish_batch(&changes);
+
+      if self.reliable_subscribers.lock().is_empty() {
+         for table_change in changes {
+            self.broadcast(table_change);
          }
+         return;
+      }
+
+      for table_change in changes {
+         // Blocks the commit hook while a reliable receiver is full. The
+         // senders are cloned out first so the lock is not held while waiting,
+         // which would stall whoever is meant to drain that receiver.
+         for tx in self.reliable_targets(&table_change.table) {
+            let _ = tx.blocking_send(TableChangeEvent::Change(table_change.clone()));
+         }
+         self.broadcast(table_change);
+      }
+      self.prune_reliable_subscribers();
+   }
+
+   /// Senders of the reliable subscribers interested in `table`, cloned so
+   /// they can be awaited or blocked on without holding the lock.
+   fn reliable_targets(&self, table: &str) -> Vec<mpsc::Sender<TableChangeEvent>> {
+      self
+         .reliable_subscribers
+         .lock()
+         .iter()
+         .filter(|subscriber| subscriber.wants(table))
+         .map(|subscriber| subscriber.tx.clone())
+         .collect()
+   }
+
+   /// Drops reliable subscribers whose receiver has been dropped.
+   fn prune_reliable_subscribers(&self) {
+      self
+         .reliable_subscribers
+         .lock()
+         .retain(|subscriber| !subscriber.tx.is_closed());
+   }
+
+   /// Reports a transaction that outgrew the buffer limit: one
+   /// [`ObserverEvent::BufferOverflowed`] per table, its counts as the
+   /// transaction summary, and a [`TableChangeEvent::Lagged`] to each reliable
+   /// subscriber with the number of changes to its tables.
+   ///
+   /// The sequence still advances by the number of changes, so batch
+   /// subscribers see the skipped changes as a gap.
+   fn publish_overflow(&self, overflow: Overflow) {
+      self
+         .commit_latencies
+         .lock()
+         .record(overflow.started.elapsed());
+
+      let total: usize = overflow.tables.values().map(OpCounts::total).sum();
+      self.next_seq.fetch_add(total as u64, Ordering::Relaxed);
+      debug!(total, "Publishing counts of overflowed transaction");
+
+      let mut tables: Vec<(&String, &OpCounts)> = overflow.tables.iter().collect();
+      tables.sort_by_key(|(table, _)| *table);
+      for (table, counts) in &tables {
+         let _ = self.event_tx.send(ObserverEvent::BufferOverflowed {
+            table: (*table).clone(),
+            dropped: counts.total() as u64,
+         });
+      }
+
+      // Blocks like change delivery, so the gap arrives in commit order; the
+      // lock is released before blocking, as in `on_commit`
+      let targets: Vec<_> = self
+         .reliable_subscribers
+         .lock()
+         .iter()
+         .filter_map(|subscriber| {
+            let missed: usize = tables
+               .iter()
+               .filter(|(table, _)| subscriber.wants(table))
+               .map(|(_, counts)| counts.total())
+               .sum();
+            (missed > 0).then(|| (subscriber.tx.clone(), missed))
+         })
+         .collect();
+      for (tx, missed) in targets {
+         let _ = tx.blocking_send(TableChangeEvent::Lagged(missed as u64));
+      }
+      self.prune_reliable_subscribers();
+
+      if self.emit_summaries.load(Ordering::Relaxed) && self.summary_tx.receiver_count() > 0 {
+         let _ = self.summary_tx.send(TransactionSummary {
+            tables: overflow.tables,
+            total,
+         });
+      }
+   }
+
+   /// Publishes changes from an async task (the generated column resolver).
+   ///
+   /// Same delivery as [`on_commit`](Self::on_commit), but waits for full
+   /// reliable receivers asynchronously instead of blocking the thread.
+   pub(crate) async fn publish_async(&self, changes: Vec<TableChange>) {
+      self.publish_batch(&changes);
+
+      for table_change in changes {
+         for tx in self.reliable_targets(&table_change.table) {
+            let _ = tx
+               .send(TableChangeEvent::Change(table_change.clone()))
+               .await;
+         }
+         self.broadcast(table_change);
+      }
+      self.prune_reliable_subscribers();
+   }
+
+   /// Numbers one commit's changes, records them in compacting logs and sends
+   /// them to batch and summary subscribers.
+   ///
+   /// The sequence advances even without batch subscribers, so ranges stay
+   /// comparable across subscribers that join later.
+   fn publish_batch(&self, changes: &[TableChange]) {
+      let len = changes.len() as u64;
+      let start = self.next_seq.fetch_add(len, Ordering::Relaxed);
+
+      self
+         .compacting_logs
+         .lock()
+         .retain(|log| match log.upgrade() {
+            Some(state) => {
+               state.lock().record(changes, start, &self.table_info.read());
+               true
+            }
+            None => false,
+         });
+
+      if self.batch_tx.receiver_count() > 0 {
+         let _ = self.batch_tx.send(TransactionBatch {
+            changes: changes.to_vec(),
+            seq_range: start..start + len,
+            committed_at: SystemTime::now(),
+         });
+      }
+
+      if self.emit_summaries.load(Ordering::Relaxed) && self.summary_tx.receiver_count() > 0 {
+         let _ = self.summary_tx.send(TransactionSummary::of(changes));
       }
    }
 
@@ -189,18 +726,44 @@ impl ObservationBroker {
    ///
    /// Clears the buffer without publishing any changes to subscribers.
    pub fn on_rollback(&self) {
-      let count = {
-         let mut buffer = self.buffer.lock();
-         let count = buffer.len();
-         buffer.clear();
-         count
-      };
+      self.schema_buffer.lock().clear();
+      let count = std::mem::take(&mut *self.buffer.lock()).len();
 
       if count > 0 {
          debug!(count, "Discarding buffered changes on rollback");
       }
    }
 
+   /// Marks the current end of the transaction's buffer, for a savepoint being
+   /// opened.
+   ///
+   /// SQLite has no hook for savepoints, so whoever runs `SAVEPOINT` must take
+   /// the mark and hand it to [`rollback_to_mark`](Self::rollback_to_mark) on
+   /// `ROLLBACK TO`; otherwise rows the savepoint undid are still published on
+   /// commit.
+   pub fn savepoint_mark(&self) -> BufferMark {
+      BufferMark {
+         events: self.buffer.lock().events.len(),
+         schema_changes: self.schema_buffer.lock().len(),
+      }
+   }
+
+   /// Discards changes buffered since `mark`, for a `ROLLBACK TO` its savepoint.
+   ///
+   /// A transaction that already outgrew the buffer limit only keeps counts,
+   /// which cannot be taken back; those still include the rolled-back changes.
+   pub fn rollback_to_mark(&self, mark: BufferMark) {
+      self.schema_buffer.lock().truncate(mark.schema_changes);
+      let mut buffer = self.buffer.lock();
+      if buffer.overflow.is_none() && buffer.events.len() > mark.events {
+         debug!(
+            count = buffer.events.len() - mark.events,
+            "Discarding buffered changes rolled back to savepoint"
+         );
+         buffer.events.truncate(mark.events);
+      }
+   }
+
    /// Subscribes to change notifications.
    ///
    /// Returns a broadcast receiver that will receive `TableChange` events
@@ -209,9 +772,105 @@ impl ObservationBroker {
       self.change_tx.subscribe()
    }
 
+   /// Subscribes to committed transactions, one [`TransactionBatch`] per commit.
+   ///
+   /// The channel holds up to `channel_capacity` batches; a receiver that falls
+   /// further behind gets `RecvError::Lagged`.
+   pub fn subscribe_batches(&self) -> broadcast::Receiver<TransactionBatch> {
+      self.batch_tx.subscribe()
+   }
+
+   /// Subscribes to one [`TransactionSummary`] per committed transaction.
+   ///
+   /// Summaries are only sent when enabled with
+   /// [`set_emit_transaction_summary`](Self::set_emit_transaction_summary).
+   pub fn subscribe_summaries(&self) -> broadcast::Receiver<TransactionSummary> {
+      self.summary_tx.subscribe()
+   }
+
+   /// Returns commit latency statistics over recent observed transactions.
+   ///
+   /// See [`LatencyStats`]; only commits that changed an observed table count.
+   pub fn commit_latency_stats(&self) -> LatencyStats {
+      self.commit_latencies.lock().stats()
+   }
+
+   /// Subscribes to committed [`SchemaChange`]s (DDL statements).
+   pub fn subscribe_schema_changes(&self) -> broadcast::Receiver<SchemaChange> {
+      self.schema_tx.subscribe()
+   }
+
+   /// Subscribes to [`ObserverEvent`] status notifications, such as schema drift.
+   pub fn subscribe_events(&self) -> broadcast::Receiver<ObserverEvent> {
+      self.event_tx.subscribe()
+   }
+
+   /// Subscribes to change notifications through a bounded `mpsc` channel.
+   ///
+   /// Unlike [`subscribe`](Self::subscribe), no change is dropped silently: when
+   /// the receiver is full, the commit hook blocks until there is room. Only
+   /// changes to `tables` are delivered (all observed tables if empty).
+   ///
+   /// The one exception is a transaction exceeding
+   /// [`set_max_buffered_changes`](Self::set_max_buffered_changes), whose rows
+   /// are not kept: in their place, in commit order, the receiver gets
+   /// [`TableChangeEvent::Lagged`] with the number of changes to `tables` it
+   /// skipped, and should re-query them.
+   ///
+   /// Blocking happens on the thread running the commit (sqlx's connection
+   /// worker thread), so a slow consumer stalls every writer on that connection.
+   ///
+   /// # Panics
+   ///
+   /// Panics if `capacity` is 0.
+   pub fn subscribe_reliable(
+      &self,
+      tables: Vec<String>,
+      capacity: usize,
+   ) -> mpsc::Receiver<TableChangeEvent> {
+      assert!(capacity > 0, "capacity must be at least 1");
+      let (tx, rx) = mpsc::channel(capacity);
+      self.reliable_subscribers.lock().push(ReliableSubscriber {
+         tables: tables.into_iter().collect(),
+         tx,
+      });
+      rx
+   }
+
+   /// Creates a [`CompactingLog`] recording the latest change per row of
+   /// `tables` (all observed tables if empty).
+   pub fn compacting_log(&self, tables: Vec<String>) -> CompactingLog {
+      let log = CompactingLog::new(tables);
+      self.compacting_logs.lock().push(Arc::downgrade(&log.state));
+      log
+   }
+
    /// Converts a PreUpdateEvent to a TableChange for broadcast.
-   fn event_to_change(&self, event: PreUpdateEvent) -> crate::Result<TableChange> {
-      let table_info = self.table_info.read().get(&event.table).cloned();
+   ///
+   /// A change to a table whose schema no longer matches its cached `TableInfo`
+   /// is still converted, flagged `schema_stale` and without schema-derived
+   /// fields, rather than dropped.
+   fn event_to_change(&self, mut event: PreUpdateEvent) -> TableChange {
+      let mut table_info = self.table_info.read().get(&event.table).cloned();
+
+      // Column indices of a drifted or altered table's info may no longer line
+      // up with the values; keep only what ALTER TABLE cannot change
+      let mut schema_stale =
+         self.has_drifted(&event) || self.altered_tables.lock().contains(&event.table);
+      if schema_stale {
+         table_info = table_info.map(|info| TableInfo::new(Vec::new(), info.without_rowid));
+      }
+
+      if let Some(info) = &table_info
+         && !info.virtual_columns.is_empty()
+      {
+         event.old_values = event
+            .old_values
+            .map(|values| Self::to_declaration_order(values, &info.virtual_columns));
+         event.new_values = event
+            .new_values
+            .map(|values| Self::to_declaration_order(values, &info.virtual_columns));
+      }
 
       // For WITHOUT ROWID tables, the rowid from preupdate hook is not meaningful
       let rowid = match &table_info {
@@ -224,26 +883,40 @@ impl ObservationBroker {
       };
 
       // Extract primary key values from the appropriate column values
-      let primary_key = self.extract_primary_key(&event, table_info.as_ref())?;
+      let primary_key = match self.extract_primary_key(&event, table_info.as_ref()) {
+         Ok(primary_key) => primary_key,
+         Err(e) => {
+            warn!(error = %e, "Primary key out of bounds; table info will be re-queried");
+            self.stale_tables.lock().insert(event.table.clone());
+            schema_stale = true;
+            table_info = None;
+            Vec::new()
+         }
+      };
 
-      let (old_values, new_values) = if self.capture_values {
+      let (old_values, new_values, column_names) = if self.capture_values {
          (
             event.old_values.map(Self::values_to_vec),
             event.new_values.map(Self::values_to_vec),
+            table_info.map(|info| info.column_names).unwrap_or_default(),
          )
       } else {
-         (None, None)
+         (None, None, Vec::new())
       };
 
-      Ok(TableChange {
+      TableChange {
+         schema: event.schema,
          table: event.table,
          operation: Some(event.operation),
          rowid,
          primary_key,
          old_values,
          new_values,
+         column_names,
+         schema_stale,
          timestamp: Instant::now(),
-      })
+         captured_at: SystemTime::now(),
+      }
    }
 
    /// Extracts primary key values from the event based on table schema.
@@ -290,6 +963,29 @@ impl ObservationBroker {
       Ok(pk_values)
    }
 
+   /// Reorders preupdate values from storage order to declaration order.
+   ///
+   /// The preupdate hook indexes values by storage position: stored columns in
+   /// declaration order, followed by one uncomputed slot per virtual column. This
+   /// puts each stored value back at its declared index, with `Null` placeholders
+   /// for the virtual columns.
+   fn to_declaration_order(
+      values: Vec<SqliteValue>,
+      virtual_columns: &[usize],
+   ) -> Vec<SqliteValue> {
+      let len = values.len();
+      let mut stored = values.into_iter();
+      (0..len)
+         .map(|idx| {
+            if virtual_columns.contains(&idx) {
+               SqliteValue::Null
+            } else {
+               stored.next().unwrap_or(SqliteValue::Null)
+            }
+         })
+         .collect()
+   }
+
    /// Converts SqliteValue vec to ColumnValue vec for TableChange.
    fn values_to_vec(values: Vec<SqliteValue>) -> Vec<crate::change::ColumnValue> {
       values.into_iter().map(|v| v.into()).collect()
@@ -1,4 +1,8 @@
-use std::time::Instant;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::{Instant, SystemTime};
+
+use indexmap::IndexMap;
 
 use crate::hooks::SqliteValue;
 
@@ -16,6 +20,22 @@ pub struct TableInfo {
    /// of the PRIMARY KEY (coerced to i64), which may not be meaningful/correct for
    /// non-integer or composite primary keys.
    pub without_rowid: bool,
+   /// Column names in declaration order, so `column_names[i]` names the value at
+   /// index `i` of a change's `old_values`/`new_values`.
+   pub column_names: Vec<String>,
+   /// Indices of `GENERATED ALWAYS AS (...) VIRTUAL` columns, in declaration order.
+   ///
+   /// The preupdate hook does not compute virtual columns, so their values are
+   /// reported as `Null` unless
+   /// [`resolve_generated_columns`](crate::ObserverConfig::resolve_generated_columns)
+   /// is enabled.
+   pub virtual_columns: Vec<usize>,
+   /// Number of columns the preupdate hook reports for a row of this table, or 0
+   /// if unknown.
+   ///
+   /// A change whose column count differs means the table was altered after this
+   /// info was captured; see [`ObserverEvent::SchemaDrift`].
+   pub column_count: usize,
 }
 
 impl TableInfo {
@@ -24,11 +44,34 @@ impl TableInfo {
       Self {
          pk_columns,
          without_rowid,
+         column_names: Vec::new(),
+         virtual_columns: Vec::new(),
+         column_count: 0,
       }
    }
+
+   /// Sets the table's column names, in declaration order.
+   pub fn with_column_names(mut self, column_names: Vec<String>) -> Self {
+      self.column_names = column_names;
+      self
+   }
+
+   /// Sets the indices of the table's virtual generated columns.
+   pub fn with_virtual_columns(mut self, virtual_columns: Vec<usize>) -> Self {
+      self.virtual_columns = virtual_columns;
+      self
+   }
+
+   /// Sets the number of columns a row of the table has, virtual ones included.
+   pub fn with_column_count(mut self, column_count: usize) -> Self {
+      self.column_count = column_count;
+      self
+   }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum ChangeOperation {
    Insert,
    Update,
@@ -40,13 +83,21 @@ pub enum ChangeOperation {
 /// Represents a single column's value with its native SQLite type.
 /// This replaces the previous JSON string representation for better
 /// type safety and performance.
+///
+/// With the `serde` feature, values serialize as `{"type": "integer", "value": 1}`
+/// (`{"type": "null"}` for `Null`), with blobs base64-encoded.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+   feature = "serde",
+   serde(tag = "type", content = "value", rename_all = "camelCase")
+)]
 pub enum ColumnValue {
    Null,
    Integer(i64),
    Real(f64),
    Text(String),
-   Blob(Vec<u8>),
+   Blob(#[cfg_attr(feature = "serde", serde(with = "serde_impls::base64_blob"))] Vec<u8>),
 }
 
 impl From<SqliteValue> for ColumnValue {
@@ -100,7 +151,8 @@ impl ColumnValue {
    }
 }
 
-/// Event yielded by [`TableChangeStream`](crate::stream::TableChangeStream).
+/// Event yielded by [`TableChangeStream`](crate::stream::TableChangeStream) and
+/// `subscribe_reliable()` receivers.
 ///
 /// Most events are `Change` variants containing the actual table change data.
 /// A `Lagged` event indicates the consumer fell behind and missed some
@@ -119,18 +171,179 @@ pub enum TableChangeEvent {
    ///   channel capacity, causing older messages to be overwritten before
    ///   the consumer reads them.
    ///
+   /// For `subscribe_reliable` receivers, it means a transaction exceeded
+   /// [`max_buffered_changes`](crate::config::ObserverConfig::max_buffered_changes)
+   /// and `n` of its changes to the subscribed tables were not kept.
+   ///
    /// When this happens, the consumer should assume its local state may
    /// be stale and re-query the database for the current state.
    Lagged(u64),
 }
 
+/// Status notification about the observer itself, rather than about a change.
+///
+/// Received via `subscribe_events()` on the broker, observer or observable
+/// database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ObserverEvent {
+   /// A change to `table` had a different number of columns than its cached
+   /// [`TableInfo`], so the table was altered while being observed.
+   ///
+   /// Changes to the table are published without primary key values or column
+   /// names until the next connection acquire re-queries its schema.
+   SchemaDrift { table: String },
+
+   /// Broadcast notifications exceeded
+   /// [`max_notifications_per_sec`](crate::ObserverConfig::max_notifications_per_sec)
+   /// during one window, and `dropped` changes were not delivered.
+   ///
+   /// `tables` lists the tables of the dropped changes, sorted; re-query them
+   /// to catch up.
+   Throttled { dropped: u64, tables: Vec<String> },
+
+   /// A committed transaction changed more rows than
+   /// [`max_buffered_changes`](crate::ObserverConfig::max_buffered_changes), and
+   /// its `dropped` changes to `table` were counted instead of published.
+   ///
+   /// Sent once per table on commit. No row of the transaction is delivered to
+   /// any subscriber (reliable subscribers get a `TableChangeEvent::Lagged`
+   /// instead); re-query the table to catch up.
+   BufferOverflowed { table: String, dropped: u64 },
+}
+
+/// Kind of schema change reported by [`SchemaChange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[non_exhaustive]
+pub enum SchemaChangeKind {
+   CreateTable,
+   AlterTable,
+   DropTable,
+   CreateIndex,
+   DropIndex,
+   CreateView,
+   DropView,
+   CreateTrigger,
+   DropTrigger,
+}
+
+impl SchemaChangeKind {
+   /// Whether the change can alter a table's columns or primary key.
+   pub fn affects_table(self) -> bool {
+      matches!(
+         self,
+         SchemaChangeKind::CreateTable | SchemaChangeKind::AlterTable | SchemaChangeKind::DropTable
+      )
+   }
+}
+
+/// A committed DDL statement, such as `CREATE TABLE` or `ALTER TABLE`.
+///
+/// Received via `subscribe_schema_changes()`. Detected with an authorizer
+/// callback while the statement is prepared and published when its
+/// transaction commits, so a DDL statement that fails (for example creating a
+/// table that already exists) may still be reported at the next commit. Treat
+/// it as a hint to re-read the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemaChange {
+   pub kind: SchemaChangeKind,
+   /// Schema of the changed object: `"main"`, `"temp"` or an attached schema.
+   pub schema: String,
+   /// Name of the table, index, view or trigger.
+   pub object: String,
+}
+
+/// All changes published by one committed transaction, in statement order.
+///
+/// Every published change is numbered by a per-observer sequence that starts at
+/// 0. `seq_range` covers the changes of this commit, so consecutive batches have
+/// contiguous ranges; a gap means batches were missed because the consumer fell
+/// behind. When the stream is filtered by table, `changes` holds only the matching
+/// changes while `seq_range` still spans the whole commit, and commits to other
+/// tables leave gaps as well.
+#[derive(Debug, Clone)]
+pub struct TransactionBatch {
+   pub changes: Vec<TableChange>,
+   pub seq_range: Range<u64>,
+   /// Wall-clock time at which the transaction committed.
+   pub committed_at: SystemTime,
+}
+
+/// Number of rows changed by each operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpCounts {
+   pub inserts: usize,
+   pub updates: usize,
+   pub deletes: usize,
+}
+
+impl OpCounts {
+   pub(crate) fn add(&mut self, operation: Option<ChangeOperation>) {
+      match operation {
+         Some(ChangeOperation::Insert) => self.inserts += 1,
+         Some(ChangeOperation::Update) => self.updates += 1,
+         Some(ChangeOperation::Delete) => self.deletes += 1,
+         None => {}
+      }
+   }
+
+   /// Total number of rows changed.
+   pub fn total(&self) -> usize {
+      self.inserts + self.updates + self.deletes
+   }
+}
+
+/// Per-table change counts of one committed transaction.
+///
+/// Sent once per commit when
+/// [`emit_transaction_summary`](crate::ObserverConfig::emit_transaction_summary)
+/// is enabled, so a consumer can refresh once per transaction instead of once
+/// per row. `tables` is keyed by table name; changes to same-named tables in
+/// attached schemas are counted together.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionSummary {
+   pub tables: HashMap<String, OpCounts>,
+   /// Number of changes in the transaction, across all tables.
+   pub total: usize,
+}
+
+impl TransactionSummary {
+   pub(crate) fn of(changes: &[TableChange]) -> Self {
+      let mut tables: HashMap<String, OpCounts> = HashMap::new();
+      for change in changes {
+         tables
+            .entry(change.table.clone())
+            .or_default()
+            .add(change.operation);
+      }
+      Self {
+         tables,
+         total: changes.len(),
+      }
+   }
+}
+
 /// Notification of a change to a database table.
 ///
-/// Contains the table name, operation type, affected rowid, and the
+/// Contains the schema and table name, operation type, affected rowid, and the
 /// old/new column values (when available). Changes are only sent after
 /// the transaction commits successfully.
+///
+/// With the `serde` feature, fields serialize in camelCase, `captured_at` as
+/// milliseconds since the Unix epoch, and the monotonic `timestamp` is skipped
+/// (it is reset to the time of deserialization).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct TableChange {
+   /// Schema the changed table belongs to: `"main"` for the connection's own
+   /// database, or the schema name of an attached database.
+   pub schema: String,
    pub table: String,
    pub operation: Option<ChangeOperation>,
    /// The SQLite internal rowid. This is `None` for WITHOUT ROWID tables
@@ -143,9 +356,201 @@ pub struct TableChange {
    pub primary_key: Vec<ColumnValue>,
    /// Column values before the change (for UPDATE and DELETE).
    /// Values are ordered by column index as defined in the table schema.
+   #[cfg_attr(
+      feature = "serde",
+      serde(default, skip_serializing_if = "Option::is_none")
+   )]
    pub old_values: Option<Vec<ColumnValue>>,
    /// Column values after the change (for INSERT and UPDATE).
    /// Values are ordered by column index as defined in the table schema.
+   #[cfg_attr(
+      feature = "serde",
+      serde(default, skip_serializing_if = "Option::is_none")
+   )]
    pub new_values: Option<Vec<ColumnValue>>,
+   /// Column names of the table, in the same order as `old_values`/`new_values`.
+   /// Empty when values are not captured or the table's schema is unknown.
+   #[cfg_attr(feature = "serde", serde(default))]
+   pub column_names: Vec<String>,
+   /// Whether the table's schema changed since its `TableInfo` was cached, so
+   /// `primary_key` and `column_names` were left empty. Re-read the row; the
+   /// schema is re-queried on the next acquire.
+   #[cfg_attr(feature = "serde", serde(default))]
+   pub schema_stale: bool,
+   /// Monotonic time at which the change was captured, for measuring delays.
+   #[cfg_attr(feature = "serde", serde(skip, default = "Instant::now"))]
    pub timestamp: Instant,
+   /// Wall-clock time at which the change was captured.
+   #[cfg_attr(feature = "serde", serde(with = "serde_impls::epoch_millis"))]
+   pub captured_at: SystemTime,
+}
+
+impl TableChange {
+   /// Column values before the change, keyed by column name.
+   ///
+   /// Returns `None` when there are no old values (e.g. for INSERT), the column
+   /// names are unknown, or the number of values no longer matches the captured
+   /// column names (the table was altered since it was observed).
+   pub fn old_values_named(&self) -> Option<IndexMap<String, ColumnValue>> {
+      self.zip_named(self.old_values.as_ref()?)
+   }
+
+   /// Column values after the change, keyed by column name.
+   ///
+   /// Returns `None` when there are no new values (e.g. for DELETE), the column
+   /// names are unknown, or the number of values no longer matches the captured
+   /// column names (the table was altered since it was observed).
+   pub fn new_values_named(&self) -> Option<IndexMap<String, ColumnValue>> {
+      self.zip_named(self.new_values.as_ref()?)
+   }
+
+   fn zip_named(&self, values: &[ColumnValue]) -> Option<IndexMap<String, ColumnValue>> {
+      if self.column_names.is_empty() || self.column_names.len() != values.len() {
+         return None;
+      }
+      Some(
+         self
+            .column_names
+            .iter()
+            .cloned()
+            .zip(values.iter().cloned())
+            .collect(),
+      )
+   }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+   pub mod base64_blob {
+      use base64::Engine;
+      use base64::engine::general_purpose::STANDARD;
+      use serde::{Deserialize, Deserializer, Serializer};
+
+      pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+         serializer.serialize_str(&STANDARD.encode(bytes))
+      }
+
+      pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+         let encoded = String::deserialize(deserializer)?;
+         STANDARD.decode(encoded).map_err(serde::de::Error::custom)
+      }
+   }
+
+   pub mod epoch_millis {
+      use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+      use serde::{Deserialize, Deserializer, Serializer};
+
+      pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+         let millis = time
+            .duration_since(UNIX_EPOCH)
+            .map_err(serde::ser::Error::custom)?
+            .as_millis();
+         serializer.serialize_u64(millis as u64)
+      }
+
+      pub fn deserialize<'de, D: Deserializer<'de>>(
+         deserializer: D,
+      ) -> Result<SystemTime, D::Error> {
+         let millis = u64::deserialize(deserializer)?;
+         Ok(UNIX_EPOCH + Duration::from_millis(millis))
+      }
+   }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+   use std::time::{Duration, UNIX_EPOCH};
+
+   use super::*;
+
+   fn change(
+      operation: ChangeOperation,
+      old_values: Option<Vec<ColumnValue>>,
+      new_values: Option<Vec<ColumnValue>>,
+   ) -> TableChange {
+      TableChange {
+         schema: "main".into(),
+         table: "users".into(),
+         operation: Some(operation),
+         rowid: Some(7),
+         primary_key: vec![ColumnValue::Integer(7)],
+         old_values,
+         new_values,
+         column_names: vec!["id".into(), "score".into(), "avatar".into()],
+         schema_stale: false,
+         timestamp: Instant::now(),
+         captured_at: UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+      }
+   }
+
+   fn assert_round_trips(change: TableChange) {
+      let json = serde_json::to_value(&change).unwrap();
+      let decoded: TableChange = serde_json::from_value(json).unwrap();
+
+      assert_eq!(decoded.schema, change.schema);
+      assert_eq!(decoded.table, change.table);
+      assert_eq!(decoded.operation, change.operation);
+      assert_eq!(decoded.rowid, change.rowid);
+      assert_eq!(decoded.primary_key, change.primary_key);
+      assert_eq!(decoded.old_values, change.old_values);
+      assert_eq!(decoded.new_values, change.new_values);
+      assert_eq!(decoded.column_names, change.column_names);
+      assert_eq!(decoded.captured_at, change.captured_at);
+   }
+
+   #[test]
+   fn test_insert_round_trips() {
+      let values = vec![
+         ColumnValue::Integer(7),
+         ColumnValue::Real(1.5),
+         ColumnValue::Blob(vec![1, 2]),
+      ];
+      let insert = change(ChangeOperation::Insert, None, Some(values));
+
+      let json = serde_json::to_value(&insert).unwrap();
+      assert_eq!(json["operation"], "insert");
+      assert_eq!(json["capturedAt"], 1_700_000_000_123u64);
+      assert_eq!(
+         json["newValues"][2],
+         serde_json::json!({"type": "blob", "value": "AQI="})
+      );
+      assert!(json.get("oldValues").is_none());
+      assert!(json.get("timestamp").is_none());
+
+      assert_round_trips(insert);
+   }
+
+   #[test]
+   fn test_update_round_trips() {
+      let old = vec![
+         ColumnValue::Integer(7),
+         ColumnValue::Null,
+         ColumnValue::Text("a.png".into()),
+      ];
+      let new = vec![
+         ColumnValue::Integer(7),
+         ColumnValue::Real(2.0),
+         ColumnValue::Null,
+      ];
+      assert_round_trips(change(ChangeOperation::Update, Some(old), Some(new)));
+   }
+
+   #[test]
+   fn test_delete_round_trips() {
+      let old = vec![
+         ColumnValue::Integer(7),
+         ColumnValue::Real(-0.5),
+         ColumnValue::Blob(Vec::new()),
+      ];
+      let delete = change(ChangeOperation::Delete, Some(old), None);
+
+      let json = serde_json::to_value(&delete).unwrap();
+      assert_eq!(
+         json["oldValues"][0],
+         serde_json::json!({"type": "integer", "value": 7})
+      );
+
+      assert_round_trips(delete);
+   }
 }
@@ -0,0 +1,179 @@
+//! Change log that keeps only the latest change per row.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::change::{ChangeOperation, ColumnValue, TableChange, TableInfo};
+
+/// Log of committed changes, compacted to the latest change per primary key.
+///
+/// Created by [`SqliteObserver::compacting_log`](crate::SqliteObserver::compacting_log).
+/// Each commit overwrites the entry of every row it touched, so a row updated a
+/// thousand times between two drains yields one change. Intermediate states are
+/// lost: only the last operation is kept, so an INSERT followed by UPDATEs is
+/// drained as the final UPDATE, and an INSERT followed by a DELETE as the
+/// DELETE. Apply drained changes as upserts and deletes keyed by primary key.
+///
+/// Rows are keyed by `(schema, table, primary_key)`, falling back to the rowid
+/// when the primary key is unknown. An UPDATE that changes a row's primary key
+/// is recorded as a DELETE of the old key, replacing any entry for it, plus the
+/// UPDATE under the new key. The old key is read from `old_values`, so this
+/// needs [`capture_values`](crate::ObserverConfig::capture_values); without it,
+/// or for rowid-keyed rows, only the new key is recorded.
+///
+/// The log is updated inside the commit hook and grows with the number of
+/// distinct rows changed. Dropping every clone unregisters it on the next commit.
+#[derive(Debug, Clone)]
+pub struct CompactingLog {
+   pub(crate) state: Arc<Mutex<CompactingState>>,
+}
+
+#[derive(Debug)]
+pub(crate) struct CompactingState {
+   /// Tables to record; empty means all observed tables.
+   tables: HashSet<String>,
+   entries: HashMap<RowKey, (u64, TableChange)>,
+}
+
+/// Hashable identity of a changed row.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub(crate) enum RowKey {
+   PrimaryKey(String, String, Vec<KeyValue>),
+   Rowid(String, String, i64),
+   /// Neither primary key nor rowid known; never compacted
+   Unkeyed(u64),
+}
+
+/// [`ColumnValue`] with reals compared by bit pattern, so it can be hashed.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub(crate) enum KeyValue {
+   Null,
+   Integer(i64),
+   Real(u64),
+   Text(String),
+   Blob(Vec<u8>),
+}
+
+impl From<&ColumnValue> for KeyValue {
+   fn from(value: &ColumnValue) -> Self {
+      match value {
+         ColumnValue::Null => KeyValue::Null,
+         ColumnValue::Integer(i) => KeyValue::Integer(*i),
+         ColumnValue::Real(r) => KeyValue::Real(r.to_bits()),
+         ColumnValue::Text(s) => KeyValue::Text(s.clone()),
+         ColumnValue::Blob(b) => KeyValue::Blob(b.clone()),
+      }
+   }
+}
+
+impl RowKey {
+   /// Identifies the row `change` touched by primary key, falling back to the
+   /// rowid; `None` when neither is known.
+   pub(crate) fn of(change: &TableChange) -> Option<Self> {
+      if !change.primary_key.is_empty() {
+         Some(RowKey::PrimaryKey(
+            change.schema.clone(),
+            change.table.clone(),
+            change.primary_key.iter().map(KeyValue::from).collect(),
+         ))
+      } else {
+         change
+            .rowid
+            .map(|rowid| RowKey::Rowid(change.schema.clone(), change.table.clone(), rowid))
+      }
+   }
+}
+
+impl CompactingLog {
+   pub(crate) fn new(tables: Vec<String>) -> Self {
+      Self {
+         state: Arc::new(Mutex::new(CompactingState {
+            tables: tables.into_iter().collect(),
+            entries: HashMap::new(),
+         })),
+      }
+   }
+
+   /// Removes and returns every entry whose latest change has a sequence
+   /// number of at least `seq`, ordered by sequence number.
+   ///
+   /// Sequence numbers are the ones of
+   /// [`TransactionBatch::seq_range`](crate::TransactionBatch::seq_range). The
+   /// whole log is cleared: entries older than `seq` are assumed to have been
+   /// synced already and are discarded. Pass 0 to drain everything, or one past
+   /// the last drained sequence number to skip changes already applied.
+   pub fn drain_since(&self, seq: u64) -> Vec<(u64, TableChange)> {
+      let mut drained: Vec<(u64, TableChange)> = std::mem::take(&mut self.state.lock().entries)
+         .into_values()
+         .filter(|(change_seq, _)| *change_seq >= seq)
+         .collect();
+      drained.sort_by_key(|(change_seq, _)| *change_seq);
+      drained
+   }
+
+   /// Returns the number of rows currently in the log.
+   pub fn len(&self) -> usize {
+      self.state.lock().entries.len()
+   }
+
+   /// Returns `true` if no changes are waiting to be drained.
+   pub fn is_empty(&self) -> bool {
+      self.state.lock().entries.is_empty()
+   }
+}
+
+impl CompactingState {
+   /// Records `changes`, numbered consecutively from `start_seq`.
+   ///
+   /// `table_info` locates the primary key in an UPDATE's `old_values`, to
+   /// record a key change as a delete of the old key.
+   pub(crate) fn record(
+      &mut self,
+      changes: &[TableChange],
+      start_seq: u64,
+      table_info: &HashMap<String, TableInfo>,
+   ) {
+      for (seq, change) in (start_seq..).zip(changes) {
+         if !self.tables.is_empty() && !self.tables.contains(&change.table) {
+            continue;
+         }
+         if let Some(deleted) = old_key_delete(change, table_info.get(&change.table)) {
+            let key = RowKey::of(&deleted).unwrap_or(RowKey::Unkeyed(seq));
+            self.entries.insert(key, (seq, deleted));
+         }
+         let key = RowKey::of(change).unwrap_or(RowKey::Unkeyed(seq));
+         self.entries.insert(key, (seq, change.clone()));
+      }
+   }
+}
+
+/// For an UPDATE that changed the row's primary key, a DELETE of the old key.
+fn old_key_delete(change: &TableChange, info: Option<&TableInfo>) -> Option<TableChange> {
+   if change.operation != Some(ChangeOperation::Update) || change.primary_key.is_empty() {
+      return None;
+   }
+   let old_values = change.old_values.as_ref()?;
+   let old_key = info?
+      .pk_columns
+      .iter()
+      .map(|&idx| old_values.get(idx).cloned())
+      .collect::<Option<Vec<_>>>()?;
+   // A length mismatch means the table info changed since the change was built
+   if old_key.len() != change.primary_key.len()
+      || old_key
+         .iter()
+         .map(KeyValue::from)
+         .eq(change.primary_key.iter().map(KeyValue::from))
+   {
+      return None;
+   }
+
+   Some(TableChange {
+      operation: Some(ChangeOperation::Delete),
+      primary_key: old_key,
+      new_values: None,
+      ..change.clone()
+   })
+}
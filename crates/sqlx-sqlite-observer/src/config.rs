@@ -56,6 +56,99 @@ pub struct ObserverConfig {
    ///
    /// [`TableChange`]: crate::TableChange
    pub capture_values: bool,
+
+   /// Whether to fill in `GENERATED ALWAYS AS (...) VIRTUAL` column values.
+   ///
+   /// SQLite's preupdate hook does not compute virtual generated columns, so by
+   /// default they appear as [`ColumnValue::Null`] in `new_values` and
+   /// `old_values`. When `true`, each committed change to a table with virtual
+   /// columns is re-read by rowid (or primary key for WITHOUT ROWID tables) on
+   /// the read pool and its `new_values` updated before publishing.
+   ///
+   /// This costs one extra read per changed row and runs after commit, so the
+   /// row is visible to the read. To keep notifications in order, all changes
+   /// are then published from a background task instead of the commit hook.
+   /// `old_values` cannot be resolved (the old row no longer exists), and rows
+   /// in attached schemas are left as-is. Requires `capture_values`.
+   ///
+   /// Default: `false`.
+   ///
+   /// [`ColumnValue::Null`]: crate::ColumnValue::Null
+   pub resolve_generated_columns: bool,
+
+   /// Maximum number of changes delivered to broadcast subscribers per second.
+   ///
+   /// Protects a slow consumer from a flood of notifications during a bulk
+   /// write. Changes are counted in fixed one-second windows; once a window's
+   /// limit is reached, further changes are dropped from the broadcast channel
+   /// and coalesced into one [`ObserverEvent::Throttled`] per window, carrying
+   /// the number of dropped changes and the tables they touched. The summary is
+   /// sent on `subscribe_events()` when the next window opens, or shortly after
+   /// writes stop.
+   ///
+   /// Only the broadcast channel is limited: reliable subscribers, transaction
+   /// batches and compacting logs receive every change.
+   ///
+   /// Default: `None` (unlimited).
+   ///
+   /// [`ObserverEvent::Throttled`]: crate::ObserverEvent::Throttled
+   pub max_notifications_per_sec: Option<u32>,
+
+   /// Whether to send a [`TransactionSummary`] for each committed transaction.
+   ///
+   /// Summaries count the changed rows per table and operation, and are
+   /// delivered through `subscribe_summaries()` on their own channel, next to
+   /// the per-row notifications. They are not throttled.
+   ///
+   /// Default: `false`.
+   ///
+   /// [`TransactionSummary`]: crate::TransactionSummary
+   pub emit_transaction_summary: bool,
+
+   /// Whether to observe every table instead of only those in `tables`.
+   ///
+   /// Internal `sqlite_%` and `_sqlx_%` tables are excluded. Tables are listed,
+   /// and their schema queried, on each acquire; changes to a table created
+   /// since the last acquire are still published, but without primary key
+   /// values or column names.
+   ///
+   /// Default: `false`.
+   pub observe_all: bool,
+
+   /// Maximum number of changes buffered per transaction.
+   ///
+   /// Each change of an open transaction is kept in memory until it commits, so
+   /// a huge transaction (such as a migration rewriting millions of rows) could
+   /// exhaust memory. Past this limit, the transaction's buffered rows are freed
+   /// and its changes only counted per table. On commit, nothing of it reaches
+   /// change subscribers, batches or compacting logs; instead one
+   /// [`ObserverEvent::BufferOverflowed`] per table is sent on
+   /// `subscribe_events()`, the counts as its transaction summary if those are
+   /// enabled, and a [`TableChangeEvent::Lagged`] to each reliable subscriber.
+   ///
+   /// Default: `None` (unlimited).
+   ///
+   /// [`ObserverEvent::BufferOverflowed`]: crate::ObserverEvent::BufferOverflowed
+   /// [`TableChangeEvent::Lagged`]: crate::TableChangeEvent::Lagged
+   pub max_buffered_changes: Option<usize>,
+
+   /// Whether to detect DDL and publish [`SchemaChange`]s.
+   ///
+   /// Detection installs an authorizer (`sqlite3_set_authorizer`) on each
+   /// observed connection, and a connection has only one. While hooks are
+   /// registered it **replaces any authorizer the application set** on that
+   /// connection, and removing the hooks clears it. Leave this off if you use
+   /// your own authorizer.
+   ///
+   /// When `false`, `subscribe_schema_changes()` yields nothing and DDL on an
+   /// observed table is only caught by the column count check (see
+   /// [`ObserverEvent::SchemaDrift`]).
+   ///
+   /// Default: `false`.
+   ///
+   /// [`SchemaChange`]: crate::SchemaChange
+   /// [`ObserverEvent::SchemaDrift`]: crate::ObserverEvent::SchemaDrift
+   pub capture_schema_changes: bool,
 }
 
 impl Default for ObserverConfig {
@@ -64,6 +157,12 @@ impl Default for ObserverConfig {
          tables: HashSet::new(),
          channel_capacity: 256,
          capture_values: true,
+         resolve_generated_columns: false,
+         max_notifications_per_sec: None,
+         emit_transaction_summary: false,
+         observe_all: false,
+         max_buffered_changes: None,
+         capture_schema_changes: false,
       }
    }
 }
@@ -71,7 +170,9 @@ impl Default for ObserverConfig {
 impl ObserverConfig {
    /// Creates a new observer configuration with default settings.
    ///
-   /// Defaults: no tables observed, channel capacity of 256, value capture enabled.
+   /// Defaults: no tables observed, channel capacity of 256, value capture enabled,
+   /// generated column resolution disabled, no notification rate limit, no
+   /// transaction summaries, no schema change capture.
    pub fn new() -> Self {
       Self::default()
    }
@@ -108,4 +209,55 @@ impl ObserverConfig {
       self.capture_values = capture;
       self
    }
+
+   /// Controls whether virtual generated column values are re-read after commit.
+   ///
+   /// See [`resolve_generated_columns`](Self::resolve_generated_columns) for the
+   /// cost and limitations.
+   pub fn with_resolve_generated_columns(mut self, resolve: bool) -> Self {
+      self.resolve_generated_columns = resolve;
+      self
+   }
+
+   /// Limits broadcast change notifications to `limit` per second.
+   ///
+   /// See [`max_notifications_per_sec`](Self::max_notifications_per_sec) for how
+   /// excess changes are coalesced.
+   pub fn with_max_notifications_per_sec(mut self, limit: u32) -> Self {
+      self.max_notifications_per_sec = Some(limit);
+      self
+   }
+
+   /// Controls whether a [`TransactionSummary`](crate::TransactionSummary) is
+   /// sent for each committed transaction.
+   pub fn with_emit_transaction_summary(mut self, emit: bool) -> Self {
+      self.emit_transaction_summary = emit;
+      self
+   }
+
+   /// Limits the changes buffered per transaction to `limit`.
+   ///
+   /// See [`max_buffered_changes`](Self::max_buffered_changes) for what happens
+   /// to a transaction exceeding it.
+   pub fn with_max_buffered_changes(mut self, limit: usize) -> Self {
+      self.max_buffered_changes = Some(limit);
+      self
+   }
+
+   /// Controls whether DDL is detected and published as schema changes.
+   ///
+   /// See [`capture_schema_changes`](Self::capture_schema_changes) for how this
+   /// interacts with an application authorizer.
+   pub fn with_capture_schema_changes(mut self, capture: bool) -> Self {
+      self.capture_schema_changes = capture;
+      self
+   }
+
+   /// Observes every table in the database, except internal ones.
+   ///
+   /// See the `observe_all` field for when schema info is resolved.
+   pub fn observe_all(mut self) -> Self {
+      self.observe_all = true;
+      self
+   }
 }
@@ -47,17 +47,18 @@ use std::sync::Arc;
 use libsqlite3_sys::sqlite3;
 use sqlx::sqlite::SqliteConnection;
 use sqlx::{Pool, Sqlite};
-use sqlx_sqlite_conn_mgr::{SqliteDatabase, WriteGuard};
-use tokio::sync::broadcast;
+use sqlx_sqlite_conn_mgr::{AttachedSpec, AttachedWriteGuard, SqliteDatabase, WriteGuard};
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::Stream;
 use tracing::{debug, trace, warn};
 
 use crate::Result;
 use crate::broker::ObservationBroker;
-use crate::change::TableChange;
+use crate::change::{TableChange, TableChangeEvent};
 use crate::config::ObserverConfig;
 use crate::hooks;
-use crate::schema::query_table_info;
-use crate::stream::TableChangeStream;
+use crate::schema::{query_table_info, query_user_tables};
+use crate::stream::{TableChangeReceiver, TableChangeStream};
 
 /// Wrapper around `SqliteDatabase` that provides change observation.
 ///
@@ -67,6 +68,7 @@ use crate::stream::TableChangeStream;
 pub struct ObservableSqliteDatabase {
    db: Arc<SqliteDatabase>,
    broker: Arc<ObservationBroker>,
+   resolve_generated_columns: bool,
 }
 
 impl ObservableSqliteDatabase {
@@ -78,30 +80,69 @@ impl ObservableSqliteDatabase {
    /// * `config` - Observer configuration specifying which tables to track
    pub fn new(db: Arc<SqliteDatabase>, config: ObserverConfig) -> Self {
       let broker = ObservationBroker::new(config.channel_capacity, config.capture_values);
+      broker.set_max_notifications_per_sec(config.max_notifications_per_sec);
+      broker.set_emit_transaction_summary(config.emit_transaction_summary);
+      broker.set_observe_all(config.observe_all);
+      broker.set_max_buffered_changes(config.max_buffered_changes);
+      broker.set_capture_schema_changes(config.capture_schema_changes);
 
       if !config.tables.is_empty() {
          broker.observe_tables(config.tables.iter().map(String::as_str));
       }
 
-      Self { db, broker }
+      Self {
+         db,
+         broker,
+         resolve_generated_columns: config.resolve_generated_columns,
+      }
    }
 
    /// Subscribe to change notifications.
    ///
-   /// Returns a broadcast receiver that will receive `TableChange` events
-   /// when observable tables are modified and transactions commit.
-   pub fn subscribe<I, S>(&self, tables: I) -> broadcast::Receiver<TableChange>
+   /// Returns a receiver of `TableChange` events when observed tables are
+   /// modified and transactions commit, filtered to `tables` (all observed
+   /// tables if empty). Subscribing does not observe the tables; see
+   /// [`observe`](Self::observe).
+   pub fn subscribe<I, S>(&self, tables: I) -> TableChangeReceiver
    where
       I: IntoIterator<Item = S>,
       S: Into<String>,
    {
       let tables: Vec<String> = tables.into_iter().map(Into::into).collect();
-      if !tables.is_empty() {
-         self
-            .broker
-            .observe_tables(tables.iter().map(String::as_str));
-      }
-      self.broker.subscribe()
+      TableChangeReceiver::new(self.broker.subscribe(), tables)
+   }
+
+   /// Subscribes to change notifications without ever dropping a change.
+   ///
+   /// Returns a bounded `mpsc` receiver with room for `capacity` changes. When it
+   /// is full, the committing connection blocks inside SQLite's commit hook until
+   /// the consumer catches up, so a slow consumer stalls every write. Use this for
+   /// a single critical consumer (sync, replication) that must see every change;
+   /// prefer [`subscribe`](Self::subscribe) for UI updates and other lossy-tolerant
+   /// listeners.
+   ///
+   /// Changes arrive as [`TableChangeEvent::Change`]. A transaction exceeding
+   /// [`max_buffered_changes`](crate::ObserverConfig::max_buffered_changes) keeps
+   /// no rows to deliver; the receiver gets [`TableChangeEvent::Lagged`] with the
+   /// number of changes it skipped instead, in commit order, and should re-query
+   /// its tables.
+   ///
+   /// Dropping the receiver unregisters the subscriber on the next commit.
+   ///
+   /// # Panics
+   ///
+   /// Panics if `capacity` is 0.
+   pub fn subscribe_reliable<I, S>(
+      &self,
+      tables: I,
+      capacity: usize,
+   ) -> mpsc::Receiver<TableChangeEvent>
+   where
+      I: IntoIterator<Item = S>,
+      S: Into<String>,
+   {
+      let tables: Vec<String> = tables.into_iter().map(Into::into).collect();
+      self.broker.subscribe_reliable(tables, capacity)
    }
 
    /// Subscribe and get a `Stream` for easier async iteration.
@@ -111,20 +152,104 @@ impl ObservableSqliteDatabase {
       S: Into<String>,
    {
       use crate::stream::TableChangeStreamExt;
+      self.subscribe(tables).into_stream()
+   }
+
+   /// Subscribes to committed transactions as a `Stream` of [`TransactionBatch`]es.
+   ///
+   /// Each item groups every change from one commit, with its sequence range and
+   /// commit time, for consumers that replicate whole transactions downstream.
+   /// If tables are specified, batches contain only changes to them; commits
+   /// touching none are skipped. On an unfiltered stream, batches missed by a
+   /// lagging consumer show up as a gap in `seq_range`.
+   ///
+   /// [`TransactionBatch`]: crate::TransactionBatch
+   pub fn subscribe_batches<I, S>(
+      &self,
+      tables: I,
+   ) -> impl Stream<Item = crate::TransactionBatch> + Send + Unpin + 'static
+   where
+      I: IntoIterator<Item = S>,
+      S: Into<String>,
+   {
       let tables: Vec<String> = tables.into_iter().map(Into::into).collect();
-      // Register tables for observation (uses references, avoids clone)
-      if !tables.is_empty() {
-         self
-            .broker
-            .observe_tables(tables.iter().map(String::as_str));
-      }
-      let rx = self.broker.subscribe();
-      let stream = rx.into_stream();
-      if tables.is_empty() {
-         stream
-      } else {
-         stream.filter_tables(tables)
-      }
+      crate::stream::batch_stream(self.broker.subscribe_batches(), tables)
+   }
+
+   /// Creates a [`CompactingLog`] that keeps only the latest change per row.
+   ///
+   /// If tables are specified, only their changes are recorded. See
+   /// [`SqliteObserver::compacting_log`](crate::SqliteObserver::compacting_log).
+   ///
+   /// [`CompactingLog`]: crate::CompactingLog
+   pub fn compacting_log<I, S>(&self, tables: I) -> crate::CompactingLog
+   where
+      I: IntoIterator<Item = S>,
+      S: Into<String>,
+   {
+      let tables: Vec<String> = tables.into_iter().map(Into::into).collect();
+      self.broker.compacting_log(tables)
+   }
+
+   /// Returns commit latency percentiles over recent observed transactions.
+   ///
+   /// Measures the time from a transaction's first change to an observed table
+   /// until it commits, over the last [`LATENCY_WINDOW`] such commits.
+   /// Transactions that touch no observed table are not measured, so this
+   /// does not cover every write.
+   ///
+   /// [`LATENCY_WINDOW`]: crate::latency::LATENCY_WINDOW
+   pub fn commit_latency_stats(&self) -> crate::LatencyStats {
+      self.broker.commit_latency_stats()
+   }
+
+   /// Subscribes to one [`TransactionSummary`] per committed transaction.
+   ///
+   /// Requires
+   /// [`emit_transaction_summary`](crate::ObserverConfig::emit_transaction_summary);
+   /// otherwise the receiver gets nothing.
+   ///
+   /// [`TransactionSummary`]: crate::TransactionSummary
+   pub fn subscribe_summaries(&self) -> broadcast::Receiver<crate::TransactionSummary> {
+      self.broker.subscribe_summaries()
+   }
+
+   /// Subscribes to committed DDL statements, such as `ALTER TABLE`.
+   ///
+   /// An `ALTER`, `CREATE` or `DROP TABLE` of an observed table also marks its
+   /// `TableInfo` stale, so the next acquire re-queries the schema. Yields
+   /// nothing unless [`ObserverConfig::capture_schema_changes`] is set.
+   pub fn subscribe_schema_changes(&self) -> broadcast::Receiver<crate::SchemaChange> {
+      self.broker.subscribe_schema_changes()
+   }
+
+   /// Subscribes to observer status notifications such as
+   /// [`ObserverEvent::SchemaDrift`](crate::ObserverEvent::SchemaDrift).
+   pub fn subscribe_events(&self) -> broadcast::Receiver<crate::ObserverEvent> {
+      self.broker.subscribe_events()
+   }
+
+   /// Reads every current row of `table` as a stream of synthetic INSERT changes.
+   ///
+   /// Lets a new replication consumer seed its state through the same apply
+   /// path as live changes: each item has `primary_key`, `new_values` and
+   /// `column_names` populated (regardless of `capture_values`), and generated
+   /// columns are included. Rows are read from the read pool in a single query, so the snapshot
+   /// is consistent but is held in memory until consumed.
+   ///
+   /// The load and the live subscription are not atomic. Subscribe *before*
+   /// calling this so no commit is missed; changes committed while the load
+   /// runs may then appear in both, so apply changes idempotently by primary
+   /// key.
+   ///
+   /// Returns [`Error::TableNotFound`](crate::Error::TableNotFound) if the table does not exist.
+   pub async fn initial_load(
+      &self,
+      table: &str,
+   ) -> Result<impl Stream<Item = TableChange> + Send + Unpin + 'static> {
+      let pool = self.db.read_pool().map_err(crate::error::Error::ConnMgr)?;
+      let changes = crate::snapshot::load_table(pool, table).await?;
+      Ok(tokio_stream::iter(changes))
    }
 
    /// Get a reference to the read-only connection pool.
@@ -145,6 +270,11 @@ impl ObservableSqliteDatabase {
    /// On first acquisition for each table, queries the schema to determine
    /// primary key columns and WITHOUT ROWID status.
    pub async fn acquire_writer(&self) -> Result<ObservableWriteGuard> {
+      // Query table info for any observed tables that don't have it yet. This
+      // runs before taking the writer, since with `max_read_connections: 0` the
+      // read pool is the write pool.
+      self.ensure_table_info().await?;
+
       let writer = self
          .db
          .acquire_writer()
@@ -153,28 +283,77 @@ impl ObservableSqliteDatabase {
 
       let mut observable = ObservableWriteGuard {
          writer: Some(writer),
+         broker: Arc::clone(&self.broker),
          hooks_registered: false,
          raw_db: None,
       };
 
-      // Query table info for any observed tables that don't have it yet
+      self.ensure_background_tasks()?;
+
+      observable.register_hooks().await?;
+      Ok(observable)
+   }
+
+   /// Acquire an observable write guard with attached database(s).
+   ///
+   /// Attached databases share the main write connection, so the broker's hooks
+   /// are registered on that connection and also fire for writes to attached
+   /// schemas. Each resulting `TableChange` carries the `schema` it was made in
+   /// (`"main"` or the attached schema name), so subscribers can tell changes to
+   /// `orders.items` apart from changes to `main.items`.
+   ///
+   /// Tables are matched by name regardless of schema: observing `items` reports
+   /// changes to `items` in every schema written through this guard.
+   ///
+   /// # Lifecycle
+   ///
+   /// Hooks are registered after all databases are attached and unregistered
+   /// when the guard is detached via
+   /// [`detach_all()`](ObservableAttachedWriteGuard::detach_all) or dropped, so the
+   /// write connection never returns to the pool with hooks pointing at this broker.
+   pub async fn acquire_writer_with_attached(
+      &self,
+      specs: Vec<AttachedSpec>,
+   ) -> Result<ObservableAttachedWriteGuard> {
       self.ensure_table_info().await?;
 
-      observable.register_hooks(Arc::clone(&self.broker)).await?;
+      let guard = sqlx_sqlite_conn_mgr::acquire_writer_with_attached(&self.db, specs)
+         .await
+         .map_err(crate::error::Error::ConnMgr)?;
+
+      let mut observable = ObservableAttachedWriteGuard {
+         guard: Some(guard),
+         broker: Arc::clone(&self.broker),
+         raw_db: None,
+      };
+
+      self.ensure_background_tasks()?;
+
+      let conn = observable.guard.as_mut().expect("guard already taken");
+      observable.raw_db = Some(register_hooks_on(conn, Arc::clone(&self.broker)).await?);
       Ok(observable)
    }
 
-   /// Ensures TableInfo is set for all observed tables.
+   /// Ensures TableInfo is set, and current, for all observed tables.
    ///
    /// Uses the read pool to query schema information, respecting conn-mgr's
-   /// requirement that all connections be acquired through it.
+   /// requirement that all connections be acquired through it. In all-tables
+   /// mode, the tables to observe are listed first.
    async fn ensure_table_info(&self) -> Result<()> {
+      if self.broker.observes_all() {
+         let pool = self.db.read_pool().map_err(crate::error::Error::ConnMgr)?;
+         let mut conn = pool.acquire().await.map_err(crate::error::Error::Sqlx)?;
+         match query_user_tables(&mut conn).await {
+            Ok(tables) => self.broker.observe_tables(tables),
+            Err(e) => warn!(error = %e, "Failed to list tables to observe"),
+         }
+      }
       let observed = self.broker.get_observed_tables();
 
       // Collect tables that need schema info
       let tables_to_query: Vec<String> = observed
          .into_iter()
-         .filter(|table| self.broker.get_table_info(table).is_none())
+         .filter(|table| self.broker.needs_table_info(table))
          .collect();
 
       if tables_to_query.is_empty() {
@@ -203,6 +382,17 @@ impl ObservableSqliteDatabase {
       Ok(())
    }
 
+   /// Starts the generated column resolver on the read pool, if enabled, and
+   /// the throttle flusher, if a rate limit is set.
+   fn ensure_background_tasks(&self) -> Result<()> {
+      if self.resolve_generated_columns {
+         let pool = self.db.read_pool().map_err(crate::error::Error::ConnMgr)?;
+         self.broker.start_generated_column_resolver(pool.clone());
+      }
+      self.broker.start_throttle_flusher();
+      Ok(())
+   }
+
    /// Get the underlying `SqliteDatabase`.
    pub fn inner(&self) -> &Arc<SqliteDatabase> {
       &self.db
@@ -213,6 +403,33 @@ impl ObservableSqliteDatabase {
       self.broker.get_observed_tables()
    }
 
+   /// Start observing an additional table at runtime.
+   ///
+   /// Existing subscribers keep their receivers and start receiving changes for
+   /// the table. Schema info is queried the next time a writer is acquired.
+   pub fn observe_table(&self, table: &str) {
+      self.broker.observe_tables([table]);
+   }
+
+   /// Adds tables to the set captured by the hooks, for every subscriber.
+   ///
+   /// Subscriptions only filter what is captured; see
+   /// [`SqliteObserver::observe`](crate::SqliteObserver::observe).
+   pub fn observe<I, S>(&self, tables: I)
+   where
+      I: IntoIterator<Item = S>,
+      S: AsRef<str>,
+   {
+      self.broker.observe_tables(tables);
+   }
+
+   /// Stop observing a table at runtime without dropping subscriptions.
+   ///
+   /// Returns `true` if the table was being observed.
+   pub fn unobserve_table(&self, table: &str) -> bool {
+      self.broker.unobserve_table(table)
+   }
+
    /// Returns a reference to the underlying observation broker.
    pub fn broker(&self) -> &Arc<ObservationBroker> {
       &self.broker
@@ -224,6 +441,7 @@ impl Clone for ObservableSqliteDatabase {
       Self {
          db: Arc::clone(&self.db),
          broker: Arc::clone(&self.broker),
+         resolve_generated_columns: self.resolve_generated_columns,
       }
    }
 }
@@ -236,6 +454,7 @@ impl Clone for ObservableSqliteDatabase {
 #[must_use = "if unused, the write lock is immediately released"]
 pub struct ObservableWriteGuard {
    writer: Option<WriteGuard>,
+   broker: Arc<ObservationBroker>,
    hooks_registered: bool,
    /// Raw sqlite3 pointer, cached during register_hooks so we can
    /// call unregister_hooks synchronously in Drop without needing
@@ -254,7 +473,7 @@ impl ObservableWriteGuard {
    }
 
    /// Registers SQLite observation hooks on this writer.
-   async fn register_hooks(&mut self, broker: Arc<ObservationBroker>) -> Result<()> {
+   async fn register_hooks(&mut self) -> Result<()> {
       if self.hooks_registered {
          return Ok(());
       }
@@ -262,18 +481,7 @@ impl ObservableWriteGuard {
       debug!("Registering SQLite observation hooks on WriteGuard");
 
       let writer = self.writer.as_mut().expect("writer already taken");
-
-      // Get raw SQLite handle
-      let mut handle = writer
-         .lock_handle()
-         .await
-         .map_err(|e| crate::Error::Database(format!("Failed to lock connection handle: {}", e)))?;
-
-      let db: *mut sqlite3 = handle.as_raw_handle().as_ptr();
-
-      unsafe {
-         hooks::register_hooks(db, broker)?;
-      }
+      let db = register_hooks_on(writer, Arc::clone(&self.broker)).await?;
 
       // Cache the raw pointer so Drop can call unregister_hooks synchronously.
       // SAFETY: The pointer remains valid for the lifetime of the WriteGuard,
@@ -283,43 +491,118 @@ impl ObservableWriteGuard {
       Ok(())
    }
 
-   /// Consumes this wrapper and returns the underlying write guard.
-   ///
-   /// Hooks are unregistered before returning the guard, so it can be
-   /// safely used without observation.
-   pub fn into_inner(mut self) -> WriteGuard {
-      // Unregister hooks before returning the writer to prevent
-      // use-after-free if the broker is dropped before the connection is reused.
+   /// Unregisters observation hooks from this writer, if registered.
+   fn unregister_hooks(&mut self) {
       if self.hooks_registered
          && let Some(db) = self.raw_db
       {
+         // SAFETY: db was obtained from lock_handle during register_hooks and
+         // remains valid because we still own the WriteGuard (self.writer).
          unsafe {
-            crate::hooks::unregister_hooks(db);
+            hooks::unregister_hooks(db);
          }
-         trace!("Hooks unregistered before returning inner WriteGuard");
       }
       self.hooks_registered = false;
       self.raw_db = None;
+   }
+
+   /// Returns the broker this guard's changes are buffered in.
+   ///
+   /// Use it to take a [`savepoint_mark`](ObservationBroker::savepoint_mark)
+   /// when opening a savepoint on this connection.
+   pub fn broker(&self) -> &Arc<ObservationBroker> {
+      &self.broker
+   }
+
+   /// Closes the connection when this guard is dropped instead of returning it
+   /// to the pool.
+   ///
+   /// See [`WriteGuard::close_on_drop`].
+   pub fn close_on_drop(&mut self) {
+      self.writer_mut().close_on_drop();
+   }
+
+   /// Runs `f` on the connection with observation hooks unregistered.
+   ///
+   /// Changes made inside `f` produce **no notifications**, even if they belong to
+   /// a transaction that commits after the scope ends. Use this for bulk
+   /// operations (large imports, data backfills) that would otherwise flood
+   /// subscribers. Hooks are re-registered when `f` completes, whether or not it
+   /// returned an error.
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_observer::ObservableWriteGuard;
+   /// # async fn example(mut writer: ObservableWriteGuard) -> sqlx_sqlite_observer::Result<()> {
+   /// writer
+   ///    .with_hooks_disabled(async |conn| {
+   ///       sqlx::query("INSERT INTO archive SELECT * FROM users")
+   ///          .execute(&mut *conn)
+   ///          .await?;
+   ///       Ok(())
+   ///    })
+   ///    .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn with_hooks_disabled<T>(
+      &mut self,
+      f: impl AsyncFnOnce(&mut SqliteConnection) -> Result<T>,
+   ) -> Result<T> {
+      self.unregister_hooks();
+      trace!("Observation hooks disabled for scope");
+
+      let result = f(self.writer_mut()).await;
+
+      self.register_hooks().await?;
+      result
+   }
+
+   /// Consumes this wrapper and returns the underlying write guard.
+   ///
+   /// Hooks are unregistered before returning the guard, so it can be
+   /// safely used without observation.
+   pub fn into_inner(mut self) -> WriteGuard {
+      // Unregister hooks before returning the writer to prevent
+      // use-after-free if the broker is dropped before the connection is reused.
+      self.unregister_hooks();
+      trace!("Hooks unregistered before returning inner WriteGuard");
       self.writer.take().expect("writer already taken")
    }
 }
 
 impl Drop for ObservableWriteGuard {
    fn drop(&mut self) {
-      if self.hooks_registered
-         && let Some(db) = self.raw_db
-      {
-         // SAFETY: db was obtained from lock_handle during register_hooks and
-         // remains valid because we still own the WriteGuard (self.writer).
-         // The writer has not been taken (into_inner clears hooks_registered).
-         unsafe {
-            hooks::unregister_hooks(db);
-         }
+      // The writer has not been taken (into_inner clears hooks_registered).
+      if self.hooks_registered {
+         self.unregister_hooks();
          trace!("ObservableWriteGuard dropped, hooks unregistered");
       }
    }
 }
 
+/// Registers observation hooks on a connection and returns its raw handle.
+///
+/// The raw pointer is returned so guards can unregister hooks synchronously
+/// in `Drop` without needing the async `lock_handle`.
+async fn register_hooks_on(
+   conn: &mut SqliteConnection,
+   broker: Arc<ObservationBroker>,
+) -> Result<*mut sqlite3> {
+   // Get raw SQLite handle
+   let mut handle = conn
+      .lock_handle()
+      .await
+      .map_err(|e| crate::Error::Database(format!("Failed to lock connection handle: {}", e)))?;
+
+   let db: *mut sqlite3 = handle.as_raw_handle().as_ptr();
+
+   unsafe {
+      hooks::register_hooks(db, broker)?;
+   }
+
+   Ok(db)
+}
+
 impl Deref for ObservableWriteGuard {
    type Target = SqliteConnection;
 
@@ -333,3 +616,72 @@ impl DerefMut for ObservableWriteGuard {
       self.writer_mut()
    }
 }
+
+/// RAII guard for observable write access with attached database(s).
+///
+/// Wraps an `AttachedWriteGuard` from `sqlx-sqlite-conn-mgr` with observation
+/// hooks registered on its connection. Changes to both the main and attached
+/// schemas are published when transactions commit.
+///
+/// **Important**: Call [`detach_all()`](Self::detach_all) before dropping, as with
+/// `AttachedWriteGuard`. Hooks are unregistered either way.
+#[must_use = "if unused, the write guard and locks are immediately dropped"]
+pub struct ObservableAttachedWriteGuard {
+   guard: Option<AttachedWriteGuard>,
+   broker: Arc<ObservationBroker>,
+   /// Raw sqlite3 pointer with hooks registered, cached for synchronous
+   /// unregistration in Drop.
+   raw_db: Option<*mut sqlite3>,
+}
+
+// SAFETY: Same reasoning as ObservableWriteGuard - raw_db is only used for hook
+// unregistration by the owner of the underlying (Send) pool connection.
+unsafe impl Send for ObservableAttachedWriteGuard {}
+
+impl ObservableAttachedWriteGuard {
+   fn unregister(&mut self) {
+      if let Some(db) = self.raw_db.take() {
+         // SAFETY: db was obtained from lock_handle when hooks were registered and
+         // remains valid because we still own the AttachedWriteGuard.
+         unsafe {
+            hooks::unregister_hooks(db);
+         }
+         trace!("Hooks unregistered on attached write guard");
+      }
+   }
+
+   /// Returns the broker this guard's changes are buffered in.
+   pub fn broker(&self) -> &Arc<ObservationBroker> {
+      &self.broker
+   }
+
+   /// Unregister observation hooks and detach all attached databases.
+   pub async fn detach_all(mut self) -> Result<()> {
+      self.unregister();
+      let guard = self.guard.take().expect("guard already taken");
+      guard
+         .detach_all()
+         .await
+         .map_err(crate::error::Error::ConnMgr)
+   }
+}
+
+impl Drop for ObservableAttachedWriteGuard {
+   fn drop(&mut self) {
+      self.unregister();
+   }
+}
+
+impl Deref for ObservableAttachedWriteGuard {
+   type Target = SqliteConnection;
+
+   fn deref(&self) -> &Self::Target {
+      self.guard.as_ref().expect("guard already taken")
+   }
+}
+
+impl DerefMut for ObservableAttachedWriteGuard {
+   fn deref_mut(&mut self) -> &mut Self::Target {
+      self.guard.as_mut().expect("guard already taken")
+   }
+}
@@ -7,6 +7,15 @@ pub enum Error {
    #[error("Hook registration failed: {0}")]
    HookRegistration(String),
 
+   /// The linked SQLite library lacks a compile-time feature the observer needs.
+   ///
+   /// `feature` names the missing option and `remedy` how to get a suitable build.
+   #[error("SQLite was not compiled with {feature}: {remedy}")]
+   UnsupportedSqlite {
+      feature: &'static str,
+      remedy: &'static str,
+   },
+
    /// SQLx database error.
    #[error("SQLx error: {0}")]
    Sqlx(#[from] sqlx::Error),
@@ -24,6 +33,14 @@ pub enum Error {
    #[error("Database error: {0}")]
    Database(String),
 
+   /// The observer was shut down.
+   #[error("Observer has been shut down")]
+   ShutDown,
+
+   /// Table does not exist in the database.
+   #[error("Table not found: {0}")]
+   TableNotFound(String),
+
    /// Schema mismatch - table schema changed while observing.
    #[error(
       "Schema mismatch for table '{table}': expected {expected} PK columns, but only {actual} values available"
@@ -0,0 +1,148 @@
+//! Post-commit resolution of virtual generated column values.
+//!
+//! SQLite's preupdate hook does not compute `GENERATED ALWAYS AS (...) VIRTUAL`
+//! columns, so changes report them as `Null`. When
+//! [`resolve_generated_columns`](crate::ObserverConfig::resolve_generated_columns)
+//! is enabled, committed changes are queued to a background task that re-reads
+//! each affected row and fills in the missing `new_values` before publishing.
+//!
+//! The commit hook runs before the commit completes, so the first read may not
+//! see the row yet. A read is only accepted once every stored column matches the
+//! change; generated expressions are deterministic, so the virtual values then
+//! belong to the same row version.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Pool, Row, Sqlite};
+use tokio::sync::mpsc;
+use tracing::{debug, trace};
+
+use crate::broker::ObservationBroker;
+use crate::change::{ColumnValue, TableChange, TableInfo};
+use crate::snapshot::{column_value, quote_name};
+
+/// Reads attempted per change before giving up and publishing placeholders
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Delay between reads that did not yet see the committed row
+const RETRY_DELAY: Duration = Duration::from_millis(5);
+
+/// Spawns the resolver task and returns the queue feeding it.
+///
+/// The task holds only a weak reference to the broker, so it exits once the
+/// broker (and with it the queue's sender) is dropped.
+pub(crate) fn spawn_resolver(
+   broker: &Arc<ObservationBroker>,
+   pool: Pool<Sqlite>,
+) -> mpsc::UnboundedSender<Vec<TableChange>> {
+   let (tx, mut rx) = mpsc::unbounded_channel::<Vec<TableChange>>();
+   let broker = Arc::downgrade(broker);
+
+   tokio::spawn(async move {
+      while let Some(mut changes) = rx.recv().await {
+         let Some(broker) = broker.upgrade() else {
+            break;
+         };
+         for change in &mut changes {
+            if let Some(info) = broker.get_table_info(&change.table)
+               && !info.virtual_columns.is_empty()
+            {
+               resolve_change(&pool, &info, change).await;
+            }
+         }
+         broker.publish_async(changes).await;
+      }
+   });
+
+   tx
+}
+
+/// Fills in the virtual column values of `change.new_values` from the committed row.
+async fn resolve_change(pool: &Pool<Sqlite>, info: &TableInfo, change: &mut TableChange) {
+   // The read pool only sees the main database
+   if change.schema != "main" {
+      return;
+   }
+   let Some(new_values) = change.new_values.as_mut() else {
+      return;
+   };
+   if new_values.len() != info.column_names.len() {
+      return;
+   }
+
+   let (filter, keys) = if info.without_rowid {
+      if change.primary_key.is_empty() {
+         return;
+      }
+      let filter = info
+         .pk_columns
+         .iter()
+         .map(|&idx| format!("{} = ?", quote_name(&info.column_names[idx])))
+         .collect::<Vec<_>>()
+         .join(" AND ");
+      (filter, change.primary_key.clone())
+   } else {
+      let Some(rowid) = change.rowid else {
+         return;
+      };
+      ("rowid = ?".to_string(), vec![ColumnValue::Integer(rowid)])
+   };
+
+   let columns = info
+      .column_names
+      .iter()
+      .map(|name| quote_name(name))
+      .collect::<Vec<_>>()
+      .join(", ");
+   let sql = format!(
+      "SELECT {columns} FROM \"main\".{} WHERE {filter}",
+      quote_name(&change.table)
+   );
+
+   for attempt in 0..MAX_ATTEMPTS {
+      if attempt > 0 {
+         tokio::time::sleep(RETRY_DELAY).await;
+      }
+
+      let query = keys
+         .iter()
+         .fold(sqlx::query(&sql), |query, key| bind_value(query, key));
+
+      match query.fetch_optional(pool).await {
+         Ok(Some(row)) => {
+            let current: Vec<ColumnValue> = (0..row.len()).map(|i| column_value(&row, i)).collect();
+            let same_row = (0..current.len())
+               .all(|idx| info.virtual_columns.contains(&idx) || current[idx] == new_values[idx]);
+
+            if same_row {
+               for &idx in &info.virtual_columns {
+                  new_values[idx] = current[idx].clone();
+               }
+               return;
+            }
+         }
+         Ok(None) => {}
+         Err(e) => trace!(table = %change.table, error = %e, "Generated column read failed"),
+      }
+   }
+
+   debug!(
+      table = %change.table,
+      "Committed row not found or already changed; leaving virtual columns as Null"
+   );
+}
+
+fn bind_value<'q>(
+   query: sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>>,
+   value: &ColumnValue,
+) -> sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>> {
+   match value {
+      ColumnValue::Null => query.bind(None::<i64>),
+      ColumnValue::Integer(i) => query.bind(*i),
+      ColumnValue::Real(r) => query.bind(*r),
+      ColumnValue::Text(s) => query.bind(s.clone()),
+      ColumnValue::Blob(b) => query.bind(b.clone()),
+   }
+}
@@ -13,18 +13,25 @@ use std::ffi::{CStr, CString, c_char, c_int, c_void};
 use std::panic::catch_unwind;
 use std::ptr;
 use std::sync::Arc;
+use std::time::Instant;
 
 use libsqlite3_sys::{
-   SQLITE_BLOB, SQLITE_DELETE, SQLITE_FLOAT, SQLITE_INSERT, SQLITE_INTEGER, SQLITE_NULL,
-   SQLITE_TEXT, SQLITE_UPDATE, sqlite3, sqlite3_commit_hook, sqlite3_compileoption_used,
-   sqlite3_preupdate_count, sqlite3_preupdate_hook, sqlite3_preupdate_new, sqlite3_preupdate_old,
-   sqlite3_rollback_hook, sqlite3_value, sqlite3_value_blob, sqlite3_value_bytes,
-   sqlite3_value_double, sqlite3_value_int64, sqlite3_value_text, sqlite3_value_type,
+   SQLITE_ALTER_TABLE, SQLITE_BLOB, SQLITE_CREATE_INDEX, SQLITE_CREATE_TABLE,
+   SQLITE_CREATE_TEMP_INDEX, SQLITE_CREATE_TEMP_TABLE, SQLITE_CREATE_TEMP_TRIGGER,
+   SQLITE_CREATE_TEMP_VIEW, SQLITE_CREATE_TRIGGER, SQLITE_CREATE_VIEW, SQLITE_DELETE,
+   SQLITE_DROP_INDEX, SQLITE_DROP_TABLE, SQLITE_DROP_TEMP_INDEX, SQLITE_DROP_TEMP_TABLE,
+   SQLITE_DROP_TEMP_TRIGGER, SQLITE_DROP_TEMP_VIEW, SQLITE_DROP_TRIGGER, SQLITE_DROP_VIEW,
+   SQLITE_FLOAT, SQLITE_INSERT, SQLITE_INTEGER, SQLITE_NULL, SQLITE_OK, SQLITE_TEXT, SQLITE_UPDATE,
+   sqlite3, sqlite3_commit_hook, sqlite3_compileoption_used, sqlite3_preupdate_count,
+   sqlite3_preupdate_hook, sqlite3_preupdate_new, sqlite3_preupdate_old, sqlite3_rollback_hook,
+   sqlite3_set_authorizer, sqlite3_threadsafe, sqlite3_value, sqlite3_value_blob,
+   sqlite3_value_bytes, sqlite3_value_double, sqlite3_value_int64, sqlite3_value_text,
+   sqlite3_value_type,
 };
 use tracing::{debug, error, trace};
 
 use crate::broker::ObservationBroker;
-use crate::change::ChangeOperation;
+use crate::change::{ChangeOperation, SchemaChange, SchemaChangeKind};
 
 /// A SQLite value extracted from preupdate hooks.
 ///
@@ -84,12 +91,18 @@ impl SqliteValue {
 /// Raw change event captured by the preupdate hook before commit decision.
 #[derive(Debug, Clone)]
 pub struct PreUpdateEvent {
+   /// Schema containing the table (`main`, `temp`, or an attached schema name).
+   pub schema: String,
    pub table: String,
    pub operation: ChangeOperation,
    pub old_rowid: i64,
    pub new_rowid: i64,
    pub old_values: Option<Vec<SqliteValue>>,
    pub new_values: Option<Vec<SqliteValue>>,
+   /// Number of columns in the row, as reported by `sqlite3_preupdate_count`.
+   pub column_count: usize,
+   /// Monotonic time at which the hook captured the change.
+   pub timestamp: Instant,
 }
 
 /// Context data passed to SQLite hook callbacks.
@@ -98,6 +111,8 @@ pub struct PreUpdateEvent {
 /// stays alive as long as hooks are registered.
 struct HookContext {
    broker: Arc<ObservationBroker>,
+   /// Whether `authorizer_callback` was installed, so unregistering clears it
+   authorizer: bool,
 }
 
 /// Checks if the linked SQLite library was compiled with `SQLITE_ENABLE_PREUPDATE_HOOK`.
@@ -119,11 +134,47 @@ pub fn is_preupdate_hook_enabled() -> bool {
    unsafe { sqlite3_compileoption_used(opt_name.as_ptr()) == 1 }
 }
 
+/// Checks that the linked SQLite library supports observation.
+///
+/// Requires `SQLITE_ENABLE_PREUPDATE_HOOK`, and a library built with
+/// `SQLITE_THREADSAFE` of 1 or 2: sqlx runs each connection on its own worker
+/// thread, so a single-threaded build is unsafe to use at all.
+///
+/// This only inspects how the library was compiled, not the threading mode
+/// of any connection. Connections do not need serialized mode: hooks are
+/// registered and removed while holding sqlx's lock on the connection handle,
+/// and the callbacks run on the connection's worker thread.
+pub(crate) fn check_sqlite_support() -> crate::Result<()> {
+   if !is_preupdate_hook_enabled() {
+      return Err(crate::Error::UnsupportedSqlite {
+         feature: "SQLITE_ENABLE_PREUPDATE_HOOK",
+         remedy: "enable the `bundled` feature of sqlx-sqlite-observer, or link a \
+                  SQLite built with SQLITE_ENABLE_PREUPDATE_HOOK",
+      });
+   }
+   // SAFETY: sqlite3_threadsafe only reads a compile-time constant.
+   if unsafe { sqlite3_threadsafe() } == 0 {
+      return Err(crate::Error::UnsupportedSqlite {
+         feature: "SQLITE_THREADSAFE=1 or SQLITE_THREADSAFE=2",
+         remedy: "enable the `bundled` feature of sqlx-sqlite-observer, or link a \
+                  SQLite that was not built with SQLITE_THREADSAFE=0",
+      });
+   }
+   Ok(())
+}
+
 /// Registers all observation hooks on a raw SQLite connection.
 ///
 /// Hooks are automatically cleaned up by SQLite when the connection is closed,
 /// either explicitly or when the connection exceeds the sqlx pool's `idle_timeout`.
 ///
+/// If the broker captures schema changes
+/// ([`ObserverConfig::capture_schema_changes`](crate::ObserverConfig::capture_schema_changes)),
+/// this also installs an authorizer, **replacing any authorizer already set on
+/// `db`**; [`unregister_hooks`] then clears it rather than restoring the
+/// previous one. Without schema capture the connection's authorizer is left
+/// untouched.
+///
 /// # Safety
 ///
 /// - `db` must be a valid pointer to an open sqlite3 connection
@@ -133,27 +184,21 @@ pub fn is_preupdate_hook_enabled() -> bool {
 ///
 /// # Errors
 ///
-/// Returns an error if preupdate hooks are not supported by the linked SQLite
-/// library, or if the hooks cannot be registered.
+/// Returns [`Error::UnsupportedSqlite`](crate::Error::UnsupportedSqlite) if the
+/// linked SQLite library does not support observation.
 pub unsafe fn register_hooks(
    db: *mut sqlite3,
    broker: Arc<ObservationBroker>,
 ) -> crate::Result<()> {
    // Check at runtime if preupdate hook is supported
-   if !is_preupdate_hook_enabled() {
-      return Err(crate::Error::HookRegistration(
-         "SQLite was not compiled with SQLITE_ENABLE_PREUPDATE_HOOK. \
-             Ensure you're using a SQLite build with preupdate hook support, \
-             or enable the 'bundled' feature on libsqlite3-sys."
-            .to_string(),
-      ));
-   }
+   check_sqlite_support()?;
 
    debug!("Registering SQLite observation hooks");
 
    // Heap-allocate the context so it outlives this function. SQLite's C API
    // requires a raw pointer to pass user data to callbacks.
-   let context = Box::new(HookContext { broker });
+   let authorizer = broker.captures_schema_changes();
+   let context = Box::new(HookContext { broker, authorizer });
    // Transfer ownership out of Rust's memory management.
    //
    // NOTE: This pointer is shared across all three hooks and is intentionally
@@ -166,12 +211,15 @@ pub unsafe fn register_hooks(
    let context_ptr = Box::into_raw(context) as *mut c_void;
 
    // SAFETY: db is a valid sqlite3 pointer (guaranteed by caller).
-   // Each hook receives the same context_ptr, which remains valid until
+   // Each hook (and the authorizer) receives the same context_ptr, which remains valid until
    // unregister_hooks is called or the process exits.
    unsafe {
       sqlite3_preupdate_hook(db, Some(preupdate_callback), context_ptr);
       sqlite3_commit_hook(db, Some(commit_callback), context_ptr);
       sqlite3_rollback_hook(db, Some(rollback_callback), context_ptr);
+      if authorizer {
+         sqlite3_set_authorizer(db, Some(authorizer_callback), context_ptr);
+      }
    }
 
    trace!("SQLite hooks registered successfully");
@@ -198,7 +246,13 @@ pub unsafe fn unregister_hooks(db: *mut sqlite3) {
    // Reclaim the HookContext we leaked in register_hooks
    if !prev_user_data.is_null() {
       // SAFETY: prev_user_data was created by Box::into_raw in register_hooks
-      let _ = unsafe { Box::from_raw(prev_user_data as *mut HookContext) };
+      let context = unsafe { Box::from_raw(prev_user_data as *mut HookContext) };
+      if context.authorizer {
+         // SAFETY: db is valid (guaranteed by caller); the authorizer is ours.
+         unsafe {
+            sqlite3_set_authorizer(db, None, ptr::null_mut());
+         }
+      }
       trace!("SQLite hooks unregistered and context freed");
    }
 }
@@ -214,7 +268,7 @@ unsafe extern "C" fn preupdate_callback(
    user_data: *mut c_void,
    db: *mut sqlite3,
    op: c_int,
-   _database: *const c_char,
+   database: *const c_char,
    table: *const c_char,
    old_rowid: i64,
    new_rowid: i64,
@@ -240,6 +294,17 @@ unsafe extern "C" fn preupdate_callback(
          return;
       }
 
+      // Schema name is "main" for the main database, or the name given in ATTACH
+      let schema_name = if database.is_null() {
+         "main".to_string()
+      } else {
+         // SAFETY: database is a non-null C string provided by SQLite, valid for this callback.
+         match unsafe { CStr::from_ptr(database) }.to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return,
+         }
+      };
+
       let operation = match op {
          SQLITE_INSERT => ChangeOperation::Insert,
          SQLITE_UPDATE => ChangeOperation::Update,
@@ -247,7 +312,7 @@ unsafe extern "C" fn preupdate_callback(
          _ => return,
       };
 
-      trace!(table = %table_name, ?operation, old_rowid, new_rowid, "Preupdate hook fired");
+      trace!(schema = %schema_name, table = %table_name, ?operation, old_rowid, new_rowid, "Preupdate hook fired");
 
       // SAFETY: db is a valid sqlite3 pointer provided by SQLite for this callback.
       let column_count = unsafe { sqlite3_preupdate_count(db) };
@@ -294,12 +359,15 @@ unsafe extern "C" fn preupdate_callback(
       };
 
       let event = PreUpdateEvent {
+         schema: schema_name,
          table: table_name,
          operation,
          old_rowid,
          new_rowid,
          old_values,
          new_values,
+         column_count,
+         timestamp: Instant::now(),
       };
 
       context.broker.on_preupdate(event);
@@ -363,6 +431,75 @@ unsafe extern "C" fn rollback_callback(user_data: *mut c_void) {
    }
 }
 
+/// Authorizer callback - records DDL statements as they are prepared.
+///
+/// Called by SQLite for every action of every statement being prepared; only
+/// schema changes are recorded, and every action is allowed. For `ALTER TABLE`
+/// the first argument is the schema and the second the table; for the other
+/// DDL actions the first names the object and `database` its schema.
+///
+/// Note: `user_data` is SQLite's C API term for callback context (our HookContext),
+/// unrelated to application-level user data.
+unsafe extern "C" fn authorizer_callback(
+   user_data: *mut c_void,
+   action: c_int,
+   arg1: *const c_char,
+   arg2: *const c_char,
+   database: *const c_char,
+   _trigger: *const c_char,
+) -> c_int {
+   let kind = match action {
+      SQLITE_CREATE_TABLE | SQLITE_CREATE_TEMP_TABLE => SchemaChangeKind::CreateTable,
+      SQLITE_ALTER_TABLE => SchemaChangeKind::AlterTable,
+      SQLITE_DROP_TABLE | SQLITE_DROP_TEMP_TABLE => SchemaChangeKind::DropTable,
+      SQLITE_CREATE_INDEX | SQLITE_CREATE_TEMP_INDEX => SchemaChangeKind::CreateIndex,
+      SQLITE_DROP_INDEX | SQLITE_DROP_TEMP_INDEX => SchemaChangeKind::DropIndex,
+      SQLITE_CREATE_VIEW | SQLITE_CREATE_TEMP_VIEW => SchemaChangeKind::CreateView,
+      SQLITE_DROP_VIEW | SQLITE_DROP_TEMP_VIEW => SchemaChangeKind::DropView,
+      SQLITE_CREATE_TRIGGER | SQLITE_CREATE_TEMP_TRIGGER => SchemaChangeKind::CreateTrigger,
+      SQLITE_DROP_TRIGGER | SQLITE_DROP_TEMP_TRIGGER => SchemaChangeKind::DropTrigger,
+      _ => return SQLITE_OK,
+   };
+   if user_data.is_null() {
+      return SQLITE_OK;
+   }
+
+   // Catch any panics to prevent unwinding across the FFI boundary (which is UB).
+   let result = catch_unwind(|| {
+      // SAFETY: arguments are null or NUL-terminated strings provided by SQLite,
+      // valid for this callback.
+      let text = |ptr: *const c_char| {
+         (!ptr.is_null()).then(|| {
+            unsafe { CStr::from_ptr(ptr) }
+               .to_string_lossy()
+               .into_owned()
+         })
+      };
+      let (schema, object) = if kind == SchemaChangeKind::AlterTable {
+         (text(arg1), text(arg2))
+      } else {
+         (text(database), text(arg1))
+      };
+      let Some(object) = object else {
+         return;
+      };
+
+      // SAFETY: user_data is a valid HookContext pointer created in register_hooks.
+      let context = unsafe { &*(user_data as *const HookContext) };
+      context.broker.on_schema_change(SchemaChange {
+         kind,
+         schema: schema.unwrap_or_else(|| "main".to_string()),
+         object,
+      });
+   });
+
+   if result.is_err() {
+      eprintln!("sqlx-sqlite-observer: panic in authorizer_callback (absorbed to prevent UB)");
+   }
+
+   SQLITE_OK
+}
+
 #[cfg(test)]
 mod tests {
    use super::*;
@@ -0,0 +1,53 @@
+//! Rolling statistics of how long observed transactions take to commit.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Number of most recent commits the statistics cover.
+pub const LATENCY_WINDOW: usize = 1024;
+
+/// Commit latency percentiles over the last [`LATENCY_WINDOW`] observed commits.
+///
+/// Latency is the time from the first buffered change of a transaction to its
+/// commit hook firing. Only transactions that changed an observed table are
+/// measured; all fields are zero until one commits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LatencyStats {
+   pub p50: Duration,
+   pub p95: Duration,
+   pub max: Duration,
+   /// Number of commits the statistics are computed from.
+   pub samples: usize,
+}
+
+/// Fixed-size window of recent commit latencies.
+#[derive(Debug, Default)]
+pub(crate) struct LatencyWindow {
+   samples: VecDeque<Duration>,
+}
+
+impl LatencyWindow {
+   pub(crate) fn record(&mut self, latency: Duration) {
+      if self.samples.len() == LATENCY_WINDOW {
+         self.samples.pop_front();
+      }
+      self.samples.push_back(latency);
+   }
+
+   pub(crate) fn stats(&self) -> LatencyStats {
+      let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+      sorted.sort_unstable();
+      // Nearest-rank percentile
+      let percentile = |p: usize| {
+         let rank = (sorted.len() * p).div_ceil(100).max(1);
+         sorted.get(rank - 1).copied().unwrap_or_default()
+      };
+      LatencyStats {
+         p50: percentile(50),
+         p95: percentile(95),
+         max: sorted.last().copied().unwrap_or_default(),
+         samples: sorted.len(),
+      }
+   }
+}
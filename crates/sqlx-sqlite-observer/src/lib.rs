@@ -20,7 +20,8 @@
 //!    Use [`is_preupdate_hook_enabled()`] to verify at runtime.
 //!
 //! If preupdate hooks are not available, [`SqliteObserver::acquire()`] will return
-//! an error with a descriptive message.
+//! [`Error::UnsupportedSqlite`]. Call [`SqliteObserver::preflight()`] at startup to
+//! check up front.
 //!
 //! # Features
 //!
@@ -123,27 +124,40 @@
 
 pub mod broker;
 pub mod change;
+pub mod compacting;
 pub mod config;
 pub mod connection;
 pub mod error;
+mod generated;
 pub mod hooks;
+pub mod latency;
 pub mod observer;
 pub mod schema;
+mod snapshot;
 pub mod stream;
+mod throttle;
 
 #[cfg(feature = "conn-mgr")]
 pub mod conn_mgr;
 
-pub use broker::ObservationBroker;
-pub use change::{ChangeOperation, ColumnValue, TableChange, TableChangeEvent, TableInfo};
+pub use broker::{BufferMark, ObservationBroker};
+pub use change::{
+   ChangeOperation, ColumnValue, ObserverEvent, OpCounts, SchemaChange, SchemaChangeKind,
+   TableChange, TableChangeEvent, TableInfo, TransactionBatch, TransactionSummary,
+};
+pub use compacting::CompactingLog;
 pub use config::ObserverConfig;
 pub use connection::ObservableConnection;
 pub use error::Error;
 pub use hooks::{SqliteValue, is_preupdate_hook_enabled, unregister_hooks};
+pub use latency::LatencyStats;
 pub use observer::SqliteObserver;
-pub use stream::{TableChangeStream, TableChangeStreamExt};
+pub use stream::{
+   DebouncedStream, TableChangeReceiver, TableChangeReceiverExt, TableChangeStream,
+   TableChangeStreamExt,
+};
 
 #[cfg(feature = "conn-mgr")]
-pub use conn_mgr::{ObservableSqliteDatabase, ObservableWriteGuard};
+pub use conn_mgr::{ObservableAttachedWriteGuard, ObservableSqliteDatabase, ObservableWriteGuard};
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -5,16 +5,18 @@
 use std::sync::Arc;
 
 use sqlx::SqlitePool;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::Stream;
 use tracing::{debug, warn};
 
 use crate::Result;
 use crate::broker::ObservationBroker;
-use crate::change::TableChange;
+use crate::change::{TableChange, TableChangeEvent};
 use crate::config::ObserverConfig;
 use crate::connection::ObservableConnection;
 use crate::error::Error;
-use crate::schema::query_table_info;
+use crate::schema::{query_table_info, query_user_tables};
+use crate::stream::TableChangeReceiver;
 
 /// SQLite database observer with transaction-safe change notifications.
 ///
@@ -26,6 +28,13 @@ use crate::schema::query_table_info;
 /// # SQLite Version Requirements
 ///
 /// Requires SQLite library compiled with `SQLITE_ENABLE_PREUPDATE_HOOK`.
+///
+/// # Authorizer
+///
+/// With [`ObserverConfig::capture_schema_changes`] set, observed connections
+/// get an authorizer (`sqlite3_set_authorizer`) that reports DDL. It replaces
+/// any authorizer the application installed on the connection, and is cleared
+/// when the hooks are removed. It is off by default.
 pub struct SqliteObserver {
    pool: SqlitePool,
    broker: Arc<ObservationBroker>,
@@ -38,6 +47,11 @@ impl SqliteObserver {
    /// Tables specified in the config will be automatically observed.
    pub fn new(pool: SqlitePool, config: ObserverConfig) -> Self {
       let broker = ObservationBroker::new(config.channel_capacity, config.capture_values);
+      broker.set_max_notifications_per_sec(config.max_notifications_per_sec);
+      broker.set_emit_transaction_summary(config.emit_transaction_summary);
+      broker.set_observe_all(config.observe_all);
+      broker.set_max_buffered_changes(config.max_buffered_changes);
+      broker.set_capture_schema_changes(config.capture_schema_changes);
 
       if !config.tables.is_empty() {
          broker.observe_tables(config.tables.iter().map(String::as_str));
@@ -50,49 +64,179 @@ impl SqliteObserver {
       }
    }
 
+   /// Checks that the linked SQLite library supports observation.
+   ///
+   /// Verifies the library was compiled with `SQLITE_ENABLE_PREUPDATE_HOOK` and
+   /// is not a single-threaded (`SQLITE_THREADSAFE=0`) build. Returns
+   /// [`Error::UnsupportedSqlite`] naming the missing compile-time option and a
+   /// remedy. Call it at startup to fail fast, instead of on the first
+   /// [`acquire`](Self::acquire), which runs the same check.
+   ///
+   /// Only compile-time options are checked; no connection is opened, and pool
+   /// connections need not be in serialized threading mode.
+   pub fn preflight() -> Result<()> {
+      crate::hooks::check_sqlite_support()
+   }
+
    /// Subscribes to change notifications for the specified tables.
    ///
-   /// If additional tables are provided, they will be added to the observed set.
-   /// Returns a broadcast receiver that will receive `TableChange` events
-   /// after transactions commit.
-   pub fn subscribe<I, S>(&self, tables: I) -> broadcast::Receiver<TableChange>
+   /// Returns a receiver of `TableChange` events after transactions commit,
+   /// filtered to `tables` (all observed tables if empty). Subscribing does not
+   /// change which tables are captured: register them with the config,
+   /// [`observe`](Self::observe) or `observe_all`.
+   pub fn subscribe<I, S>(&self, tables: I) -> TableChangeReceiver
    where
       I: IntoIterator<Item = S>,
       S: Into<String>,
    {
       let tables: Vec<String> = tables.into_iter().map(Into::into).collect();
-      if !tables.is_empty() {
-         self
-            .broker
-            .observe_tables(tables.iter().map(String::as_str));
-      }
-      self.broker.subscribe()
+      TableChangeReceiver::new(self.broker.subscribe(), tables)
+   }
+
+   /// Subscribes to change notifications without ever dropping a change.
+   ///
+   /// Returns a bounded `mpsc` receiver with room for `capacity` changes. When it
+   /// is full, the committing connection blocks inside SQLite's commit hook until
+   /// the consumer catches up, so a slow consumer stalls every write. Use this for
+   /// a single critical consumer (sync, replication) that must see every change;
+   /// prefer [`subscribe`](Self::subscribe) for UI updates and other lossy-tolerant
+   /// listeners.
+   ///
+   /// Changes arrive as [`TableChangeEvent::Change`]. A transaction exceeding
+   /// [`max_buffered_changes`](crate::ObserverConfig::max_buffered_changes) keeps
+   /// no rows to deliver; the receiver gets [`TableChangeEvent::Lagged`] with the
+   /// number of changes it skipped instead, in commit order, and should re-query
+   /// its tables.
+   ///
+   /// Dropping the receiver unregisters the subscriber on the next commit.
+   ///
+   /// # Panics
+   ///
+   /// Panics if `capacity` is 0.
+   pub fn subscribe_reliable<I, S>(
+      &self,
+      tables: I,
+      capacity: usize,
+   ) -> mpsc::Receiver<TableChangeEvent>
+   where
+      I: IntoIterator<Item = S>,
+      S: Into<String>,
+   {
+      let tables: Vec<String> = tables.into_iter().map(Into::into).collect();
+      self.broker.subscribe_reliable(tables, capacity)
    }
 
    /// Subscribes to change notifications as a Stream.
    ///
    /// Returns a `TableChangeStream` that implements `futures::Stream`.
-   /// If tables are specified, the stream will only yield changes for those tables.
+   /// If tables are specified, the stream will only yield changes for those
+   /// tables; like [`subscribe`](Self::subscribe), it does not observe them.
    pub fn subscribe_stream<I, S>(&self, tables: I) -> crate::stream::TableChangeStream
    where
       I: IntoIterator<Item = S>,
       S: Into<String>,
    {
       use crate::stream::TableChangeStreamExt;
+      self.subscribe(tables).into_stream()
+   }
+
+   /// Subscribes to committed transactions as a `Stream` of [`TransactionBatch`]es.
+   ///
+   /// Each item groups every change from one commit, with its sequence range and
+   /// commit time, for consumers that replicate whole transactions downstream.
+   /// If tables are specified, batches contain only changes to them; commits
+   /// touching none are skipped. On an unfiltered stream, batches missed by a
+   /// lagging consumer show up as a gap in `seq_range`.
+   ///
+   /// [`TransactionBatch`]: crate::TransactionBatch
+   pub fn subscribe_batches<I, S>(
+      &self,
+      tables: I,
+   ) -> impl Stream<Item = crate::TransactionBatch> + Send + Unpin + 'static
+   where
+      I: IntoIterator<Item = S>,
+      S: Into<String>,
+   {
       let tables: Vec<String> = tables.into_iter().map(Into::into).collect();
-      // Register tables for observation (uses references, avoids clone)
-      if !tables.is_empty() {
-         self
-            .broker
-            .observe_tables(tables.iter().map(String::as_str));
-      }
-      let rx = self.broker.subscribe();
-      let stream = rx.into_stream();
-      if tables.is_empty() {
-         stream
-      } else {
-         stream.filter_tables(tables)
-      }
+      crate::stream::batch_stream(self.broker.subscribe_batches(), tables)
+   }
+
+   /// Creates a [`CompactingLog`] that keeps only the latest change per row.
+   ///
+   /// For snapshot-style replication, where only the net state of each row
+   /// matters between sync cycles. If tables are specified, only their changes
+   /// are recorded. Changes committed before this call are not included.
+   /// Intermediate states are lost; see [`CompactingLog`] for how changes are
+   /// keyed and merged.
+   ///
+   /// [`CompactingLog`]: crate::CompactingLog
+   pub fn compacting_log<I, S>(&self, tables: I) -> crate::CompactingLog
+   where
+      I: IntoIterator<Item = S>,
+      S: Into<String>,
+   {
+      let tables: Vec<String> = tables.into_iter().map(Into::into).collect();
+      self.broker.compacting_log(tables)
+   }
+
+   /// Returns commit latency percentiles over recent observed transactions.
+   ///
+   /// Measures the time from a transaction's first change to an observed table
+   /// until it commits, over the last [`LATENCY_WINDOW`] such commits.
+   /// Transactions that touch no observed table are not measured, so this
+   /// does not cover every write.
+   ///
+   /// [`LATENCY_WINDOW`]: crate::latency::LATENCY_WINDOW
+   pub fn commit_latency_stats(&self) -> crate::LatencyStats {
+      self.broker.commit_latency_stats()
+   }
+
+   /// Subscribes to one [`TransactionSummary`] per committed transaction.
+   ///
+   /// Requires
+   /// [`emit_transaction_summary`](crate::ObserverConfig::emit_transaction_summary);
+   /// otherwise the receiver gets nothing.
+   ///
+   /// [`TransactionSummary`]: crate::TransactionSummary
+   pub fn subscribe_summaries(&self) -> broadcast::Receiver<crate::TransactionSummary> {
+      self.broker.subscribe_summaries()
+   }
+
+   /// Subscribes to committed DDL statements, such as `ALTER TABLE`.
+   ///
+   /// An `ALTER`, `CREATE` or `DROP TABLE` of an observed table also marks its
+   /// `TableInfo` stale, so the next acquire re-queries the schema. Yields
+   /// nothing unless [`ObserverConfig::capture_schema_changes`] is set.
+   pub fn subscribe_schema_changes(&self) -> broadcast::Receiver<crate::SchemaChange> {
+      self.broker.subscribe_schema_changes()
+   }
+
+   /// Subscribes to observer status notifications such as
+   /// [`ObserverEvent::SchemaDrift`](crate::ObserverEvent::SchemaDrift).
+   pub fn subscribe_events(&self) -> broadcast::Receiver<crate::ObserverEvent> {
+      self.broker.subscribe_events()
+   }
+
+   /// Reads every current row of `table` as a stream of synthetic INSERT changes.
+   ///
+   /// Lets a new replication consumer seed its state through the same apply
+   /// path as live changes: each item has `primary_key`, `new_values` and
+   /// `column_names` populated (regardless of `capture_values`), and generated
+   /// columns are included. All rows come from a single read, so the snapshot
+   /// is consistent but is held in memory until consumed.
+   ///
+   /// The load and the live subscription are not atomic. Subscribe *before*
+   /// calling this so no commit is missed; changes committed while the load
+   /// runs may then appear in both, so apply changes idempotently by primary
+   /// key.
+   ///
+   /// Returns [`Error::TableNotFound`] if the table does not exist.
+   pub async fn initial_load(
+      &self,
+      table: &str,
+   ) -> Result<impl Stream<Item = TableChange> + Send + Unpin + 'static> {
+      let changes = crate::snapshot::load_table(&self.pool, table).await?;
+      Ok(tokio_stream::iter(changes))
    }
 
    /// Acquires a connection from the pool with observation hooks registered.
@@ -102,7 +246,19 @@ impl SqliteObserver {
    ///
    /// On first acquisition for each table, queries the schema to determine
    /// primary key columns and WITHOUT ROWID status.
+   ///
+   /// Returns [`Error::ShutDown`] after [`shutdown`](Self::shutdown).
    pub async fn acquire(&self) -> Result<ObservableConnection> {
+      if self.broker.is_closed() {
+         return Err(Error::ShutDown);
+      }
+      if self.config.resolve_generated_columns {
+         self
+            .broker
+            .start_generated_column_resolver(self.pool.clone());
+      }
+      self.broker.start_throttle_flusher();
+
       let conn = self.pool.acquire().await.map_err(|_| Error::PoolAcquire)?;
       let mut observable = ObservableConnection::new(conn, Arc::clone(&self.broker));
 
@@ -114,12 +270,18 @@ impl SqliteObserver {
       Ok(observable)
    }
 
-   /// Ensures TableInfo is set for all observed tables.
+   /// Ensures TableInfo is set, and current, for all observed tables.
    async fn ensure_table_info(&self, conn: &mut ObservableConnection) -> Result<()> {
+      if self.broker.observes_all() {
+         match query_user_tables(conn).await {
+            Ok(tables) => self.broker.observe_tables(tables),
+            Err(e) => warn!(error = %e, "Failed to list tables to observe"),
+         }
+      }
       let observed = self.broker.get_observed_tables();
 
       for table in observed {
-         if self.broker.get_table_info(&table).is_none() {
+         if self.broker.needs_table_info(&table) {
             match query_table_info(conn, &table).await {
                Ok(Some(info)) => {
                   debug!(table = %table, pk_columns = ?info.pk_columns, without_rowid = info.without_rowid, "Queried table info");
@@ -161,10 +323,45 @@ impl SqliteObserver {
       self.broker.get_observed_tables()
    }
 
+   /// Adds tables to the set captured by the hooks, for every subscriber.
+   ///
+   /// Subscriptions only filter what is captured, so a table must be observed
+   /// (here, in the config, or with `observe_all`) before any subscriber sees
+   /// its changes. Schema info is queried on the next acquire.
+   pub fn observe<I, S>(&self, tables: I)
+   where
+      I: IntoIterator<Item = S>,
+      S: AsRef<str>,
+   {
+      self.broker.observe_tables(tables);
+   }
+
    /// Returns a reference to the underlying observation broker.
    pub fn broker(&self) -> &Arc<ObservationBroker> {
       &self.broker
    }
+
+   /// Stops observing, for this observer and every clone of it.
+   ///
+   /// Changes buffered by a transaction that has not committed are discarded
+   /// on purpose: they were never committed, so a notification would report a
+   /// write that may never happen. Later acquires fail with
+   /// [`Error::ShutDown`]. The pool itself is left open.
+   ///
+   /// Hooks are not removed from connections that are still checked out. By
+   /// default sqlx opens connections without SQLite's per-connection mutex
+   /// (`SQLITE_OPEN_NOMUTEX`), so replacing hooks from this thread while the
+   /// owning task runs a statement would race with the callbacks. Instead the
+   /// hooks stop recording and publishing at once, and are removed when the
+   /// [`ObservableConnection`] is dropped, before the connection returns to the
+   /// pool. Idle pooled connections carry no hooks.
+   pub fn shutdown(self) {
+      debug!(
+         pending = self.broker.pending_buffer_len(),
+         "Shutting down observer"
+      );
+      self.broker.close();
+   }
 }
 
 impl Clone for SqliteObserver {
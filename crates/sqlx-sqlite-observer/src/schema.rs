@@ -11,7 +11,8 @@ use crate::change::TableInfo;
 
 /// Queries the schema information for a table.
 ///
-/// Returns `TableInfo` containing primary key column indices and WITHOUT ROWID status.
+/// Returns `TableInfo` containing primary key column indices, column names,
+/// virtual generated columns and WITHOUT ROWID status.
 /// Returns `None` if the table doesn't exist.
 pub async fn query_table_info(
    conn: &mut SqliteConnection,
@@ -20,21 +21,40 @@ pub async fn query_table_info(
    // Check if table exists and get WITHOUT ROWID status
    let without_rowid = is_without_rowid(conn, table_name).await?;
 
-   // Get primary key columns using pragma_table_info()
-   let pk_columns = query_pk_columns(conn, table_name).await?;
+   // Get primary key columns and column names using pragma_table_xinfo()
+   let columns = query_columns(conn, table_name).await?;
 
    // Determine if table exists:
-   // - If pk_columns is None, pragma_table_info returned no rows (table doesn't exist)
+   // - If columns is None, pragma_table_xinfo returned no rows (table doesn't exist)
    // - If without_rowid is true, the table must exist (we found it in sqlite_master)
-   // - A table with no explicit PK returns Some([]), not None
-   if pk_columns.is_none() && !without_rowid {
+   // - A table with no explicit PK returns Some with empty pk_columns, not None
+   if columns.is_none() && !without_rowid {
       return Ok(None);
    }
 
-   Ok(Some(TableInfo::new(
-      pk_columns.unwrap_or_default(),
-      without_rowid,
-   )))
+   let mut info = columns.unwrap_or_default();
+   info.without_rowid = without_rowid;
+   Ok(Some(info))
+}
+
+/// Lists the user tables of the main database, excluding internal
+/// `sqlite_%` and `_sqlx_%` tables.
+pub async fn query_user_tables(conn: &mut SqliteConnection) -> crate::Result<Vec<String>> {
+   let tables: Vec<String> =
+      sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+         .fetch_all(&mut *conn)
+         .await
+         .map_err(crate::Error::Sqlx)?;
+   Ok(tables
+      .into_iter()
+      .filter(|table| !is_internal_table(table))
+      .collect())
+}
+
+/// Whether `table` is an internal SQLite or SQLx table, never observed in
+/// all-tables mode.
+pub fn is_internal_table(table: &str) -> bool {
+   table.starts_with("sqlite_") || table.starts_with("_sqlx_")
 }
 
 /// Checks if a table was created with WITHOUT ROWID.
@@ -73,22 +93,27 @@ fn has_without_rowid_clause(create_sql: &str) -> bool {
    re.is_match(create_sql)
 }
 
-/// Queries the primary key column indices for a table.
+/// Queries the primary key column indices, column names and virtual generated
+/// columns for a table.
 ///
-/// Returns column indices in the order they appear in the PRIMARY KEY definition.
-/// For composite primary keys, the `pk` column in PRAGMA table_info indicates
+/// Returns PK column indices in the order they appear in the PRIMARY KEY
+/// definition, and all column names in declaration order, including generated
+/// columns.
+/// For composite primary keys, the `pk` column in PRAGMA table_xinfo indicates
 /// the position (1-indexed) within the PK.
 ///
-/// Uses the `pragma_table_info()` table-valued function (available since SQLite
-/// 3.16.0) so the table name can be bound as a parameter instead of interpolated
-/// into the SQL string.
-async fn query_pk_columns(
+/// Uses the `pragma_table_xinfo()` table-valued function so the table name can be
+/// bound as a parameter instead of interpolated into the SQL string. Unlike
+/// `pragma_table_info()`, it also lists generated columns.
+async fn query_columns(
    conn: &mut SqliteConnection,
    table_name: &str,
-) -> crate::Result<Option<Vec<usize>>> {
-   // pragma_table_info returns: cid, name, type, notnull, dflt_value, pk
-   // pk is 0 for non-PK columns, or 1-indexed position for PK columns
-   let sql = "SELECT cid, name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1)";
+) -> crate::Result<Option<TableInfo>> {
+   // pragma_table_xinfo returns: cid, name, type, notnull, dflt_value, pk, hidden
+   // pk is 0 for non-PK columns, or 1-indexed position for PK columns.
+   // hidden is 0 for normal columns, 2 for VIRTUAL and 3 for STORED generated
+   // columns (1 is only used by virtual tables).
+   let sql = "SELECT cid, name, pk, hidden FROM pragma_table_xinfo(?1) WHERE hidden != 1";
 
    let rows = sqlx::query(sql)
       .bind(table_name)
@@ -117,8 +142,24 @@ async fn query_pk_columns(
    // Sort by pk position to get correct order for composite PKs
    pk_columns.sort_by_key(|(_, pk_pos)| *pk_pos);
 
-   // Return just the column indices
-   Ok(Some(pk_columns.into_iter().map(|(cid, _)| cid).collect()))
+   // pragma_table_xinfo returns columns in cid (declaration) order
+   let column_names = rows.iter().map(|row| row.get("name")).collect();
+
+   let virtual_columns = rows
+      .iter()
+      .filter(|row| row.get::<i32, _>("hidden") == 2)
+      .map(|row| row.get::<i32, _>("cid") as usize)
+      .collect();
+
+   // Virtual columns are included here and in the preupdate hook's count
+   let column_count = rows.len();
+
+   Ok(Some(
+      TableInfo::new(pk_columns.into_iter().map(|(cid, _)| cid).collect(), false)
+         .with_column_names(column_names)
+         .with_virtual_columns(virtual_columns)
+         .with_column_count(column_count),
+   ))
 }
 
 #[cfg(test)]
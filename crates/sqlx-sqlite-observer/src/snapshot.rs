@@ -0,0 +1,104 @@
+//! Initial-state snapshots of observed tables.
+//!
+//! A replication consumer that starts from an existing database first needs
+//! every current row, then the live changes. [`load_table`] expresses the
+//! current rows as synthetic INSERT changes so both phases can go through the
+//! same apply logic.
+
+use std::time::{Instant, SystemTime};
+
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Pool, Row, Sqlite, TypeInfo, ValueRef};
+
+use crate::change::{ChangeOperation, ColumnValue, TableChange};
+use crate::error::Error;
+use crate::schema::query_table_info;
+
+/// Reads every row of `table` in the main database as an INSERT change.
+///
+/// Each change carries `primary_key`, `new_values` (including generated
+/// columns) and `column_names`, as the broker would publish them with value
+/// capture enabled. The rows are read by one `SELECT`, so they form a
+/// consistent snapshot.
+pub(crate) async fn load_table(
+   pool: &Pool<Sqlite>,
+   table: &str,
+) -> crate::Result<Vec<TableChange>> {
+   let info = {
+      let mut conn = pool.acquire().await.map_err(|_| Error::PoolAcquire)?;
+      query_table_info(&mut conn, table)
+         .await?
+         .ok_or_else(|| Error::TableNotFound(table.to_string()))?
+   };
+
+   let columns = info
+      .column_names
+      .iter()
+      .map(|name| quote_name(name))
+      .collect::<Vec<_>>()
+      .join(", ");
+   let sql = if info.without_rowid {
+      format!("SELECT {columns} FROM \"main\".{}", quote_name(table))
+   } else {
+      format!(
+         "SELECT rowid, {columns} FROM \"main\".{}",
+         quote_name(table)
+      )
+   };
+
+   let rows = sqlx::query(&sql).fetch_all(pool).await?;
+   let offset = usize::from(!info.without_rowid);
+
+   let changes = rows
+      .iter()
+      .map(|row| {
+         let rowid = (!info.without_rowid).then(|| row.get::<i64, _>(0));
+         let values: Vec<ColumnValue> = (offset..row.len())
+            .map(|idx| column_value(row, idx))
+            .collect();
+         let primary_key = info
+            .pk_columns
+            .iter()
+            .map(|&idx| values[idx].clone())
+            .collect();
+
+         TableChange {
+            schema: "main".to_string(),
+            table: table.to_string(),
+            operation: Some(ChangeOperation::Insert),
+            rowid,
+            primary_key,
+            old_values: None,
+            new_values: Some(values),
+            column_names: info.column_names.clone(),
+            schema_stale: false,
+            timestamp: Instant::now(),
+            captured_at: SystemTime::now(),
+         }
+      })
+      .collect();
+
+   Ok(changes)
+}
+
+/// Decodes a column by its runtime storage class, matching the preupdate hook.
+pub(crate) fn column_value(row: &SqliteRow, idx: usize) -> ColumnValue {
+   let Ok(raw) = row.try_get_raw(idx) else {
+      return ColumnValue::Null;
+   };
+   if raw.is_null() {
+      return ColumnValue::Null;
+   }
+
+   let value = match raw.type_info().name() {
+      "INTEGER" => row.try_get(idx).map(ColumnValue::Integer),
+      "REAL" => row.try_get(idx).map(ColumnValue::Real),
+      "BLOB" => row.try_get(idx).map(ColumnValue::Blob),
+      _ => row.try_get(idx).map(ColumnValue::Text),
+   };
+   value.unwrap_or(ColumnValue::Null)
+}
+
+pub(crate) fn quote_name(name: &str) -> String {
+   format!("\"{}\"", name.replace('"', "\"\""))
+}
@@ -1,12 +1,18 @@
+use std::collections::VecDeque;
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
+use indexmap::IndexMap;
 use tokio::sync::broadcast;
+use tokio::time::{Instant, Sleep};
 use tokio_stream::Stream;
 use tokio_stream::wrappers::BroadcastStream;
 use tracing::warn;
 
-use crate::change::{TableChange, TableChangeEvent};
+use crate::change::{TableChange, TableChangeEvent, TransactionBatch};
+use crate::compacting::RowKey;
 
 /// A filtered stream of table change notifications.
 ///
@@ -29,6 +35,11 @@ impl TableChangeStream {
       self.filter_tables = Some(tables);
       self
    }
+
+   /// Coalesces changes to the same row within `window`; see [`DebouncedStream`].
+   pub fn debounce(self, window: Duration) -> DebouncedStream {
+      DebouncedStream::new(self, window)
+   }
 }
 
 impl Stream for TableChangeStream {
@@ -74,10 +85,294 @@ pub trait TableChangeStreamExt {
    ///
    /// The returned stream can be further filtered using [`TableChangeStream::filter_tables`].
    fn into_stream(self) -> TableChangeStream;
+
+   /// Converts this receiver into a stream that coalesces changes to the same
+   /// row within `window`; see [`DebouncedStream`].
+   fn debounce(self, window: Duration) -> DebouncedStream;
 }
 
 impl TableChangeStreamExt for broadcast::Receiver<TableChange> {
    fn into_stream(self) -> TableChangeStream {
       TableChangeStream::new(self)
    }
+
+   fn debounce(self, window: Duration) -> DebouncedStream {
+      self.into_stream().debounce(window)
+   }
+}
+
+/// A change stream that coalesces rapid changes to the same row.
+///
+/// The first change to a row starts a `window`; further changes to that row
+/// with the same operation replace it, and only the latest is emitted when the
+/// window ends. A change with a different operation is never merged: the
+/// pending change is emitted at once and the new one starts its own window, so
+/// a DELETE followed by an INSERT of the same rowid yields both, in order.
+///
+/// Rows are identified by schema, table and primary key (or rowid). Changes
+/// with neither are passed through immediately. Order is kept per row, but not
+/// across rows. On lag or the end of the inner stream, pending changes are
+/// flushed first.
+pub struct DebouncedStream {
+   inner: TableChangeStream,
+   inner_done: bool,
+   window: Duration,
+   /// Pending change per row with its deadline, earliest deadline first
+   pending: IndexMap<RowKey, (Instant, TableChange)>,
+   ready: VecDeque<TableChangeEvent>,
+   sleep: Pin<Box<Sleep>>,
+}
+
+impl DebouncedStream {
+   fn new(inner: TableChangeStream, window: Duration) -> Self {
+      Self {
+         inner,
+         inner_done: false,
+         window,
+         pending: IndexMap::new(),
+         ready: VecDeque::new(),
+         sleep: Box::pin(tokio::time::sleep(Duration::ZERO)),
+      }
+   }
+
+   fn add(&mut self, change: TableChange) {
+      let Some(key) = RowKey::of(&change) else {
+         self.ready.push_back(TableChangeEvent::Change(change));
+         return;
+      };
+      match self.pending.get_mut(&key) {
+         Some((_, pending)) if pending.operation == change.operation => *pending = change,
+         Some(_) => {
+            let (_, previous) = self.pending.shift_remove(&key).expect("entry exists");
+            self.ready.push_back(TableChangeEvent::Change(previous));
+            self
+               .pending
+               .insert(key, (Instant::now() + self.window, change));
+         }
+         None => {
+            self
+               .pending
+               .insert(key, (Instant::now() + self.window, change));
+         }
+      }
+   }
+
+   fn flush(&mut self) {
+      self.ready.extend(
+         self
+            .pending
+            .drain(..)
+            .map(|(_, (_, change))| TableChangeEvent::Change(change)),
+      );
+   }
+}
+
+impl Stream for DebouncedStream {
+   type Item = TableChangeEvent;
+
+   fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+      loop {
+         if let Some(event) = self.ready.pop_front() {
+            return Poll::Ready(Some(event));
+         }
+
+         if !self.inner_done {
+            match Pin::new(&mut self.inner).poll_next(cx) {
+               Poll::Ready(Some(TableChangeEvent::Change(change))) => {
+                  self.add(change);
+                  continue;
+               }
+               Poll::Ready(Some(lagged)) => {
+                  self.flush();
+                  self.ready.push_back(lagged);
+                  continue;
+               }
+               Poll::Ready(None) => {
+                  self.inner_done = true;
+                  self.flush();
+                  continue;
+               }
+               Poll::Pending => {}
+            }
+         }
+
+         let Some(&(deadline, _)) = self.pending.first().map(|(_, entry)| entry) else {
+            return if self.inner_done {
+               Poll::Ready(None)
+            } else {
+               Poll::Pending
+            };
+         };
+         if deadline <= Instant::now() {
+            if let Some((_, (_, change))) = self.pending.shift_remove_index(0) {
+               self.ready.push_back(TableChangeEvent::Change(change));
+            }
+            continue;
+         }
+         self.sleep.as_mut().reset(deadline);
+         if self.sleep.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+         }
+      }
+   }
+}
+
+/// A broadcast receiver of table changes, filtered to a subscriber's tables.
+///
+/// Returned by `subscribe()`. Mirrors the `broadcast::Receiver` API; changes to
+/// other tables are skipped, so lag counts and `len()` still cover them.
+#[derive(Debug)]
+pub struct TableChangeReceiver {
+   inner: broadcast::Receiver<TableChange>,
+   /// Tables to yield; empty means all captured tables
+   tables: Vec<String>,
+}
+
+impl TableChangeReceiver {
+   /// Wraps a broadcast receiver, yielding only changes to `tables`.
+   ///
+   /// An empty `tables` yields every change the receiver gets. Usually
+   /// obtained from `subscribe()` rather than built directly, e.g. around
+   /// [`ObservationBroker::subscribe`](crate::ObservationBroker::subscribe).
+   pub fn new(rx: broadcast::Receiver<TableChange>, tables: Vec<String>) -> Self {
+      Self { inner: rx, tables }
+   }
+
+   fn wants(&self, change: &TableChange) -> bool {
+      self.tables.is_empty() || self.tables.contains(&change.table)
+   }
+
+   /// Waits for the next change to one of the subscriber's tables.
+   ///
+   /// Cancel safe, like `broadcast::Receiver::recv`.
+   pub async fn recv(&mut self) -> Result<TableChange, broadcast::error::RecvError> {
+      loop {
+         let change = self.inner.recv().await?;
+         if self.wants(&change) {
+            return Ok(change);
+         }
+      }
+   }
+
+   /// Returns the next change to one of the subscriber's tables, without waiting.
+   pub fn try_recv(&mut self) -> Result<TableChange, broadcast::error::TryRecvError> {
+      loop {
+         let change = self.inner.try_recv()?;
+         if self.wants(&change) {
+            return Ok(change);
+         }
+      }
+   }
+
+   /// Creates another receiver for the same tables, starting from the next change.
+   pub fn resubscribe(&self) -> Self {
+      Self::new(self.inner.resubscribe(), self.tables.clone())
+   }
+
+   /// Returns the number of buffered changes, including ones to other tables.
+   pub fn len(&self) -> usize {
+      self.inner.len()
+   }
+
+   /// Returns `true` if no changes are buffered.
+   pub fn is_empty(&self) -> bool {
+      self.inner.is_empty()
+   }
+
+   /// Returns the tables this receiver yields; empty means all.
+   pub fn tables(&self) -> &[String] {
+      &self.tables
+   }
+}
+
+impl TableChangeStreamExt for TableChangeReceiver {
+   fn into_stream(self) -> TableChangeStream {
+      let stream = TableChangeStream::new(self.inner);
+      if self.tables.is_empty() {
+         stream
+      } else {
+         stream.filter_tables(self.tables)
+      }
+   }
+
+   fn debounce(self, window: Duration) -> DebouncedStream {
+      self.into_stream().debounce(window)
+   }
+}
+
+/// Extension trait for polling a change receiver without awaiting.
+///
+/// For consumers that cannot await `recv()`, such as a game loop or an FFI
+/// bridge polling once per frame.
+pub trait TableChangeReceiverExt {
+   /// Returns every change that is ready, in order, without waiting.
+   ///
+   /// Calls `try_recv()` until the channel is empty (or closed). If the receiver
+   /// fell behind, a [`TableChangeEvent::Lagged`] with the number of missed
+   /// changes comes first, followed by the oldest changes still buffered.
+   fn drain_ready(&mut self) -> Vec<TableChangeEvent>;
+}
+
+impl TableChangeReceiverExt for broadcast::Receiver<TableChange> {
+   fn drain_ready(&mut self) -> Vec<TableChangeEvent> {
+      drain_with(|| self.try_recv())
+   }
+}
+
+impl TableChangeReceiverExt for TableChangeReceiver {
+   fn drain_ready(&mut self) -> Vec<TableChangeEvent> {
+      drain_with(|| self.try_recv())
+   }
+}
+
+fn drain_with(
+   mut try_recv: impl FnMut() -> Result<TableChange, broadcast::error::TryRecvError>,
+) -> Vec<TableChangeEvent> {
+   use broadcast::error::TryRecvError;
+
+   let mut events = Vec::new();
+   loop {
+      match try_recv() {
+         Ok(change) => events.push(TableChangeEvent::Change(change)),
+         Err(TryRecvError::Lagged(count)) => {
+            warn!(
+               missed = count,
+               "Receiver lagged — missed change notifications. \
+                Consider increasing channel_capacity."
+            );
+            events.push(TableChangeEvent::Lagged(count));
+         }
+         Err(TryRecvError::Empty | TryRecvError::Closed) => return events,
+      }
+   }
+}
+
+/// Converts a batch receiver into a stream of batches, keeping only changes to
+/// `tables` (all tables if empty) and skipping batches left empty.
+///
+/// Lagged batches are logged and skipped; on an unfiltered stream consumers
+/// detect them as a gap between consecutive `seq_range`s.
+pub(crate) fn batch_stream(
+   rx: broadcast::Receiver<TransactionBatch>,
+   tables: Vec<String>,
+) -> impl Stream<Item = TransactionBatch> + Send + Unpin + 'static {
+   use tokio_stream::StreamExt;
+
+   BroadcastStream::new(rx).filter_map(move |result| match result {
+      Ok(mut batch) => {
+         if !tables.is_empty() {
+            batch
+               .changes
+               .retain(|change| tables.contains(&change.table));
+         }
+         (!batch.changes.is_empty()).then_some(batch)
+      }
+      Err(tokio_stream::wrappers::errors::BroadcastStreamRecvError::Lagged(count)) => {
+         warn!(
+            missed = count,
+            "Batch stream lagged — missed transactions. Consider increasing channel_capacity."
+         );
+         None
+      }
+   })
 }
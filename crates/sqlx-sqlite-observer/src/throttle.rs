@@ -0,0 +1,111 @@
+//! Rate limit for broadcast change notifications.
+//!
+//! When [`max_notifications_per_sec`](crate::ObserverConfig::max_notifications_per_sec)
+//! is set, the broker admits at most that many changes to the broadcast channel
+//! per one-second window. Changes over the limit are dropped and coalesced into
+//! a single [`ObserverEvent::Throttled`] per window, naming how many changes were
+//! dropped and which tables they touched, so a consumer knows what to re-query.
+//!
+//! The summary is sent when the next window opens: by the first change after
+//! it, or by a background flusher when writes stop, so the tail of a burst is
+//! never left unreported.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::broker::ObservationBroker;
+use crate::change::ObserverEvent;
+
+/// Length of one rate limit window
+pub(crate) const WINDOW: Duration = Duration::from_secs(1);
+
+/// Fixed-window counter of broadcast changes
+#[derive(Debug)]
+pub(crate) struct Throttle {
+   limit: u32,
+   window_start: Option<Instant>,
+   sent: u32,
+   dropped: u64,
+   dropped_tables: BTreeSet<String>,
+}
+
+impl Throttle {
+   pub(crate) fn new(limit: u32) -> Self {
+      Self {
+         limit,
+         window_start: None,
+         sent: 0,
+         dropped: 0,
+         dropped_tables: BTreeSet::new(),
+      }
+   }
+
+   /// Counts a change to `table` against the current window.
+   ///
+   /// Returns whether to broadcast it, and the previous window's summary if
+   /// this change opened a new window.
+   pub(crate) fn admit(&mut self, table: &str, now: Instant) -> (bool, Option<ObserverEvent>) {
+      let summary = if self
+         .window_start
+         .is_none_or(|start| now.duration_since(start) >= WINDOW)
+      {
+         let summary = self.take_summary();
+         self.window_start = Some(now);
+         self.sent = 0;
+         summary
+      } else {
+         None
+      };
+
+      if self.sent < self.limit {
+         self.sent += 1;
+         (true, summary)
+      } else {
+         self.dropped += 1;
+         if !self.dropped_tables.contains(table) {
+            self.dropped_tables.insert(table.to_string());
+         }
+         (false, summary)
+      }
+   }
+
+   /// Returns the summary of a window that has ended with drops, if any.
+   pub(crate) fn flush(&mut self, now: Instant) -> Option<ObserverEvent> {
+      match self.window_start {
+         Some(start) if now.duration_since(start) >= WINDOW => self.take_summary(),
+         _ => None,
+      }
+   }
+
+   fn take_summary(&mut self) -> Option<ObserverEvent> {
+      if self.dropped == 0 {
+         return None;
+      }
+      Some(ObserverEvent::Throttled {
+         dropped: std::mem::take(&mut self.dropped),
+         tables: std::mem::take(&mut self.dropped_tables)
+            .into_iter()
+            .collect(),
+      })
+   }
+}
+
+/// Spawns the task sending summaries of windows that ended without a later
+/// change to carry them.
+///
+/// The task holds only a weak reference to the broker and exits once it is
+/// dropped.
+pub(crate) fn spawn_flusher(broker: &Arc<ObservationBroker>) {
+   let broker = Arc::downgrade(broker);
+   tokio::spawn(async move {
+      let mut interval = tokio::time::interval(WINDOW / 4);
+      loop {
+         interval.tick().await;
+         let Some(broker) = broker.upgrade() else {
+            break;
+         };
+         broker.flush_throttle();
+      }
+   });
+}
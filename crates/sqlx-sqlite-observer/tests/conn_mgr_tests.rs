@@ -8,8 +8,10 @@
 #![cfg(feature = "conn-mgr")]
 
 use futures::StreamExt;
-use sqlx_sqlite_conn_mgr::SqliteDatabase;
-use sqlx_sqlite_observer::{ChangeOperation, ObservableSqliteDatabase, ObserverConfig};
+use sqlx_sqlite_conn_mgr::{AttachedMode, AttachedSpec, SqliteDatabase};
+use sqlx_sqlite_observer::{
+   ChangeOperation, ColumnValue, ObservableSqliteDatabase, ObserverConfig,
+};
 use std::time::Duration;
 use tokio::time::timeout;
 
@@ -104,6 +106,69 @@ async fn test_commit_publishes_notification() {
    assert_eq!(change.operation, Some(ChangeOperation::Insert));
 }
 
+#[tokio::test]
+async fn test_attached_write_tagged_with_schema() {
+   let test_db = setup_test_db().await;
+   let other_db = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observable = ObservableSqliteDatabase::new(test_db.db.clone(), config);
+
+   let mut rx = observable.subscribe(["users"]);
+   let mut writer = observable
+      .acquire_writer_with_attached(vec![AttachedSpec {
+         database: other_db.db.clone(),
+         schema_name: "other".to_string(),
+         mode: AttachedMode::ReadWrite,
+      }])
+      .await
+      .unwrap();
+
+   sqlx::query("BEGIN").execute(&mut *writer).await.unwrap();
+   sqlx::query("INSERT INTO other.users (name) VALUES ('Alice')")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO main.users (name) VALUES ('Bob')")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   sqlx::query("COMMIT").execute(&mut *writer).await.unwrap();
+
+   writer.detach_all().await.unwrap();
+
+   let first = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .expect("Should receive attached change")
+      .unwrap();
+   assert_eq!(first.table, "users");
+   assert_eq!(first.schema, "other");
+
+   let second = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .expect("Should receive main change")
+      .unwrap();
+   assert_eq!(second.schema, "main");
+
+   // Hooks are gone after detach: a plain write on the pooled connection still
+   // notifies exactly once through a freshly registered guard.
+   let mut writer = observable.acquire_writer().await.unwrap();
+   sqlx::query("INSERT INTO users (name) VALUES ('Carol')")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+   drop(writer);
+
+   let third = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .expect("Should receive change after detach")
+      .unwrap();
+   assert_eq!(third.schema, "main");
+   assert!(
+      timeout(Duration::from_millis(50), rx.recv()).await.is_err(),
+      "No duplicate notifications expected"
+   );
+}
+
 #[tokio::test]
 async fn test_uncommitted_changes_not_published() {
    let test_db = setup_test_db().await;
@@ -225,6 +290,110 @@ async fn test_delete_notification() {
    assert_eq!(change.operation, Some(ChangeOperation::Delete));
 }
 
+#[tokio::test]
+async fn test_hooks_disabled_scope_emits_no_changes() {
+   let test_db = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observable = ObservableSqliteDatabase::new(test_db.db.clone(), config);
+
+   let mut rx = observable.subscribe(["users"]);
+   let mut writer = observable.acquire_writer().await.unwrap();
+
+   let inserted = writer
+      .with_hooks_disabled(async |conn| {
+         let result = sqlx::query(
+            "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1000)
+             INSERT INTO users (name) SELECT 'bulk' || i FROM n",
+         )
+         .execute(&mut *conn)
+         .await?;
+         Ok(result.rows_affected())
+      })
+      .await
+      .unwrap();
+   assert_eq!(inserted, 1000);
+
+   // Hooks are back once the scope ends
+   sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
+      .execute(&mut *writer)
+      .await
+      .unwrap();
+
+   let change = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(change.rowid, Some(1001));
+   assert!(
+      timeout(Duration::from_millis(50), rx.recv()).await.is_err(),
+      "Bulk insert should not produce notifications"
+   );
+}
+
+// ============================================================================
+// Generated Columns
+// ============================================================================
+
+#[tokio::test]
+async fn test_generated_columns_resolved_after_commit() {
+   let test_db = setup_test_db().await;
+   let mut writer = test_db.db.acquire_writer().await.unwrap();
+   sqlx::query(
+      r#"
+      CREATE TABLE items (
+         id INTEGER PRIMARY KEY,
+         price INTEGER NOT NULL,
+         total INTEGER GENERATED ALWAYS AS (price * 2) VIRTUAL,
+         label TEXT NOT NULL,
+         code TEXT GENERATED ALWAYS AS (upper(label)) STORED
+      )
+      "#,
+   )
+   .execute(&mut *writer)
+   .await
+   .unwrap();
+   drop(writer);
+
+   for (id, resolve) in [(1, false), (2, true)] {
+      let config = ObserverConfig::new()
+         .with_tables(["items"])
+         .with_resolve_generated_columns(resolve);
+      let observable = ObservableSqliteDatabase::new(test_db.db.clone(), config);
+
+      let mut rx = observable.subscribe(["items"]);
+      let mut writer = observable.acquire_writer().await.unwrap();
+      sqlx::query("INSERT INTO items (id, price, label) VALUES (?, 21, 'abc')")
+         .bind(id)
+         .execute(&mut *writer)
+         .await
+         .unwrap();
+      drop(writer);
+
+      let change = timeout(Duration::from_millis(500), rx.recv())
+         .await
+         .unwrap()
+         .unwrap();
+      assert_eq!(change.operation, Some(ChangeOperation::Insert));
+
+      let named = change.new_values_named().unwrap();
+      assert_eq!(
+         named.keys().collect::<Vec<_>>(),
+         ["id", "price", "total", "label", "code"]
+      );
+      assert_eq!(named["price"], ColumnValue::Integer(21));
+      assert_eq!(named["label"], ColumnValue::Text("abc".into()));
+      // Stored generated columns come from the hook either way
+      assert_eq!(named["code"], ColumnValue::Text("ABC".into()));
+
+      let expected_total = if resolve {
+         ColumnValue::Integer(42)
+      } else {
+         ColumnValue::Null
+      };
+      assert_eq!(named["total"], expected_total);
+   }
+}
+
 // ============================================================================
 // Read Pool
 // ============================================================================
@@ -9,7 +9,10 @@
 
 use futures::StreamExt;
 use sqlx::SqlitePool;
-use sqlx_sqlite_observer::{ChangeOperation, ColumnValue, ObserverConfig, SqliteObserver};
+use sqlx_sqlite_observer::{
+   ChangeOperation, ColumnValue, ObserverConfig, ObserverEvent, OpCounts, SchemaChange,
+   SchemaChangeKind, SqliteObserver, TableChangeEvent, TableChangeReceiverExt,
+};
 use std::time::Duration;
 use tokio::time::timeout;
 
@@ -55,6 +58,12 @@ fn has_text_value(values: &[ColumnValue], expected: &str) -> bool {
 // Observer Lifecycle
 // ============================================================================
 
+#[cfg(feature = "bundled")]
+#[test]
+fn test_preflight_succeeds_with_bundled_sqlite() {
+   SqliteObserver::preflight().unwrap();
+}
+
 #[tokio::test]
 async fn test_observer_starts_with_no_tables() {
    let pool = setup_test_db().await;
@@ -64,12 +73,22 @@ async fn test_observer_starts_with_no_tables() {
 }
 
 #[tokio::test]
-async fn test_subscribe_adds_tables_to_observed_set() {
+async fn test_subscribe_leaves_observed_set_unchanged() {
    let pool = setup_test_db().await;
    let observer = SqliteObserver::new(pool, ObserverConfig::default());
 
    let _rx = observer.subscribe(["users", "posts"]);
 
+   assert!(observer.observed_tables().is_empty());
+}
+
+#[tokio::test]
+async fn test_observe_adds_tables_to_observed_set() {
+   let pool = setup_test_db().await;
+   let observer = SqliteObserver::new(pool, ObserverConfig::default());
+
+   observer.observe(["users", "posts"]);
+
    let tables = observer.observed_tables();
    assert_eq!(tables.len(), 2);
    assert!(tables.contains(&"users".to_string()));
@@ -230,63 +249,771 @@ async fn test_update_notification() {
    let mut conn = observer.acquire().await.unwrap();
 
    sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
-   sqlx::query("UPDATE users SET name = 'Bob' WHERE id = 1")
+   sqlx::query("UPDATE users SET name = 'Bob' WHERE id = 1")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+
+   let change = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+
+   assert_eq!(change.table, "users");
+   assert_eq!(change.operation, Some(ChangeOperation::Update));
+   assert!(change.old_values.is_some(), "UPDATE has old_values");
+   assert!(change.new_values.is_some(), "UPDATE has new_values");
+   assert!(has_text_value(change.old_values.as_ref().unwrap(), "Alice"));
+   assert!(has_text_value(change.new_values.as_ref().unwrap(), "Bob"));
+}
+
+#[tokio::test]
+async fn test_delete_notification() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observer = SqliteObserver::new(pool, config);
+
+   // Seed data
+   sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
+      .execute(observer.pool())
+      .await
+      .unwrap();
+
+   let mut rx = observer.subscribe(["users"]);
+   let mut conn = observer.acquire().await.unwrap();
+
+   // Implicit transaction (auto-commit)
+   sqlx::query("DELETE FROM users WHERE id = 1")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let change = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+
+   assert_eq!(change.table, "users");
+   assert_eq!(change.operation, Some(ChangeOperation::Delete));
+   assert!(change.old_values.is_some(), "DELETE has old_values");
+   assert!(change.new_values.is_none(), "DELETE has no new_values");
+   assert!(has_text_value(change.old_values.as_ref().unwrap(), "Alice"));
+}
+
+#[tokio::test]
+async fn test_named_values_match_row() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut rx = observer.subscribe(["users"]);
+   let mut conn = observer.acquire().await.unwrap();
+
+   sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   sqlx::query("DELETE FROM users WHERE id = 1")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let insert = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   let named = insert
+      .new_values_named()
+      .expect("INSERT has named new values");
+   assert_eq!(named.keys().collect::<Vec<_>>(), ["id", "name"]);
+   assert_eq!(named["id"], ColumnValue::Integer(1));
+   assert_eq!(named["name"], ColumnValue::Text("Alice".into()));
+   assert!(insert.old_values_named().is_none());
+
+   let delete = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   let named = delete
+      .old_values_named()
+      .expect("DELETE has named old values");
+   assert_eq!(named["id"], ColumnValue::Integer(1));
+   assert_eq!(named["name"], ColumnValue::Text("Alice".into()));
+   assert!(delete.new_values_named().is_none());
+}
+
+#[tokio::test]
+async fn test_column_names_follow_declaration_order() {
+   let pool = setup_test_db().await;
+   sqlx::query(
+      "CREATE TABLE readings (zeta TEXT, alpha INTEGER, id INTEGER PRIMARY KEY, mid REAL)",
+   )
+   .execute(&pool)
+   .await
+   .unwrap();
+   let observer = SqliteObserver::new(pool, ObserverConfig::new().with_tables(["readings"]));
+
+   let mut rx = observer.subscribe(["readings"]);
+   let mut conn = observer.acquire().await.unwrap();
+   sqlx::query("INSERT INTO readings (mid, zeta, alpha) VALUES (0.5, 'z', 7)")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let change = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(change.column_names, ["zeta", "alpha", "id", "mid"]);
+   let named = change.new_values_named().unwrap();
+   assert_eq!(
+      named.keys().map(String::as_str).collect::<Vec<_>>(),
+      ["zeta", "alpha", "id", "mid"]
+   );
+   assert_eq!(named["zeta"], ColumnValue::Text("z".into()));
+   assert_eq!(named["alpha"], ColumnValue::Integer(7));
+   assert_eq!(named["id"], ColumnValue::Integer(1));
+   assert_eq!(named["mid"], ColumnValue::Real(0.5));
+}
+
+#[tokio::test]
+async fn test_schema_drift_detected_and_table_info_refreshed() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut rx = observer.subscribe(["users"]);
+   let mut events = observer.subscribe_events();
+   let mut conn = observer.acquire().await.unwrap();
+   assert_eq!(
+      observer
+         .broker()
+         .get_table_info("users")
+         .unwrap()
+         .column_count,
+      2
+   );
+
+   sqlx::query("ALTER TABLE users ADD COLUMN email TEXT")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO users (name, email) VALUES ('Alice', 'a@example.com')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let event = timeout(Duration::from_millis(100), events.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(
+      event,
+      ObserverEvent::SchemaDrift {
+         table: "users".into()
+      }
+   );
+   assert!(observer.broker().needs_table_info("users"));
+
+   // The drifted change is still published, without schema-derived fields
+   let drifted = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(drifted.new_values.as_ref().unwrap().len(), 3);
+   assert!(drifted.primary_key.is_empty());
+   assert!(drifted.column_names.is_empty());
+   assert!(drifted.schema_stale);
+
+   // The next acquire re-queries the schema
+   drop(conn);
+   let mut conn = observer.acquire().await.unwrap();
+   assert!(!observer.broker().needs_table_info("users"));
+   assert_eq!(
+      observer
+         .broker()
+         .get_table_info("users")
+         .unwrap()
+         .column_count,
+      3
+   );
+
+   sqlx::query("INSERT INTO users (name, email) VALUES ('Bob', 'b@example.com')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let change = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(change.column_names, ["id", "name", "email"]);
+   assert_eq!(change.primary_key, [ColumnValue::Integer(2)]);
+   assert!(!change.schema_stale);
+   assert!(events.try_recv().is_err(), "drift is reported once");
+}
+
+// ============================================================================
+// Filtering
+// ============================================================================
+
+#[tokio::test]
+async fn test_notifications_throttled_with_summary() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new()
+      .with_tables(["users"])
+      .with_max_notifications_per_sec(10);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut rx = observer.subscribe(["users"]);
+   let mut events = observer.subscribe_events();
+   let mut conn = observer.acquire().await.unwrap();
+
+   sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
+   for i in 0..50 {
+      sqlx::query("INSERT INTO users (name) VALUES (?)")
+         .bind(format!("user{i}"))
+         .execute(&mut **conn)
+         .await
+         .unwrap();
+   }
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+
+   // The summary arrives once the window closes, with no further writes
+   let event = timeout(Duration::from_secs(3), events.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(
+      event,
+      ObserverEvent::Throttled {
+         dropped: 40,
+         tables: vec!["users".into()],
+      }
+   );
+
+   let mut delivered = 0;
+   while rx.try_recv().is_ok() {
+      delivered += 1;
+   }
+   assert_eq!(delivered, 10);
+}
+
+#[tokio::test]
+async fn test_transaction_summary_per_commit() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new()
+      .with_tables(["users"])
+      .with_emit_transaction_summary(true);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut summaries = observer.subscribe_summaries();
+   let mut conn = observer.acquire().await.unwrap();
+
+   sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
+   for i in 0..100 {
+      sqlx::query("INSERT INTO users (name) VALUES (?)")
+         .bind(format!("user{i}"))
+         .execute(&mut **conn)
+         .await
+         .unwrap();
+   }
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+
+   let summary = timeout(Duration::from_millis(100), summaries.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(summary.total, 100);
+   assert_eq!(
+      summary.tables["users"],
+      OpCounts {
+         inserts: 100,
+         updates: 0,
+         deletes: 0,
+      }
+   );
+   assert!(summaries.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_buffer_overflow_degrades_to_counts() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new()
+      .with_tables(["users", "posts"])
+      .with_emit_transaction_summary(true)
+      .with_max_buffered_changes(5);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut rx = observer.subscribe(Vec::<String>::new());
+   let mut events = observer.subscribe_events();
+   let mut summaries = observer.subscribe_summaries();
+   let mut conn = observer.acquire().await.unwrap();
+
+   sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
+   for i in 0..20 {
+      sqlx::query("INSERT INTO users (name) VALUES (?)")
+         .bind(format!("user{i}"))
+         .execute(&mut **conn)
+         .await
+         .unwrap();
+   }
+   sqlx::query("INSERT INTO posts (user_id, title) VALUES (1, 'Hello')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   assert_eq!(observer.broker().pending_buffer_len(), 21);
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+
+   let mut overflowed = Vec::new();
+   for _ in 0..2 {
+      overflowed.push(
+         timeout(Duration::from_millis(100), events.recv())
+            .await
+            .unwrap()
+            .unwrap(),
+      );
+   }
+   assert_eq!(
+      overflowed,
+      [
+         ObserverEvent::BufferOverflowed {
+            table: "posts".into(),
+            dropped: 1,
+         },
+         ObserverEvent::BufferOverflowed {
+            table: "users".into(),
+            dropped: 20,
+         },
+      ]
+   );
+
+   let summary = timeout(Duration::from_millis(100), summaries.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(summary.total, 21);
+   assert_eq!(summary.tables["users"].inserts, 20);
+   assert_eq!(summary.tables["posts"].inserts, 1);
+   assert!(
+      rx.try_recv().is_err(),
+      "no rows of the overflowed transaction"
+   );
+
+   // The next transaction within the bound is published as usual
+   sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   let change = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(change.table, "users");
+   assert!(events.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_observe_all_includes_unlisted_tables() {
+   let pool = setup_test_db().await;
+   sqlx::query("CREATE TABLE _sqlx_internal (id INTEGER PRIMARY KEY)")
+      .execute(&pool)
+      .await
+      .unwrap();
+   let observer = SqliteObserver::new(pool, ObserverConfig::new().observe_all());
+
+   let mut rx = observer.subscribe(Vec::<String>::new());
+   let mut conn = observer.acquire().await.unwrap();
+   assert!(observer.broker().is_table_observed("posts"));
+   assert!(!observer.broker().is_table_observed("sqlite_sequence"));
+
+   sqlx::query("INSERT INTO _sqlx_internal (id) VALUES (1)")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO posts (user_id, title) VALUES (1, 'Hello')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let users = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(users.table, "users");
+   let posts = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(posts.table, "posts");
+   // Schema info was resolved at acquire
+   assert_eq!(posts.column_names, ["id", "user_id", "title"]);
+   assert_eq!(posts.primary_key, vec![ColumnValue::Integer(1)]);
+   assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_drain_ready_collects_changes_and_lag() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new()
+      .with_tables(["users"])
+      .with_channel_capacity(2);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut rx = observer.subscribe(["users"]);
+   let mut conn = observer.acquire().await.unwrap();
+   assert!(rx.drain_ready().is_empty());
+
+   let insert = async |conn: &mut sqlx_sqlite_observer::ObservableConnection, name: &str| {
+      sqlx::query("INSERT INTO users (name) VALUES (?)")
+         .bind(name)
+         .execute(&mut ***conn)
+         .await
+         .unwrap();
+   };
+   insert(&mut conn, "a").await;
+   insert(&mut conn, "b").await;
+
+   let rowids = |events: &[TableChangeEvent]| -> Vec<Option<i64>> {
+      events
+         .iter()
+         .map(|event| match event {
+            TableChangeEvent::Change(change) => change.rowid,
+            TableChangeEvent::Lagged(_) => None,
+         })
+         .collect()
+   };
+   let events = rx.drain_ready();
+   assert_eq!(rowids(&events), [Some(1), Some(2)]);
+
+   // Overflow the two-slot channel
+   for name in ["c", "d", "e", "f", "g"] {
+      insert(&mut conn, name).await;
+   }
+   let events = rx.drain_ready();
+   assert!(matches!(events[0], TableChangeEvent::Lagged(3)));
+   assert_eq!(rowids(&events[1..]), [Some(6), Some(7)]);
+   assert!(rx.drain_ready().is_empty());
+}
+
+#[tokio::test]
+async fn test_dropped_count_tracks_overflow() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new()
+      .with_tables(["users"])
+      .with_channel_capacity(2);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut rx = observer.subscribe(["users"]);
+   let mut conn = observer.acquire().await.unwrap();
+   for name in ["a", "b", "c", "d", "e"] {
+      sqlx::query("INSERT INTO users (name) VALUES (?)")
+         .bind(name)
+         .execute(&mut **conn)
+         .await
+         .unwrap();
+   }
+
+   assert_eq!(observer.broker().dropped_count(), 3);
+   assert!(matches!(
+      rx.recv().await,
+      Err(tokio::sync::broadcast::error::RecvError::Lagged(3))
+   ));
+
+   // Changes published with no subscriber count as dropped too
+   drop(rx);
+   sqlx::query("INSERT INTO users (name) VALUES ('f')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   assert_eq!(observer.broker().dropped_count(), 4);
+}
+
+#[tokio::test]
+async fn test_debounce_coalesces_updates_to_one_row() {
+   let pool = setup_test_db().await;
+   let observer = SqliteObserver::new(pool, ObserverConfig::new().with_tables(["users"]));
+
+   let mut stream = observer
+      .subscribe_stream(["users"])
+      .debounce(Duration::from_millis(200));
+   let mut conn = observer.acquire().await.unwrap();
+
+   sqlx::query("INSERT INTO users (name) VALUES ('v')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   for i in 0..10 {
+      sqlx::query("UPDATE users SET name = ? WHERE id = 1")
+         .bind(format!("v{i}"))
+         .execute(&mut **conn)
+         .await
+         .unwrap();
+   }
+
+   // The insert is a different operation, so it is not merged with the updates
+   let Some(TableChangeEvent::Change(insert)) = timeout(Duration::from_secs(1), stream.next())
+      .await
+      .unwrap()
+   else {
+      panic!("expected a change");
+   };
+   assert_eq!(insert.operation, Some(ChangeOperation::Insert));
+
+   let Some(TableChangeEvent::Change(update)) = timeout(Duration::from_secs(1), stream.next())
+      .await
+      .unwrap()
+   else {
+      panic!("expected a change");
+   };
+   assert_eq!(update.operation, Some(ChangeOperation::Update));
+   assert_eq!(
+      update.new_values_named().unwrap()["name"],
+      ColumnValue::Text("v9".into())
+   );
+
+   assert!(
+      timeout(Duration::from_millis(300), stream.next())
+         .await
+         .is_err()
+   );
+}
+
+#[tokio::test]
+async fn test_schema_changes_not_captured_by_default() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observer = SqliteObserver::new(pool, config);
+   let mut schema = observer.subscribe_schema_changes();
+
+   let mut conn = observer.acquire().await.unwrap();
+   sqlx::query("ALTER TABLE users ADD COLUMN email TEXT")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   assert!(
+      timeout(Duration::from_millis(100), schema.recv())
+         .await
+         .is_err()
+   );
+}
+
+#[tokio::test]
+async fn test_schema_change_marks_table_info_stale() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new()
+      .with_tables(["users"])
+      .with_capture_schema_changes(true);
+   let observer = SqliteObserver::new(pool, config);
+   let mut schema = observer.subscribe_schema_changes();
+
+   let mut conn = observer.acquire().await.unwrap();
+   sqlx::query("ALTER TABLE users ADD COLUMN email TEXT")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let change = timeout(Duration::from_secs(1), schema.recv())
+      .await
+      .expect("timeout")
+      .unwrap();
+   assert_eq!(
+      change,
+      SchemaChange {
+         kind: SchemaChangeKind::AlterTable,
+         schema: "main".to_string(),
+         object: "users".to_string(),
+      }
+   );
+   assert!(observer.broker().needs_table_info("users"));
+   drop(conn);
+
+   let mut rx = observer.subscribe(["users"]);
+   let mut conn = observer.acquire().await.unwrap();
+   sqlx::query("INSERT INTO users (name, email) VALUES ('Alice', 'a@example.com')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let change = timeout(Duration::from_secs(1), rx.recv())
+      .await
+      .expect("timeout")
+      .unwrap();
+   assert_eq!(change.column_names, ["id", "name", "email"]);
+}
+
+#[tokio::test]
+async fn test_changes_keep_flowing_after_column_rename() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new()
+      .with_tables(["users"])
+      .with_capture_schema_changes(true);
+   let observer = SqliteObserver::new(pool, config);
+   let mut rx = observer.subscribe(["users"]);
+
+   // A rename keeps the column count, so only the DDL marks the table stale
+   let mut conn = observer.acquire().await.unwrap();
+   sqlx::query("ALTER TABLE users RENAME COLUMN name TO full_name")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO users (full_name) VALUES ('Alice')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let stale = timeout(Duration::from_secs(1), rx.recv())
+      .await
+      .expect("timeout")
+      .unwrap();
+   assert!(stale.schema_stale);
+   assert!(stale.column_names.is_empty());
+   assert_eq!(stale.rowid, Some(1));
+
+   drop(conn);
+   let mut conn = observer.acquire().await.unwrap();
+   sqlx::query("INSERT INTO users (full_name) VALUES ('Bob')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let fresh = timeout(Duration::from_secs(1), rx.recv())
+      .await
+      .expect("timeout")
+      .unwrap();
+   assert!(!fresh.schema_stale);
+   assert_eq!(fresh.column_names, ["id", "full_name"]);
+   assert_eq!(fresh.primary_key, [ColumnValue::Integer(2)]);
+}
+
+#[tokio::test]
+async fn test_subscribers_receive_only_their_tables() {
+   let pool = setup_test_db().await;
+   let observer = SqliteObserver::new(pool, ObserverConfig::default());
+   observer.observe(["users", "posts"]);
+
+   let mut users_rx = observer.subscribe(["users"]);
+   let mut posts_rx = observer.subscribe(["posts"]);
+   let mut tables = observer.observed_tables();
+   tables.sort();
+   assert_eq!(tables, ["posts", "users"]);
+
+   let mut conn = observer.acquire().await.unwrap();
+   sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   sqlx::query("INSERT INTO posts (user_id, title) VALUES (1, 'Hello')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let users = timeout(Duration::from_millis(100), users_rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(users.table, "users");
+   let posts = timeout(Duration::from_millis(100), posts_rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(posts.table, "posts");
+
+   assert!(users_rx.try_recv().is_err());
+   assert!(posts_rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_shutdown_discards_uncommitted_buffer() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observer = SqliteObserver::new(pool, config);
+   let broker = observer.broker().clone();
+   let mut rx = observer.subscribe(["users"]);
+   let clone = observer.clone();
+
+   let mut conn = observer.acquire().await.unwrap();
+   sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
+   sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
       .execute(&mut **conn)
       .await
       .unwrap();
+   assert_eq!(broker.pending_buffer_len(), 1);
 
-   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+   observer.shutdown();
+   assert!(broker.is_closed());
+   assert_eq!(broker.pending_buffer_len(), 0);
 
-   let change = timeout(Duration::from_millis(100), rx.recv())
+   // The still-registered hooks no longer record or publish
+   sqlx::query("INSERT INTO users (name) VALUES ('Bob')")
+      .execute(&mut **conn)
       .await
-      .unwrap()
       .unwrap();
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+   assert_eq!(broker.pending_buffer_len(), 0);
+   assert!(
+      timeout(Duration::from_millis(50), rx.recv()).await.is_err(),
+      "no notification after shutdown"
+   );
 
-   assert_eq!(change.table, "users");
-   assert_eq!(change.operation, Some(ChangeOperation::Update));
-   assert!(change.old_values.is_some(), "UPDATE has old_values");
-   assert!(change.new_values.is_some(), "UPDATE has new_values");
-   assert!(has_text_value(change.old_values.as_ref().unwrap(), "Alice"));
-   assert!(has_text_value(change.new_values.as_ref().unwrap(), "Bob"));
+   drop(conn);
+   assert!(matches!(
+      clone.acquire().await,
+      Err(sqlx_sqlite_observer::Error::ShutDown)
+   ));
 }
 
 #[tokio::test]
-async fn test_delete_notification() {
+async fn test_rollback_to_mark_discards_savepoint_changes() {
    let pool = setup_test_db().await;
    let config = ObserverConfig::new().with_tables(["users"]);
    let observer = SqliteObserver::new(pool, config);
+   let broker = observer.broker().clone();
+   let mut rx = observer.subscribe(["users"]);
 
-   // Seed data
+   let mut conn = observer.acquire().await.unwrap();
+   sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
    sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
-      .execute(observer.pool())
+      .execute(&mut **conn)
       .await
       .unwrap();
+   sqlx::query("SAVEPOINT sp")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   let mark = broker.savepoint_mark();
+   sqlx::query("INSERT INTO users (name) VALUES ('Bob')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   assert_eq!(broker.pending_buffer_len(), 2);
 
-   let mut rx = observer.subscribe(["users"]);
-   let mut conn = observer.acquire().await.unwrap();
-
-   // Implicit transaction (auto-commit)
-   sqlx::query("DELETE FROM users WHERE id = 1")
+   sqlx::query("ROLLBACK TO sp")
       .execute(&mut **conn)
       .await
       .unwrap();
+   broker.rollback_to_mark(mark);
+   assert_eq!(broker.pending_buffer_len(), 1);
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
 
    let change = timeout(Duration::from_millis(100), rx.recv())
       .await
       .unwrap()
       .unwrap();
-
-   assert_eq!(change.table, "users");
-   assert_eq!(change.operation, Some(ChangeOperation::Delete));
-   assert!(change.old_values.is_some(), "DELETE has old_values");
-   assert!(change.new_values.is_none(), "DELETE has no new_values");
-   assert!(has_text_value(change.old_values.as_ref().unwrap(), "Alice"));
+   assert!(has_text_value(change.new_values.as_ref().unwrap(), "Alice"));
+   assert!(
+      timeout(Duration::from_millis(50), rx.recv()).await.is_err(),
+      "rolled-back row must not be delivered"
+   );
 }
 
-// ============================================================================
-// Filtering
-// ============================================================================
-
 #[tokio::test]
 async fn test_untracked_table_ignored() {
    let pool = setup_test_db().await;
@@ -344,6 +1071,139 @@ async fn test_all_subscribers_receive_notification() {
    assert!(result2.is_ok(), "Subscriber 2 receives notification");
 }
 
+#[tokio::test]
+async fn test_reliable_subscriber_sees_every_change() {
+   const ROWS: i64 = 50;
+
+   let pool = setup_test_db().await;
+   // Tiny broadcast buffer: lossy subscribers would lag, the reliable one must not
+   let config = ObserverConfig::new()
+      .with_tables(["users"])
+      .with_channel_capacity(2);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut rx = observer.subscribe_reliable(["users"], 2);
+   let mut conn = observer.acquire().await.unwrap();
+
+   let writer = tokio::spawn(async move {
+      for i in 0..ROWS {
+         sqlx::query("INSERT INTO users (name) VALUES (?)")
+            .bind(format!("user-{i}"))
+            .execute(&mut **conn)
+            .await
+            .unwrap();
+      }
+   });
+
+   let mut received = Vec::new();
+   while received.len() < ROWS as usize {
+      let event = timeout(Duration::from_secs(5), rx.recv())
+         .await
+         .expect("writer stalled without delivering")
+         .expect("reliable channel closed early");
+      let TableChangeEvent::Change(change) = event else {
+         panic!("unexpected gap: {event:?}");
+      };
+      received.push(change.rowid.unwrap());
+      // Slow consumer
+      tokio::time::sleep(Duration::from_millis(2)).await;
+   }
+
+   writer.await.unwrap();
+   assert_eq!(received, (1..=ROWS).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn test_subscribe_reliable_while_commit_waits_on_full_receiver() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut rx = observer.subscribe_reliable(["users"], 1);
+   let mut conn = observer.acquire().await.unwrap();
+
+   let writer = tokio::spawn(async move {
+      for i in 0..3 {
+         sqlx::query("INSERT INTO users (name) VALUES (?)")
+            .bind(format!("user-{i}"))
+            .execute(&mut **conn)
+            .await
+            .unwrap();
+      }
+   });
+
+   // Give the writer time to fill the receiver and block in the commit hook
+   tokio::time::sleep(Duration::from_millis(100)).await;
+
+   // The blocked commit must not hold the subscriber lock
+   let subscriber = observer.clone();
+   let other = timeout(
+      Duration::from_secs(2),
+      tokio::task::spawn_blocking(move || subscriber.subscribe_reliable(["posts"], 1)),
+   )
+   .await
+   .expect("subscribe_reliable blocked behind a waiting commit")
+   .unwrap();
+
+   for _ in 0..3 {
+      let event = timeout(Duration::from_secs(2), rx.recv())
+         .await
+         .unwrap()
+         .unwrap();
+      assert!(
+         matches!(event, TableChangeEvent::Change(_)),
+         "got {event:?}"
+      );
+   }
+   writer.await.unwrap();
+   drop(other);
+}
+
+#[tokio::test]
+async fn test_reliable_subscriber_gets_gap_for_overflowed_transaction() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new()
+      .with_tables(["users", "posts"])
+      .with_max_buffered_changes(2);
+   let observer = SqliteObserver::new(pool, config);
+   let mut rx = observer.subscribe_reliable(["users"], 16);
+   let mut conn = observer.acquire().await.unwrap();
+
+   sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
+   for i in 0..5 {
+      sqlx::query("INSERT INTO users (name) VALUES (?)")
+         .bind(format!("user-{i}"))
+         .execute(&mut **conn)
+         .await
+         .unwrap();
+   }
+   sqlx::query("INSERT INTO posts (user_id, title) VALUES (1, 'Hello')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+
+   sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   // Only the subscriber's own tables count towards the gap
+   let gap = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert!(matches!(gap, TableChangeEvent::Lagged(5)), "got {gap:?}");
+   let next = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+   assert!(
+      matches!(&next, TableChangeEvent::Change(change) if change.rowid == Some(6)),
+      "got {next:?}"
+   );
+}
+
 #[tokio::test]
 async fn test_cloned_observer_shares_state() {
    let pool = setup_test_db().await;
@@ -402,6 +1262,226 @@ async fn test_stream_receives_notifications() {
    }
 }
 
+#[tokio::test]
+async fn test_batch_stream_groups_transaction() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users", "posts"]);
+   let observer = SqliteObserver::new(pool, config);
+
+   let mut batches = observer.subscribe_batches(["users"]);
+   let mut conn = observer.acquire().await.unwrap();
+
+   sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
+   for name in ["Alice", "Bob", "Carol"] {
+      sqlx::query("INSERT INTO users (name) VALUES (?)")
+         .bind(name)
+         .execute(&mut **conn)
+         .await
+         .unwrap();
+   }
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+
+   // Only touches an unrequested table: numbered, but not delivered
+   sqlx::query("INSERT INTO posts (user_id, title) VALUES (1, 'Hi')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   sqlx::query("DELETE FROM users WHERE id = 3")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let batch = timeout(Duration::from_millis(100), batches.next())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(batch.changes.len(), 3);
+   assert_eq!(batch.seq_range, 0..3);
+   assert!(
+      batch
+         .changes
+         .iter()
+         .all(|c| c.operation == Some(ChangeOperation::Insert))
+   );
+
+   let batch = timeout(Duration::from_millis(100), batches.next())
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(batch.changes.len(), 1);
+   assert_eq!(batch.changes[0].operation, Some(ChangeOperation::Delete));
+   assert_eq!(batch.seq_range, 4..5);
+}
+
+#[tokio::test]
+async fn test_compacting_log_keeps_latest_change_per_row() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observer = SqliteObserver::new(pool, config);
+   let log = observer.compacting_log(["users"]);
+   let mut conn = observer.acquire().await.unwrap();
+
+   sqlx::query("INSERT INTO users (name) VALUES ('v0')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   for i in 1..=5 {
+      sqlx::query("UPDATE users SET name = ? WHERE id = 1")
+         .bind(format!("v{i}"))
+         .execute(&mut **conn)
+         .await
+         .unwrap();
+   }
+   // Not observed, so neither numbered nor logged
+   sqlx::query("INSERT INTO posts (user_id, title) VALUES (1, 'Hi')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   assert_eq!(log.len(), 1);
+   let drained = log.drain_since(0);
+   assert_eq!(drained.len(), 1);
+   let (seq, change) = &drained[0];
+   assert_eq!(*seq, 5);
+   assert_eq!(change.operation, Some(ChangeOperation::Update));
+   assert_eq!(change.primary_key, vec![ColumnValue::Integer(1)]);
+   assert!(has_text_value(change.new_values.as_ref().unwrap(), "v5"));
+   assert!(log.is_empty());
+
+   // Entries older than `seq` are discarded with the rest of the log
+   sqlx::query("INSERT INTO users (name) VALUES ('Bob')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   sqlx::query("UPDATE users SET name = 'v6' WHERE id = 1")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   let drained = log.drain_since(7);
+   assert_eq!(drained.len(), 1);
+   assert_eq!(drained[0].0, 7);
+   assert_eq!(drained[0].1.primary_key, vec![ColumnValue::Integer(1)]);
+   assert!(log.is_empty());
+}
+
+#[tokio::test]
+async fn test_compacting_log_records_primary_key_change_as_delete() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observer = SqliteObserver::new(pool, config);
+   let log = observer.compacting_log(["users"]);
+   let mut conn = observer.acquire().await.unwrap();
+
+   sqlx::query("INSERT INTO users (id, name) VALUES (1, 'Alice')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   log.drain_since(0);
+   sqlx::query("UPDATE users SET id = 2 WHERE id = 1")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+
+   let drained = log.drain_since(0);
+   assert_eq!(drained.len(), 2);
+   let deleted = drained
+      .iter()
+      .map(|(_, change)| change)
+      .find(|change| change.operation == Some(ChangeOperation::Delete))
+      .expect("delete of the old key");
+   assert_eq!(deleted.primary_key, vec![ColumnValue::Integer(1)]);
+   assert!(deleted.new_values.is_none());
+   let updated = drained
+      .iter()
+      .map(|(_, change)| change)
+      .find(|change| change.operation == Some(ChangeOperation::Update))
+      .expect("update under the new key");
+   assert_eq!(updated.primary_key, vec![ColumnValue::Integer(2)]);
+   assert!(log.is_empty());
+}
+
+#[tokio::test]
+async fn test_observation_survives_idle_connection_eviction() {
+   let dir = tempfile::TempDir::new().unwrap();
+   let options = sqlx::sqlite::SqliteConnectOptions::new()
+      .filename(dir.path().join("idle.db"))
+      .create_if_missing(true);
+   let pool = sqlx::sqlite::SqlitePoolOptions::new()
+      .min_connections(0)
+      .idle_timeout(Duration::from_millis(50))
+      .connect_with(options)
+      .await
+      .unwrap();
+   sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+      .execute(&pool)
+      .await
+      .unwrap();
+
+   let observer = SqliteObserver::new(pool, ObserverConfig::new().with_tables(["users"]));
+   let mut rx = observer.subscribe(["users"]);
+
+   for name in ["Alice", "Bob"] {
+      let mut conn = observer.acquire().await.unwrap();
+      sqlx::query("INSERT INTO users (name) VALUES (?)")
+         .bind(name)
+         .execute(&mut **conn)
+         .await
+         .unwrap();
+      drop(conn);
+
+      let change = timeout(Duration::from_millis(100), rx.recv())
+         .await
+         .unwrap()
+         .unwrap();
+      assert_eq!(change.operation, Some(ChangeOperation::Insert));
+
+      // Let the pool reap the idle connection before the next acquire
+      tokio::time::sleep(Duration::from_millis(200)).await;
+   }
+   assert_eq!(observer.pool().num_idle(), 0);
+}
+
+#[tokio::test]
+async fn test_initial_load_emits_existing_rows_as_inserts() {
+   let pool = setup_test_db().await;
+   for name in ["Alice", "Bob"] {
+      sqlx::query("INSERT INTO users (name) VALUES (?)")
+         .bind(name)
+         .execute(&pool)
+         .await
+         .unwrap();
+   }
+   let observer = SqliteObserver::new(pool, ObserverConfig::new().with_tables(["users"]));
+
+   let changes: Vec<_> = observer
+      .initial_load("users")
+      .await
+      .unwrap()
+      .collect()
+      .await;
+   assert_eq!(changes.len(), 2);
+   for (change, (id, name)) in changes.iter().zip([(1, "Alice"), (2, "Bob")]) {
+      assert_eq!(change.operation, Some(ChangeOperation::Insert));
+      assert_eq!(change.table, "users");
+      assert_eq!(change.rowid, Some(id));
+      assert_eq!(change.primary_key, vec![ColumnValue::Integer(id)]);
+      assert_eq!(change.column_names, ["id", "name"]);
+      assert_eq!(
+         change.new_values,
+         Some(vec![
+            ColumnValue::Integer(id),
+            ColumnValue::Text(name.into())
+         ])
+      );
+      assert!(change.old_values.is_none());
+   }
+
+   assert!(matches!(
+      observer.initial_load("missing").await,
+      Err(sqlx_sqlite_observer::Error::TableNotFound(_))
+   ));
+}
+
 #[tokio::test]
 async fn test_stream_filters_tables() {
    let pool = setup_test_db().await;
@@ -765,3 +1845,38 @@ async fn test_delete_returns_old_primary_key() {
       "DELETE should return old PK value"
    );
 }
+
+#[tokio::test]
+async fn test_commit_latency_stats_measure_observed_transactions() {
+   let pool = setup_test_db().await;
+   let config = ObserverConfig::new().with_tables(["users"]);
+   let observer = SqliteObserver::new(pool, config);
+   let mut conn = observer.acquire().await.unwrap();
+
+   assert_eq!(observer.commit_latency_stats().samples, 0);
+
+   sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
+   sqlx::query("INSERT INTO users (name) VALUES ('Alice')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   tokio::time::sleep(Duration::from_millis(100)).await;
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+
+   let stats = observer.commit_latency_stats();
+   assert_eq!(stats.samples, 1);
+   assert!(stats.max >= Duration::from_millis(100), "{stats:?}");
+   assert_eq!(stats.p50, stats.max);
+   assert_eq!(stats.p95, stats.max);
+
+   // Transactions touching only unobserved tables are not measured
+   sqlx::query("BEGIN").execute(&mut **conn).await.unwrap();
+   sqlx::query("INSERT INTO posts (user_id, title) VALUES (1, 'Hi')")
+      .execute(&mut **conn)
+      .await
+      .unwrap();
+   tokio::time::sleep(Duration::from_millis(20)).await;
+   sqlx::query("COMMIT").execute(&mut **conn).await.unwrap();
+
+   assert_eq!(observer.commit_latency_stats().samples, 1);
+}
@@ -3,14 +3,19 @@
 use std::future::{Future, IntoFuture};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use indexmap::IndexMap;
 use serde_json::Value as JsonValue;
+use sqlx::sqlite::{SqliteConnection, SqliteRow};
 use sqlx_sqlite_conn_mgr::AttachedSpec;
 
 use crate::Error;
+use crate::decode::{DecodeMode, DecodeOptions, KeyCase, untag};
+use crate::migrations::MigrationGate;
 use crate::pagination::{KeysetColumn, KeysetPage, build_paginated_query};
-use crate::wrapper::{DatabaseWrapper, WriteQueryResult, bind_value};
+use crate::statement::validate_single_statement;
+use crate::wrapper::{BindOptions, DatabaseWrapper, WriteQueryResult, bind_value};
 
 /// Builder for SELECT queries returning multiple rows
 pub struct FetchAllBuilder {
@@ -18,6 +23,12 @@ pub struct FetchAllBuilder {
    query: String,
    values: Vec<JsonValue>,
    attached: Vec<AttachedSpec>,
+   max_rows: Option<usize>,
+   decode_options: DecodeOptions,
+   bind_options: BindOptions,
+   on_writer: bool,
+   timeout: Option<Duration>,
+   migrations: Option<Arc<MigrationGate>>,
 }
 
 impl FetchAllBuilder {
@@ -31,37 +42,118 @@ impl FetchAllBuilder {
          query,
          values,
          attached: Vec::new(),
+         max_rows: None,
+         decode_options: DecodeOptions::default(),
+         bind_options: BindOptions::default(),
+         on_writer: false,
+         timeout: None,
+         migrations: None,
       }
    }
 
+   /// Refuse to run until the wrapper's migrations have completed
+   pub(crate) fn migration_gate(mut self, gate: Option<Arc<MigrationGate>>) -> Self {
+      self.migrations = gate;
+      self
+   }
+
    /// Attach additional databases for this query
    pub fn attach(mut self, attached: Vec<AttachedSpec>) -> Self {
       self.attached = attached;
       self
    }
 
+   /// Fail with `Error::ResultSetTooLarge` if the query returns more than `limit` rows
+   ///
+   /// Only `limit + 1` rows are ever read, so an accidentally unbounded query is
+   /// rejected without materializing the whole result set. `None` disables the check.
+   pub fn max_rows(mut self, limit: Option<usize>) -> Self {
+      self.max_rows = limit;
+      self
+   }
+
+   /// Set how result values are decoded (e.g. which columns are booleans)
+   pub fn decode_options(mut self, options: DecodeOptions) -> Self {
+      self.decode_options = options;
+      self
+   }
+
+   /// Set how storage classes are represented, keeping the other decode options
+   pub fn decode_mode(mut self, mode: DecodeMode) -> Self {
+      self.decode_options.mode = mode;
+      self
+   }
+
+   /// Set how parameter values are bound (e.g. empty strings as `NULL`)
+   pub fn bind_options(mut self, options: BindOptions) -> Self {
+      self.bind_options = options;
+      self
+   }
+
+   /// Run the query on the write connection instead of the read pool
+   ///
+   /// For reads that must see the writer's state, such as per-connection
+   /// `PRAGMA`s or rows written in the writer's current snapshot. This contends
+   /// for the write lock: the query waits for in-flight writes and blocks other
+   /// writers while it runs.
+   pub fn on_writer(mut self) -> Self {
+      self.on_writer = true;
+      self
+   }
+
+   /// Interrupt the query with `Error::QueryTimeout` if it runs longer than `timeout`
+   ///
+   /// The running statement is stopped with `sqlite3_interrupt`, not merely
+   /// abandoned, so the connection is free for other queries once this
+   /// returns. The deadline covers running the query, not waiting for a
+   /// connection.
+   pub fn timeout(mut self, timeout: Duration) -> Self {
+      self.timeout = Some(timeout);
+      self
+   }
+
    /// Execute the query and return all matching rows
-   pub async fn execute(self) -> Result<Vec<IndexMap<String, JsonValue>>, Error> {
+   pub async fn execute(mut self) -> Result<Vec<IndexMap<String, JsonValue>>, Error> {
+      validate_single_statement(&self.query)?;
+      if let Some(gate) = &self.migrations {
+         gate.check()?;
+      }
+      self.values = self.bind_options.apply(self.values);
+
+      let max_rows = self.max_rows;
+      let timeout = self.timeout;
+      let mut q = sqlx::query(&self.query);
+      for value in self.values {
+         q = bind_value(q, value);
+      }
+      let fetch = move |conn| fetch_rows_limited(conn, q, max_rows);
+
+      if self.on_writer {
+         if self.attached.is_empty() {
+            let mut writer = self.db.acquire_writer().await?;
+            let rows = fetch_with_timeout(&mut writer, timeout, fetch).await?;
+            return decode_rows(rows, &self.decode_options);
+         }
+
+         let mut conn =
+            sqlx_sqlite_conn_mgr::acquire_writer_with_attached(&self.db, self.attached).await?;
+         let rows = fetch_with_timeout(&mut conn, timeout, fetch).await?;
+         let result = decode_rows(rows, &self.decode_options)?;
+         conn.detach_all().await?;
+         return Ok(result);
+      }
+
       if self.attached.is_empty() {
          // No attached databases - use regular read pool
-         let pool = self.db.read_pool()?;
-         let mut q = sqlx::query(&self.query);
-         for value in self.values {
-            q = bind_value(q, value);
-         }
-         let rows = q.fetch_all(pool).await?;
-         Ok(decode_rows(rows)?)
+         let mut conn = self.db.read_pool()?.acquire().await?;
+         let rows = fetch_with_timeout(&mut conn, timeout, fetch).await?;
+         Ok(decode_rows(rows, &self.decode_options)?)
       } else {
          // With attached database(s) - acquire reader with attached database(s)
          let mut conn =
             sqlx_sqlite_conn_mgr::acquire_reader_with_attached(&self.db, self.attached).await?;
-
-         let mut q = sqlx::query(&self.query);
-         for value in self.values {
-            q = bind_value(q, value);
-         }
-         let rows = sqlx::Executor::fetch_all(&mut *conn, q).await?;
-         let result = decode_rows(rows)?;
+         let rows = fetch_with_timeout(&mut conn, timeout, fetch).await?;
+         let result = decode_rows(rows, &self.decode_options)?;
 
          // Explicit cleanup
          conn.detach_all().await?;
@@ -70,6 +162,53 @@ impl FetchAllBuilder {
    }
 }
 
+/// Fetch all rows, or at most `limit + 1` rows when a limit is set, erroring if
+/// the limit is exceeded
+pub(crate) async fn fetch_rows_limited<'e, E>(
+   executor: E,
+   query: sqlx::query::Query<'e, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'e>>,
+   limit: Option<usize>,
+) -> Result<Vec<sqlx::sqlite::SqliteRow>, Error>
+where
+   E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+   use futures_util::{StreamExt, TryStreamExt};
+
+   let Some(limit) = limit else {
+      return Ok(query.fetch_all(executor).await?);
+   };
+
+   let rows: Vec<_> = query
+      .fetch(executor)
+      .take(limit.saturating_add(1))
+      .try_collect()
+      .await?;
+
+   if rows.len() > limit {
+      return Err(Error::ResultSetTooLarge { limit });
+   }
+
+   Ok(rows)
+}
+
+/// Run `fetch` on `conn`, interrupting it with `Error::QueryTimeout` once
+/// `timeout` (if any) has passed
+async fn fetch_with_timeout<'c, F>(
+   conn: &'c mut SqliteConnection,
+   timeout: Option<Duration>,
+   fetch: impl FnOnce(&'c mut SqliteConnection) -> F,
+) -> Result<Vec<SqliteRow>, Error>
+where
+   F: Future<Output = Result<Vec<SqliteRow>, Error>>,
+{
+   let Some(timeout) = timeout else {
+      return fetch(conn).await;
+   };
+   sqlx_sqlite_conn_mgr::with_interrupt_timeout(conn, timeout, fetch)
+      .await?
+      .unwrap_or(Err(Error::QueryTimeout(timeout)))
+}
+
 impl IntoFuture for FetchAllBuilder {
    type Output = Result<Vec<IndexMap<String, JsonValue>>, Error>;
    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
@@ -85,6 +224,11 @@ pub struct FetchOneBuilder {
    query: String,
    values: Vec<JsonValue>,
    attached: Vec<AttachedSpec>,
+   decode_options: DecodeOptions,
+   bind_options: BindOptions,
+   on_writer: bool,
+   timeout: Option<Duration>,
+   migrations: Option<Arc<MigrationGate>>,
 }
 
 impl FetchOneBuilder {
@@ -98,35 +242,101 @@ impl FetchOneBuilder {
          query,
          values,
          attached: Vec::new(),
+         decode_options: DecodeOptions::default(),
+         bind_options: BindOptions::default(),
+         on_writer: false,
+         timeout: None,
+         migrations: None,
       }
    }
 
+   /// Refuse to run until the wrapper's migrations have completed
+   pub(crate) fn migration_gate(mut self, gate: Option<Arc<MigrationGate>>) -> Self {
+      self.migrations = gate;
+      self
+   }
+
    /// Attach additional databases for this query
    pub fn attach(mut self, attached: Vec<AttachedSpec>) -> Self {
       self.attached = attached;
       self
    }
 
+   /// Set how result values are decoded (e.g. which columns are booleans)
+   pub fn decode_options(mut self, options: DecodeOptions) -> Self {
+      self.decode_options = options;
+      self
+   }
+
+   /// Set how storage classes are represented, keeping the other decode options
+   pub fn decode_mode(mut self, mode: DecodeMode) -> Self {
+      self.decode_options.mode = mode;
+      self
+   }
+
+   /// Set how parameter values are bound (e.g. empty strings as `NULL`)
+   pub fn bind_options(mut self, options: BindOptions) -> Self {
+      self.bind_options = options;
+      self
+   }
+
+   /// Run the query on the write connection instead of the read pool
+   ///
+   /// For reads that must see the writer's state, such as per-connection
+   /// `PRAGMA`s or rows written in the writer's current snapshot. This contends
+   /// for the write lock: the query waits for in-flight writes and blocks other
+   /// writers while it runs.
+   pub fn on_writer(mut self) -> Self {
+      self.on_writer = true;
+      self
+   }
+
+   /// Interrupt the query with `Error::QueryTimeout` if it runs longer than `timeout`
+   ///
+   /// The running statement is stopped with `sqlite3_interrupt`, not merely
+   /// abandoned, so the connection is free for other queries once this
+   /// returns. The deadline covers running the query, not waiting for a
+   /// connection.
+   pub fn timeout(mut self, timeout: Duration) -> Self {
+      self.timeout = Some(timeout);
+      self
+   }
+
    /// Execute the query and return zero or one row
-   pub async fn execute(self) -> Result<Option<IndexMap<String, JsonValue>>, Error> {
-      let rows = if self.attached.is_empty() {
-         // No attached databases - use regular read pool
-         let pool = self.db.read_pool()?;
-         let mut q = sqlx::query(&self.query);
-         for value in self.values {
-            q = bind_value(q, value);
+   pub async fn execute(mut self) -> Result<Option<IndexMap<String, JsonValue>>, Error> {
+      validate_single_statement(&self.query)?;
+      if let Some(gate) = &self.migrations {
+         gate.check()?;
+      }
+      self.values = self.bind_options.apply(self.values);
+
+      let timeout = self.timeout;
+      let mut q = sqlx::query(&self.query);
+      for value in self.values {
+         q = bind_value(q, value);
+      }
+      let fetch = move |conn| fetch_rows_limited(conn, q, None);
+
+      let rows = if self.on_writer {
+         if self.attached.is_empty() {
+            let mut writer = self.db.acquire_writer().await?;
+            fetch_with_timeout(&mut writer, timeout, fetch).await?
+         } else {
+            let mut conn =
+               sqlx_sqlite_conn_mgr::acquire_writer_with_attached(&self.db, self.attached).await?;
+            let rows = fetch_with_timeout(&mut conn, timeout, fetch).await?;
+            conn.detach_all().await?;
+            rows
          }
-         q.fetch_all(pool).await?
+      } else if self.attached.is_empty() {
+         // No attached databases - use regular read pool
+         let mut conn = self.db.read_pool()?.acquire().await?;
+         fetch_with_timeout(&mut conn, timeout, fetch).await?
       } else {
          // With attached database(s) - acquire reader with attached database(s)
          let mut conn =
             sqlx_sqlite_conn_mgr::acquire_reader_with_attached(&self.db, self.attached).await?;
-
-         let mut q = sqlx::query(&self.query);
-         for value in self.values {
-            q = bind_value(q, value);
-         }
-         let rows = sqlx::Executor::fetch_all(&mut *conn, q).await?;
+         let rows = fetch_with_timeout(&mut conn, timeout, fetch).await?;
 
          // Explicit cleanup
          conn.detach_all().await?;
@@ -137,7 +347,8 @@ impl FetchOneBuilder {
       match rows.len() {
          0 => Ok(None),
          1 => {
-            let decoded = decode_rows(vec![rows.into_iter().next().unwrap()])?;
+            let decoded =
+               decode_rows(vec![rows.into_iter().next().unwrap()], &self.decode_options)?;
             Ok(Some(decoded.into_iter().next().unwrap()))
          }
          count => Err(Error::MultipleRowsReturned(count)),
@@ -154,6 +365,80 @@ impl IntoFuture for FetchOneBuilder {
    }
 }
 
+/// Builder for SELECT queries returning a single value
+///
+/// Runs the query like [`FetchOneBuilder`] and returns the first column of the
+/// row, so callers needing one number (a count, a max id) don't handle a row map.
+pub struct FetchScalarBuilder {
+   inner: FetchOneBuilder,
+}
+
+impl FetchScalarBuilder {
+   pub(crate) fn new(
+      db: Arc<sqlx_sqlite_conn_mgr::SqliteDatabase>,
+      query: String,
+      values: Vec<JsonValue>,
+   ) -> Self {
+      Self {
+         inner: FetchOneBuilder::new(db, query, values),
+      }
+   }
+
+   /// Refuse to run until the wrapper's migrations have completed
+   pub(crate) fn migration_gate(mut self, gate: Option<Arc<MigrationGate>>) -> Self {
+      self.inner = self.inner.migration_gate(gate);
+      self
+   }
+
+   /// Attach additional databases for this query
+   pub fn attach(mut self, attached: Vec<AttachedSpec>) -> Self {
+      self.inner = self.inner.attach(attached);
+      self
+   }
+
+   /// Interrupt the query with `Error::QueryTimeout` if it runs longer than `timeout`
+   pub fn timeout(mut self, timeout: Duration) -> Self {
+      self.inner = self.inner.timeout(timeout);
+      self
+   }
+
+   /// Set how the result value is decoded (e.g. whether it is a boolean)
+   pub fn decode_options(mut self, options: DecodeOptions) -> Self {
+      self.inner = self.inner.decode_options(options);
+      self
+   }
+
+   /// Set how storage classes are represented, keeping the other decode options
+   pub fn decode_mode(mut self, mode: DecodeMode) -> Self {
+      self.inner = self.inner.decode_mode(mode);
+      self
+   }
+
+   /// Set how parameter values are bound (e.g. empty strings as `NULL`)
+   pub fn bind_options(mut self, options: BindOptions) -> Self {
+      self.inner = self.inner.bind_options(options);
+      self
+   }
+
+   /// Execute the query and return the first column of zero or one row
+   ///
+   /// Returns `None` when no row matches and `Error::MultipleRowsReturned` when
+   /// more than one does.
+   pub async fn execute(self) -> Result<Option<JsonValue>, Error> {
+      let row = self.inner.execute().await?;
+      Ok(row.and_then(|row| row.into_iter().next().map(|(_, value)| value)))
+   }
+}
+
+impl IntoFuture for FetchScalarBuilder {
+   type Output = Result<Option<JsonValue>, Error>;
+   type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+   fn into_future(self) -> Self::IntoFuture {
+      Box::pin(self.execute())
+   }
+}
+
 /// Internal cursor position for forward vs backward pagination.
 enum CursorPosition {
    Forward(Vec<JsonValue>),
@@ -169,6 +454,9 @@ pub struct FetchPageBuilder {
    page_size: usize,
    cursor: Option<CursorPosition>,
    attached: Vec<AttachedSpec>,
+   decode_options: DecodeOptions,
+   bind_options: BindOptions,
+   migrations: Option<Arc<MigrationGate>>,
 }
 
 impl FetchPageBuilder {
@@ -187,9 +475,18 @@ impl FetchPageBuilder {
          page_size,
          cursor: None,
          attached: Vec::new(),
+         decode_options: DecodeOptions::default(),
+         bind_options: BindOptions::default(),
+         migrations: None,
       }
    }
 
+   /// Refuse to run until the wrapper's migrations have completed
+   pub(crate) fn migration_gate(mut self, gate: Option<Arc<MigrationGate>>) -> Self {
+      self.migrations = gate;
+      self
+   }
+
    /// Set the cursor for fetching the next page (forward pagination).
    ///
    /// Pass the `next_cursor` from a previous `KeysetPage` to fetch the page
@@ -214,9 +511,31 @@ impl FetchPageBuilder {
       self
    }
 
+   /// Set how result values are decoded (e.g. which columns are booleans)
+   pub fn decode_options(mut self, options: DecodeOptions) -> Self {
+      self.decode_options = options;
+      self
+   }
+
+   /// Set how storage classes are represented, keeping the other decode options
+   pub fn decode_mode(mut self, mode: DecodeMode) -> Self {
+      self.decode_options.mode = mode;
+      self
+   }
+
+   /// Set how parameter values are bound (e.g. empty strings as `NULL`)
+   pub fn bind_options(mut self, options: BindOptions) -> Self {
+      self.bind_options = options;
+      self
+   }
+
    /// Execute the paginated query and return a page of results
    pub async fn execute(self) -> Result<KeysetPage, Error> {
       // Validate inputs
+      validate_single_statement(&self.query)?;
+      if let Some(gate) = &self.migrations {
+         gate.check()?;
+      }
       if self.keyset.is_empty() {
          return Err(Error::EmptyKeysetColumns);
       }
@@ -252,8 +571,9 @@ impl FetchPageBuilder {
          self.values.len(),
       )?;
 
-      // Combine user values + cursor bind values
-      let mut all_values = self.values;
+      // Combine user values + cursor bind values; cursors come from decoded rows,
+      // so they already hold what is stored
+      let mut all_values = self.bind_options.apply(self.values);
       all_values.extend(cursor_bind_values);
 
       // Execute query
@@ -280,7 +600,7 @@ impl FetchPageBuilder {
       };
 
       // Decode rows
-      let mut decoded = decode_rows(rows)?;
+      let mut decoded = decode_rows(rows, &self.decode_options)?;
 
       // Determine has_more by checking if we got more rows than page_size
       let has_more = decoded.len() > self.page_size;
@@ -305,11 +625,12 @@ impl FetchPageBuilder {
             let mut cursor_vals = Vec::with_capacity(self.keyset.len());
             for col in &self.keyset {
                let value = row
-                  .get(&col.name)
+                  .get(&self.decode_options.key_case.apply(&col.name))
                   .ok_or_else(|| Error::CursorColumnNotFound {
                      column: col.name.clone(),
                   })?;
-               cursor_vals.push(value.clone());
+               // Cursors are bound back as parameters, so drop any type tags
+               cursor_vals.push(untag(value));
             }
             Some(cursor_vals)
          } else {
@@ -342,6 +663,7 @@ pub struct ExecuteBuilder {
    query: String,
    values: Vec<JsonValue>,
    attached: Vec<AttachedSpec>,
+   bind_options: BindOptions,
 }
 
 impl ExecuteBuilder {
@@ -351,6 +673,7 @@ impl ExecuteBuilder {
          query,
          values,
          attached: Vec::new(),
+         bind_options: BindOptions::default(),
       }
    }
 
@@ -360,8 +683,17 @@ impl ExecuteBuilder {
       self
    }
 
+   /// Set how parameter values are bound (e.g. empty strings as `NULL`)
+   pub fn bind_options(mut self, options: BindOptions) -> Self {
+      self.bind_options = options;
+      self
+   }
+
    /// Execute the write operation
-   pub async fn execute(self) -> Result<WriteQueryResult, Error> {
+   pub async fn execute(mut self) -> Result<WriteQueryResult, Error> {
+      validate_single_statement(&self.query)?;
+      self.values = self.bind_options.apply(self.values);
+
       if self.attached.is_empty() {
          // No attached databases - use wrapper's writer (routes through observer when in use)
          let mut writer = self.db.acquire_writer().await?;
@@ -376,9 +708,7 @@ impl ExecuteBuilder {
          })
       } else {
          // With attached database(s) - acquire writer with attached database(s)
-         let mut conn =
-            sqlx_sqlite_conn_mgr::acquire_writer_with_attached(self.db.inner(), self.attached)
-               .await?;
+         let mut conn = self.db.acquire_writer_with_attached(self.attached).await?;
 
          let mut q = sqlx::query(&self.query);
          for value in self.values {
@@ -395,6 +725,105 @@ impl ExecuteBuilder {
          Ok(write_result)
       }
    }
+
+   /// Execute the write operation and return the rows of its `RETURNING` clause
+   ///
+   /// For `INSERT`/`UPDATE`/`DELETE ... RETURNING` (SQLite 3.35+). Rows are
+   /// decoded with the wrapper's decode options; a statement without
+   /// `RETURNING` still runs and yields no rows.
+   pub async fn execute_returning(mut self) -> Result<Vec<IndexMap<String, JsonValue>>, Error> {
+      validate_single_statement(&self.query)?;
+      self.values = self.bind_options.apply(self.values);
+
+      let mut q = sqlx::query(&self.query);
+      for value in self.values {
+         q = bind_value(q, value);
+      }
+
+      let rows = if self.attached.is_empty() {
+         // No attached databases - use wrapper's writer (routes through observer when in use)
+         let mut writer = self.db.acquire_writer().await?;
+         q.fetch_all(&mut *writer).await?
+      } else {
+         // With attached database(s) - acquire writer with attached database(s)
+         let mut conn = self.db.acquire_writer_with_attached(self.attached).await?;
+         let rows = sqlx::Executor::fetch_all(&mut *conn, q).await?;
+
+         // Explicit cleanup
+         conn.detach_all().await?;
+         rows
+      };
+
+      decode_rows(rows, self.db.decode_options())
+   }
+
+   /// Execute the write operation, then run `read_query` on the same connection
+   ///
+   /// Read-your-writes: the read runs on the write connection before it is
+   /// released, so it always sees this write and no other write can land in
+   /// between. A read through the read pool right after `execute` also sees the
+   /// committed write in WAL mode, but may observe later writes from other tasks
+   /// too. The tradeoff is that the read holds the writer, blocking other writers
+   /// while it runs; keep it small. The read honors `max_result_rows` and the
+   /// wrapper's decode options.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(db: &sqlx_sqlite_toolkit::DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// use serde_json::json;
+   ///
+   /// let (result, rows) = db
+   ///    .execute("INSERT INTO todos (title) VALUES (?)".into(), vec![json!("Ship")])
+   ///    .execute_and_read("SELECT COUNT(*) AS open FROM todos WHERE done = 0".into(), vec![])
+   ///    .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn execute_and_read(
+      mut self,
+      read_query: String,
+      read_values: Vec<JsonValue>,
+   ) -> Result<(WriteQueryResult, Vec<IndexMap<String, JsonValue>>), Error> {
+      validate_single_statement(&self.query)?;
+      validate_single_statement(&read_query)?;
+      self.values = self.bind_options.apply(self.values);
+      let limit = self.db.max_result_rows();
+
+      let mut q = sqlx::query(&self.query);
+      for value in self.values {
+         q = bind_value(q, value);
+      }
+      let mut read = sqlx::query(&read_query);
+      for value in read_values {
+         read = bind_value(read, value);
+      }
+
+      let (result, rows) = if self.attached.is_empty() {
+         // No attached databases - use wrapper's writer (routes through observer when in use)
+         let mut writer = self.db.acquire_writer().await?;
+         let result = q.execute(&mut *writer).await?;
+         let rows = fetch_rows_limited(&mut *writer, read, limit).await?;
+         (result, rows)
+      } else {
+         // With attached database(s) - acquire writer with attached database(s)
+         let mut conn = self.db.acquire_writer_with_attached(self.attached).await?;
+         let result = sqlx::Executor::execute(&mut *conn, q).await?;
+         let rows = fetch_rows_limited(&mut *conn, read, limit).await?;
+
+         // Explicit cleanup
+         conn.detach_all().await?;
+         (result, rows)
+      };
+
+      Ok((
+         WriteQueryResult {
+            rows_affected: result.rows_affected(),
+            last_insert_id: result.last_insert_rowid(),
+         },
+         decode_rows(rows, self.db.decode_options())?,
+      ))
+   }
 }
 
 impl IntoFuture for ExecuteBuilder {
@@ -406,9 +835,34 @@ impl IntoFuture for ExecuteBuilder {
    }
 }
 
+/// Helper to decode SQLite rows to a JSON array of objects
+pub(crate) fn decode_rows_json(
+   rows: Vec<sqlx::sqlite::SqliteRow>,
+   options: &DecodeOptions,
+) -> Result<JsonValue, Error> {
+   use sqlx::{Column, Row};
+
+   let mut values = Vec::with_capacity(rows.len());
+   for row in rows {
+      let mut value = serde_json::Map::with_capacity(row.columns().len());
+      for (i, column) in row.columns().iter().enumerate() {
+         let v = row.try_get_raw(i)?;
+         let v = options.decode(v, column)?;
+         let key = options.key_case.apply(column.name());
+         if options.key_case != KeyCase::AsIs && value.contains_key(&key) {
+            return Err(Error::DuplicateColumnKey(key));
+         }
+         value.insert(key, v);
+      }
+      values.push(JsonValue::Object(value));
+   }
+   Ok(JsonValue::Array(values))
+}
+
 /// Helper to decode SQLite rows to JSON
 pub(crate) fn decode_rows(
    rows: Vec<sqlx::sqlite::SqliteRow>,
+   options: &DecodeOptions,
 ) -> Result<Vec<IndexMap<String, JsonValue>>, Error> {
    use sqlx::{Column, Row};
 
@@ -417,8 +871,12 @@ pub(crate) fn decode_rows(
       let mut value = IndexMap::default();
       for (i, column) in row.columns().iter().enumerate() {
          let v = row.try_get_raw(i)?;
-         let v = crate::decode::to_json(v)?;
-         value.insert(column.name().to_string(), v);
+         let v = options.decode(v, column)?;
+         let key = options.key_case.apply(column.name());
+         if options.key_case != KeyCase::AsIs && value.contains_key(&key) {
+            return Err(Error::DuplicateColumnKey(key));
+         }
+         value.insert(key, v);
       }
       values.push(value);
    }
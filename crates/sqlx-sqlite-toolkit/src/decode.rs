@@ -1,18 +1,260 @@
 use serde_json::Value as JsonValue;
-use sqlx::sqlite::SqliteValueRef;
-use sqlx::{TypeInfo, Value, ValueRef};
+use sqlx::sqlite::{SqliteColumn, SqliteValueRef};
+use sqlx::{Column, TypeInfo, Value, ValueRef};
 use time::PrimitiveDateTime;
 
 use crate::Error;
 
+/// Options controlling how query results are decoded to JSON.
+///
+/// SQLite has no boolean storage class: `BOOLEAN` columns hold the integers `0`
+/// and `1`, which decode as JSON numbers by default. These options opt columns in
+/// to decoding as JSON `false`/`true` instead. Values other than `0`/`1` in a
+/// boolean column (including `NULL`) pass through unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct DecodeOptions {
+   /// Result column names to decode as booleans.
+   pub boolean_columns: Vec<String>,
+   /// Also decode columns whose declared type is `BOOLEAN` (or `BOOL`) as booleans.
+   ///
+   /// Only table columns carry a declared type; computed expressions must be
+   /// listed in `boolean_columns`.
+   pub detect_boolean_columns: bool,
+   /// Casing applied to column names when building each result row.
+   ///
+   /// Row keys keep the query's column order. If two columns map to the same
+   /// key after conversion (e.g. `user_id` and `userId` under
+   /// [`KeyCase::CamelCase`]), decoding fails with
+   /// [`Error::DuplicateColumnKey`] rather than silently dropping a value.
+   pub key_case: KeyCase,
+   /// Result column names holding timestamps, decoded as normalized UTC strings.
+   ///
+   /// Values are read from ISO-8601 TEXT (RFC 3339, or SQLite's
+   /// `YYYY-MM-DD HH:MM:SS` taken as UTC) or INTEGER Unix seconds and returned
+   /// in the form `DateTime<Utc>` serializes to, so they deserialize into
+   /// `chrono::DateTime<Utc>` fields. Unparseable values pass through unchanged.
+   ///
+   /// Requires the `chrono` feature.
+   #[cfg(feature = "chrono")]
+   pub datetime_columns: Vec<String>,
+   /// How storage classes are represented in the decoded JSON.
+   pub mode: DecodeMode,
+}
+
+/// Representation of SQLite values in decoded JSON.
+///
+/// JSON has no binary type, so [`DecodeMode::JsonLossy`] returns BLOBs as
+/// base64 strings that cannot be told apart from TEXT holding base64.
+/// [`DecodeMode::TaggedTypes`] keeps the storage class of every non-NULL value:
+///
+/// | Storage class | `TaggedTypes` value |
+/// |---------------|---------------------|
+/// | INTEGER | `{"$type": "integer", "value": 42}` |
+/// | REAL | `{"$type": "real", "value": 1.5}` |
+/// | TEXT | `{"$type": "text", "value": "abc"}` |
+/// | BLOB | `{"$type": "blob", "data": "<base64>"}` |
+/// | NULL | `null` |
+///
+/// Columns listed in [`DecodeOptions::boolean_columns`] (or detected as
+/// booleans) and datetime columns are still returned as plain JSON booleans and
+/// strings, since the caller has already said what they hold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+   /// Plain JSON values; BLOBs become base64 strings.
+   #[default]
+   JsonLossy,
+   /// `{"$type": ...}` objects recording each value's storage class.
+   TaggedTypes,
+}
+
+/// Casing convention for the keys of decoded rows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyCase {
+   /// Keep column names exactly as SQLite reports them.
+   #[default]
+   AsIs,
+   /// Convert `snake_case` names to `camelCase` (`user_id` → `userId`).
+   CamelCase,
+   /// Convert `camelCase` names to `snake_case` (`userId` → `user_id`).
+   SnakeCase,
+}
+
+impl KeyCase {
+   /// Convert a column name to this casing.
+   pub fn apply(self, name: &str) -> String {
+      match self {
+         KeyCase::AsIs => name.to_string(),
+         KeyCase::CamelCase => to_camel_case(name),
+         KeyCase::SnakeCase => to_snake_case(name),
+      }
+   }
+}
+
+/// Drops underscores between words and uppercases the letter that follows.
+///
+/// Leading underscores are kept so `_rowid_` style names stay distinguishable.
+fn to_camel_case(name: &str) -> String {
+   let body = name.trim_start_matches('_');
+   let mut out = String::with_capacity(name.len());
+   out.push_str(&name[..name.len() - body.len()]);
+
+   let mut upper_next = false;
+   for c in body.chars() {
+      if c == '_' {
+         upper_next = true;
+      } else if upper_next {
+         out.extend(c.to_uppercase());
+         upper_next = false;
+      } else {
+         out.push(c);
+      }
+   }
+   if upper_next {
+      out.push('_');
+   }
+   out
+}
+
+/// Inserts an underscore at each word boundary and lowercases the result.
+///
+/// A boundary is an uppercase letter after a lowercase letter or digit, or the
+/// last capital of an acronym followed by a lowercase letter (`HTTPServer` →
+/// `http_server`).
+fn to_snake_case(name: &str) -> String {
+   let chars: Vec<char> = name.chars().collect();
+   let mut out = String::with_capacity(name.len() + 4);
+   for (i, &c) in chars.iter().enumerate() {
+      if c.is_uppercase() && i > 0 {
+         let prev = chars[i - 1];
+         let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+         if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+            out.push('_');
+         }
+      }
+      out.extend(c.to_lowercase());
+   }
+   out
+}
+
+impl DecodeOptions {
+   fn is_boolean_column(&self, column: &SqliteColumn) -> bool {
+      (self.detect_boolean_columns && column.type_info().name() == "BOOLEAN")
+         || self.boolean_columns.iter().any(|c| c == column.name())
+   }
+
+   /// Decode a column value, applying these options.
+   pub(crate) fn decode(
+      &self,
+      value: SqliteValueRef,
+      column: &SqliteColumn,
+   ) -> Result<JsonValue, Error> {
+      let storage_class = value.type_info().name().to_string();
+      let json = to_plain_json(value)?;
+      #[cfg(feature = "chrono")]
+      if self.datetime_columns.iter().any(|c| c == column.name()) {
+         return Ok(match parse_datetime(&json) {
+            Some(dt) => JsonValue::String(format_datetime(&dt)),
+            None => tag(json, &storage_class, self.mode),
+         });
+      }
+      if !self.is_boolean_column(column) {
+         return Ok(tag(json, &storage_class, self.mode));
+      }
+      Ok(match json.as_i64() {
+         Some(0) => JsonValue::Bool(false),
+         Some(1) => JsonValue::Bool(true),
+         _ => tag(json, &storage_class, self.mode),
+      })
+   }
+}
+
+/// Parse a decoded timestamp value into UTC.
+///
+/// Accepts RFC 3339 strings, SQLite's `datetime()` format (`YYYY-MM-DD HH:MM:SS`
+/// with optional fractional seconds, interpreted as UTC) and integer Unix seconds.
+#[cfg(feature = "chrono")]
+pub fn parse_datetime(value: &JsonValue) -> Option<chrono::DateTime<chrono::Utc>> {
+   use chrono::{DateTime, NaiveDateTime, Utc};
+
+   match value {
+      JsonValue::String(s) => DateTime::parse_from_rfc3339(s)
+         .map(|dt| dt.with_timezone(&Utc))
+         .or_else(|_| {
+            NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").map(|dt| dt.and_utc())
+         })
+         .ok(),
+      JsonValue::Number(n) => DateTime::from_timestamp(n.as_i64()?, 0),
+      _ => None,
+   }
+}
+
+/// Format a timestamp as stored and decoded by the toolkit: RFC 3339 in UTC.
+#[cfg(feature = "chrono")]
+fn format_datetime(datetime: &chrono::DateTime<chrono::Utc>) -> String {
+   datetime.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, false)
+}
+
+/// Normalize an RFC 3339 timestamp to the UTC form used for storage.
+///
+/// Returns `None` if `text` is not an RFC 3339 timestamp.
+#[cfg(feature = "chrono")]
+pub fn normalize_datetime(text: &str) -> Option<String> {
+   let datetime = chrono::DateTime::parse_from_rfc3339(text).ok()?;
+   Some(format_datetime(&datetime.with_timezone(&chrono::Utc)))
+}
+
 /// Convert a SQLite value to a JSON value.
 ///
 /// This function handles the type conversion from SQLite's native types
 /// to JSON-compatible representations.
 ///
-/// Note: BLOB values are returned as base64-encoded strings since JSON
-/// has no native binary type. Boolean values are stored as INTEGER in SQLite.
-pub fn to_json(value: SqliteValueRef) -> Result<JsonValue, Error> {
+/// Note: under [`DecodeMode::JsonLossy`], BLOB values are returned as
+/// base64-encoded strings since JSON has no native binary type;
+/// [`DecodeMode::TaggedTypes`] wraps every non-NULL value with its storage
+/// class instead. Boolean values are stored as INTEGER in SQLite.
+pub fn to_json(value: SqliteValueRef, mode: DecodeMode) -> Result<JsonValue, Error> {
+   let storage_class = value.type_info().name().to_string();
+   Ok(tag(to_plain_json(value)?, &storage_class, mode))
+}
+
+/// Wrap a plain decoded value with its storage class under `TaggedTypes`
+fn tag(json: JsonValue, storage_class: &str, mode: DecodeMode) -> JsonValue {
+   if mode == DecodeMode::JsonLossy || json.is_null() {
+      return json;
+   }
+
+   let (kind, key) = match storage_class {
+      "BLOB" => ("blob", "data"),
+      "INTEGER" | "NUMERIC" | "BOOLEAN" => ("integer", "value"),
+      "REAL" => ("real", "value"),
+      _ => ("text", "value"),
+   };
+   let mut tagged = serde_json::Map::new();
+   tagged.insert("$type".into(), kind.into());
+   tagged.insert(key.into(), json);
+   JsonValue::Object(tagged)
+}
+
+/// Reverse [`DecodeMode::TaggedTypes`], returning the [`DecodeMode::JsonLossy`]
+/// form of a value.
+///
+/// Values that are not tagged objects are returned unchanged.
+pub fn untag(value: &JsonValue) -> JsonValue {
+   let JsonValue::Object(map) = value else {
+      return value.clone();
+   };
+   let inner = match map.get("$type").and_then(JsonValue::as_str) {
+      Some("blob") => map.get("data"),
+      Some("integer" | "real" | "text") => map.get("value"),
+      _ => None,
+   };
+   match inner {
+      Some(inner) if map.len() == 2 => inner.clone(),
+      _ => value.clone(),
+   }
+}
+
+fn to_plain_json(value: SqliteValueRef) -> Result<JsonValue, Error> {
    if value.is_null() {
       return Ok(JsonValue::Null);
    }
@@ -122,6 +364,54 @@ fn base64_encode(data: &[u8]) -> String {
 #[cfg(test)]
 mod tests {
    use super::*;
+   #[cfg(feature = "chrono")]
+   use serde_json::json;
+
+   #[test]
+   fn test_key_case_camel() {
+      assert_eq!(KeyCase::CamelCase.apply("user_id"), "userId");
+      assert_eq!(KeyCase::CamelCase.apply("created_at_utc"), "createdAtUtc");
+      assert_eq!(KeyCase::CamelCase.apply("userId"), "userId");
+      assert_eq!(KeyCase::CamelCase.apply("_rowid_"), "_rowid_");
+      assert_eq!(KeyCase::CamelCase.apply("id"), "id");
+   }
+
+   #[test]
+   fn test_key_case_snake() {
+      assert_eq!(KeyCase::SnakeCase.apply("userId"), "user_id");
+      assert_eq!(KeyCase::SnakeCase.apply("createdAtUtc"), "created_at_utc");
+      assert_eq!(KeyCase::SnakeCase.apply("HTTPServer"), "http_server");
+      assert_eq!(KeyCase::SnakeCase.apply("line2Total"), "line2_total");
+      assert_eq!(KeyCase::SnakeCase.apply("user_id"), "user_id");
+   }
+
+   #[cfg(feature = "chrono")]
+   #[test]
+   fn test_parse_datetime_formats() {
+      let expected = "2024-03-01T12:30:00+00:00";
+      for value in [
+         json!("2024-03-01T14:30:00+02:00"),
+         json!("2024-03-01 12:30:00"),
+         json!(1_709_296_200),
+      ] {
+         assert_eq!(
+            parse_datetime(&value)
+               .map(|dt| format_datetime(&dt))
+               .as_deref(),
+            Some(expected)
+         );
+      }
+      assert_eq!(parse_datetime(&json!("not a date")), None);
+      assert_eq!(
+         normalize_datetime("2024-03-01T12:30:00Z").as_deref(),
+         Some(expected)
+      );
+   }
+
+   #[test]
+   fn test_key_case_as_is() {
+      assert_eq!(KeyCase::AsIs.apply("user_ID"), "user_ID");
+   }
 
    #[test]
    fn test_base64_encode() {
@@ -0,0 +1,119 @@
+//! Query plan diagnostics for development-time performance tuning.
+//!
+//! [`DatabaseWrapper::suggest_indexes`](crate::DatabaseWrapper::suggest_indexes)
+//! runs `EXPLAIN QUERY PLAN` and flags steps that read a whole table where an
+//! index could have been used instead.
+//!
+//! The analysis is heuristic: it only looks at the textual plan SQLite reports
+//! for the given bind values, and a full scan is sometimes the best plan (e.g.
+//! when most rows match). Treat suggestions as hints, not as errors.
+
+use serde::Serialize;
+
+/// Tables with fewer rows than this are not flagged for full scans.
+///
+/// Scanning a small table is usually cheaper than maintaining an index on it.
+pub const LARGE_TABLE_MIN_ROWS: u64 = 1000;
+
+/// A query plan step that would likely benefit from an index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexSuggestion {
+   /// Table (or alias, as reported by the plan) being scanned
+   pub table: String,
+   /// Raw `EXPLAIN QUERY PLAN` detail for the step
+   pub plan_detail: String,
+   /// Number of rows in the table, when `table` names a table in the database
+   pub row_count: Option<u64>,
+   /// Human-readable suggestion
+   pub message: String,
+}
+
+/// What a plan step does to a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PlanAccess {
+   /// Reads every row without an index (`SCAN t`)
+   FullScan,
+   /// SQLite builds a transient index for this query (`USING AUTOMATIC ... INDEX`)
+   AutomaticIndex,
+}
+
+/// Parse an `EXPLAIN QUERY PLAN` detail string into the table it reads and how,
+/// returning `None` for steps that use a persistent index or read no table.
+///
+/// Handles both the current format (`SCAN t`) and the pre-3.36 format
+/// (`SCAN TABLE t AS a`).
+pub(crate) fn parse_plan_detail(detail: &str) -> Option<(String, PlanAccess)> {
+   let rest = detail
+      .strip_prefix("SCAN ")
+      .or_else(|| detail.strip_prefix("SEARCH "))?;
+   let rest = rest.strip_prefix("TABLE ").unwrap_or(rest);
+
+   // Subqueries, CTEs and constant rows are not tables
+   if rest.starts_with('(') || rest.starts_with("CONSTANT ROW") {
+      return None;
+   }
+
+   let table = rest.split_whitespace().next()?.to_string();
+
+   if rest.contains(" USING AUTOMATIC ") {
+      Some((table, PlanAccess::AutomaticIndex))
+   } else if detail.starts_with("SCAN ") && !rest.contains(" USING ") {
+      Some((table, PlanAccess::FullScan))
+   } else {
+      None
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn full_scan_detected() {
+      assert_eq!(
+         parse_plan_detail("SCAN users"),
+         Some(("users".into(), PlanAccess::FullScan))
+      );
+   }
+
+   #[test]
+   fn legacy_full_scan_detected() {
+      assert_eq!(
+         parse_plan_detail("SCAN TABLE users AS u"),
+         Some(("users".into(), PlanAccess::FullScan))
+      );
+   }
+
+   #[test]
+   fn automatic_index_detected() {
+      assert_eq!(
+         parse_plan_detail("SEARCH v USING AUTOMATIC COVERING INDEX (name=?)"),
+         Some(("v".into(), PlanAccess::AutomaticIndex))
+      );
+   }
+
+   #[test]
+   fn index_use_not_flagged() {
+      assert_eq!(
+         parse_plan_detail("SEARCH users USING INDEX ia (age>?)"),
+         None
+      );
+      assert_eq!(
+         parse_plan_detail("SEARCH users USING INTEGER PRIMARY KEY (rowid=?)"),
+         None
+      );
+      assert_eq!(
+         parse_plan_detail("SCAN users USING COVERING INDEX ia"),
+         None
+      );
+   }
+
+   #[test]
+   fn non_table_steps_ignored() {
+      assert_eq!(parse_plan_detail("SCAN (subquery-1)"), None);
+      assert_eq!(parse_plan_detail("SCAN CONSTANT ROW"), None);
+      assert_eq!(parse_plan_detail("USE TEMP B-TREE FOR ORDER BY"), None);
+      assert_eq!(parse_plan_detail("CO-ROUTINE cte"), None);
+   }
+}
@@ -8,7 +8,21 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
    /// Error from SQLx operations.
    #[error(transparent)]
-   Sqlx(#[from] sqlx::Error),
+   Sqlx(sqlx::Error),
+
+   /// The database or disk is full (`SQLITE_FULL`).
+   ///
+   /// Classified from the SQLite result code so callers can show a specific
+   /// "out of storage" message instead of a generic failure.
+   #[error("database or disk is full: {0}")]
+   DiskFull(sqlx::Error),
+
+   /// Another connection holds a conflicting lock (`SQLITE_BUSY` or `SQLITE_LOCKED`).
+   ///
+   /// Transient: the operation usually succeeds if run again later. See
+   /// [`Error::is_retryable`].
+   #[error("database is busy: {0}")]
+   Busy(sqlx::Error),
 
    /// Error from the connection manager.
    #[error(transparent)]
@@ -54,6 +68,11 @@ pub enum Error {
    #[error(transparent)]
    Observer(#[from] sqlx_sqlite_observer::Error),
 
+   /// Observation has not been enabled on this database.
+   #[cfg(feature = "observer")]
+   #[error("observation not enabled")]
+   ObservationNotEnabled,
+
    /// I/O error when accessing database files.
    #[error("io error: {0}")]
    Io(#[from] std::io::Error),
@@ -90,27 +109,137 @@ pub enum Error {
    #[error("invalid keyset column name '{name}': must match [a-zA-Z_][a-zA-Z0-9_.]*")]
    InvalidColumnName { name: String },
 
+   /// Query returned more rows than the configured `max_result_rows` limit.
+   #[error("query returned more than {limit} rows (max_result_rows limit)")]
+   ResultSetTooLarge { limit: usize },
+
+   /// Query passed to a single-statement API contains more than one statement.
+   #[error("query contains multiple statements; run them separately or use execute_transaction()")]
+   MultipleStatements,
+
+   /// Transaction has more statements than the configured `max_transaction_statements`.
+   #[error("transaction has more than {limit} statements (max_transaction_statements limit)")]
+   TooManyStatements { limit: usize },
+
+   /// Table does not exist in the database.
+   #[error("table not found: {0}")]
+   TableNotFound(String),
+
+   /// Imported data names a column that the target table does not have.
+   #[error("table '{table}' has no column named '{column}'")]
+   UnknownColumn { table: String, column: String },
+
+   /// A line in a JSON Lines import is not a JSON object.
+   #[error("invalid JSON on line {line}: {message}")]
+   InvalidJsonLine { line: usize, message: String },
+
+   /// A `bulk_insert` row has a different number of values than there are columns.
+   #[error("row {row} has {actual} values but {expected} columns were given")]
+   RowWidthMismatch {
+      row: usize,
+      expected: usize,
+      actual: usize,
+   },
+
+   /// `with_pragma` was given a pragma outside its allowlist or an unsafe value.
+   #[error("invalid pragma: {0}")]
+   InvalidPragma(String),
+
+   /// A query set with a `timeout` ran past it and was interrupted.
+   #[error("query interrupted after exceeding its {0:?} timeout")]
+   QueryTimeout(std::time::Duration),
+
+   /// Savepoint name is not a plain identifier.
+   ///
+   /// Savepoint names must match `[a-zA-Z_][a-zA-Z0-9_]*`.
+   #[error("invalid savepoint name '{name}': must match [a-zA-Z_][a-zA-Z0-9_]*")]
+   InvalidSavepointName { name: String },
+
+   /// JSON path is not a dotted list of keys with optional array indexes.
+   ///
+   /// Each key must match `[a-zA-Z_][a-zA-Z0-9_]*`, optionally followed by
+   /// indexes like `[0]`, e.g. `address.city` or `tags[0].name`.
+   #[error("invalid JSON path '{path}': expected keys like address.city or tags[0]")]
+   InvalidJsonPath { path: String },
+
+   /// Query passed to `execute_named` has both named and positional placeholders.
+   #[error("query mixes named (:name, @name, $name) and positional (?, $1) parameters")]
+   MixedParameterStyles,
+
+   /// A named placeholder of an `execute_named` query has no value in the map.
+   #[error("no value for named parameter '{name}'")]
+   MissingNamedParameter { name: String },
+
+   /// Query attempted before migrations started by `with_migrations` completed.
+   #[error("migrations have not completed yet")]
+   MigrationsPending,
+
+   /// Migrations started by `with_migrations` failed, so queries are refused.
+   #[error("migrations failed: {0}")]
+   MigrationsFailed(String),
+
    /// Cannot provide both `after` and `before` cursors.
    #[error("cannot provide both 'after' and 'before' cursors")]
    ConflictingCursors,
 
+   /// Two result columns map to the same row key under `DecodeOptions::key_case`.
+   #[error("column key '{0}' appears more than once after key case conversion")]
+   DuplicateColumnKey(String),
+
    /// Generic error for operations that don't fit other categories.
    #[error("{0}")]
    Other(String),
 }
 
+/// SQLite primary result codes matched by [`Error::classify`].
+const SQLITE_BUSY: i32 = 5;
+const SQLITE_LOCKED: i32 = 6;
+const SQLITE_FULL: i32 = 13;
+
+impl From<sqlx::Error> for Error {
+   fn from(e: sqlx::Error) -> Self {
+      Error::classify(e)
+   }
+}
+
 impl Error {
+   /// Classify a SQLx error into the most specific toolkit error variant.
+   ///
+   /// SQLite reports extended result codes (e.g. `SQLITE_IOERR_WRITE`); the
+   /// primary code is the low byte, which is what classification matches on.
+   /// Errors without a more specific variant are wrapped as [`Error::Sqlx`].
+   pub fn classify(err: sqlx::Error) -> Self {
+      match primary_result_code(&err) {
+         Some(SQLITE_FULL) => Error::DiskFull(err),
+         Some(SQLITE_BUSY | SQLITE_LOCKED) => Error::Busy(err),
+         _ => Error::Sqlx(err),
+      }
+   }
+
+   /// Whether the operation failed on lock contention and may succeed if retried.
+   pub fn is_retryable(&self) -> bool {
+      match self {
+         Error::Busy(_) => true,
+         Error::ConnectionManager(e) => e.is_retryable(),
+         _ => false,
+      }
+   }
+
    /// Extract a structured error code from the error type.
    ///
    /// This provides machine-readable error codes for error handling.
    pub fn error_code(&self) -> String {
       match self {
-         Error::Sqlx(e) => {
+         Error::Sqlx(e) | Error::Busy(e) => {
             if let Some(code) = e.as_database_error().and_then(|db_err| db_err.code()) {
                return format!("SQLITE_{}", code);
             }
             "SQLX_ERROR".to_string()
          }
+         Error::DiskFull(_) => "SQLITE_FULL".to_string(),
+         Error::ConnectionManager(sqlx_sqlite_conn_mgr::Error::ReadOnlyDatabase) => {
+            "READ_ONLY_DATABASE".to_string()
+         }
          Error::ConnectionManager(_) => "CONNECTION_ERROR".to_string(),
          Error::UnsupportedDatatype(_) => "UNSUPPORTED_DATATYPE".to_string(),
          Error::MultipleRowsReturned(_) => "MULTIPLE_ROWS_RETURNED".to_string(),
@@ -121,7 +250,11 @@ impl Error {
          Error::InvalidTransactionToken => "INVALID_TRANSACTION_TOKEN".to_string(),
          Error::TransactionTimedOut(_) => "TRANSACTION_TIMED_OUT".to_string(),
          #[cfg(feature = "observer")]
+         Error::Observer(sqlx_sqlite_observer::Error::ShutDown) => "OBSERVER_SHUT_DOWN".to_string(),
+         #[cfg(feature = "observer")]
          Error::Observer(_) => "OBSERVER_ERROR".to_string(),
+         #[cfg(feature = "observer")]
+         Error::ObservationNotEnabled => "OBSERVATION_NOT_ENABLED".to_string(),
          Error::Io(_) => "IO_ERROR".to_string(),
          Error::EmptyKeysetColumns => "EMPTY_KEYSET_COLUMNS".to_string(),
          Error::InvalidPageSize => "INVALID_PAGE_SIZE".to_string(),
@@ -129,12 +262,34 @@ impl Error {
          Error::InvalidPaginationQuery => "INVALID_PAGINATION_QUERY".to_string(),
          Error::CursorColumnNotFound { .. } => "CURSOR_COLUMN_NOT_FOUND".to_string(),
          Error::InvalidColumnName { .. } => "INVALID_COLUMN_NAME".to_string(),
+         Error::ResultSetTooLarge { .. } => "RESULT_SET_TOO_LARGE".to_string(),
+         Error::MultipleStatements => "MULTIPLE_STATEMENTS".to_string(),
+         Error::TooManyStatements { .. } => "TOO_MANY_STATEMENTS".to_string(),
+         Error::TableNotFound(_) => "TABLE_NOT_FOUND".to_string(),
+         Error::UnknownColumn { .. } => "UNKNOWN_COLUMN".to_string(),
+         Error::InvalidJsonLine { .. } => "INVALID_JSON_LINE".to_string(),
+         Error::RowWidthMismatch { .. } => "ROW_WIDTH_MISMATCH".to_string(),
+         Error::InvalidSavepointName { .. } => "INVALID_SAVEPOINT_NAME".to_string(),
+         Error::InvalidJsonPath { .. } => "INVALID_JSON_PATH".to_string(),
+         Error::MixedParameterStyles => "MIXED_PARAMETER_STYLES".to_string(),
+         Error::MissingNamedParameter { .. } => "MISSING_NAMED_PARAMETER".to_string(),
+         Error::QueryTimeout(_) => "QUERY_TIMEOUT".to_string(),
+         Error::InvalidPragma(_) => "INVALID_PRAGMA".to_string(),
+         Error::MigrationsPending => "MIGRATIONS_PENDING".to_string(),
+         Error::MigrationsFailed(_) => "MIGRATIONS_FAILED".to_string(),
          Error::ConflictingCursors => "CONFLICTING_CURSORS".to_string(),
+         Error::DuplicateColumnKey(_) => "DUPLICATE_COLUMN_KEY".to_string(),
          Error::Other(_) => "ERROR".to_string(),
       }
    }
 }
 
+/// Primary SQLite result code of a database error, if any
+fn primary_result_code(err: &sqlx::Error) -> Option<i32> {
+   let code = err.as_database_error()?.code()?;
+   code.parse::<i32>().ok().map(|extended| extended & 0xff)
+}
+
 #[cfg(test)]
 mod tests {
    use super::*;
@@ -220,6 +375,33 @@ mod tests {
       assert_eq!(err.error_code(), "SQLX_ERROR");
    }
 
+   #[test]
+   fn test_classify_non_database_error() {
+      let err = Error::classify(sqlx::Error::RowNotFound);
+      assert!(matches!(err, Error::Sqlx(_)));
+   }
+
+   #[test]
+   fn test_busy_is_retryable() {
+      assert!(Error::Busy(sqlx::Error::PoolTimedOut).is_retryable());
+      assert!(
+         Error::ConnectionManager(sqlx_sqlite_conn_mgr::Error::Busy(sqlx::Error::PoolTimedOut))
+            .is_retryable()
+      );
+      assert!(!Error::Sqlx(sqlx::Error::RowNotFound).is_retryable());
+      assert_eq!(
+         Error::Busy(sqlx::Error::PoolTimedOut).error_code(),
+         "SQLX_ERROR"
+      );
+   }
+
+   #[test]
+   fn test_error_code_disk_full() {
+      let err = Error::DiskFull(sqlx::Error::PoolClosed);
+      assert_eq!(err.error_code(), "SQLITE_FULL");
+      assert!(err.to_string().contains("disk is full"));
+   }
+
    #[test]
    fn test_error_code_empty_keyset_columns() {
       let err = Error::EmptyKeysetColumns;
@@ -270,6 +452,131 @@ mod tests {
       assert!(err.to_string().contains("bad;name"));
    }
 
+   #[test]
+   fn test_error_code_result_set_too_large() {
+      let err = Error::ResultSetTooLarge { limit: 100 };
+      assert_eq!(err.error_code(), "RESULT_SET_TOO_LARGE");
+      assert!(err.to_string().contains("100"));
+   }
+
+   #[cfg(feature = "observer")]
+   #[test]
+   fn test_error_code_observation_not_enabled() {
+      let err = Error::ObservationNotEnabled;
+      assert_eq!(err.error_code(), "OBSERVATION_NOT_ENABLED");
+   }
+
+   #[cfg(feature = "observer")]
+   #[test]
+   fn test_error_code_observer_shut_down() {
+      let err = Error::from(sqlx_sqlite_observer::Error::ShutDown);
+      assert_eq!(err.error_code(), "OBSERVER_SHUT_DOWN");
+
+      let err = Error::from(sqlx_sqlite_observer::Error::PoolAcquire);
+      assert_eq!(err.error_code(), "OBSERVER_ERROR");
+   }
+
+   #[test]
+   fn test_error_code_multiple_statements() {
+      let err = Error::MultipleStatements;
+      assert_eq!(err.error_code(), "MULTIPLE_STATEMENTS");
+      assert!(err.to_string().contains("execute_transaction"));
+   }
+
+   #[test]
+   fn test_error_code_too_many_statements() {
+      let err = Error::TooManyStatements { limit: 50 };
+      assert_eq!(err.error_code(), "TOO_MANY_STATEMENTS");
+      assert!(err.to_string().contains("50"));
+   }
+
+   #[test]
+   fn test_error_code_table_not_found() {
+      let err = Error::TableNotFound("users".into());
+      assert_eq!(err.error_code(), "TABLE_NOT_FOUND");
+      assert!(err.to_string().contains("users"));
+   }
+
+   #[test]
+   fn test_error_code_unknown_column() {
+      let err = Error::UnknownColumn {
+         table: "users".into(),
+         column: "nickname".into(),
+      };
+      assert_eq!(err.error_code(), "UNKNOWN_COLUMN");
+      assert!(err.to_string().contains("nickname"));
+   }
+
+   #[test]
+   fn test_error_code_invalid_json_line() {
+      let err = Error::InvalidJsonLine {
+         line: 3,
+         message: "expected value".into(),
+      };
+      assert_eq!(err.error_code(), "INVALID_JSON_LINE");
+      assert!(err.to_string().contains("line 3"));
+   }
+
+   #[test]
+   fn test_error_code_row_width_mismatch() {
+      let err = Error::RowWidthMismatch {
+         row: 2,
+         expected: 3,
+         actual: 1,
+      };
+      assert_eq!(err.error_code(), "ROW_WIDTH_MISMATCH");
+      assert!(err.to_string().contains("row 2"));
+   }
+
+   #[test]
+   fn test_error_code_invalid_pragma() {
+      let err = Error::InvalidPragma("'key' is not a pragma that can be scoped".into());
+      assert_eq!(err.error_code(), "INVALID_PRAGMA");
+      assert!(err.to_string().contains("key"));
+   }
+
+   #[test]
+   fn test_error_code_query_timeout() {
+      let err = Error::QueryTimeout(std::time::Duration::from_millis(250));
+      assert_eq!(err.error_code(), "QUERY_TIMEOUT");
+      assert!(err.to_string().contains("250ms"));
+   }
+
+   #[test]
+   fn test_error_code_invalid_savepoint_name() {
+      let err = Error::InvalidSavepointName {
+         name: "sp; DROP".into(),
+      };
+      assert_eq!(err.error_code(), "INVALID_SAVEPOINT_NAME");
+      assert!(err.to_string().contains("sp; DROP"));
+   }
+
+   #[test]
+   fn test_error_code_invalid_json_path() {
+      let err = Error::InvalidJsonPath { path: "a'b".into() };
+      assert_eq!(err.error_code(), "INVALID_JSON_PATH");
+      assert!(err.to_string().contains("a'b"));
+   }
+
+   #[test]
+   fn test_error_code_named_parameters() {
+      assert_eq!(
+         Error::MixedParameterStyles.error_code(),
+         "MIXED_PARAMETER_STYLES"
+      );
+      let err = Error::MissingNamedParameter { name: ":id".into() };
+      assert_eq!(err.error_code(), "MISSING_NAMED_PARAMETER");
+      assert!(err.to_string().contains(":id"));
+   }
+
+   #[test]
+   fn test_error_code_migrations() {
+      assert_eq!(Error::MigrationsPending.error_code(), "MIGRATIONS_PENDING");
+      let err = Error::MigrationsFailed("no such table".into());
+      assert_eq!(err.error_code(), "MIGRATIONS_FAILED");
+      assert!(err.to_string().contains("no such table"));
+   }
+
    #[test]
    fn test_error_code_conflicting_cursors() {
       let err = Error::ConflictingCursors;
@@ -277,4 +584,11 @@ mod tests {
       assert!(err.to_string().contains("after"));
       assert!(err.to_string().contains("before"));
    }
+
+   #[test]
+   fn test_error_code_duplicate_column_key() {
+      let err = Error::DuplicateColumnKey("userId".into());
+      assert_eq!(err.error_code(), "DUPLICATE_COLUMN_KEY");
+      assert!(err.to_string().contains("userId"));
+   }
 }
@@ -4,7 +4,7 @@
 //! and application-level code (e.g., a Tauri plugin). It provides:
 //!
 //! - [`DatabaseWrapper`] — main entry point wrapping a connection-managed database
-//! - Builder-pattern APIs for queries ([`ExecuteBuilder`], [`FetchAllBuilder`], [`FetchOneBuilder`], [`FetchPageBuilder`])
+//! - Builder-pattern APIs for queries ([`ExecuteBuilder`], [`FetchAllBuilder`], [`FetchOneBuilder`], [`FetchScalarBuilder`], [`FetchPageBuilder`])
 //! - Transaction support ([`TransactionExecutionBuilder`], [`InterruptibleTransactionBuilder`])
 //! - JSON type decoding for SQLite values
 //!
@@ -36,24 +36,34 @@
 
 pub mod builders;
 pub mod decode;
+pub mod diagnostics;
 pub mod error;
+mod migrations;
 pub mod pagination;
+mod params;
+mod statement;
 pub mod transactions;
 pub mod wrapper;
 
-pub use builders::{ExecuteBuilder, FetchAllBuilder, FetchOneBuilder, FetchPageBuilder};
+pub use builders::{
+   ExecuteBuilder, FetchAllBuilder, FetchOneBuilder, FetchPageBuilder, FetchScalarBuilder,
+};
+pub use decode::{DecodeMode, DecodeOptions, KeyCase, untag};
+pub use diagnostics::IndexSuggestion;
 pub use error::{Error, Result};
-pub use pagination::{KeysetColumn, KeysetPage, SortDirection};
+pub use migrations::MigrationStatementProgress;
+pub use pagination::{KeysetColumn, KeysetPage, OffsetPage, SortDirection};
 pub use transactions::{
    ActiveInterruptibleTransaction, ActiveInterruptibleTransactions, ActiveRegularTransactions,
-   Statement, TransactionWriter, cleanup_all_transactions,
+   SavepointGuard, Statement, TransactionWriter, cleanup_all_transactions,
 };
 pub use wrapper::{
-   DatabaseWrapper, InterruptibleTransaction, InterruptibleTransactionBuilder,
-   TransactionExecutionBuilder, WriteQueryResult, WriterGuard, bind_value,
+   AttachedWriterGuard, BindOptions, ColumnCell, DatabaseWrapper, InterruptibleTransaction,
+   InterruptibleTransactionBuilder, ScriptStatementResult, TransactionExecutionBuilder,
+   WriteQueryResult, WriterGuard, bind_value, escape_like,
 };
 
 // Re-export commonly used types from dependencies
 pub use sqlx_sqlite_conn_mgr::{
-   AttachedMode, AttachedSpec, Migrator, SqliteDatabase, SqliteDatabaseConfig,
+   AttachedMode, AttachedSpec, BackupProgress, Migrator, SqliteDatabase, SqliteDatabaseConfig,
 };
@@ -0,0 +1,158 @@
+//! Readiness gate for migrations started by `DatabaseWrapper::with_migrations`,
+//! and a migration runner reporting per-statement progress
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+use serde::Serialize;
+use sqlx::migrate::{MigrateError, Migrator};
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Executor};
+use tokio::sync::watch;
+
+use crate::Error;
+
+#[derive(Debug, Clone)]
+enum MigrationStatus {
+   Pending,
+   Complete,
+   Failed(String),
+}
+
+/// Shared by every clone of a wrapper; flipped once by the migration task
+#[derive(Debug)]
+pub(crate) struct MigrationGate {
+   status: watch::Sender<MigrationStatus>,
+}
+
+impl MigrationGate {
+   pub(crate) fn new() -> Self {
+      Self {
+         status: watch::Sender::new(MigrationStatus::Pending),
+      }
+   }
+
+   pub(crate) fn finish(&self, result: Result<(), String>) {
+      self.status.send_replace(match result {
+         Ok(()) => MigrationStatus::Complete,
+         Err(message) => MigrationStatus::Failed(message),
+      });
+   }
+
+   /// `Ok` once migrations completed, without waiting
+   pub(crate) fn check(&self) -> Result<(), Error> {
+      Self::to_result(&self.status.borrow())
+   }
+
+   /// Wait for the migration task, then report its result
+   pub(crate) async fn wait(&self) -> Result<(), Error> {
+      let mut rx = self.status.subscribe();
+      let status = rx
+         .wait_for(|status| !matches!(status, MigrationStatus::Pending))
+         .await
+         .map_err(|_| Error::MigrationsPending)?;
+      Self::to_result(&status)
+   }
+
+   fn to_result(status: &MigrationStatus) -> Result<(), Error> {
+      match status {
+         MigrationStatus::Pending => Err(Error::MigrationsPending),
+         MigrationStatus::Complete => Ok(()),
+         MigrationStatus::Failed(message) => Err(Error::MigrationsFailed(message.clone())),
+      }
+   }
+}
+
+/// Progress through one migration's statements, reported after each statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MigrationStatementProgress {
+   /// Version of the migration being applied
+   pub version: i64,
+   /// Statements executed so far
+   pub done: usize,
+   /// Statements in the migration
+   pub total: usize,
+}
+
+/// Apply pending migrations of `migrator` one statement at a time
+///
+/// Same checks and bookkeeping as `Migrator::run`: a dirty or modified applied
+/// migration fails the run, and each migration's statements run in one
+/// transaction together with its `_sqlx_migrations` row. Migrations marked
+/// `-- no-transaction` are applied whole, reporting a single step.
+pub(crate) async fn run_with_progress(
+   conn: &mut SqliteConnection,
+   migrator: &Migrator,
+   mut on_progress: impl FnMut(MigrationStatementProgress),
+) -> Result<(), MigrateError> {
+   use sqlx::migrate::Migrate;
+
+   conn.ensure_migrations_table().await?;
+   if let Some(version) = conn.dirty_version().await? {
+      return Err(MigrateError::Dirty(version));
+   }
+
+   let applied = conn.list_applied_migrations().await?;
+   let known: HashSet<i64> = migrator.iter().map(|m| m.version).collect();
+   if !migrator.ignore_missing
+      && let Some(missing) = applied.iter().find(|m| !known.contains(&m.version))
+   {
+      return Err(MigrateError::VersionMissing(missing.version));
+   }
+
+   for migration in migrator.iter() {
+      if migration.migration_type.is_down_migration() {
+         continue;
+      }
+      if let Some(applied) = applied.iter().find(|m| m.version == migration.version) {
+         if applied.checksum != migration.checksum {
+            return Err(MigrateError::VersionMismatch(migration.version));
+         }
+         continue;
+      }
+
+      if migration.no_tx {
+         conn.apply(migration).await?;
+         on_progress(MigrationStatementProgress {
+            version: migration.version,
+            done: 1,
+            total: 1,
+         });
+         continue;
+      }
+
+      let statements = crate::statement::split_statements(&migration.sql);
+      let total = statements.len();
+      let start = Instant::now();
+
+      let mut tx = conn.begin().await?;
+      for (done, statement) in (1..).zip(statements) {
+         tx.execute(statement)
+            .await
+            .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+         on_progress(MigrationStatementProgress {
+            version: migration.version,
+            done,
+            total,
+         });
+      }
+      sqlx::query(
+         "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+          VALUES (?1, ?2, TRUE, ?3, -1)",
+      )
+      .bind(migration.version)
+      .bind(&*migration.description)
+      .bind(&*migration.checksum)
+      .execute(&mut *tx)
+      .await?;
+      tx.commit().await?;
+
+      sqlx::query("UPDATE _sqlx_migrations SET execution_time = ?1 WHERE version = ?2")
+         .bind(start.elapsed().as_nanos() as i64)
+         .bind(migration.version)
+         .execute(&mut *conn)
+         .await?;
+   }
+
+   Ok(())
+}
@@ -30,6 +30,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
 use crate::Error;
+use crate::statement::{skip_block_comment, skip_line_comment, skip_quoted};
 
 /// Sort direction for a keyset column.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -145,12 +146,26 @@ pub struct KeysetPage {
    pub has_more: bool,
 }
 
+/// A page of results from offset pagination, with the total row count.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OffsetPage {
+   /// The rows in this page
+   pub rows: Vec<indexmap::IndexMap<String, JsonValue>>,
+   /// Number of rows the base query returns across all pages
+   pub total: u64,
+   /// Maximum number of rows requested for this page
+   pub limit: usize,
+   /// Number of rows skipped before this page
+   pub offset: usize,
+}
+
 /// Check whether `keyword` appears as a standalone keyword at position `i`
 /// in the uppercased byte slice `bytes` (length `len`).
 ///
 /// "Standalone" means the character before and after the keyword (if present)
 /// is not an identifier character (`[A-Z0-9_]`).
-fn is_keyword_at(bytes: &[u8], len: usize, i: usize, keyword: &[u8]) -> bool {
+pub(crate) fn is_keyword_at(bytes: &[u8], len: usize, i: usize, keyword: &[u8]) -> bool {
    let klen = keyword.len();
    if i + klen > len {
       return false;
@@ -178,58 +193,17 @@ fn is_order_by_at(bytes: &[u8], len: usize, i: usize) -> bool {
    is_keyword_at(bytes, len, j, b"BY")
 }
 
-/// Advance the scanner index past a quoted literal or identifier.
-///
-/// `quote` is the opening quote character (`'` or `"`). The scanner handles
-/// SQL-standard doubled-quote escaping (`''` or `""`).
-fn skip_quoted(bytes: &[u8], len: usize, i: usize, quote: u8) -> usize {
-   let mut j = i + 1;
-   while j < len {
-      if bytes[j] == quote {
-         // Doubled quote is an escape — skip both and continue
-         if j + 1 < len && bytes[j + 1] == quote {
-            j += 2;
-            continue;
-         }
-         // End of quoted section
-         return j;
-      }
-      j += 1;
-   }
-   j // unterminated — return end
-}
-
-/// Advance the scanner index past a `--` line comment (until newline or end).
-fn skip_line_comment(bytes: &[u8], len: usize, i: usize) -> usize {
-   let mut j = i + 2; // skip the `--`
-   while j < len && bytes[j] != b'\n' {
-      j += 1;
-   }
-   j
-}
-
-/// Advance the scanner index past a `/* … */` block comment.
-fn skip_block_comment(bytes: &[u8], len: usize, i: usize) -> usize {
-   let mut j = i + 2; // skip the `/*`
-   while j + 1 < len {
-      if bytes[j] == b'*' && bytes[j + 1] == b'/' {
-         return j + 1; // position of the closing `/`
-      }
-      j += 1;
-   }
-   len.saturating_sub(1) // unterminated — return end
-}
-
 /// Scan the uppercased query, calling `on_keyword` at each top-level position
 /// (depth == 0, outside quotes and comments).
 ///
 /// `on_keyword` receives `(uppercased_bytes, len, position)` and returns
 /// `Some(T)` to short-circuit or `None` to keep scanning.
-fn scan_top_level<T>(
+pub(crate) fn scan_top_level<T>(
    query: &str,
    mut on_keyword: impl FnMut(&[u8], usize, usize) -> Option<T>,
 ) -> Option<T> {
-   let upper = query.to_uppercase();
+   // ASCII-only uppercasing keeps byte offsets aligned with `query`
+   let upper = query.to_ascii_uppercase();
    let bytes = upper.as_bytes();
    let len = bytes.len();
    let mut depth: i32 = 0;
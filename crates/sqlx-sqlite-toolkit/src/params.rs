@@ -0,0 +1,171 @@
+//! Named parameter binding for `DatabaseWrapper::execute_named`
+
+use indexmap::IndexMap;
+use serde_json::Value as JsonValue;
+
+use crate::Error;
+use crate::statement::{skip_block_comment, skip_line_comment, skip_quoted};
+
+/// Placeholders of a query, by style
+#[derive(Debug, Default)]
+struct Placeholders<'q> {
+   /// Distinct `:name`/`@name`/`$name` placeholders in order of first appearance
+   named: Vec<&'q str>,
+   /// Byte range of every named placeholder, with its index into `named`
+   occurrences: Vec<(usize, usize, usize)>,
+   /// Whether the query also has `?`, `?NNN` or `$NNN` placeholders
+   positional: bool,
+}
+
+/// Find the placeholders of `query`, skipping string literals, quoted
+/// identifiers and comments
+fn scan_placeholders(query: &str) -> Placeholders<'_> {
+   let bytes = query.as_bytes();
+   let len = bytes.len();
+   let mut found = Placeholders::default();
+   let mut i = 0;
+
+   while i < len {
+      match bytes[i] {
+         quote @ (b'\'' | b'"' | b'`') => i = skip_quoted(bytes, len, i, quote),
+         b'[' => {
+            while i < len && bytes[i] != b']' {
+               i += 1;
+            }
+         }
+         b'-' if i + 1 < len && bytes[i + 1] == b'-' => i = skip_line_comment(bytes, len, i),
+         b'/' if i + 1 < len && bytes[i + 1] == b'*' => i = skip_block_comment(bytes, len, i),
+         b'?' => found.positional = true,
+         b':' | b'@' | b'$' => {
+            let start = i;
+            let mut j = i + 1;
+            // Non-ASCII bytes are identifier characters in SQLite
+            while j < len
+               && (bytes[j].is_ascii_alphanumeric() || bytes[j] == b'_' || bytes[j] >= 0x80)
+            {
+               j += 1;
+            }
+            let name = &query[start + 1..j];
+            if name.bytes().all(|b| b.is_ascii_digit()) {
+               // `$1`-style placeholders are used positionally
+               found.positional |= !name.is_empty();
+            } else {
+               let placeholder = &query[start..j];
+               let index = found
+                  .named
+                  .iter()
+                  .position(|seen| *seen == placeholder)
+                  .unwrap_or_else(|| {
+                     found.named.push(placeholder);
+                     found.named.len() - 1
+                  });
+               found.occurrences.push((start, j, index));
+            }
+            i = j - 1;
+         }
+         // Skip the rest of a word, so `$` or `:` inside one is not read as a placeholder
+         byte if byte.is_ascii_alphanumeric() || byte == b'_' => {
+            while i + 1 < len && (bytes[i + 1].is_ascii_alphanumeric() || bytes[i + 1] == b'_') {
+               i += 1;
+            }
+         }
+         _ => {}
+      }
+      i += 1;
+   }
+
+   found
+}
+
+/// Rewrite the named placeholders of `query` to positional ones and order
+/// `params` to match
+///
+/// Like SQLite, each distinct name is numbered by its first appearance; it is
+/// rewritten to `?N` since SQLx binds only positional placeholders. Keys are
+/// looked up without the `:`/`@`/`$` prefix, then with it. Unused keys are
+/// ignored.
+pub(crate) fn bind_named(
+   query: &str,
+   params: &IndexMap<String, JsonValue>,
+) -> Result<(String, Vec<JsonValue>), Error> {
+   let placeholders = scan_placeholders(query);
+   if placeholders.positional && !placeholders.named.is_empty() {
+      return Err(Error::MixedParameterStyles);
+   }
+
+   let mut rewritten = String::with_capacity(query.len());
+   let mut copied = 0;
+   for &(start, end, index) in &placeholders.occurrences {
+      rewritten.push_str(&query[copied..start]);
+      rewritten.push_str(&format!("?{}", index + 1));
+      copied = end;
+   }
+   rewritten.push_str(&query[copied..]);
+
+   let values = placeholders
+      .named
+      .into_iter()
+      .map(|placeholder| {
+         params
+            .get(&placeholder[1..])
+            .or_else(|| params.get(placeholder))
+            .cloned()
+            .ok_or_else(|| Error::MissingNamedParameter {
+               name: placeholder.to_string(),
+            })
+      })
+      .collect::<Result<_, _>>()?;
+   Ok((rewritten, values))
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn named(query: &str) -> Vec<&str> {
+      scan_placeholders(query).named
+   }
+
+   #[test]
+   fn finds_named_placeholders_in_order_of_first_appearance() {
+      assert_eq!(
+         named("SELECT * FROM t WHERE a = :b OR c = @a OR d = $d OR e = :b"),
+         vec![":b", "@a", "$d"]
+      );
+   }
+
+   #[test]
+   fn ignores_placeholders_in_literals_and_comments() {
+      let query = "SELECT ':x', \"@y\", [$z], `:w` -- :v\n FROM t /* @u */ WHERE id = :id";
+      assert_eq!(named(query), vec![":id"]);
+      assert!(!scan_placeholders("SELECT '?' FROM t").positional);
+   }
+
+   #[test]
+   fn detects_positional_placeholders() {
+      assert!(scan_placeholders("SELECT ? , ?2").positional);
+      assert!(scan_placeholders("SELECT $1").positional);
+      assert!(!scan_placeholders("SELECT :a").positional);
+   }
+
+   #[test]
+   fn rejects_mixed_styles() {
+      let params = IndexMap::from([("a".to_string(), JsonValue::from(1))]);
+      let err = bind_named("SELECT :a, ?", &params).unwrap_err();
+      assert!(matches!(err, Error::MixedParameterStyles));
+   }
+
+   #[test]
+   fn looks_up_keys_with_or_without_prefix() {
+      let params = IndexMap::from([
+         ("a".to_string(), JsonValue::from(1)),
+         ("@b".to_string(), JsonValue::from(2)),
+      ]);
+      let (query, values) = bind_named("SELECT :a, @b, :a", &params).unwrap();
+      assert_eq!(query, "SELECT ?1, ?2, ?1");
+      assert_eq!(values, vec![JsonValue::from(1), JsonValue::from(2)]);
+
+      let err = bind_named("SELECT :c", &params).unwrap_err();
+      assert!(matches!(err, Error::MissingNamedParameter { name } if name == ":c"));
+   }
+}
@@ -0,0 +1,215 @@
+//! Statement splitting and validation, plus the quote and comment scanners
+//! shared by the other SQL helpers
+
+use crate::Error;
+use crate::pagination::{is_keyword_at, scan_top_level};
+
+/// Reject SQL containing more than one statement.
+///
+/// Statement boundaries are determined as in [`split_statements`]. A trailing
+/// `;` (optionally followed by whitespace, comments, or more `;`) is allowed.
+pub(crate) fn validate_single_statement(query: &str) -> Result<(), Error> {
+   if split_statements(query).len() > 1 {
+      return Err(Error::MultipleStatements);
+   }
+
+   Ok(())
+}
+
+/// Split a SQL script into its statements, without the terminating `;`.
+///
+/// A `;` ends a statement unless it appears inside a string literal, quoted
+/// identifier, comment, or parentheses. Empty statements (only whitespace,
+/// comments, or repeated `;`) are dropped.
+///
+/// `CREATE TRIGGER` bodies contain `;`-separated statements between `BEGIN` and
+/// `END`, so for triggers only a `;` directly after the trigger's `END` ends the
+/// statement. `END` keywords closing a `CASE` expression are not counted.
+pub(crate) fn split_statements(sql: &str) -> Vec<&str> {
+   let mut statements = Vec::new();
+   let mut start: Option<usize> = None;
+   let mut is_trigger = false;
+   let mut last_was_end = false;
+   let mut case_depth = 0usize;
+   let mut skip_until = 0;
+
+   scan_top_level(sql, |bytes, len, i| {
+      let byte = bytes[i];
+      if i < skip_until || byte.is_ascii_whitespace() {
+         return None::<()>;
+      }
+
+      if byte == b';' {
+         if let Some(begin) = start
+            && (!is_trigger || last_was_end)
+         {
+            statements.push(sql[begin..i].trim_end());
+            start = None;
+         }
+         last_was_end = false;
+         return None;
+      }
+
+      // First token of a new statement
+      if start.is_none() {
+         start = Some(i);
+         is_trigger = is_create_trigger(&sql[i..]);
+         case_depth = 0;
+      }
+
+      last_was_end = false;
+      if is_keyword_at(bytes, len, i, b"CASE") {
+         case_depth += 1;
+      } else if is_keyword_at(bytes, len, i, b"END") {
+         if case_depth > 0 {
+            case_depth -= 1;
+         } else {
+            last_was_end = true;
+         }
+      }
+
+      if byte.is_ascii_alphanumeric() || byte == b'_' {
+         // Keywords only start at word boundaries; skip the rest of the word
+         let mut j = i;
+         while j < len && (bytes[j].is_ascii_alphanumeric() || bytes[j] == b'_') {
+            j += 1;
+         }
+         skip_until = j;
+      }
+      None
+   });
+
+   if let Some(begin) = start {
+      statements.push(sql[begin..].trim_end());
+   }
+
+   statements
+}
+
+/// Whether a statement starts with `CREATE [TEMP|TEMPORARY] TRIGGER`
+fn is_create_trigger(statement: &str) -> bool {
+   let mut words = statement.split_whitespace().map(str::to_ascii_uppercase);
+   words.next().as_deref() == Some("CREATE")
+      && match words.next().as_deref() {
+         Some("TEMP") | Some("TEMPORARY") => words.next().as_deref() == Some("TRIGGER"),
+         Some("TRIGGER") => true,
+         _ => false,
+      }
+}
+
+/// Advance the scanner index past a quoted literal or identifier.
+///
+/// `quote` is the opening quote character (`'` or `"`). The scanner handles
+/// SQL-standard doubled-quote escaping (`''` or `""`).
+pub(crate) fn skip_quoted(bytes: &[u8], len: usize, i: usize, quote: u8) -> usize {
+   let mut j = i + 1;
+   while j < len {
+      if bytes[j] == quote {
+         // Doubled quote is an escape — skip both and continue
+         if j + 1 < len && bytes[j + 1] == quote {
+            j += 2;
+            continue;
+         }
+         // End of quoted section
+         return j;
+      }
+      j += 1;
+   }
+   j // unterminated — return end
+}
+
+/// Advance the scanner index past a `--` line comment (until newline or end).
+pub(crate) fn skip_line_comment(bytes: &[u8], len: usize, i: usize) -> usize {
+   let mut j = i + 2; // skip the `--`
+   while j < len && bytes[j] != b'\n' {
+      j += 1;
+   }
+   j
+}
+
+/// Advance the scanner index past a `/* … */` block comment.
+pub(crate) fn skip_block_comment(bytes: &[u8], len: usize, i: usize) -> usize {
+   let mut j = i + 2; // skip the `/*`
+   while j + 1 < len {
+      if bytes[j] == b'*' && bytes[j + 1] == b'/' {
+         return j + 1; // position of the closing `/`
+      }
+      j += 1;
+   }
+   len.saturating_sub(1) // unterminated — return end
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn accepts_single_statement() {
+      assert!(validate_single_statement("SELECT * FROM users").is_ok());
+   }
+
+   #[test]
+   fn accepts_trailing_semicolon() {
+      assert!(validate_single_statement("SELECT 1;").is_ok());
+      assert!(validate_single_statement("SELECT 1; -- done\n ;  ").is_ok());
+   }
+
+   #[test]
+   fn accepts_semicolon_in_string_literal() {
+      assert!(validate_single_statement("INSERT INTO t (v) VALUES ('a; DROP TABLE t')").is_ok());
+      assert!(validate_single_statement("SELECT 'it''s; fine'").is_ok());
+   }
+
+   #[test]
+   fn accepts_semicolon_in_quoted_identifier() {
+      assert!(validate_single_statement(r#"SELECT "weird;name" FROM t"#).is_ok());
+   }
+
+   #[test]
+   fn accepts_semicolon_in_comments() {
+      assert!(validate_single_statement("SELECT 1 -- ; DELETE FROM t").is_ok());
+      assert!(validate_single_statement("SELECT /* ; DELETE FROM t; */ 1").is_ok());
+   }
+
+   #[test]
+   fn accepts_trigger_body() {
+      let sql = "CREATE TRIGGER trg AFTER INSERT ON t BEGIN \
+                 UPDATE t SET n = CASE WHEN n > 0 THEN n END; \
+                 INSERT INTO log VALUES (1); END;";
+      assert!(validate_single_statement(sql).is_ok());
+   }
+
+   #[test]
+   fn rejects_multiple_statements() {
+      assert!(matches!(
+         validate_single_statement("SELECT 1; SELECT 2"),
+         Err(Error::MultipleStatements)
+      ));
+      assert!(validate_single_statement("DELETE FROM t;DROP TABLE t").is_err());
+      assert!(validate_single_statement("SELECT ';'; DELETE FROM t").is_err());
+   }
+
+   #[test]
+   fn splits_script_into_statements() {
+      let sql = "CREATE TABLE t (v TEXT); -- setup\n\
+                 INSERT INTO t VALUES ('a;b');;\n\
+                 CREATE TRIGGER trg AFTER INSERT ON t BEGIN SELECT 1; END;\n\
+                 SELECT * FROM t";
+      assert_eq!(
+         split_statements(sql),
+         [
+            "CREATE TABLE t (v TEXT)",
+            "INSERT INTO t VALUES ('a;b')",
+            "CREATE TRIGGER trg AFTER INSERT ON t BEGIN SELECT 1; END",
+            "SELECT * FROM t",
+         ]
+      );
+      assert!(split_statements(" ; -- nothing\n").is_empty());
+   }
+
+   #[test]
+   fn rejects_statement_after_trigger() {
+      let sql = "CREATE TEMP TRIGGER trg AFTER INSERT ON t BEGIN SELECT 1; END; DROP TABLE t";
+      assert!(validate_single_statement(sql).is_err());
+   }
+}
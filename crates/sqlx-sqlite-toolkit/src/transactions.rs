@@ -1,6 +1,7 @@
 //! Transaction management for interruptible transactions
 
 use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -14,18 +15,20 @@ use tokio::task::AbortHandle;
 use tracing::{debug, warn};
 
 #[cfg(feature = "observer")]
-use sqlx_sqlite_observer::ObservableWriteGuard;
+use sqlx_sqlite_observer::{ObservableAttachedWriteGuard, ObservableWriteGuard};
 
-use crate::wrapper::WriterGuard;
+use crate::wrapper::{AttachedWriterGuard, WriterGuard};
 use crate::{Error, Result, WriteQueryResult};
 
-/// Wrapper around WriteGuard, ObservableWriteGuard, or AttachedWriteGuard
-/// to unify transaction handling.
+/// Wrapper around WriteGuard, AttachedWriteGuard, or their observable
+/// counterparts to unify transaction handling.
 pub enum TransactionWriter {
    Regular(WriteGuard),
    Attached(AttachedWriteGuard),
    #[cfg(feature = "observer")]
    Observable(ObservableWriteGuard),
+   #[cfg(feature = "observer")]
+   ObservableAttached(ObservableAttachedWriteGuard),
 }
 
 impl TransactionWriter {
@@ -39,6 +42,8 @@ impl TransactionWriter {
          Self::Attached(w) => query.execute(&mut **w).await.map_err(Into::into),
          #[cfg(feature = "observer")]
          Self::Observable(w) => query.execute(&mut **w).await.map_err(Into::into),
+         #[cfg(feature = "observer")]
+         Self::ObservableAttached(w) => query.execute(&mut **w).await.map_err(Into::into),
       }
    }
 
@@ -52,6 +57,8 @@ impl TransactionWriter {
          Self::Attached(w) => query.fetch_all(&mut **w).await.map_err(Into::into),
          #[cfg(feature = "observer")]
          Self::Observable(w) => query.fetch_all(&mut **w).await.map_err(Into::into),
+         #[cfg(feature = "observer")]
+         Self::ObservableAttached(w) => query.fetch_all(&mut **w).await.map_err(Into::into),
       }
    }
 
@@ -73,10 +80,23 @@ impl TransactionWriter {
       Ok(())
    }
 
+   /// Observation broker buffering this writer's changes, if observed
+   #[cfg(feature = "observer")]
+   fn broker(&self) -> Option<&Arc<sqlx_sqlite_observer::ObservationBroker>> {
+      match self {
+         Self::Observable(w) => Some(w.broker()),
+         Self::ObservableAttached(w) => Some(w.broker()),
+         _ => None,
+      }
+   }
+
    /// Detach all attached databases if this is an attached writer
    pub async fn detach_if_attached(self) -> Result<()> {
-      if let Self::Attached(w) = self {
-         w.detach_all().await?;
+      match self {
+         Self::Attached(w) => w.detach_all().await?,
+         #[cfg(feature = "observer")]
+         Self::ObservableAttached(w) => w.detach_all().await.map_err(Error::Observer)?,
+         _ => {}
       }
       Ok(())
    }
@@ -92,6 +112,16 @@ impl From<WriterGuard> for TransactionWriter {
    }
 }
 
+impl From<AttachedWriterGuard> for TransactionWriter {
+   fn from(guard: AttachedWriterGuard) -> Self {
+      match guard {
+         AttachedWriterGuard::Regular(w) => TransactionWriter::Attached(w),
+         #[cfg(feature = "observer")]
+         AttachedWriterGuard::Observable(w) => TransactionWriter::ObservableAttached(w),
+      }
+   }
+}
+
 /// Active transaction state holding the writer and metadata
 #[must_use = "if unused, the transaction is immediately rolled back"]
 pub struct ActiveInterruptibleTransaction {
@@ -99,15 +129,28 @@ pub struct ActiveInterruptibleTransaction {
    transaction_id: String,
    writer: Option<TransactionWriter>,
    created_at: Instant,
+   /// Last time a statement ran on this transaction, for the idle timeout
+   last_activity: Instant,
+   /// Quoted names of dropped `SavepointGuard`s, rolled back before the next operation
+   pending_savepoint_rollbacks: Vec<String>,
+   /// Broker buffer position of each open savepoint, innermost last, keyed by
+   /// lowercased quoted name
+   #[cfg(feature = "observer")]
+   savepoint_marks: Vec<(String, sqlx_sqlite_observer::BufferMark)>,
 }
 
 impl ActiveInterruptibleTransaction {
    pub fn new(db_path: String, transaction_id: String, writer: TransactionWriter) -> Self {
+      let now = Instant::now();
       Self {
          db_path,
          transaction_id,
          writer: Some(writer),
-         created_at: Instant::now(),
+         created_at: now,
+         last_activity: now,
+         pending_savepoint_rollbacks: Vec::new(),
+         #[cfg(feature = "observer")]
+         savepoint_marks: Vec::new(),
       }
    }
 
@@ -122,6 +165,75 @@ impl ActiveInterruptibleTransaction {
       self.writer.take().ok_or(Error::TransactionAlreadyFinalized)
    }
 
+   /// Roll back savepoints whose guards were dropped without being committed.
+   ///
+   /// `Drop` cannot run async statements, so a dropped `SavepointGuard` only queues
+   /// its rollback; it is applied here before the transaction is used again.
+   async fn apply_pending_savepoint_rollbacks(&mut self) -> Result<()> {
+      if self.pending_savepoint_rollbacks.is_empty() {
+         return Ok(());
+      }
+      let pending = std::mem::take(&mut self.pending_savepoint_rollbacks);
+      for name in pending {
+         self
+            .writer_mut()?
+            .execute_query(sqlx::query(&format!("ROLLBACK TO SAVEPOINT {name}")))
+            .await?;
+         self.rollback_savepoint_mark(&name);
+         self
+            .writer_mut()?
+            .execute_query(sqlx::query(&format!("RELEASE SAVEPOINT {name}")))
+            .await?;
+         self.release_savepoint_mark(&name);
+      }
+      Ok(())
+   }
+
+   /// Record where the observer's buffer ends as savepoint `quoted` opens
+   fn mark_savepoint(&mut self, quoted: &str) {
+      #[cfg(feature = "observer")]
+      if let Some(broker) = self.writer.as_ref().and_then(TransactionWriter::broker) {
+         let mark = broker.savepoint_mark();
+         self
+            .savepoint_marks
+            .push((quoted.to_ascii_lowercase(), mark));
+      }
+      #[cfg(not(feature = "observer"))]
+      let _ = quoted;
+   }
+
+   /// Drop the observed changes undone by `ROLLBACK TO` savepoint `quoted`, so
+   /// they are not published on commit
+   ///
+   /// The savepoint stays open; savepoints opened after it are discarded.
+   fn rollback_savepoint_mark(&mut self, quoted: &str) {
+      #[cfg(feature = "observer")]
+      {
+         let key = quoted.to_ascii_lowercase();
+         if let Some(index) = self.savepoint_marks.iter().rposition(|(k, _)| *k == key) {
+            if let Some(broker) = self.writer.as_ref().and_then(TransactionWriter::broker) {
+               broker.rollback_to_mark(self.savepoint_marks[index].1);
+            }
+            self.savepoint_marks.truncate(index + 1);
+         }
+      }
+      #[cfg(not(feature = "observer"))]
+      let _ = quoted;
+   }
+
+   /// Forget the marks of savepoint `quoted` and those opened after it, once released
+   fn release_savepoint_mark(&mut self, quoted: &str) {
+      #[cfg(feature = "observer")]
+      {
+         let key = quoted.to_ascii_lowercase();
+         if let Some(index) = self.savepoint_marks.iter().rposition(|(k, _)| *k == key) {
+            self.savepoint_marks.truncate(index);
+         }
+      }
+      #[cfg(not(feature = "observer"))]
+      let _ = quoted;
+   }
+
    pub fn db_path(&self) -> &str {
       &self.db_path
    }
@@ -141,6 +253,8 @@ impl ActiveInterruptibleTransaction {
          q = crate::wrapper::bind_value(q, value);
       }
 
+      self.apply_pending_savepoint_rollbacks().await?;
+      self.last_activity = Instant::now();
       let rows = self.writer_mut()?.fetch_all(q).await?;
 
       let mut results = Vec::new();
@@ -148,7 +262,7 @@ impl ActiveInterruptibleTransaction {
          let mut value = IndexMap::default();
          for (i, column) in row.columns().iter().enumerate() {
             let v = row.try_get_raw(i)?;
-            let v = crate::decode::to_json(v)?;
+            let v = crate::decode::to_json(v, crate::decode::DecodeMode::JsonLossy)?;
             value.insert(column.name().to_string(), v);
          }
          results.push(value);
@@ -164,6 +278,8 @@ impl ActiveInterruptibleTransaction {
       &mut self,
       statements: I,
    ) -> Result<Vec<WriteQueryResult>> {
+      self.apply_pending_savepoint_rollbacks().await?;
+      self.last_activity = Instant::now();
       let mut results = Vec::new();
       let writer = self.writer_mut()?;
       for statement in statements {
@@ -181,8 +297,82 @@ impl ActiveInterruptibleTransaction {
       Ok(results)
    }
 
+   /// Open a savepoint scoped to the returned guard.
+   ///
+   /// Statements run through the guard (which derefs to this transaction) can be
+   /// kept with `SavepointGuard::commit()`, which releases the savepoint. If the
+   /// guard is dropped without being committed — for example because a sub-step
+   /// returned early with an error — the savepoint is rolled back, undoing only the
+   /// changes made since it was opened. The outer transaction stays open. With
+   /// observation enabled, the undone changes are not published on commit.
+   ///
+   /// Scopes nest: call `savepoint_scope()` on a guard to open an inner savepoint.
+   pub async fn savepoint_scope(&mut self, name: &str) -> Result<SavepointGuard<'_>> {
+      self.apply_pending_savepoint_rollbacks().await?;
+      let name = crate::wrapper::quote_name(name);
+      self
+         .writer_mut()?
+         .execute_query(sqlx::query(&format!("SAVEPOINT {name}")))
+         .await?;
+      self.mark_savepoint(&name);
+      Ok(SavepointGuard {
+         tx: self,
+         name,
+         finished: false,
+      })
+   }
+
+   /// Create a savepoint named `name` within this transaction.
+   ///
+   /// Unlike [`savepoint_scope`](Self::savepoint_scope), the savepoint is not
+   /// tied to a guard: it stays open until released with
+   /// [`release_savepoint`](Self::release_savepoint) or the transaction ends.
+   /// Names must be plain identifiers (`[a-zA-Z_][a-zA-Z0-9_]*`), otherwise
+   /// [`Error::InvalidSavepointName`] is returned.
+   pub async fn savepoint(&mut self, name: &str) -> Result<()> {
+      self.run_savepoint_statement("SAVEPOINT", name).await?;
+      self.mark_savepoint(&crate::wrapper::quote_name(name));
+      Ok(())
+   }
+
+   /// Release the savepoint `name`, keeping its changes as part of the transaction.
+   ///
+   /// Savepoints created after it are released too.
+   pub async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+      self
+         .run_savepoint_statement("RELEASE SAVEPOINT", name)
+         .await?;
+      self.release_savepoint_mark(&crate::wrapper::quote_name(name));
+      Ok(())
+   }
+
+   /// Undo every change made since the savepoint `name` was created.
+   ///
+   /// The savepoint itself stays open, so it can be rolled back to again or
+   /// released; savepoints created after it are discarded. With observation
+   /// enabled, the undone changes are not published when the transaction commits.
+   pub async fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+      self
+         .run_savepoint_statement("ROLLBACK TO SAVEPOINT", name)
+         .await?;
+      self.rollback_savepoint_mark(&crate::wrapper::quote_name(name));
+      Ok(())
+   }
+
+   async fn run_savepoint_statement(&mut self, command: &str, name: &str) -> Result<()> {
+      validate_savepoint_name(name)?;
+      self.apply_pending_savepoint_rollbacks().await?;
+      self.last_activity = Instant::now();
+      self
+         .writer_mut()?
+         .execute_query(sqlx::query(&format!("{command} {name}")))
+         .await?;
+      Ok(())
+   }
+
    /// Commit this transaction
    pub async fn commit(mut self) -> Result<()> {
+      self.apply_pending_savepoint_rollbacks().await?;
       let mut writer = self.take_writer()?;
       writer.commit().await?;
 
@@ -208,6 +398,84 @@ impl ActiveInterruptibleTransaction {
    }
 }
 
+/// Reject savepoint names that are not plain identifiers, so they can be put
+/// into SQL unquoted
+fn validate_savepoint_name(name: &str) -> Result<()> {
+   let mut chars = name.chars();
+   let valid_start = chars
+      .next()
+      .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+   if valid_start && chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+      Ok(())
+   } else {
+      Err(Error::InvalidSavepointName {
+         name: name.to_string(),
+      })
+   }
+}
+
+/// A savepoint within an `ActiveInterruptibleTransaction`, rolled back on drop
+/// unless committed.
+///
+/// Created by `ActiveInterruptibleTransaction::savepoint_scope()`. Derefs to the
+/// transaction, so `read()`, `continue_with()` and nested `savepoint_scope()` calls
+/// can be made through the guard.
+#[must_use = "if unused, the savepoint is immediately rolled back"]
+pub struct SavepointGuard<'a> {
+   tx: &'a mut ActiveInterruptibleTransaction,
+   name: String,
+   finished: bool,
+}
+
+impl SavepointGuard<'_> {
+   /// Release the savepoint, keeping its changes as part of the outer transaction
+   pub async fn commit(mut self) -> Result<()> {
+      self.tx.apply_pending_savepoint_rollbacks().await?;
+      let sql = format!("RELEASE SAVEPOINT {}", self.name);
+      self
+         .tx
+         .writer_mut()?
+         .execute_query(sqlx::query(&sql))
+         .await?;
+      self.tx.release_savepoint_mark(&self.name);
+      self.finished = true;
+      Ok(())
+   }
+
+   /// Roll back to the savepoint immediately, undoing its changes
+   pub async fn rollback(mut self) -> Result<()> {
+      self.finished = true;
+      self.tx.pending_savepoint_rollbacks.push(self.name.clone());
+      self.tx.apply_pending_savepoint_rollbacks().await
+   }
+}
+
+impl Deref for SavepointGuard<'_> {
+   type Target = ActiveInterruptibleTransaction;
+
+   fn deref(&self) -> &Self::Target {
+      self.tx
+   }
+}
+
+impl DerefMut for SavepointGuard<'_> {
+   fn deref_mut(&mut self) -> &mut Self::Target {
+      self.tx
+   }
+}
+
+impl Drop for SavepointGuard<'_> {
+   fn drop(&mut self) {
+      if !self.finished {
+         debug!(
+            "Dropping savepoint {} for db: {} (will roll back)",
+            self.name, self.tx.db_path
+         );
+         self.tx.pending_savepoint_rollbacks.push(self.name.clone());
+      }
+   }
+}
+
 /// Statement in a transaction with query and bind values
 #[derive(Debug, Deserialize)]
 pub struct Statement {
@@ -244,14 +512,23 @@ impl Drop for ActiveInterruptibleTransaction {
    }
 }
 
-/// Default transaction timeout (5 minutes).
+/// Default transaction idle timeout (5 minutes).
 const DEFAULT_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(300);
 
 /// Global state tracking all active interruptible transactions.
 ///
-/// Enforces one interruptible transaction per database path and applies a configurable
-/// timeout. Expired transactions are cleaned up lazily on the next `insert()` or
-/// `remove()` call — no background task is needed.
+/// Enforces one interruptible transaction per database path and applies
+/// configurable timeouts. Expired transactions are cleaned up lazily on the next
+/// `insert()` or `remove()` call — no background task is needed.
+///
+/// Two timeouts apply:
+///
+/// - The **idle timeout** (default 5 minutes) is measured from the last
+///   `read()`/`continue_with()` call, so a transaction doing steady work is never
+///   rolled back; only one left unattended is.
+/// - The optional **total timeout** caps a transaction's age regardless of
+///   activity, for callers that want a hard limit on how long the write lock can
+///   be held.
 ///
 /// Uses `Mutex` rather than `RwLock` because all operations require write access,
 /// and `Mutex<T>` only requires `T: Send` (not `T: Sync`) — avoiding an
@@ -260,7 +537,8 @@ const DEFAULT_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(300);
 #[derive(Clone)]
 pub struct ActiveInterruptibleTransactions {
    inner: Arc<Mutex<HashMap<String, ActiveInterruptibleTransaction>>>,
-   timeout: Duration,
+   idle_timeout: Duration,
+   total_timeout: Option<Duration>,
 }
 
 impl Default for ActiveInterruptibleTransactions {
@@ -270,11 +548,42 @@ impl Default for ActiveInterruptibleTransactions {
 }
 
 impl ActiveInterruptibleTransactions {
-   /// Create a new instance with the given transaction timeout.
-   pub fn new(timeout: Duration) -> Self {
+   /// Create a new instance with the given idle timeout and no total timeout.
+   pub fn new(idle_timeout: Duration) -> Self {
       Self {
          inner: Arc::new(Mutex::new(HashMap::new())),
-         timeout,
+         idle_timeout,
+         total_timeout: None,
+      }
+   }
+
+   /// Set how long a transaction may go without activity before it is rolled back.
+   pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+      self.idle_timeout = timeout;
+      self
+   }
+
+   /// Set the maximum age of a transaction, however active it is.
+   pub fn with_total_timeout(mut self, timeout: Duration) -> Self {
+      self.total_timeout = Some(timeout);
+      self
+   }
+
+   /// Check a transaction against both timeouts, returning a description of the
+   /// one exceeded (for logging), if any.
+   fn expiry(&self, tx: &ActiveInterruptibleTransaction) -> Option<String> {
+      let idle = tx.last_activity.elapsed();
+      if idle >= self.idle_timeout {
+         return Some(format!(
+            "idle for {idle:?}, idle timeout: {:?}",
+            self.idle_timeout
+         ));
+      }
+
+      let age = tx.created_at.elapsed();
+      match self.total_timeout {
+         Some(total) if age >= total => Some(format!("age: {age:?}, total timeout: {total:?}")),
+         _ => None,
       }
    }
 
@@ -290,12 +599,10 @@ impl ActiveInterruptibleTransactions {
          Entry::Occupied(mut e) => {
             // If the existing transaction has expired, drop it (auto-rollback) and
             // replace with the new one.
-            if e.get().created_at.elapsed() >= self.timeout {
+            if let Some(reason) = self.expiry(e.get()) {
                warn!(
-                  "Evicting expired transaction for db: {} (age: {:?}, timeout: {:?})",
-                  db_path,
-                  e.get().created_at.elapsed(),
-                  self.timeout,
+                  "Evicting expired transaction for db: {} ({})",
+                  db_path, reason
                );
                // Drop the expired transaction (auto-rollback) before inserting the new one
                let _expired = e.insert(tx);
@@ -326,8 +633,8 @@ impl ActiveInterruptibleTransactions {
    /// Remove and return transaction for commit/rollback.
    ///
    /// Returns `Err(Error::TransactionTimedOut)` if the transaction has exceeded the
-   /// configured timeout. The expired transaction is dropped (auto-rolled-back) in
-   /// that case.
+   /// idle or total timeout. The expired transaction is dropped (auto-rolled-back)
+   /// in that case.
    pub async fn remove(
       &self,
       db_path: &str,
@@ -345,13 +652,8 @@ impl ActiveInterruptibleTransactions {
       }
 
       // Check if the transaction has expired
-      if tx.created_at.elapsed() >= self.timeout {
-         warn!(
-            "Transaction timed out for db: {} (age: {:?}, timeout: {:?})",
-            db_path,
-            tx.created_at.elapsed(),
-            self.timeout,
-         );
+      if let Some(reason) = self.expiry(tx) {
+         warn!("Transaction timed out for db: {} ({})", db_path, reason);
          // Drop the expired transaction (auto-rollback via Drop)
          txs.remove(db_path);
          return Err(Error::TransactionTimedOut(db_path.to_string()));
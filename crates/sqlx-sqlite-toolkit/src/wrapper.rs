@@ -1,15 +1,22 @@
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use sqlx::sqlite::SqliteConnection;
-use sqlx_sqlite_conn_mgr::{SqliteDatabase, SqliteDatabaseConfig, WriteGuard};
+use sqlx_sqlite_conn_mgr::{
+   AttachedSpec, AttachedWriteGuard, SqliteDatabase, SqliteDatabaseConfig, WriteGuard,
+};
 
 #[cfg(feature = "observer")]
-use sqlx_sqlite_observer::{ObservableSqliteDatabase, ObservableWriteGuard, ObserverConfig};
+use sqlx_sqlite_observer::{
+   ObservableAttachedWriteGuard, ObservableSqliteDatabase, ObservableWriteGuard, ObserverConfig,
+};
 
 use crate::Error;
+use crate::decode::DecodeOptions;
+use crate::migrations::MigrationGate;
 
 /// Result returned from write operations (e.g. INSERT, UPDATE, DELETE).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +30,34 @@ pub struct WriteQueryResult {
    pub last_insert_id: i64,
 }
 
+/// Result of one statement run by [`DatabaseWrapper::execute_script_collecting`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptStatementResult {
+   /// The number of rows changed by the statement (0 for DDL and plain SELECTs).
+   pub rows_affected: u64,
+   /// The connection's last inserted ROWID after the statement ran.
+   pub last_insert_id: i64,
+   /// Rows produced by statements that return columns (SELECT, `RETURNING`,
+   /// some PRAGMAs); `None` for statements without result columns.
+   pub rows: Option<Vec<indexmap::IndexMap<String, JsonValue>>>,
+}
+
+/// One value of a row fetched by [`DatabaseWrapper::fetch_all_with_types`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnCell {
+   /// Column name, after the wrapper's `key_case` conversion.
+   pub name: String,
+   /// The value, decoded as `fetch_all` would.
+   pub value: JsonValue,
+   /// Type sqlx derives from the column's declared type (`"INTEGER"`, `"REAL"`,
+   /// `"TEXT"`, `"BLOB"`, `"NUMERIC"`, `"BOOLEAN"`, `"DATE"`, `"TIME"`,
+   /// `"DATETIME"`), or `None` for expressions without one.
+   pub declared_type: Option<String>,
+   /// Storage class of this value: `"NULL"`, `"INTEGER"`, `"REAL"`, `"TEXT"`
+   /// or `"BLOB"`. Tells `1` apart from `1.0`, which both decode to a JSON number.
+   pub sqlite_type: String,
+}
+
 /// Unified writer guard that routes through observer when enabled.
 ///
 /// Derefs to `SqliteConnection` so it can be used with `sqlx::query().execute()`.
@@ -56,6 +91,99 @@ impl DerefMut for WriterGuard {
    }
 }
 
+impl WriterGuard {
+   /// Close the connection when this guard is dropped instead of returning it
+   /// to the pool.
+   pub fn close_on_drop(&mut self) {
+      match self {
+         WriterGuard::Regular(w) => w.close_on_drop(),
+         #[cfg(feature = "observer")]
+         WriterGuard::Observable(w) => w.close_on_drop(),
+      }
+   }
+}
+
+/// Writer with a `with_pragma` setting applied, closed on drop unless the
+/// setting was restored.
+///
+/// Covers the scope's future being dropped before it finishes, so a
+/// connection with the temporary setting never goes back to the pool.
+struct PragmaScope {
+   writer: WriterGuard,
+   restored: bool,
+}
+
+impl Drop for PragmaScope {
+   fn drop(&mut self) {
+      if !self.restored {
+         self.writer.close_on_drop();
+      }
+   }
+}
+
+/// Read connection with `case_sensitive_like` applied by `search_like`, closed
+/// on drop unless the setting was reset.
+///
+/// Like [`PragmaScope`], keeps a case-sensitive connection out of the read pool
+/// if the search future is dropped before the reset runs.
+struct CaseSensitiveLikeScope {
+   conn: sqlx::pool::PoolConnection<sqlx::Sqlite>,
+   restored: bool,
+}
+
+impl Drop for CaseSensitiveLikeScope {
+   fn drop(&mut self) {
+      if !self.restored {
+         self.conn.close_on_drop();
+      }
+   }
+}
+
+/// Unified attached-writer guard that routes through observer when enabled.
+///
+/// Derefs to `SqliteConnection`. Call [`detach_all()`](Self::detach_all) when done.
+pub enum AttachedWriterGuard {
+   /// Regular attached writer from the connection manager.
+   Regular(AttachedWriteGuard),
+   /// Observable attached writer that tracks changes to main and attached schemas.
+   #[cfg(feature = "observer")]
+   Observable(ObservableAttachedWriteGuard),
+}
+
+impl AttachedWriterGuard {
+   /// Detach all attached databases and release the writer.
+   pub async fn detach_all(self) -> Result<(), Error> {
+      match self {
+         AttachedWriterGuard::Regular(w) => w.detach_all().await?,
+         #[cfg(feature = "observer")]
+         AttachedWriterGuard::Observable(w) => w.detach_all().await.map_err(Error::Observer)?,
+      }
+      Ok(())
+   }
+}
+
+impl Deref for AttachedWriterGuard {
+   type Target = SqliteConnection;
+
+   fn deref(&self) -> &Self::Target {
+      match self {
+         AttachedWriterGuard::Regular(w) => w,
+         #[cfg(feature = "observer")]
+         AttachedWriterGuard::Observable(w) => w,
+      }
+   }
+}
+
+impl DerefMut for AttachedWriterGuard {
+   fn deref_mut(&mut self) -> &mut Self::Target {
+      match self {
+         AttachedWriterGuard::Regular(w) => &mut *w,
+         #[cfg(feature = "observer")]
+         AttachedWriterGuard::Observable(w) => &mut *w,
+      }
+   }
+}
+
 /// Wrapper around SqliteDatabase that provides a high-level API for database operations.
 ///
 /// This struct is the main entry point for interacting with SQLite databases through
@@ -67,6 +195,11 @@ impl DerefMut for WriterGuard {
 #[derive(Clone)]
 pub struct DatabaseWrapper {
    inner: Arc<SqliteDatabase>,
+   max_result_rows: Option<usize>,
+   max_transaction_statements: Option<usize>,
+   decode_options: DecodeOptions,
+   /// Set by `with_migrations`; queries fail until it reports completion
+   migrations: Option<Arc<MigrationGate>>,
    #[cfg(feature = "observer")]
    observer: Option<ObservableSqliteDatabase>,
 }
@@ -85,11 +218,47 @@ impl DatabaseWrapper {
       &self.inner
    }
 
+   /// Build an `AttachedSpec` for attaching this database to another one's queries.
+   ///
+   /// Clones the inner `Arc` and validates `schema_name` now, returning
+   /// `sqlx_sqlite_conn_mgr::Error::InvalidSchemaName` (as
+   /// [`Error::ConnectionManager`]) instead of failing when the spec is used.
+   /// The spec can be passed to `.attach()` on any builder or transaction.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(
+   /// #    main: &sqlx_sqlite_toolkit::DatabaseWrapper,
+   /// #    archive: &sqlx_sqlite_toolkit::DatabaseWrapper,
+   /// # ) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// use sqlx_sqlite_toolkit::AttachedMode;
+   ///
+   /// let rows = main
+   ///    .fetch_all("SELECT * FROM archive.posts".into(), vec![])
+   ///    .attach(vec![archive.as_attached("archive", AttachedMode::ReadOnly)?])
+   ///    .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub fn as_attached(
+      &self,
+      schema_name: &str,
+      mode: sqlx_sqlite_conn_mgr::AttachedMode,
+   ) -> Result<sqlx_sqlite_conn_mgr::AttachedSpec, Error> {
+      Ok(sqlx_sqlite_conn_mgr::AttachedSpec::new(
+         Arc::clone(&self.inner),
+         schema_name,
+         mode,
+      )?)
+   }
+
    /// Acquire a writer guard.
    ///
    /// When observation is enabled, returns an observable writer that tracks
    /// changes via SQLite hooks. Otherwise, returns a regular writer.
    pub async fn acquire_writer(&self) -> Result<WriterGuard, Error> {
+      self.check_migrations()?;
       #[cfg(feature = "observer")]
       if let Some(ref observable) = self.observer {
          let writer = observable.acquire_writer().await.map_err(Error::Observer)?;
@@ -99,12 +268,35 @@ impl DatabaseWrapper {
       Ok(WriterGuard::Regular(self.inner.acquire_writer().await?))
    }
 
+   /// Acquire a writer guard with attached database(s).
+   ///
+   /// When observation is enabled, hooks are registered on the attached writer so
+   /// changes to attached schemas are also published, tagged with their schema name.
+   pub async fn acquire_writer_with_attached(
+      &self,
+      specs: Vec<AttachedSpec>,
+   ) -> Result<AttachedWriterGuard, Error> {
+      self.check_migrations()?;
+      #[cfg(feature = "observer")]
+      if let Some(ref observable) = self.observer {
+         let writer = observable
+            .acquire_writer_with_attached(specs)
+            .await
+            .map_err(Error::Observer)?;
+         return Ok(AttachedWriterGuard::Observable(writer));
+      }
+
+      let writer = sqlx_sqlite_conn_mgr::acquire_writer_with_attached(&self.inner, specs).await?;
+      Ok(AttachedWriterGuard::Regular(writer))
+   }
+
    /// Acquire a regular (non-observable) writer connection.
    ///
    /// This always bypasses the observer, even when observation is enabled.
    /// Useful when you need a writer for operations that should not trigger
    /// change notifications (e.g., internal bookkeeping).
    pub async fn acquire_regular_writer(&self) -> Result<WriteGuard, Error> {
+      self.check_migrations()?;
       Ok(self.inner.acquire_writer().await?)
    }
 
@@ -160,12 +352,91 @@ impl DatabaseWrapper {
       custom_config: Option<SqliteDatabaseConfig>,
    ) -> Result<Self, Error> {
       let db = SqliteDatabase::connect(abs_path, custom_config).await?;
+      Ok(Self::from_database(db))
+   }
+
+   /// Connect to an existing SQLite database without write access.
+   ///
+   /// See [`SqliteDatabase::connect_readonly`]: the file is never created and
+   /// every write (`execute`, transactions, migrations) fails with
+   /// `READ_ONLY_DATABASE`.
+   pub async fn connect_readonly(
+      abs_path: &std::path::Path,
+      custom_config: Option<SqliteDatabaseConfig>,
+   ) -> Result<Self, Error> {
+      let db = SqliteDatabase::connect_readonly(abs_path, custom_config).await?;
+      Ok(Self::from_database(db))
+   }
 
-      Ok(Self {
+   fn from_database(db: Arc<SqliteDatabase>) -> Self {
+      Self {
          inner: db,
+         max_result_rows: None,
+         max_transaction_statements: None,
+         decode_options: DecodeOptions::default(),
+         migrations: None,
          #[cfg(feature = "observer")]
          observer: None,
-      })
+      }
+   }
+
+   /// Run `migrator` in the background, refusing queries until it completes.
+   ///
+   /// Gives direct toolkit users the guarantee the plugin gets from awaiting
+   /// migrations in `load`: until the migrations finish, every query and write
+   /// through this wrapper (and its clones) fails with
+   /// [`Error::MigrationsPending`], and after a failed run with
+   /// [`Error::MigrationsFailed`]. Await
+   /// [`wait_for_migrations`](Self::wait_for_migrations) before the first
+   /// query to avoid the error. Other wrappers for the same path, made by
+   /// another `connect`, are not gated.
+   ///
+   /// Must be called from within a Tokio runtime.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// # async fn example(migrator: sqlx_sqlite_toolkit::Migrator) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// use std::path::Path;
+   ///
+   /// // `migrator` from e.g. `sqlx::migrate!("./migrations")`
+   /// let db = DatabaseWrapper::connect(Path::new("/tmp/my.db"), None)
+   ///    .await?
+   ///    .with_migrations(migrator);
+   /// db.wait_for_migrations().await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub fn with_migrations(mut self, migrator: sqlx_sqlite_conn_mgr::Migrator) -> Self {
+      let gate = Arc::new(MigrationGate::new());
+      let db = Arc::clone(&self.inner);
+      let task_gate = Arc::clone(&gate);
+      tokio::spawn(async move {
+         let result = db.run_migrations(&migrator).await;
+         task_gate.finish(result.map_err(|e| e.to_string()));
+      });
+      self.migrations = Some(gate);
+      self
+   }
+
+   /// Wait for migrations started by [`with_migrations`](Self::with_migrations).
+   ///
+   /// Returns [`Error::MigrationsFailed`] if they failed. Returns immediately
+   /// when `with_migrations` was not used.
+   pub async fn wait_for_migrations(&self) -> Result<(), Error> {
+      match &self.migrations {
+         Some(gate) => gate.wait().await,
+         None => Ok(()),
+      }
+   }
+
+   /// Fail unless migrations from `with_migrations` (if any) have completed
+   pub(crate) fn check_migrations(&self) -> Result<(), Error> {
+      match &self.migrations {
+         Some(gate) => gate.check(),
+         None => Ok(()),
+      }
    }
 
    /// Create a builder for write queries (INSERT/UPDATE/DELETE).
@@ -191,6 +462,42 @@ impl DatabaseWrapper {
       crate::builders::ExecuteBuilder::new(self.clone(), query, values)
    }
 
+   /// Execute a write query with `:name`, `@name` or `$name` placeholders bound
+   /// from a map
+   ///
+   /// Keys are placeholder names without the prefix (`"id"` for `:id`); the
+   /// prefixed form is accepted too. Placeholders are rewritten to `?N`,
+   /// numbered by first appearance, so a name used several times in the query is
+   /// bound once. Keys not used by the query are ignored. Returns
+   /// [`Error::MissingNamedParameter`] when a placeholder has no value, and
+   /// [`Error::MixedParameterStyles`] when the query also has `?` or `$1`-style
+   /// placeholders.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(db: &sqlx_sqlite_toolkit::DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// use indexmap::IndexMap;
+   /// use serde_json::json;
+   ///
+   /// let params = IndexMap::from([
+   ///    ("name".to_string(), json!("Alice")),
+   ///    ("age".to_string(), json!(30)),
+   /// ]);
+   /// db.execute_named("INSERT INTO users (name, age) VALUES (:name, :age)".into(), params)
+   ///    .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn execute_named(
+      &self,
+      query: String,
+      params: indexmap::IndexMap<String, JsonValue>,
+   ) -> Result<WriteQueryResult, Error> {
+      let (query, values) = crate::params::bind_named(&query, &params)?;
+      self.execute(query, values).await
+   }
+
    /// Execute multiple statements atomically within a transaction.
    ///
    /// Returns a builder that allows attaching databases before executing the transaction.
@@ -246,6 +553,111 @@ impl DatabaseWrapper {
       values: Vec<JsonValue>,
    ) -> crate::builders::FetchAllBuilder {
       crate::builders::FetchAllBuilder::new(Arc::clone(&self.inner), query, values)
+         .migration_gate(self.migrations.clone())
+         .max_rows(self.max_result_rows)
+         .decode_options(self.decode_options.clone())
+   }
+
+   /// Run several independent SELECTs concurrently on the read pool.
+   ///
+   /// Each `(query, values)` pair runs like [`fetch_all`](Self::fetch_all) on its
+   /// own read connection, with at most as many queries in flight as the read
+   /// pool has connections, so a dashboard's unrelated reads overlap instead of
+   /// queueing. Results are returned in the order of `queries`. The queries do not
+   /// share a snapshot: a write that commits while they run may be visible to some
+   /// and not others. The first error is returned and the remaining queries are
+   /// dropped.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(db: &sqlx_sqlite_toolkit::DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// let results = db.fetch_all_parallel(vec![
+   ///     ("SELECT COUNT(*) AS n FROM users".into(), vec![]),
+   ///     ("SELECT * FROM orders ORDER BY id DESC LIMIT 10".into(), vec![]),
+   /// ]).await?;
+   /// let (user_count, recent_orders) = (&results[0], &results[1]);
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn fetch_all_parallel(
+      &self,
+      queries: Vec<(String, Vec<JsonValue>)>,
+   ) -> Result<Vec<Vec<indexmap::IndexMap<String, JsonValue>>>, Error> {
+      use futures_util::{StreamExt, TryStreamExt};
+
+      let max_in_flight = self
+         .inner
+         .read_pool()?
+         .options()
+         .get_max_connections()
+         .max(1) as usize;
+
+      futures_util::stream::iter(queries)
+         .map(|(query, values)| self.fetch_all(query, values).execute())
+         .buffered(max_in_flight)
+         .try_collect()
+         .await
+   }
+
+   /// Set a soft limit on the number of rows `fetch_all` may return.
+   ///
+   /// When set, `fetch_all` returns `Error::ResultSetTooLarge` instead of rows if a
+   /// query would return more than `limit` rows (detected by reading at most
+   /// `limit + 1`). This protects callers such as a UI from a mistyped query that
+   /// returns millions of rows. Defaults to `None` (no limit).
+   ///
+   /// Individual queries can override the limit with `FetchAllBuilder::max_rows()`.
+   pub fn set_max_result_rows(&mut self, limit: Option<usize>) {
+      self.max_result_rows = limit;
+   }
+
+   /// Get the configured `fetch_all` row limit, if any.
+   pub fn max_result_rows(&self) -> Option<usize> {
+      self.max_result_rows
+   }
+
+   /// Set a limit on the number of statements in one transaction call.
+   ///
+   /// When set, `execute_transaction` and `begin_interruptible_transaction`
+   /// return `Error::TooManyStatements` before executing anything if given more
+   /// than `limit` statements. This guards against a runaway statement list
+   /// (e.g. from untrusted frontend input) holding the write lock and memory.
+   /// Defaults to `None` (no limit).
+   pub fn set_max_transaction_statements(&mut self, limit: Option<usize>) {
+      self.max_transaction_statements = limit;
+   }
+
+   /// Get the configured per-transaction statement limit, if any.
+   pub fn max_transaction_statements(&self) -> Option<usize> {
+      self.max_transaction_statements
+   }
+
+   /// Check a statement count against `max_transaction_statements`.
+   ///
+   /// Exposed for callers that run statements on a transaction directly, such as
+   /// continuing an interruptible transaction.
+   pub fn check_transaction_statements(&self, count: usize) -> Result<(), Error> {
+      match self.max_transaction_statements {
+         Some(limit) if count > limit => Err(Error::TooManyStatements { limit }),
+         _ => Ok(()),
+      }
+   }
+
+   /// Set the default decode options for `fetch_all`, `fetch_one`, `fetch_scalar`
+   /// and `fetch_page`.
+   ///
+   /// Use this to decode `BOOLEAN` columns as JSON `true`/`false` rather than
+   /// `1`/`0`. Defaults to `DecodeOptions::default()` (no boolean conversion).
+   /// Individual queries can override the options with the builders'
+   /// `decode_options()` method.
+   pub fn set_decode_options(&mut self, options: DecodeOptions) {
+      self.decode_options = options;
+   }
+
+   /// Get the configured default decode options.
+   pub fn decode_options(&self) -> &DecodeOptions {
+      &self.decode_options
    }
 
    /// Create a builder for paginated SELECT queries using keyset (cursor-based) pagination.
@@ -313,6 +725,98 @@ impl DatabaseWrapper {
          keyset,
          page_size,
       )
+      .decode_options(self.decode_options.clone())
+      .migration_gate(self.migrations.clone())
+   }
+
+   /// Fetch one page of a SELECT by `LIMIT`/`OFFSET`, with the total row count.
+   ///
+   /// The count and the rows come from a single statement,
+   /// `SELECT *, COUNT(*) OVER() AS <alias> FROM (query) LIMIT n OFFSET m`, so
+   /// they are always consistent with each other. The alias is unique to the
+   /// call, so it cannot clash with a column of `query`, and it is removed from
+   /// the returned rows. Only when the page is empty and `offset` is past the
+   /// first row is a separate `COUNT(*)` run, since an empty page carries no
+   /// count; both statements run in one read transaction, so the count still
+   /// comes from the same snapshot.
+   ///
+   /// Put any `ORDER BY` in `query`; without one the page order is undefined.
+   /// For large tables prefer [`fetch_page`](Self::fetch_page), whose cost does
+   /// not grow with the offset. Returns [`Error::InvalidPageSize`] when `limit`
+   /// is zero.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(db: &sqlx_sqlite_toolkit::DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// let page = db
+   ///    .fetch_offset_page("SELECT * FROM posts ORDER BY id".into(), vec![], 25, 50)
+   ///    .await?;
+   /// println!("rows {}-{} of {}", page.offset + 1, page.offset + page.rows.len(), page.total);
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn fetch_offset_page(
+      &self,
+      query: String,
+      values: Vec<JsonValue>,
+      limit: usize,
+      offset: usize,
+   ) -> Result<crate::pagination::OffsetPage, Error> {
+      crate::statement::validate_single_statement(&query)?;
+      self.check_migrations()?;
+      if limit == 0 {
+         return Err(Error::InvalidPageSize);
+      }
+
+      // Without its trailing `;`, so the query can be used as a subquery
+      let base = crate::statement::split_statements(&query)
+         .first()
+         .copied()
+         .unwrap_or_default();
+      // A per-call alias cannot collide with a column of `query`. Limit and offset
+      // are inlined: `?` binds would collide with `$N` placeholders in `query`.
+      let total_column = format!("__total_{}", uuid::Uuid::new_v4().simple());
+      let sql = format!(
+         "SELECT *, COUNT(*) OVER() AS {total_column} FROM ({base}) LIMIT {limit} OFFSET {offset}"
+      );
+
+      use sqlx::{Connection, Row};
+      let mut conn = self.inner.read_pool()?.acquire().await?;
+      // Holds one read snapshot across the page and the fallback count
+      let mut tx = conn.begin().await?;
+      let mut q = sqlx::query(&sql);
+      for value in values.iter().cloned() {
+         q = bind_value(q, value);
+      }
+      let rows = crate::builders::fetch_rows_limited(&mut *tx, q, self.max_result_rows).await?;
+
+      let total: i64 = match rows.first() {
+         Some(row) => row.try_get(total_column.as_str())?,
+         None if offset > 0 => {
+            let count_sql = format!("SELECT COUNT(*) FROM ({base})");
+            let mut q = sqlx::query(&count_sql);
+            for value in values {
+               q = bind_value(q, value);
+            }
+            q.fetch_one(&mut *tx).await?.try_get(0)?
+         }
+         None => 0,
+      };
+      tx.commit().await?;
+
+      let mut rows = crate::builders::decode_rows(rows, &self.decode_options)?;
+      let total_key = self.decode_options.key_case.apply(&total_column);
+      for row in &mut rows {
+         row.shift_remove(&total_key);
+      }
+
+      Ok(crate::pagination::OffsetPage {
+         rows,
+         total: total as u64,
+         limit,
+         offset,
+      })
    }
 
    /// Create a builder for SELECT queries returning zero or one row.
@@ -342,54 +846,1239 @@ impl DatabaseWrapper {
       values: Vec<JsonValue>,
    ) -> crate::builders::FetchOneBuilder {
       crate::builders::FetchOneBuilder::new(Arc::clone(&self.inner), query, values)
+         .migration_gate(self.migrations.clone())
+         .decode_options(self.decode_options.clone())
    }
 
-   /// Run database migrations
+   /// Create a builder for SELECT queries returning a single value.
    ///
-   /// Runs all pending migrations from the provided migrator.
-   /// SQLx tracks applied migrations, so this is safe to call multiple times.
-   pub async fn run_migrations(
+   /// Returns a builder that can optionally attach databases before executing.
+   /// Resolves to the first column of the row, or `None` if no row matches.
+   /// Returns an error if the query returns more than one row.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(db: &sqlx_sqlite_toolkit::DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// let count = db
+   ///     .fetch_scalar("SELECT COUNT(*) FROM users".into(), vec![])
+   ///     .await?;
+   /// println!("{} users", count.unwrap_or_default());
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub fn fetch_scalar(
       &self,
-      migrator: &sqlx_sqlite_conn_mgr::Migrator,
-   ) -> Result<(), Error> {
-      self.inner.run_migrations(migrator).await?;
-      Ok(())
+      query: String,
+      values: Vec<JsonValue>,
+   ) -> crate::builders::FetchScalarBuilder {
+      crate::builders::FetchScalarBuilder::new(Arc::clone(&self.inner), query, values)
+         .migration_gate(self.migrations.clone())
+         .decode_options(self.decode_options.clone())
    }
 
-   /// Close the database connection.
+   /// Fetch all rows of a SELECT, decoding each into `T` with its `sqlx::FromRow` impl.
    ///
-   /// Checkpoints the WAL and closes all connection pools.
-   /// If observation is enabled, it is disabled first to unregister SQLite hooks
-   /// and allow the write connection to close cleanly.
-   pub async fn close(mut self) -> Result<(), Error> {
-      #[cfg(feature = "observer")]
-      self.disable_observation();
+   /// Unlike `fetch_all`, rows are not converted to JSON, so decode options do not
+   /// apply and fields decode with sqlx's own type mapping. With the `chrono`
+   /// feature, `chrono::DateTime<Utc>` fields read ISO-8601 TEXT columns directly.
+   /// Honors `max_result_rows`.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(db: &sqlx_sqlite_toolkit::DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// let names: Vec<(i64, String)> = db
+   ///     .fetch_all_as("SELECT id, name FROM users".into(), vec![])
+   ///     .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn fetch_all_as<T>(
+      &self,
+      query: String,
+      values: Vec<JsonValue>,
+   ) -> Result<Vec<T>, Error>
+   where
+      T: for<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + Send + Unpin,
+   {
+      crate::statement::validate_single_statement(&query)?;
+      self.check_migrations()?;
 
-      self.inner.close().await?;
-      Ok(())
+      let pool = self.inner.read_pool()?;
+      let mut q = sqlx::query(&query);
+      for value in values {
+         q = bind_value(q, value);
+      }
+      let rows = crate::builders::fetch_rows_limited(pool, q, self.max_result_rows).await?;
+      Ok(rows.iter().map(T::from_row).collect::<Result<_, _>>()?)
    }
 
-   /// Close the database connection and remove all database files.
+   /// Fetch zero or one row of a SELECT, decoded into `T` with its `sqlx::FromRow` impl.
    ///
-   /// Removes the main database file, WAL, and SHM files.
-   /// If observation is enabled, it is disabled first to unregister SQLite hooks
-   /// and allow the write connection to close cleanly.
-   pub async fn remove(mut self) -> Result<(), Error> {
-      #[cfg(feature = "observer")]
-      self.disable_observation();
+   /// The typed counterpart of `fetch_one`: returns `Error::MultipleRowsReturned`
+   /// if the query returns more than one row. Like
+   /// [`fetch_all_as`](Self::fetch_all_as), decode options do not apply.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(db: &sqlx_sqlite_toolkit::DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// use serde_json::json;
+   ///
+   /// let user: Option<(i64, String)> = db
+   ///     .fetch_one_as("SELECT id, name FROM users WHERE id = ?".into(), vec![json!(1)])
+   ///     .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn fetch_one_as<T>(
+      &self,
+      query: String,
+      values: Vec<JsonValue>,
+   ) -> Result<Option<T>, Error>
+   where
+      T: for<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + Send + Unpin,
+   {
+      crate::statement::validate_single_statement(&query)?;
+      self.check_migrations()?;
 
-      self.inner.remove().await?;
-      Ok(())
+      let pool = self.inner.read_pool()?;
+      let mut q = sqlx::query(&query);
+      for value in values {
+         q = bind_value(q, value);
+      }
+      let rows = q.fetch_all(pool).await?;
+      match rows.as_slice() {
+         [] => Ok(None),
+         [row] => Ok(Some(T::from_row(row)?)),
+         _ => Err(Error::MultipleRowsReturned(rows.len())),
+      }
    }
 
-   /// Enable observation on this database for the specified tables.
+   /// Fetch all rows of a SELECT with each value's type next to it.
    ///
-   /// After calling this, write operations will be tracked and subscribers
-   /// can receive change notifications.
+   /// Each row is a list of [`ColumnCell`]s in column order, for frontends that
+   /// render by type and cannot tell from JSON whether `1` came from an INTEGER
+   /// or a REAL. Runs on the read pool and honors `max_result_rows` and the
+   /// wrapper's decode options like `fetch_all`.
    ///
-   /// If observation is already enabled, the previous observer is disabled first.
-   /// This drops the old broadcast broker, causing existing subscriber streams to
-   /// terminate. Callers must re-subscribe after re-enabling observation.
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(db: &sqlx_sqlite_toolkit::DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// let rows = db
+   ///     .fetch_all_with_types("SELECT price FROM products".into(), vec![])
+   ///     .await?;
+   /// for cell in rows.iter().flatten() {
+   ///     println!("{} = {} ({})", cell.name, cell.value, cell.sqlite_type);
+   /// }
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn fetch_all_with_types(
+      &self,
+      query: String,
+      values: Vec<JsonValue>,
+   ) -> Result<Vec<Vec<ColumnCell>>, Error> {
+      use sqlx::{Column, Row, TypeInfo, Value, ValueRef};
+
+      crate::statement::validate_single_statement(&query)?;
+      self.check_migrations()?;
+
+      let pool = self.inner.read_pool()?;
+      let mut q = sqlx::query(&query);
+      for value in values {
+         q = bind_value(q, value);
+      }
+      let rows = crate::builders::fetch_rows_limited(pool, q, self.max_result_rows).await?;
+
+      let options = &self.decode_options;
+      let mut result = Vec::with_capacity(rows.len());
+      for row in rows {
+         let mut cells = Vec::with_capacity(row.columns().len());
+         for (i, column) in row.columns().iter().enumerate() {
+            let raw = row.try_get_raw(i)?;
+            // A value's type_info is its runtime storage class unless it is NULL
+            let sqlite_type = if raw.is_null() {
+               "NULL".to_string()
+            } else {
+               raw.to_owned().type_info().name().to_string()
+            };
+            let declared_type = Some(column.type_info().name())
+               .filter(|name| *name != "NULL")
+               .map(str::to_string);
+            cells.push(ColumnCell {
+               name: options.key_case.apply(column.name()),
+               value: options.decode(raw, column)?,
+               declared_type,
+               sqlite_type,
+            });
+         }
+         result.push(cells);
+      }
+
+      Ok(result)
+   }
+
+   /// Fetch all rows of a SELECT as a JSON array of row objects.
+   ///
+   /// Same rows as [`fetch_all`](Self::fetch_all), but each row is built
+   /// directly as a `serde_json` object instead of an `IndexMap`, for callers
+   /// that only forward the result as JSON. Runs on the read pool and honors
+   /// `max_result_rows` and the wrapper's decode options.
+   ///
+   /// Object keys follow the query's column order only with the
+   /// `preserve_order` feature (which enables `serde_json/preserve_order`);
+   /// without it, `serde_json` sorts them.
+   pub async fn fetch_all_json(
+      &self,
+      query: String,
+      values: Vec<JsonValue>,
+   ) -> Result<JsonValue, Error> {
+      crate::statement::validate_single_statement(&query)?;
+      self.check_migrations()?;
+
+      let pool = self.inner.read_pool()?;
+      let mut q = sqlx::query(&query);
+      for value in values {
+         q = bind_value(q, value);
+      }
+      let rows = crate::builders::fetch_rows_limited(pool, q, self.max_result_rows).await?;
+      crate::builders::decode_rows_json(rows, &self.decode_options)
+   }
+
+   /// Fetch all rows of a SELECT using the write connection instead of the read pool.
+   ///
+   /// Reading on the writer guarantees the query observes every write already
+   /// made through this wrapper, e.g. to fetch the full row right after an INSERT.
+   /// Honors `max_result_rows` and the wrapper's decode options like `fetch_all`.
+   ///
+   /// This contends for the write lock: it waits for in-flight writes and blocks
+   /// other writers while it runs. Prefer `fetch_all` for ordinary reads.
+   pub async fn fetch_all_on_writer(
+      &self,
+      query: String,
+      values: Vec<JsonValue>,
+   ) -> Result<Vec<indexmap::IndexMap<String, JsonValue>>, Error> {
+      crate::statement::validate_single_statement(&query)?;
+
+      let mut writer = self.acquire_writer().await?;
+      let mut q = sqlx::query(&query);
+      for value in values {
+         q = bind_value(q, value);
+      }
+      let rows = crate::builders::fetch_rows_limited(&mut *writer, q, self.max_result_rows).await?;
+      crate::builders::decode_rows(rows, &self.decode_options)
+   }
+
+   /// Fetch zero or one row of a SELECT using the write connection.
+   ///
+   /// The single-row counterpart of [`fetch_all_on_writer`](Self::fetch_all_on_writer);
+   /// returns `Error::MultipleRowsReturned` if the query returns more than one row.
+   /// Contends for the write lock.
+   pub async fn fetch_one_on_writer(
+      &self,
+      query: String,
+      values: Vec<JsonValue>,
+   ) -> Result<Option<indexmap::IndexMap<String, JsonValue>>, Error> {
+      crate::statement::validate_single_statement(&query)?;
+
+      let mut writer = self.acquire_writer().await?;
+      let mut q = sqlx::query(&query);
+      for value in values {
+         q = bind_value(q, value);
+      }
+      let rows = q.fetch_all(&mut *writer).await?;
+      if rows.len() > 1 {
+         return Err(Error::MultipleRowsReturned(rows.len()));
+      }
+      Ok(crate::builders::decode_rows(rows, &self.decode_options)?
+         .into_iter()
+         .next())
+   }
+
+   /// Copy the result set of a query into a new shared in-memory database.
+   ///
+   /// Runs the query against this database and creates a fresh in-memory database
+   /// containing a single table, `new_table`, populated with the decoded rows. The
+   /// returned wrapper can be queried independently (e.g. for offline processing of
+   /// a subset) without touching the main database.
+   ///
+   /// Column types are inferred from the first row: integers and booleans become
+   /// `INTEGER`, other numbers `REAL`, strings `TEXT`, arrays/objects `TEXT` (as
+   /// JSON), and `NULL` values leave the column untyped. BLOBs arrive decoded as
+   /// base64 strings and are therefore stored as `TEXT`. Rows are decoded with the
+   /// default [`DecodeOptions`], not this wrapper's, so values and column names
+   /// are copied unchanged. All rows are inserted in a single transaction, as in
+   /// [`bulk_insert`](Self::bulk_insert).
+   ///
+   /// The in-memory database lives until the returned wrapper is closed.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # async fn example(db: &sqlx_sqlite_toolkit::DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// use serde_json::json;
+   ///
+   /// let subset = db.materialize_query(
+   ///     "SELECT id, name FROM users WHERE active = ?".into(),
+   ///     vec![json!(1)],
+   ///     "active_users",
+   /// ).await?;
+   ///
+   /// let rows = subset.fetch_all("SELECT * FROM active_users".into(), vec![]).await?;
+   /// subset.close().await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn materialize_query(
+      &self,
+      query: String,
+      values: Vec<JsonValue>,
+      new_table: &str,
+   ) -> Result<DatabaseWrapper, Error> {
+      use sqlx::{Column, Connection, Executor};
+
+      // Copy plain values under the original column names, whatever this
+      // wrapper's decode options say
+      let rows = self
+         .fetch_all(query.clone(), values)
+         .decode_options(DecodeOptions::default())
+         .await?;
+
+      // Column names come from the decoded rows; an empty result still needs a
+      // schema, so fall back to describing the statement
+      let columns: Vec<String> = match rows.first() {
+         Some(row) => row.keys().cloned().collect(),
+         None => {
+            let described = self.inner.read_pool()?.describe(&query).await?;
+            let mut names: Vec<String> = Vec::new();
+            for column in described.columns() {
+               let name = column.name().to_string();
+               if !names.contains(&name) {
+                  names.push(name);
+               }
+            }
+            names
+         }
+      };
+
+      let column_defs = columns
+         .iter()
+         .map(|name| {
+            let affinity = rows
+               .first()
+               .and_then(|row| row.get(name))
+               .map(inferred_column_type)
+               .unwrap_or("");
+            format!("{} {}", quote_name(name), affinity)
+               .trim_end()
+               .to_string()
+         })
+         .collect::<Vec<_>>()
+         .join(", ");
+
+      let uri = format!(
+         "file:materialized-{}?mode=memory&cache=shared",
+         uuid::Uuid::new_v4()
+      );
+      let target = DatabaseWrapper::connect(std::path::Path::new(&uri), None).await?;
+
+      let rows: Vec<Vec<JsonValue>> = rows
+         .into_iter()
+         .map(|mut row| {
+            columns
+               .iter()
+               .map(|column| row.swap_remove(column).unwrap_or(JsonValue::Null))
+               .collect()
+         })
+         .collect();
+      let column_names: Vec<&str> = columns.iter().map(String::as_str).collect();
+
+      let populate = async {
+         let mut writer = target.acquire_writer().await?;
+
+         sqlx::query(&format!(
+            "CREATE TABLE {} ({})",
+            quote_name(new_table),
+            column_defs
+         ))
+         .execute(&mut *writer)
+         .await?;
+
+         let mut tx = writer.begin().await?;
+         insert_rows(&mut tx, new_table, &column_names, &rows).await?;
+         tx.commit().await?;
+
+         Ok::<(), Error>(())
+      }
+      .await;
+
+      match populate {
+         Ok(()) => Ok(target),
+         Err(e) => {
+            if let Err(close_err) = target.close().await {
+               tracing::error!("closing materialized database failed: {}", close_err);
+            }
+            Err(e)
+         }
+      }
+   }
+
+   /// Check whether a table with this name exists in the main database.
+   ///
+   /// Only tables count; views, indexes and triggers of the same name do not.
+   /// The name is bound as a parameter, so it is never interpolated into SQL.
+   pub async fn table_exists(&self, table: &str) -> Result<bool, Error> {
+      self.check_migrations()?;
+      let exists: bool = sqlx::query_scalar(
+         "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+      )
+      .bind(table)
+      .fetch_one(self.inner.read_pool()?)
+      .await?;
+      Ok(exists)
+   }
+
+   /// List the column names of a table, in declaration order.
+   ///
+   /// Returns [`Error::TableNotFound`] when the table does not exist. The name is
+   /// bound as a parameter, so it is never interpolated into SQL.
+   pub async fn table_columns(&self, table: &str) -> Result<Vec<String>, Error> {
+      self.check_migrations()?;
+      let columns: Vec<String> =
+         sqlx::query_scalar("SELECT name FROM pragma_table_info(?) ORDER BY cid")
+            .bind(table)
+            .fetch_all(self.inner.read_pool()?)
+            .await?;
+
+      if columns.is_empty() {
+         return Err(Error::TableNotFound(table.to_string()));
+      }
+      Ok(columns)
+   }
+
+   /// Fetch a subtree of a self-referencing table in depth-first order.
+   ///
+   /// `parent_col` references `id_col` of the parent row. With `root_id`, the
+   /// subtree starts at that row; with `None`, it starts at every row whose
+   /// parent is `NULL`. Each row gets an extra `_depth` column (0 for the
+   /// starting rows) and is followed by its descendants, with siblings ordered
+   /// by `id_col`.
+   ///
+   /// The table and both columns must exist (checked with
+   /// [`table_columns`](Self::table_columns), which returns
+   /// [`Error::TableNotFound`] or [`Error::UnknownColumn`]), and are quoted
+   /// before being put into the query. Recursion stops at depth
+   /// `MAX_TREE_DEPTH` (1000), so a cycle in the data cannot make the query run
+   /// forever, but the rows of such a cycle repeat up to that depth; the
+   /// wrapper's `max_result_rows` limit applies as usual.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// # async fn example(db: &DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// let subtree = db
+   ///    .fetch_tree("categories", "id", "parent_id", Some(serde_json::json!(3)))
+   ///    .await?;
+   /// for row in &subtree {
+   ///    println!("{} at depth {}", row["name"], row["_depth"]);
+   /// }
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn fetch_tree(
+      &self,
+      table: &str,
+      id_col: &str,
+      parent_col: &str,
+      root_id: Option<JsonValue>,
+   ) -> Result<Vec<indexmap::IndexMap<String, JsonValue>>, Error> {
+      let columns = self.table_columns(table).await?;
+      if let Some(column) = [id_col, parent_col]
+         .into_iter()
+         .find(|column| !columns.iter().any(|c| c == column))
+      {
+         return Err(Error::UnknownColumn {
+            table: table.to_string(),
+            column: column.to_string(),
+         });
+      }
+
+      let (table, id, parent) = (
+         quote_name(table),
+         quote_name(id_col),
+         quote_name(parent_col),
+      );
+      let (start, values) = match root_id {
+         Some(root_id) => (format!("{id} = ?"), vec![root_id]),
+         None => (format!("{parent} IS NULL"), Vec::new()),
+      };
+
+      // SQLite expands queued rows in ORDER BY order; deepest first makes the
+      // queue a stack, which yields depth-first order
+      let sql = format!(
+         "WITH RECURSIVE _tree AS ( \
+            SELECT _node.*, 0 AS _depth FROM {table} AS _node WHERE _node.{start} \
+            UNION ALL \
+            SELECT _child.*, _tree._depth + 1 FROM {table} AS _child \
+            JOIN _tree ON _child.{parent} = _tree.{id} \
+            WHERE _tree._depth < {MAX_TREE_DEPTH} \
+            ORDER BY _depth DESC, {id} \
+         ) SELECT * FROM _tree"
+      );
+      self.fetch_all(sql, values).await
+   }
+
+   /// Fetch the rows of `table` whose `column` contains `term` literally.
+   ///
+   /// `term` is escaped with [`escape_like`] and bound as
+   /// `column LIKE '%term%' ESCAPE '\'`, so `%` and `_` in user input match
+   /// themselves rather than acting as wildcards. `case_sensitive` sets
+   /// `PRAGMA case_sensitive_like` on the read connection for this query only
+   /// and resets it afterwards; if the reset does not run (the future is
+   /// dropped) or fails, the connection is closed instead of returned to the
+   /// pool. Either way, case folding only covers ASCII
+   /// letters. The table and column must exist (checked with
+   /// [`table_columns`](Self::table_columns)). Honors `max_result_rows` and the
+   /// wrapper's decode options.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// # async fn example(db: &DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// // Matches "100% cotton", not "100 cotton"
+   /// let rows = db.search_like("products", "name", "100%", false).await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn search_like(
+      &self,
+      table: &str,
+      column: &str,
+      term: &str,
+      case_sensitive: bool,
+   ) -> Result<Vec<indexmap::IndexMap<String, JsonValue>>, Error> {
+      let columns = self.table_columns(table).await?;
+      if !columns.iter().any(|c| c == column) {
+         return Err(Error::UnknownColumn {
+            table: table.to_string(),
+            column: column.to_string(),
+         });
+      }
+
+      let sql = format!(
+         "SELECT * FROM {} WHERE {} LIKE ? ESCAPE '\\'",
+         quote_name(table),
+         quote_name(column)
+      );
+      let pattern = format!("%{}%", escape_like(term));
+
+      let mut scope = CaseSensitiveLikeScope {
+         conn: self.inner.read_pool()?.acquire().await?,
+         restored: false,
+      };
+      sqlx::query(if case_sensitive {
+         "PRAGMA case_sensitive_like = ON"
+      } else {
+         "PRAGMA case_sensitive_like = OFF"
+      })
+      .execute(&mut *scope.conn)
+      .await?;
+      // OFF is the default, so only a case-sensitive connection needs a reset
+      scope.restored = !case_sensitive;
+
+      let rows = crate::builders::fetch_rows_limited(
+         &mut *scope.conn,
+         sqlx::query(&sql).bind(pattern),
+         self.max_result_rows,
+      )
+      .await;
+
+      if case_sensitive {
+         scope.restored = sqlx::query("PRAGMA case_sensitive_like = OFF")
+            .execute(&mut *scope.conn)
+            .await
+            .is_ok();
+      }
+
+      crate::builders::decode_rows(rows?, &self.decode_options)
+   }
+
+   /// Fetch rows whose JSON `column` holds `value` at `path`.
+   ///
+   /// Runs `SELECT * FROM table WHERE json_extract(column, '$.path') = ?`
+   /// using SQLite's built-in JSON functions. `path` is given without the
+   /// leading `$.`, as dotted keys with optional array indexes (`address.city`,
+   /// `tags[0]`); anything else, including quotes, is rejected with
+   /// [`Error::InvalidJsonPath`]. Returns [`Error::TableNotFound`] or
+   /// [`Error::UnknownColumn`] when the table or column does not exist.
+   ///
+   /// `json_extract` returns JSON strings as TEXT and numbers as INTEGER or
+   /// REAL, so `value` compares against the unwrapped scalar at `path`.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// # use serde_json::json;
+   /// # async fn example(db: &DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// let rows = db
+   ///    .fetch_all_json_path("users", "profile", "address.city", json!("Oslo"))
+   ///    .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn fetch_all_json_path(
+      &self,
+      table: &str,
+      column: &str,
+      path: &str,
+      value: JsonValue,
+   ) -> Result<Vec<indexmap::IndexMap<String, JsonValue>>, Error> {
+      if !is_valid_json_path(path) {
+         return Err(Error::InvalidJsonPath {
+            path: path.to_string(),
+         });
+      }
+
+      let columns = self.table_columns(table).await?;
+      if !columns.iter().any(|c| c == column) {
+         return Err(Error::UnknownColumn {
+            table: table.to_string(),
+            column: column.to_string(),
+         });
+      }
+
+      let sql = format!(
+         "SELECT * FROM {} WHERE json_extract({}, '$.{}') = ?",
+         quote_name(table),
+         quote_name(column),
+         path
+      );
+
+      let rows = crate::builders::fetch_rows_limited(
+         self.inner.read_pool()?,
+         bind_value(sqlx::query(&sql), value),
+         self.max_result_rows,
+      )
+      .await?;
+
+      crate::builders::decode_rows(rows, &self.decode_options)
+   }
+
+   /// Rebuild the database file with `VACUUM`, returning free pages to the OS.
+   ///
+   /// `VACUUM` cannot run inside a transaction, so this waits for the writer,
+   /// which no interruptible or regular transaction of this wrapper holds while
+   /// it runs. The WAL is then checkpointed with `TRUNCATE`, since in WAL mode
+   /// the rebuilt pages only reach the main file once checkpointed. Needs free
+   /// disk space of up to twice the database size, and blocks writers while it
+   /// runs. Also applies a changed `auto_vacuum` mode to an existing database.
+   pub async fn vacuum(&self) -> Result<(), Error> {
+      let mut writer = self.acquire_writer().await?;
+      sqlx::query("VACUUM").execute(&mut *writer).await?;
+      sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+         .execute(&mut *writer)
+         .await?;
+      Ok(())
+   }
+
+   /// Delete every row from every user table, keeping the schema.
+   ///
+   /// **Destructive:** all data outside `preserve` is gone once this returns.
+   /// Internal `sqlite_%` and `_sqlx_%` tables (including the migration
+   /// history) and the tables named in `preserve` are left alone; FTS shadow
+   /// tables are cleared through their virtual table. All deletes run in one
+   /// transaction with `defer_foreign_keys` on, so tables can be emptied in any
+   /// order. `AUTOINCREMENT` counters of the emptied tables are reset, so new
+   /// rows start again at 1. Returns the total number of rows deleted.
+   pub async fn truncate_all(&self, preserve: &[&str]) -> Result<u64, Error> {
+      use sqlx::Connection;
+
+      let mut writer = self.acquire_writer().await?;
+      let tables: Vec<String> = sqlx::query_scalar(
+         "SELECT name FROM pragma_table_list \
+          WHERE schema = 'main' AND type IN ('table', 'virtual') \
+          AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\' \
+          AND name NOT LIKE '\\_sqlx\\_%' ESCAPE '\\' \
+          ORDER BY name",
+      )
+      .fetch_all(&mut *writer)
+      .await?;
+      let tables: Vec<String> = tables
+         .into_iter()
+         .filter(|table| !preserve.contains(&table.as_str()))
+         .collect();
+
+      let mut tx = writer.begin().await?;
+      sqlx::query("PRAGMA defer_foreign_keys = ON")
+         .execute(&mut *tx)
+         .await?;
+
+      let mut deleted = 0;
+      for table in &tables {
+         deleted += sqlx::query(&format!("DELETE FROM {}", quote_name(table)))
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+      }
+
+      let has_sequence: bool = sqlx::query_scalar(
+         "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'sqlite_sequence')",
+      )
+      .fetch_one(&mut *tx)
+      .await?;
+      if has_sequence {
+         for table in &tables {
+            sqlx::query("DELETE FROM sqlite_sequence WHERE name = ?")
+               .bind(table)
+               .execute(&mut *tx)
+               .await?;
+         }
+      }
+
+      tx.commit().await?;
+      Ok(deleted)
+   }
+
+   /// Run `f` on the writer with `PRAGMA pragma = value` set, restoring the
+   /// previous value afterwards.
+   ///
+   /// The writer is held for the whole scope and passed to `f`, so statements
+   /// in `f` must run on that connection rather than through `self` (which
+   /// would wait for the writer forever). The previous value is restored
+   /// whether or not `f` fails; if the returned future is dropped before it
+   /// finishes, or the restore itself fails, the writer connection is closed
+   /// rather than returned to the pool with the setting still applied.
+   ///
+   /// `pragma` must be one of the integer-valued settings in `SCOPED_PRAGMAS`
+   /// and `value` an integer or a keyword such as `ON`/`OFF`, otherwise
+   /// [`Error::InvalidPragma`] is returned. Note that SQLite ignores
+   /// `foreign_keys` changes inside a transaction, so set it before `f` begins
+   /// one.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// # async fn example(db: &DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// db.with_pragma("foreign_keys", "OFF", async |conn| {
+   ///    sqlx::query("INSERT INTO orders (user_id) VALUES (42)")
+   ///       .execute(&mut *conn)
+   ///       .await?;
+   ///    sqlx::query("INSERT INTO users (id) VALUES (42)")
+   ///       .execute(&mut *conn)
+   ///       .await?;
+   ///    Ok(())
+   /// })
+   /// .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn with_pragma<T>(
+      &self,
+      pragma: &str,
+      value: &str,
+      f: impl AsyncFnOnce(&mut SqliteConnection) -> Result<T, Error>,
+   ) -> Result<T, Error> {
+      validate_scoped_pragma(pragma, value)?;
+
+      let mut scope = PragmaScope {
+         writer: self.acquire_writer().await?,
+         restored: false,
+      };
+      let previous: i64 = sqlx::query_scalar(&format!("PRAGMA {pragma}"))
+         .fetch_one(&mut *scope.writer)
+         .await?;
+      sqlx::query(&format!("PRAGMA {pragma} = {value}"))
+         .execute(&mut *scope.writer)
+         .await?;
+
+      let result = f(&mut scope.writer).await;
+
+      let restored = sqlx::query(&format!("PRAGMA {pragma} = {previous}"))
+         .execute(&mut *scope.writer)
+         .await;
+      scope.restored = restored.is_ok();
+      match (result, restored) {
+         (Ok(value), Ok(_)) => Ok(value),
+         (Ok(_), Err(e)) => Err(e.into()),
+         (Err(e), restored) => {
+            if let Err(restore_err) = restored {
+               tracing::error!("restoring PRAGMA {} failed: {}", pragma, restore_err);
+            }
+            Err(e)
+         }
+      }
+   }
+
+   /// Back up the database into a new file at `dest` using SQLite's online backup API.
+   ///
+   /// Copies pages in batches on a read connection, so other readers are not blocked.
+   /// `on_progress` is called after each batch. Fails if `dest` already exists.
+   pub async fn backup(
+      &self,
+      dest: impl AsRef<std::path::Path>,
+      on_progress: impl FnMut(sqlx_sqlite_conn_mgr::BackupProgress),
+   ) -> Result<(), Error> {
+      Ok(self.inner.backup_to(dest, on_progress).await?)
+   }
+
+   /// Suggest indexes for a query by inspecting its `EXPLAIN QUERY PLAN` output.
+   ///
+   /// Flags full-table scans (`SCAN t`) of tables with at least
+   /// [`LARGE_TABLE_MIN_ROWS`](crate::diagnostics::LARGE_TABLE_MIN_ROWS) rows,
+   /// and steps where SQLite builds a transient automatic index. The query is
+   /// planned, not executed.
+   ///
+   /// This is a heuristic intended for development: a full scan is sometimes the
+   /// best plan, and the plan depends on the data and bind values supplied.
+   pub async fn suggest_indexes(
+      &self,
+      query: String,
+      values: Vec<JsonValue>,
+   ) -> Result<Vec<crate::diagnostics::IndexSuggestion>, Error> {
+      use crate::diagnostics::{IndexSuggestion, LARGE_TABLE_MIN_ROWS, PlanAccess};
+      use sqlx::Row;
+
+      crate::statement::validate_single_statement(&query)?;
+      self.check_migrations()?;
+      let mut conn = self.inner.read_pool()?.acquire().await?;
+
+      // EXPLAIN never checks the schema cookie, so it plans against whatever schema
+      // this connection last loaded. Read `sqlite_schema` first to reload it, and
+      // don't cache the EXPLAIN, so indexes created since are taken into account.
+      sqlx::query("SELECT 1 FROM sqlite_schema LIMIT 1")
+         .fetch_optional(&mut *conn)
+         .await?;
+      let explain = format!("EXPLAIN QUERY PLAN {query}");
+      let mut q = sqlx::query(&explain).persistent(false);
+      for value in values {
+         q = bind_value(q, value);
+      }
+      let plan = q.fetch_all(&mut *conn).await?;
+
+      let mut suggestions = Vec::new();
+      for step in plan {
+         let detail: String = step.try_get("detail")?;
+         let Some((table, access)) = crate::diagnostics::parse_plan_detail(&detail) else {
+            continue;
+         };
+
+         // The plan reports aliases in place of table names; only real tables
+         // can be counted.
+         let is_table: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_schema WHERE type = 'table' AND name = ?)",
+         )
+         .bind(&table)
+         .fetch_one(&mut *conn)
+         .await?;
+         let row_count = if is_table {
+            let count: i64 =
+               sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", quote_name(&table)))
+                  .fetch_one(&mut *conn)
+                  .await?;
+            Some(count as u64)
+         } else {
+            None
+         };
+
+         let message = match access {
+            PlanAccess::FullScan => {
+               if row_count.is_some_and(|n| n < LARGE_TABLE_MIN_ROWS) {
+                  continue;
+               }
+               format!(
+                  "full scan of `{table}`; consider an index on the columns it is filtered, joined or sorted by"
+               )
+            }
+            PlanAccess::AutomaticIndex => format!(
+               "SQLite builds a temporary index on `{table}` for this query; consider creating it permanently"
+            ),
+         };
+
+         suggestions.push(IndexSuggestion {
+            table,
+            plan_detail: detail,
+            row_count,
+            message,
+         });
+      }
+
+      Ok(suggestions)
+   }
+
+   /// Bulk-load a JSON Lines file into an existing table.
+   ///
+   /// Each non-blank line must be a JSON object whose keys name columns of
+   /// `table`; missing keys take the column default. The file is read line by
+   /// line and every line is validated before anything is written. Rows are then
+   /// grouped by key set and inserted as in [`bulk_insert`](Self::bulk_insert),
+   /// all in a single transaction, so a malformed line or unknown column leaves
+   /// the table unchanged. Returns the number of rows inserted.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// # async fn example(db: &DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// // seed.jsonl:
+   /// // {"name": "Alice", "age": 30}
+   /// // {"name": "Bob"}
+   /// let inserted = db.import_jsonl("users", "seed.jsonl").await?;
+   /// assert_eq!(inserted, 2);
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn import_jsonl(
+      &self,
+      table: &str,
+      src_path: impl AsRef<std::path::Path>,
+   ) -> Result<u64, Error> {
+      use sqlx::Connection;
+      use tokio::io::AsyncBufReadExt;
+
+      let columns = self.table_columns(table).await?;
+
+      let file = tokio::fs::File::open(src_path.as_ref()).await?;
+      let mut lines = tokio::io::BufReader::new(file).lines();
+
+      // Rows are grouped by their (sorted) key set, so each group can go
+      // through the multi-row insert path with a single column list
+      let mut groups: indexmap::IndexMap<Vec<String>, Vec<Vec<JsonValue>>> =
+         indexmap::IndexMap::new();
+      let mut line_number = 0;
+      while let Some(line) = lines.next_line().await? {
+         line_number += 1;
+         let line = line.trim();
+         if line.is_empty() {
+            continue;
+         }
+
+         let row: serde_json::Map<String, JsonValue> =
+            serde_json::from_str(line).map_err(|e| Error::InvalidJsonLine {
+               line: line_number,
+               message: e.to_string(),
+            })?;
+
+         if let Some(column) = row.keys().find(|key| !columns.contains(key)) {
+            return Err(Error::UnknownColumn {
+               table: table.to_string(),
+               column: column.clone(),
+            });
+         }
+
+         let mut entries: Vec<(String, JsonValue)> = row.into_iter().collect();
+         entries.sort_by(|a, b| a.0.cmp(&b.0));
+         let (keys, values): (Vec<String>, Vec<JsonValue>) = entries.into_iter().unzip();
+         groups.entry(keys).or_default().push(values);
+      }
+
+      let mut writer = self.acquire_writer().await?;
+      let mut tx = writer.begin().await?;
+
+      let mut inserted = 0;
+      for (keys, rows) in &groups {
+         let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+         inserted += insert_rows(&mut tx, table, &keys, rows)
+            .await?
+            .rows_affected;
+      }
+
+      tx.commit().await?;
+
+      Ok(inserted)
+   }
+
+   /// Insert a serializable value as one row of `table`.
+   ///
+   /// `value` must serialize to a JSON object; its keys name the columns and
+   /// must all exist in `table` (checked with [`table_columns`](Self::table_columns)),
+   /// otherwise [`Error::UnknownColumn`] is returned. Omitted columns take their
+   /// defaults, and values bind as in [`execute`](Self::execute) with booleans
+   /// stored as `0`/`1`. Nested arrays and objects are stored as JSON text.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// # async fn example(db: &DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// #[derive(serde::Serialize)]
+   /// struct NewUser<'a> {
+   ///    name: &'a str,
+   ///    age: u32,
+   /// }
+   ///
+   /// let result = db.insert("users", &NewUser { name: "Alice", age: 30 }).await?;
+   /// println!("inserted row {}", result.last_insert_id);
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn insert<T: Serialize>(
+      &self,
+      table: &str,
+      value: &T,
+   ) -> Result<WriteQueryResult, Error> {
+      let row = match serde_json::to_value(value) {
+         Ok(JsonValue::Object(row)) => row,
+         Ok(other) => {
+            return Err(Error::Other(format!(
+               "insert value must serialize to a JSON object, got {}",
+               other
+            )));
+         }
+         Err(e) => {
+            return Err(Error::Other(format!(
+               "serializing insert value failed: {}",
+               e
+            )));
+         }
+      };
+
+      let columns = self.table_columns(table).await?;
+      if let Some(column) = row.keys().find(|key| !columns.contains(key)) {
+         return Err(Error::UnknownColumn {
+            table: table.to_string(),
+            column: column.clone(),
+         });
+      }
+
+      let (sql, values) = insert_statement(table, &row);
+      self.execute(sql, values).await
+   }
+
+   /// Insert many rows into `table` with multi-row `INSERT` statements.
+   ///
+   /// Every row gives one value per entry of `columns`, in the same order; a
+   /// row of a different width fails with [`Error::RowWidthMismatch`] and
+   /// unknown columns with [`Error::UnknownColumn`], before anything is
+   /// written. Rows are sent as `INSERT INTO t (cols) VALUES (?, ..), (?, ..)`
+   /// in chunks of at most 999 bound values, SQLite's lowest parameter limit,
+   /// all inside one transaction, so a failing chunk leaves the table
+   /// unchanged. Values bind as in [`insert`](Self::insert).
+   ///
+   /// Returns the total rows affected and the rowid of the last inserted row.
+   /// Empty `rows` is a no-op returning zeros.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// # use serde_json::json;
+   /// # async fn example(db: &DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// let rows = vec![vec![json!("Alice"), json!(30)], vec![json!("Bob"), json!(25)]];
+   /// let result = db.bulk_insert("users", &["name", "age"], rows).await?;
+   /// assert_eq!(result.rows_affected, 2);
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn bulk_insert(
+      &self,
+      table: &str,
+      columns: &[&str],
+      rows: Vec<Vec<JsonValue>>,
+   ) -> Result<WriteQueryResult, Error> {
+      use sqlx::Connection;
+
+      if let Some((row, values)) = rows
+         .iter()
+         .enumerate()
+         .find(|(_, values)| values.len() != columns.len())
+      {
+         return Err(Error::RowWidthMismatch {
+            row,
+            expected: columns.len(),
+            actual: values.len(),
+         });
+      }
+      if rows.is_empty() {
+         return Ok(WriteQueryResult {
+            rows_affected: 0,
+            last_insert_id: 0,
+         });
+      }
+      if columns.is_empty() {
+         return Err(Error::Other(
+            "bulk_insert requires at least one column".into(),
+         ));
+      }
+
+      let table_columns = self.table_columns(table).await?;
+      if let Some(column) = columns
+         .iter()
+         .find(|column| !table_columns.iter().any(|c| c == *column))
+      {
+         return Err(Error::UnknownColumn {
+            table: table.to_string(),
+            column: column.to_string(),
+         });
+      }
+
+      let mut writer = self.acquire_writer().await?;
+      let mut tx = writer.begin().await?;
+      let result = insert_rows(&mut tx, table, columns, &rows).await?;
+      tx.commit().await?;
+
+      Ok(result)
+   }
+
+   /// Run a multi-statement SQL script, returning a result for each statement.
+   ///
+   /// The script is split on top-level `;` (not those in string literals,
+   /// quoted identifiers, comments or trigger bodies) and the statements run in
+   /// order on the writer inside one transaction. If any statement fails, the
+   /// whole script is rolled back and the error returned, so the script must not
+   /// manage its own transaction with `BEGIN`/`COMMIT`.
+   ///
+   /// Statements that return columns (SELECT, `INSERT ... RETURNING`, ...) have
+   /// their rows decoded with the wrapper's decode options. Honors
+   /// `max_transaction_statements` but not `max_result_rows`.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// # async fn example(db: &DatabaseWrapper) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// let results = db
+   ///    .execute_script_collecting(
+   ///       "CREATE TABLE t (id INTEGER PRIMARY KEY, v TEXT);
+   ///        INSERT INTO t (v) VALUES ('a') RETURNING id;
+   ///        SELECT * FROM t;",
+   ///    )
+   ///    .await?;
+   /// assert_eq!(results.len(), 3);
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn execute_script_collecting(
+      &self,
+      sql: &str,
+   ) -> Result<Vec<ScriptStatementResult>, Error> {
+      use futures_util::TryStreamExt;
+      use sqlx::{Connection, Either, Executor, Statement};
+
+      let statements = crate::statement::split_statements(sql);
+      self.check_transaction_statements(statements.len())?;
+
+      let mut writer = self.acquire_writer().await?;
+      let mut tx = writer.begin().await?;
+
+      let mut results = Vec::with_capacity(statements.len());
+      for sql in statements {
+         let statement = (&mut *tx).prepare(sql).await?;
+         let returns_rows = !statement.columns().is_empty();
+
+         let mut rows = Vec::new();
+         let mut rows_affected = 0;
+         let mut last_insert_id = 0;
+         let mut stream = (&mut *tx).fetch_many(statement.query());
+         while let Some(item) = stream.try_next().await? {
+            match item {
+               Either::Left(done) => {
+                  rows_affected += done.rows_affected();
+                  last_insert_id = done.last_insert_rowid();
+               }
+               Either::Right(row) => rows.push(row),
+            }
+         }
+
+         results.push(ScriptStatementResult {
+            rows_affected,
+            last_insert_id,
+            rows: if returns_rows {
+               Some(crate::builders::decode_rows(rows, &self.decode_options)?)
+            } else {
+               None
+            },
+         });
+      }
+
+      tx.commit().await?;
+
+      Ok(results)
+   }
+
+   /// Run database migrations
+   ///
+   /// Runs all pending migrations from the provided migrator.
+   /// SQLx tracks applied migrations, so this is safe to call multiple times.
+   pub async fn run_migrations(
+      &self,
+      migrator: &sqlx_sqlite_conn_mgr::Migrator,
+   ) -> Result<(), Error> {
+      self.inner.run_migrations(migrator).await?;
+      Ok(())
+   }
+
+   /// Run database migrations, reporting progress after each statement
+   ///
+   /// Like [`run_migrations`](Self::run_migrations), but each migration's SQL is
+   /// split into statements (respecting string literals, comments and trigger
+   /// bodies) and executed one at a time, calling `on_progress` after each. A
+   /// migration still runs in a single transaction, so a failing statement rolls
+   /// back the whole migration.
+   ///
+   /// ```no_run
+   /// # use sqlx_sqlite_toolkit::DatabaseWrapper;
+   /// # async fn example(db: &DatabaseWrapper, migrator: sqlx_sqlite_toolkit::Migrator) -> Result<(), sqlx_sqlite_toolkit::Error> {
+   /// db.run_migrations_with_progress(&migrator, |progress| {
+   ///    println!("migration {}: {}/{}", progress.version, progress.done, progress.total);
+   /// })
+   /// .await?;
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub async fn run_migrations_with_progress(
+      &self,
+      migrator: &sqlx_sqlite_conn_mgr::Migrator,
+      on_progress: impl FnMut(crate::MigrationStatementProgress),
+   ) -> Result<(), Error> {
+      let mut writer = self.inner.acquire_writer().await?;
+      crate::migrations::run_with_progress(&mut writer, migrator, on_progress)
+         .await
+         .map_err(|e| Error::ConnectionManager(sqlx_sqlite_conn_mgr::Error::Migration(e)))
+   }
+
+   /// Close the database connection.
+   ///
+   /// Checkpoints the WAL and closes all connection pools.
+   /// If observation is enabled, it is disabled first to unregister SQLite hooks
+   /// and allow the write connection to close cleanly.
+   pub async fn close(mut self) -> Result<(), Error> {
+      #[cfg(feature = "observer")]
+      self.disable_observation();
+
+      self.inner.close().await?;
+      Ok(())
+   }
+
+   /// Close the database connection and remove all database files.
+   ///
+   /// Removes the main database file, WAL, and SHM files.
+   /// If observation is enabled, it is disabled first to unregister SQLite hooks
+   /// and allow the write connection to close cleanly.
+   pub async fn remove(mut self) -> Result<(), Error> {
+      #[cfg(feature = "observer")]
+      self.disable_observation();
+
+      self.inner.remove().await?;
+      Ok(())
+   }
+
+   /// Enable observation on this database for the specified tables.
+   ///
+   /// After calling this, write operations will be tracked and subscribers
+   /// can receive change notifications.
+   ///
+   /// If observation is already enabled, the previous observer is disabled first.
+   /// This drops the old broadcast broker, causing existing subscriber streams to
+   /// terminate. Callers must re-subscribe after re-enabling observation.
+   /// To add or remove tables while keeping subscribers, use
+   /// [`observe()`](Self::observe) and [`unobserve()`](Self::unobserve) instead.
    ///
    /// Requires the `observer` feature.
    #[cfg(feature = "observer")]
@@ -412,6 +2101,35 @@ impl DatabaseWrapper {
       self.observer = None;
    }
 
+   /// Start observing a table without recreating the observer.
+   ///
+   /// Unlike calling `enable_observation()` again, existing subscribers are kept
+   /// and start receiving changes for `table` on the next write.
+   ///
+   /// Returns [`Error::ObservationNotEnabled`] if observation is not enabled.
+   ///
+   /// Requires the `observer` feature.
+   #[cfg(feature = "observer")]
+   pub fn observe(&self, table: &str) -> Result<(), Error> {
+      let observable = self.observer.as_ref().ok_or(Error::ObservationNotEnabled)?;
+      observable.observe_table(table);
+      Ok(())
+   }
+
+   /// Stop observing a table without recreating the observer.
+   ///
+   /// Existing subscribers are kept; they stop receiving changes for `table`.
+   /// Returns `true` if the table was being observed.
+   ///
+   /// Returns [`Error::ObservationNotEnabled`] if observation is not enabled.
+   ///
+   /// Requires the `observer` feature.
+   #[cfg(feature = "observer")]
+   pub fn unobserve(&self, table: &str) -> Result<bool, Error> {
+      let observable = self.observer.as_ref().ok_or(Error::ObservationNotEnabled)?;
+      Ok(observable.unobserve_table(table))
+   }
+
    /// Get a reference to the observable database, if observation is enabled.
    ///
    /// Returns `None` if observation has not been enabled via `enable_observation()`.
@@ -460,15 +2178,17 @@ impl InterruptibleTransactionBuilder {
    ) -> Result<InterruptibleTransaction, Error> {
       use crate::transactions::{ActiveInterruptibleTransaction, TransactionWriter};
 
+      self
+         .db
+         .check_transaction_statements(initial_statements.len())?;
+
       // Acquire appropriate writer based on whether databases are attached
       let mut writer = if self.attached.is_empty() {
          let guard = self.db.acquire_writer().await?;
          TransactionWriter::from(guard)
       } else {
-         let guard =
-            sqlx_sqlite_conn_mgr::acquire_writer_with_attached(self.db.inner(), self.attached)
-               .await?;
-         TransactionWriter::Attached(guard)
+         let guard = self.db.acquire_writer_with_attached(self.attached).await?;
+         TransactionWriter::from(guard)
       };
 
       // Begin transaction
@@ -518,6 +2238,33 @@ impl InterruptibleTransaction {
       self.inner.read(query, values).await
    }
 
+   /// Open a savepoint that is rolled back when the returned guard is dropped
+   ///
+   /// See `ActiveInterruptibleTransaction::savepoint_scope()`.
+   pub async fn savepoint_scope(
+      &mut self,
+      name: &str,
+   ) -> Result<crate::transactions::SavepointGuard<'_>, Error> {
+      self.inner.savepoint_scope(name).await
+   }
+
+   /// Create a named savepoint
+   ///
+   /// See `ActiveInterruptibleTransaction::savepoint()`.
+   pub async fn savepoint(&mut self, name: &str) -> Result<(), Error> {
+      self.inner.savepoint(name).await
+   }
+
+   /// Release a named savepoint, keeping its changes
+   pub async fn release_savepoint(&mut self, name: &str) -> Result<(), Error> {
+      self.inner.release_savepoint(name).await
+   }
+
+   /// Undo changes made since a named savepoint, which stays open
+   pub async fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), Error> {
+      self.inner.rollback_to_savepoint(name).await
+   }
+
    /// Commit this transaction
    ///
    /// Consumes the transaction, making all changes permanent.
@@ -538,6 +2285,9 @@ pub struct TransactionExecutionBuilder {
    db: DatabaseWrapper,
    statements: Vec<(String, Vec<JsonValue>)>,
    attached: Vec<sqlx_sqlite_conn_mgr::AttachedSpec>,
+   bind_options: BindOptions,
+   max_attempts: u32,
+   backoff: Duration,
 }
 
 impl TransactionExecutionBuilder {
@@ -549,6 +2299,9 @@ impl TransactionExecutionBuilder {
             .map(|(query, values)| (query.to_string(), values))
             .collect(),
          attached: Vec::new(),
+         bind_options: BindOptions::default(),
+         max_attempts: 1,
+         backoff: Duration::ZERO,
       }
    }
 
@@ -558,11 +2311,57 @@ impl TransactionExecutionBuilder {
       self
    }
 
+   /// Set how parameter values of every statement are bound (e.g. empty strings as `NULL`)
+   pub fn bind_options(mut self, options: BindOptions) -> Self {
+      self.bind_options = options;
+      self
+   }
+
+   /// Re-run the whole transaction when it fails on lock contention
+   ///
+   /// When an attempt fails with a retryable error (see [`Error::is_retryable`]),
+   /// it is rolled back, the writer released, and the transaction run again on a
+   /// freshly acquired writer, up to `max_attempts` attempts in total. The delay
+   /// before retry `n` is `backoff * 2^(n-1)`, capped at one second, and
+   /// jittered to between half and all of that. Other errors are returned right
+   /// away.
+   pub fn with_retry(mut self, max_attempts: u32, backoff: Duration) -> Self {
+      self.max_attempts = max_attempts.max(1);
+      self.backoff = backoff;
+      self
+   }
+
    /// Execute the transaction atomically
    ///
    /// All statements execute within a single transaction. If any statement fails,
    /// all changes are rolled back automatically.
    pub async fn execute(self) -> Result<Vec<WriteQueryResult>, Error> {
+      self
+         .db
+         .check_transaction_statements(self.statements.len())?;
+
+      let mut attempt = 1;
+      loop {
+         match self.execute_once().await {
+            Err(err) if err.is_retryable() && attempt < self.max_attempts => {
+               let delay = retry_delay(self.backoff, attempt);
+               tracing::warn!(
+                  "Transaction busy, retry {}/{} in {:?}: {}",
+                  attempt,
+                  self.max_attempts - 1,
+                  delay,
+                  err
+               );
+               attempt += 1;
+               tokio::time::sleep(delay).await;
+            }
+            result => return result,
+         }
+      }
+   }
+
+   /// Run one attempt of the transaction on a newly acquired writer
+   async fn execute_once(&self) -> Result<Vec<WriteQueryResult>, Error> {
       use crate::transactions::TransactionWriter;
 
       // Acquire appropriate writer based on whether databases are attached
@@ -570,10 +2369,11 @@ impl TransactionExecutionBuilder {
          let guard = self.db.acquire_writer().await?;
          TransactionWriter::from(guard)
       } else {
-         let guard =
-            sqlx_sqlite_conn_mgr::acquire_writer_with_attached(self.db.inner(), self.attached)
-               .await?;
-         TransactionWriter::Attached(guard)
+         let guard = self
+            .db
+            .acquire_writer_with_attached(self.attached.clone())
+            .await?;
+         TransactionWriter::from(guard)
       };
 
       // Begin transaction
@@ -582,9 +2382,9 @@ impl TransactionExecutionBuilder {
       // Execute all statements
       let exec_result = async {
          let mut results = Vec::new();
-         for (query, values) in self.statements {
-            let mut q = sqlx::query(&query);
-            for value in values {
+         for (query, values) in &self.statements {
+            let mut q = sqlx::query(query);
+            for value in self.bind_options.apply(values.clone()) {
                q = bind_value(q, value);
             }
             let exec_result = writer.execute_query(q).await?;
@@ -624,15 +2424,293 @@ impl std::future::IntoFuture for TransactionExecutionBuilder {
    }
 }
 
+/// Longest delay between `with_retry` attempts, before jitter
+const RETRY_BACKOFF_MAX: Duration = Duration::from_secs(1);
+
+/// Jittered delay before retry `attempt` (1-based) of a busy transaction
+///
+/// Exponential from `backoff` up to `RETRY_BACKOFF_MAX`, then scaled to a point
+/// in its upper half so concurrent retries spread out instead of colliding
+/// again. The jitter comes from the clock's sub-second nanoseconds, which is
+/// plenty to desynchronize retries and needs no random number generator.
+fn retry_delay(backoff: Duration, attempt: u32) -> Duration {
+   let delay = backoff
+      .saturating_mul(1 << (attempt - 1).min(16))
+      .min(RETRY_BACKOFF_MAX);
+   let nanos = std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .map_or(0, |elapsed| elapsed.subsec_nanos());
+   let jitter = nanos % 1024;
+   delay / 2 + (delay / 2) * jitter / 1023
+}
+
+/// Deepest level [`DatabaseWrapper::fetch_tree`] descends to
+const MAX_TREE_DEPTH: u32 = 1000;
+
+/// Most bound values per `bulk_insert` statement
+///
+/// SQLite's default `SQLITE_MAX_VARIABLE_NUMBER` is 999 before 3.32.0 and 32766
+/// since; the lower bound keeps chunks valid on either.
+const MAX_BULK_INSERT_PARAMS: usize = 999;
+
+/// Pragmas [`DatabaseWrapper::with_pragma`] may set
+///
+/// All of them read back as a single integer, so the previous value can be
+/// restored verbatim.
+const SCOPED_PRAGMAS: &[&str] = &[
+   "automatic_index",
+   "busy_timeout",
+   "cache_size",
+   "cell_size_check",
+   "defer_foreign_keys",
+   "foreign_keys",
+   "ignore_check_constraints",
+   "query_only",
+   "recursive_triggers",
+   "reverse_unordered_selects",
+   "synchronous",
+   "temp_store",
+   "trusted_schema",
+];
+
+/// Reject pragma names outside [`SCOPED_PRAGMAS`] and values that are not an
+/// integer or a keyword, since both are put into SQL unquoted
+fn validate_scoped_pragma(pragma: &str, value: &str) -> Result<(), Error> {
+   if !SCOPED_PRAGMAS.contains(&pragma) {
+      return Err(Error::InvalidPragma(format!(
+         "'{pragma}' is not a pragma that can be scoped"
+      )));
+   }
+   let digits = value.strip_prefix('-').unwrap_or(value);
+   let is_integer = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit());
+   let is_keyword = !value.is_empty() && value.bytes().all(|b| b.is_ascii_alphabetic());
+   if !is_integer && !is_keyword {
+      return Err(Error::InvalidPragma(format!(
+         "'{value}' is not a valid value for '{pragma}'"
+      )));
+   }
+   Ok(())
+}
+
+/// Escape `%`, `_` and `\` in `term` for a `LIKE ... ESCAPE '\'` pattern
+///
+/// The result matches `term` literally; add `%` around it for a contains
+/// search, as [`DatabaseWrapper::search_like`] does.
+///
+/// ```
+/// assert_eq!(sqlx_sqlite_toolkit::escape_like(r"50%_off\"), r"50\%\_off\\");
+/// ```
+pub fn escape_like(term: &str) -> String {
+   let mut escaped = String::with_capacity(term.len());
+   for c in term.chars() {
+      if matches!(c, '%' | '_' | '\\') {
+         escaped.push('\\');
+      }
+      escaped.push(c);
+   }
+   escaped
+}
+
+/// Whether `path` is dotted keys matching `[a-zA-Z_][a-zA-Z0-9_]*`, each
+/// optionally followed by array indexes like `[0]`
+fn is_valid_json_path(path: &str) -> bool {
+   !path.is_empty()
+      && path.split('.').all(|segment| {
+         let (key, mut rest) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+         let mut chars = key.chars();
+         let valid_key = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+         if !valid_key {
+            return false;
+         }
+         while !rest.is_empty() {
+            let Some((index, after)) = rest.strip_prefix('[').and_then(|r| r.split_once(']'))
+            else {
+               return false;
+            };
+            if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+               return false;
+            }
+            rest = after;
+         }
+         true
+      })
+}
+
+/// Quote a single (unqualified) identifier, doubling any embedded double quotes
+pub(crate) fn quote_name(name: &str) -> String {
+   format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Build an `INSERT` of one row keyed by column name, with its bind values
+///
+/// Booleans become `0`/`1`, matching how SQLite stores them.
+fn insert_statement(
+   table: &str,
+   row: &serde_json::Map<String, JsonValue>,
+) -> (String, Vec<JsonValue>) {
+   if row.is_empty() {
+      return (
+         format!("INSERT INTO {} DEFAULT VALUES", quote_name(table)),
+         Vec::new(),
+      );
+   }
+
+   let sql = format!(
+      "INSERT INTO {} ({}) VALUES ({})",
+      quote_name(table),
+      row.keys()
+         .map(|c| quote_name(c))
+         .collect::<Vec<_>>()
+         .join(", "),
+      vec!["?"; row.len()].join(", ")
+   );
+   let values = row
+      .values()
+      .map(|value| match value {
+         JsonValue::Bool(b) => JsonValue::from(*b as i64),
+         other => other.clone(),
+      })
+      .collect();
+   (sql, values)
+}
+
+/// Insert `rows` into `table` on `conn`, as multi-row `INSERT` statements of at
+/// most `MAX_BULK_INSERT_PARAMS` bound values each
+///
+/// Every row gives one value per entry of `columns`; with no columns, each row
+/// is inserted as `DEFAULT VALUES`. Booleans become `0`/`1`. The caller owns
+/// the transaction and has already validated the columns.
+async fn insert_rows(
+   conn: &mut SqliteConnection,
+   table: &str,
+   columns: &[&str],
+   rows: &[Vec<JsonValue>],
+) -> Result<WriteQueryResult, Error> {
+   let mut result = WriteQueryResult {
+      rows_affected: 0,
+      last_insert_id: 0,
+   };
+
+   if columns.is_empty() {
+      let sql = format!("INSERT INTO {} DEFAULT VALUES", quote_name(table));
+      for _ in rows {
+         let done = sqlx::query(&sql).execute(&mut *conn).await?;
+         result.rows_affected += done.rows_affected();
+         result.last_insert_id = done.last_insert_rowid();
+      }
+      return Ok(result);
+   }
+
+   let prefix = format!(
+      "INSERT INTO {} ({}) VALUES ",
+      quote_name(table),
+      columns
+         .iter()
+         .map(|c| quote_name(c))
+         .collect::<Vec<_>>()
+         .join(", ")
+   );
+   let placeholders = format!("({})", vec!["?"; columns.len()].join(", "));
+   let rows_per_chunk = (MAX_BULK_INSERT_PARAMS / columns.len()).max(1);
+
+   for chunk in rows.chunks(rows_per_chunk) {
+      let sql = format!(
+         "{prefix}{}",
+         vec![placeholders.as_str(); chunk.len()].join(", ")
+      );
+      let mut q = sqlx::query(&sql);
+      for value in chunk.iter().flatten() {
+         q = bind_value(
+            q,
+            match value {
+               JsonValue::Bool(b) => JsonValue::from(*b as i64),
+               other => other.clone(),
+            },
+         );
+      }
+      let done = q.execute(&mut *conn).await?;
+      result.rows_affected += done.rows_affected();
+      result.last_insert_id = done.last_insert_rowid();
+   }
+
+   Ok(result)
+}
+
+/// Infer a SQLite column type from a decoded JSON value
+fn inferred_column_type(value: &JsonValue) -> &'static str {
+   match value {
+      JsonValue::Null => "",
+      JsonValue::Bool(_) => "INTEGER",
+      JsonValue::Number(n) if n.is_i64() || n.is_u64() => "INTEGER",
+      JsonValue::Number(_) => "REAL",
+      JsonValue::String(_) | JsonValue::Array(_) | JsonValue::Object(_) => "TEXT",
+   }
+}
+
+/// Options controlling how JSON parameter values are bound.
+///
+/// Set per query with the builders' `bind_options()` method. The defaults bind
+/// every value as-is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BindOptions {
+   /// Bind empty strings (`""`) as `NULL` instead of as empty TEXT.
+   ///
+   /// For schemas carried over from systems that treat `''` and `NULL` as the
+   /// same value. This changes the meaning of the query, not just what is
+   /// stored: `""` then fails `NOT NULL` constraints, and `WHERE name = ?` with
+   /// `""` compares against `NULL` and matches no rows (use `IS ?` instead).
+   pub empty_string_as_null: bool,
+   /// Bind strings holding an RFC 3339 timestamp as ISO-8601 TEXT in UTC.
+   ///
+   /// Stored timestamps then compare and sort correctly as text regardless of
+   /// the input offset (see `decode::normalize_datetime`). The original offset
+   /// is not kept, so only enable this for values meant to be stored or
+   /// compared as UTC instants.
+   ///
+   /// Requires the `chrono` feature.
+   #[cfg(feature = "chrono")]
+   pub normalize_datetimes: bool,
+}
+
+impl BindOptions {
+   /// Apply these options to a query's parameter values
+   pub(crate) fn apply(&self, values: Vec<JsonValue>) -> Vec<JsonValue> {
+      #[cfg(feature = "chrono")]
+      let normalize_datetimes = self.normalize_datetimes;
+      #[cfg(not(feature = "chrono"))]
+      let normalize_datetimes = false;
+      if !self.empty_string_as_null && !normalize_datetimes {
+         return values;
+      }
+      values
+         .into_iter()
+         .map(|value| match value {
+            JsonValue::String(s) if s.is_empty() && self.empty_string_as_null => JsonValue::Null,
+            #[cfg(feature = "chrono")]
+            JsonValue::String(s) if normalize_datetimes => {
+               JsonValue::String(crate::decode::normalize_datetime(&s).unwrap_or(s))
+            }
+            other => other,
+         })
+         .collect()
+   }
+}
+
 /// Helper function to bind a JSON value to a SQLx query
+///
+/// Strings are bound unchanged; see [`BindOptions::normalize_datetimes`] for
+/// storing timestamps in UTC.
 pub fn bind_value<'a>(
    query: sqlx::query::Query<'a, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'a>>,
    value: JsonValue,
 ) -> sqlx::query::Query<'a, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'a>> {
    if value.is_null() {
       query.bind(None::<JsonValue>)
-   } else if value.is_string() {
-      query.bind(value.as_str().unwrap().to_owned())
+   } else if let Some(text) = value.as_str() {
+      query.bind(text.to_owned())
    } else if let Some(number) = value.as_number() {
       // Preserve integer precision by binding as i64 when possible
       if let Some(int_val) = number.as_i64() {
@@ -122,6 +122,116 @@ async fn test_basic_interruptible_transaction() {
    db.remove().await.unwrap();
 }
 
+#[tokio::test]
+async fn test_savepoint_scope_dropped_rolls_back_sub_step() {
+   let (db, _temp) = create_test_db("test.db").await;
+
+   db.execute(
+      "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let mut tx = db
+      .begin_interruptible_transaction()
+      .execute(vec![(
+         "INSERT INTO users (name) VALUES (?)",
+         vec![json!("Alice")],
+      )])
+      .await
+      .unwrap();
+
+   // Sub-step dropped without commit: only its changes are undone
+   {
+      let mut sp = tx.savepoint_scope("sub_step").await.unwrap();
+      sp.continue_with(vec![(
+         "INSERT INTO users (name) VALUES (?)",
+         vec![json!("Bob")],
+      )])
+      .await
+      .unwrap();
+      let rows = sp
+         .read("SELECT name FROM users".to_string(), vec![])
+         .await
+         .unwrap();
+      assert_eq!(rows.len(), 2);
+   }
+
+   // Committed sub-step is kept
+   let mut sp = tx.savepoint_scope("kept").await.unwrap();
+   sp.continue_with(vec![(
+      "INSERT INTO users (name) VALUES (?)",
+      vec![json!("Carol")],
+   )])
+   .await
+   .unwrap();
+   sp.commit().await.unwrap();
+
+   // The outer transaction continues
+   tx.continue_with(vec![Statement {
+      query: "INSERT INTO users (name) VALUES (?)".to_string(),
+      values: vec![json!("Dave")],
+   }])
+   .await
+   .unwrap();
+   tx.commit().await.unwrap();
+
+   let rows = db
+      .fetch_all("SELECT name FROM users ORDER BY id".into(), vec![])
+      .await
+      .unwrap();
+   let names: Vec<_> = rows.iter().map(|r| r["name"].clone()).collect();
+   assert_eq!(names, vec![json!("Alice"), json!("Carol"), json!("Dave")]);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_named_savepoint_rollback_keeps_earlier_insert() {
+   let (db, _temp) = create_test_db("test.db").await;
+
+   db.execute(
+      "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let mut tx = db
+      .begin_interruptible_transaction()
+      .execute(vec![(
+         "INSERT INTO users (name) VALUES (?)",
+         vec![json!("Alice")],
+      )])
+      .await
+      .unwrap();
+
+   tx.savepoint("before_bob").await.unwrap();
+   tx.continue_with(vec![Statement {
+      query: "INSERT INTO users (name) VALUES (?)".to_string(),
+      values: vec![json!("Bob")],
+   }])
+   .await
+   .unwrap();
+   tx.rollback_to_savepoint("before_bob").await.unwrap();
+   tx.release_savepoint("before_bob").await.unwrap();
+
+   let err = tx.savepoint("sp; DELETE FROM users").await.unwrap_err();
+   assert_eq!(err.error_code(), "INVALID_SAVEPOINT_NAME");
+
+   tx.commit().await.unwrap();
+
+   let rows = db
+      .fetch_all("SELECT name FROM users".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+   assert_eq!(rows[0]["name"], json!("Alice"));
+
+   db.remove().await.unwrap();
+}
+
 #[tokio::test]
 async fn test_interruptible_transaction_with_attached() {
    let (main_db, _temp_main) = create_test_db("main.db").await;
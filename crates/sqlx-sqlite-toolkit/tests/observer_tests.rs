@@ -0,0 +1,181 @@
+//! Observer integration tests for `DatabaseWrapper`.
+//!
+//! Run with: cargo test --features observer
+
+#![cfg(feature = "observer")]
+
+use sqlx_sqlite_observer::ObserverConfig;
+use sqlx_sqlite_toolkit::{DatabaseWrapper, Error, Statement};
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+async fn create_test_db() -> (DatabaseWrapper, TempDir) {
+   let temp_dir = TempDir::new().expect("Failed to create temp directory");
+   let db_path = temp_dir.path().join("test.db");
+   let wrapper = DatabaseWrapper::connect(&db_path, None)
+      .await
+      .expect("Failed to connect to test database");
+
+   wrapper
+      .execute(
+         "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+   wrapper
+      .execute(
+         "CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT)".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+
+   (wrapper, temp_dir)
+}
+
+#[tokio::test]
+async fn test_observe_table_at_runtime() {
+   let (mut db, _temp) = create_test_db().await;
+
+   assert!(matches!(
+      db.observe("posts"),
+      Err(Error::ObservationNotEnabled)
+   ));
+
+   db.enable_observation(ObserverConfig::new().with_tables(["users"]));
+   let mut rx = db.observable().unwrap().subscribe(Vec::<String>::new());
+
+   // Not observed yet: no notification
+   db.execute("INSERT INTO posts (title) VALUES ('first')".into(), vec![])
+      .await
+      .unwrap();
+   assert!(timeout(Duration::from_millis(50), rx.recv()).await.is_err());
+
+   db.observe("posts").unwrap();
+   db.execute("INSERT INTO posts (title) VALUES ('second')".into(), vec![])
+      .await
+      .unwrap();
+
+   let change = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .expect("existing subscriber should see newly observed table")
+      .unwrap();
+   assert_eq!(change.table, "posts");
+   assert_eq!(change.primary_key.len(), 1);
+
+   assert!(db.unobserve("posts").unwrap());
+   assert!(!db.unobserve("posts").unwrap());
+   db.execute("INSERT INTO posts (title) VALUES ('third')".into(), vec![])
+      .await
+      .unwrap();
+   assert!(timeout(Duration::from_millis(50), rx.recv()).await.is_err());
+
+   // Other observed tables keep flowing to the same subscriber
+   db.execute("INSERT INTO users (name) VALUES ('Alice')".into(), vec![])
+      .await
+      .unwrap();
+   let change = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .expect("subscriber should survive unobserve")
+      .unwrap();
+   assert_eq!(change.table, "users");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_rolled_back_savepoint_changes_not_published() {
+   let (mut db, _temp) = create_test_db().await;
+   db.enable_observation(ObserverConfig::new().with_tables(["users"]));
+   let mut rx = db.observable().unwrap().subscribe(Vec::<String>::new());
+
+   let mut tx = db
+      .begin_interruptible_transaction()
+      .execute(vec![(
+         "INSERT INTO users (name) VALUES (?)",
+         vec![serde_json::json!("Alice")],
+      )])
+      .await
+      .unwrap();
+   tx.savepoint("before_bob").await.unwrap();
+   tx.continue_with(vec![Statement {
+      query: "INSERT INTO users (name) VALUES (?)".to_string(),
+      values: vec![serde_json::json!("Bob")],
+   }])
+   .await
+   .unwrap();
+   tx.rollback_to_savepoint("before_bob").await.unwrap();
+   tx.release_savepoint("before_bob").await.unwrap();
+   tx.commit().await.unwrap();
+
+   let change = timeout(Duration::from_millis(100), rx.recv())
+      .await
+      .expect("committed insert should be published")
+      .unwrap();
+   assert_eq!(change.table, "users");
+   assert!(
+      timeout(Duration::from_millis(50), rx.recv()).await.is_err(),
+      "insert rolled back to the savepoint must not be published"
+   );
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_dropped_savepoint_scope_changes_not_published() {
+   let (mut db, _temp) = create_test_db().await;
+   db.enable_observation(ObserverConfig::new().with_tables(["users"]));
+   let mut rx = db.observable().unwrap().subscribe(Vec::<String>::new());
+
+   let mut tx = db
+      .begin_interruptible_transaction()
+      .execute(vec![(
+         "INSERT INTO users (name) VALUES (?)",
+         vec![serde_json::json!("Alice")],
+      )])
+      .await
+      .unwrap();
+   {
+      let mut sp = tx.savepoint_scope("sub_step").await.unwrap();
+      sp.continue_with(vec![Statement {
+         query: "INSERT INTO users (name) VALUES (?)".to_string(),
+         values: vec![serde_json::json!("Bob")],
+      }])
+      .await
+      .unwrap();
+      // Dropped without commit: rolled back before the next operation
+   }
+   let mut sp = tx.savepoint_scope("kept").await.unwrap();
+   sp.continue_with(vec![Statement {
+      query: "INSERT INTO users (name) VALUES (?)".to_string(),
+      values: vec![serde_json::json!("Carol")],
+   }])
+   .await
+   .unwrap();
+   sp.commit().await.unwrap();
+   tx.commit().await.unwrap();
+
+   let mut rowids = Vec::new();
+   for _ in 0..2 {
+      let change = timeout(Duration::from_millis(100), rx.recv())
+         .await
+         .expect("committed inserts should be published")
+         .unwrap();
+      rowids.push(change.rowid);
+   }
+   assert!(
+      timeout(Duration::from_millis(50), rx.recv()).await.is_err(),
+      "insert of the dropped savepoint scope must not be published"
+   );
+
+   let rows = db
+      .fetch_all("SELECT id FROM users ORDER BY id".into(), vec![])
+      .await
+      .unwrap();
+   let stored: Vec<_> = rows.iter().map(|r| r["id"].as_i64()).collect();
+   assert_eq!(rowids, stored);
+
+   db.remove().await.unwrap();
+}
@@ -1,5 +1,7 @@
 use serde_json::json;
-use sqlx_sqlite_toolkit::{DatabaseWrapper, Error, KeysetColumn, KeysetPage};
+use sqlx_sqlite_toolkit::{
+   DatabaseWrapper, DecodeOptions, Error, KeyCase, KeysetColumn, KeysetPage,
+};
 use tempfile::TempDir;
 
 async fn create_test_db() -> (DatabaseWrapper, TempDir) {
@@ -678,3 +680,138 @@ async fn error_query_contains_limit() {
 
    db.remove().await.unwrap();
 }
+
+// ─── Offset Pagination ───
+
+#[tokio::test]
+async fn offset_page_first_page_with_total() {
+   let (db, _temp) = create_test_db().await;
+   seed_posts_table(&db).await;
+
+   let page = db
+      .fetch_offset_page(
+         "SELECT id, title FROM posts ORDER BY id".into(),
+         vec![],
+         3,
+         0,
+      )
+      .await
+      .unwrap();
+
+   let ids: Vec<i64> = page
+      .rows
+      .iter()
+      .map(|r| r["id"].as_i64().unwrap())
+      .collect();
+   assert_eq!(ids, vec![1, 2, 3]);
+   assert_eq!(page.total, 7);
+   assert_eq!((page.limit, page.offset), (3, 0));
+   assert!(!page.rows[0].contains_key("__total"));
+   assert_eq!(page.rows[0].len(), 2);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn offset_page_keeps_query_total_column() {
+   let (mut db, _temp) = create_test_db().await;
+   seed_posts_table(&db).await;
+
+   let page = db
+      .fetch_offset_page(
+         "SELECT id, score * 2 AS __total FROM posts ORDER BY id".into(),
+         vec![],
+         2,
+         0,
+      )
+      .await
+      .unwrap();
+   assert_eq!(page.total, 7);
+   let keys: Vec<&str> = page.rows[0].keys().map(String::as_str).collect();
+   assert_eq!(keys, ["id", "__total"]);
+   assert!(page.rows[0]["__total"].is_number());
+
+   // The count column is found after key renaming too
+   db.set_decode_options(DecodeOptions {
+      key_case: KeyCase::CamelCase,
+      ..Default::default()
+   });
+   let page = db
+      .fetch_offset_page(
+         "SELECT id AS post_id FROM posts ORDER BY id".into(),
+         vec![],
+         2,
+         0,
+      )
+      .await
+      .unwrap();
+   let keys: Vec<&str> = page.rows[0].keys().map(String::as_str).collect();
+   assert_eq!(keys, ["postId"]);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn offset_page_last_partial_page() {
+   let (db, _temp) = create_test_db().await;
+   seed_posts_table(&db).await;
+
+   let page = db
+      .fetch_offset_page(
+         "SELECT id FROM posts WHERE category = $1 ORDER BY score DESC;".into(),
+         vec![json!("tech")],
+         2,
+         2,
+      )
+      .await
+      .unwrap();
+
+   let ids: Vec<i64> = page
+      .rows
+      .iter()
+      .map(|r| r["id"].as_i64().unwrap())
+      .collect();
+   assert_eq!(ids, vec![5]);
+   assert_eq!(page.total, 3);
+
+   // Past the end: no rows, but the total is still reported
+   let page = db
+      .fetch_offset_page(
+         "SELECT id FROM posts WHERE category = $1".into(),
+         vec![json!("tech")],
+         2,
+         10,
+      )
+      .await
+      .unwrap();
+   assert!(page.rows.is_empty());
+   assert_eq!(page.total, 3);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn offset_page_empty_result() {
+   let (db, _temp) = create_test_db().await;
+   seed_posts_table(&db).await;
+
+   let page = db
+      .fetch_offset_page(
+         "SELECT id FROM posts WHERE score > 100".into(),
+         vec![],
+         10,
+         0,
+      )
+      .await
+      .unwrap();
+   assert!(page.rows.is_empty());
+   assert_eq!(page.total, 0);
+
+   let err = db
+      .fetch_offset_page("SELECT id FROM posts".into(), vec![], 0, 0)
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::InvalidPageSize));
+
+   db.remove().await.unwrap();
+}
@@ -276,6 +276,106 @@ async fn test_non_expired_transaction_not_evicted() {
    assert_eq!(err.error_code(), "TRANSACTION_ALREADY_ACTIVE");
 }
 
+/// Take the transaction out of `state`, run one statement on it and put it back,
+/// as the plugin does for each `continue_with` call.
+async fn touch(
+   state: &ActiveInterruptibleTransactions,
+   db_path: &str,
+   tx_id: &str,
+) -> Result<(), Error> {
+   let mut tx = state.remove(db_path, tx_id).await?;
+   tx.continue_with(vec![("INSERT INTO t DEFAULT VALUES", vec![])])
+      .await?;
+   state.insert(db_path.into(), tx).await
+}
+
+#[tokio::test]
+async fn test_active_transaction_survives_idle_timeout() {
+   let (db, _temp) = create_test_db("active.db").await;
+   db.execute("CREATE TABLE t (id INTEGER PRIMARY KEY)".into(), vec![])
+      .await
+      .unwrap();
+
+   let state = ActiveInterruptibleTransactions::default()
+      .with_idle_timeout(std::time::Duration::from_millis(100));
+
+   let tx = begin_transaction(&db, "active.db").await;
+   let tx_id = tx.transaction_id().to_string();
+   state.insert("active.db".into(), tx).await.unwrap();
+
+   // Total elapsed time exceeds the idle timeout, but no gap does
+   for _ in 0..5 {
+      tokio::time::sleep(std::time::Duration::from_millis(40)).await;
+      touch(&state, "active.db", &tx_id).await.unwrap();
+   }
+
+   let tx = state.remove("active.db", &tx_id).await.unwrap();
+   tx.commit().await.unwrap();
+
+   let rows = db
+      .fetch_all("SELECT id FROM t".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 5);
+}
+
+#[tokio::test]
+async fn test_idle_transaction_rolled_back() {
+   let (db, _temp) = create_test_db("idle.db").await;
+   db.execute("CREATE TABLE t (id INTEGER PRIMARY KEY)".into(), vec![])
+      .await
+      .unwrap();
+
+   let state = ActiveInterruptibleTransactions::default()
+      .with_idle_timeout(std::time::Duration::from_millis(50));
+
+   let tx = begin_transaction(&db, "idle.db").await;
+   let tx_id = tx.transaction_id().to_string();
+   state.insert("idle.db".into(), tx).await.unwrap();
+   touch(&state, "idle.db", &tx_id).await.unwrap();
+
+   tokio::time::sleep(std::time::Duration::from_millis(80)).await;
+
+   let err = expect_err(state.remove("idle.db", &tx_id).await);
+   assert_eq!(err.error_code(), "TRANSACTION_TIMED_OUT");
+
+   // The expired transaction was rolled back and released the writer
+   let rows = db
+      .fetch_all("SELECT id FROM t".into(), vec![])
+      .await
+      .unwrap();
+   assert!(rows.is_empty());
+   db.execute("INSERT INTO t DEFAULT VALUES".into(), vec![])
+      .await
+      .unwrap();
+}
+
+#[tokio::test]
+async fn test_total_timeout_ends_active_transaction() {
+   let (db, _temp) = create_test_db("total.db").await;
+   db.execute("CREATE TABLE t (id INTEGER PRIMARY KEY)".into(), vec![])
+      .await
+      .unwrap();
+
+   let state = ActiveInterruptibleTransactions::default()
+      .with_total_timeout(std::time::Duration::from_millis(100));
+
+   let tx = begin_transaction(&db, "total.db").await;
+   let tx_id = tx.transaction_id().to_string();
+   state.insert("total.db".into(), tx).await.unwrap();
+
+   let mut result = Ok(());
+   for _ in 0..5 {
+      tokio::time::sleep(std::time::Duration::from_millis(40)).await;
+      result = touch(&state, "total.db", &tx_id).await;
+      if result.is_err() {
+         break;
+      }
+   }
+
+   assert_eq!(result.unwrap_err().error_code(), "TRANSACTION_TIMED_OUT");
+}
+
 // ============================================================================
 // ActiveRegularTransactions tests
 // ============================================================================
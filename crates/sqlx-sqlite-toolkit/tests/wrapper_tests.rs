@@ -1,5 +1,9 @@
+use std::time::Instant;
+
 use serde_json::{Value as JsonValue, json};
-use sqlx_sqlite_toolkit::DatabaseWrapper;
+use sqlx_sqlite_toolkit::{
+   BindOptions, DatabaseWrapper, DecodeMode, DecodeOptions, KeyCase, untag,
+};
 use tempfile::TempDir;
 
 async fn create_test_db() -> (DatabaseWrapper, TempDir) {
@@ -286,6 +290,376 @@ async fn test_type_binding_and_decoding() {
    db.remove().await.unwrap();
 }
 
+#[tokio::test]
+async fn test_boolean_column_decoding() {
+   let (mut db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE t (id INTEGER PRIMARY KEY, flag BOOLEAN, n INTEGER)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "INSERT INTO t (id, flag, n) VALUES (1, 1, 1), (2, 0, 0), (3, 2, 1), (4, NULL, 0)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let query = "SELECT flag, n FROM t ORDER BY id";
+
+   // Detect by declared type: BOOLEAN converts, INTEGER stays numeric
+   db.set_decode_options(DecodeOptions {
+      detect_boolean_columns: true,
+      ..Default::default()
+   });
+   let rows = db.fetch_all(query.into(), vec![]).await.unwrap();
+   assert_eq!(rows[0].get("flag"), Some(&json!(true)));
+   assert_eq!(rows[1].get("flag"), Some(&json!(false)));
+   assert_eq!(rows[2].get("flag"), Some(&json!(2)));
+   assert_eq!(rows[3].get("flag"), Some(&JsonValue::Null));
+   assert_eq!(rows[0].get("n"), Some(&json!(1)));
+   assert_eq!(rows[1].get("n"), Some(&json!(0)));
+
+   let row = db
+      .fetch_one("SELECT flag FROM t WHERE id = 2".into(), vec![])
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(row.get("flag"), Some(&json!(false)));
+
+   // Explicit column list, overriding the wrapper default per query
+   let rows = db
+      .fetch_all(query.into(), vec![])
+      .decode_options(DecodeOptions {
+         boolean_columns: vec!["n".into()],
+         ..Default::default()
+      })
+      .await
+      .unwrap();
+   assert_eq!(rows[0].get("n"), Some(&json!(true)));
+   assert_eq!(rows[0].get("flag"), Some(&json!(1)));
+
+   let scalar = db
+      .fetch_scalar("SELECT n AS n FROM t WHERE id = 2".into(), vec![])
+      .decode_options(DecodeOptions {
+         boolean_columns: vec!["n".into()],
+         ..Default::default()
+      })
+      .await
+      .unwrap();
+   assert_eq!(scalar, Some(json!(false)));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_key_case_conversion() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE t (user_id INTEGER, display_name TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute("INSERT INTO t VALUES (7, 'Ana')".into(), vec![])
+      .await
+      .unwrap();
+
+   let camel = DecodeOptions {
+      key_case: KeyCase::CamelCase,
+      ..Default::default()
+   };
+   let rows = db
+      .fetch_all("SELECT user_id, display_name FROM t".into(), vec![])
+      .decode_options(camel.clone())
+      .await
+      .unwrap();
+   let keys: Vec<&str> = rows[0].keys().map(String::as_str).collect();
+   assert_eq!(keys, ["userId", "displayName"]);
+   assert_eq!(rows[0].get("userId"), Some(&json!(7)));
+
+   // And back again from camelCase aliases
+   let rows = db
+      .fetch_all(
+         "SELECT user_id AS userId, display_name AS displayName FROM t".into(),
+         vec![],
+      )
+      .decode_options(DecodeOptions {
+         key_case: KeyCase::SnakeCase,
+         ..Default::default()
+      })
+      .await
+      .unwrap();
+   let keys: Vec<&str> = rows[0].keys().map(String::as_str).collect();
+   assert_eq!(keys, ["user_id", "display_name"]);
+
+   // Two columns converging on one key is an error, not a silent overwrite
+   let err = db
+      .fetch_all("SELECT user_id, user_id AS userId FROM t".into(), vec![])
+      .decode_options(camel)
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "DUPLICATE_COLUMN_KEY");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_decode_mode_tagged_types() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE files (id INTEGER PRIMARY KEY, data BLOB, encoded TEXT, size REAL)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "INSERT INTO files (id, data, encoded, size) VALUES (7, X'DEADBEEF', '3q2+7w==', 4.5)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   // By default the BLOB and the base64 TEXT decode identically
+   let row = db
+      .fetch_one("SELECT data, encoded FROM files".into(), vec![])
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(row["data"], row["encoded"]);
+
+   let rows = db
+      .fetch_all(
+         "SELECT id, data, encoded, size, NULL AS missing FROM files".into(),
+         vec![],
+      )
+      .decode_mode(DecodeMode::TaggedTypes)
+      .await
+      .unwrap();
+   let row = &rows[0];
+   assert_eq!(row["data"], json!({"$type": "blob", "data": "3q2+7w=="}));
+   assert_eq!(
+      row["encoded"],
+      json!({"$type": "text", "value": "3q2+7w=="})
+   );
+   assert_eq!(row["id"], json!({"$type": "integer", "value": 7}));
+   assert_eq!(row["size"], json!({"$type": "real", "value": 4.5}));
+   assert_eq!(row["missing"], JsonValue::Null);
+
+   // The tagged blob round-trips back to the bytes written
+   use base64::Engine;
+   let data = untag(&row["data"]);
+   let bytes = base64::engine::general_purpose::STANDARD
+      .decode(data.as_str().unwrap())
+      .unwrap();
+   assert_eq!(bytes, [0xDE, 0xAD, 0xBE, 0xEF]);
+   assert_eq!(untag(&row["id"]), json!(7));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_suggest_indexes() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE events (id INTEGER PRIMARY KEY, kind TEXT, payload TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 2000)
+       INSERT INTO events (kind, payload) SELECT 'k' || (i % 10), 'x' FROM n"
+         .into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let query = "SELECT * FROM events WHERE kind = ?";
+
+   let suggestions = db
+      .suggest_indexes(query.into(), vec![json!("k1")])
+      .await
+      .unwrap();
+   assert_eq!(suggestions.len(), 1);
+   assert_eq!(suggestions[0].table, "events");
+   assert_eq!(suggestions[0].row_count, Some(2000));
+   assert!(suggestions[0].plan_detail.starts_with("SCAN"));
+
+   db.execute(
+      "CREATE INDEX idx_events_kind ON events (kind)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   let suggestions = db
+      .suggest_indexes(query.into(), vec![json!("k1")])
+      .await
+      .unwrap();
+   assert!(suggestions.is_empty(), "{suggestions:?}");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_on_writer_sees_prior_write() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, created TEXT DEFAULT 'now')".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let result = db
+      .execute(
+         "INSERT INTO users (name) VALUES (?)".into(),
+         vec![json!("Alice")],
+      )
+      .await
+      .unwrap();
+
+   let row = db
+      .fetch_one_on_writer(
+         "SELECT * FROM users WHERE id = ?".into(),
+         vec![json!(result.last_insert_id)],
+      )
+      .await
+      .unwrap()
+      .expect("inserted row is visible on the writer");
+   assert_eq!(row.get("name"), Some(&json!("Alice")));
+   assert_eq!(row.get("created"), Some(&json!("now")));
+
+   let rows = db
+      .fetch_all_on_writer("SELECT name FROM users".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+
+   db.execute(
+      "INSERT INTO users (name) VALUES (?)".into(),
+      vec![json!("Bob")],
+   )
+   .await
+   .unwrap();
+   let err = db
+      .fetch_one_on_writer("SELECT name FROM users".into(), vec![])
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "MULTIPLE_ROWS_RETURNED");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_builders_on_writer() {
+   let (db, _temp) = create_test_db().await;
+
+   // Temp tables live only on the connection that created them: the writer
+   db.execute(
+      "CREATE TEMP TABLE scratch (id INTEGER, label TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let columns = db
+      .fetch_all("PRAGMA temp.table_info(scratch)".into(), vec![])
+      .on_writer()
+      .await
+      .unwrap();
+   let names: Vec<_> = columns.iter().map(|c| c["name"].clone()).collect();
+   assert_eq!(names, vec![json!("id"), json!("label")]);
+
+   let on_reader = db
+      .fetch_all("PRAGMA temp.table_info(scratch)".into(), vec![])
+      .await
+      .unwrap();
+   assert!(on_reader.is_empty());
+
+   let column = db
+      .fetch_one(
+         "SELECT name FROM pragma_table_info('scratch', 'temp') WHERE cid = ?".into(),
+         vec![json!(1)],
+      )
+      .on_writer()
+      .await
+      .unwrap();
+   assert_eq!(column.unwrap()["name"], json!("label"));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_all_parallel() {
+   let (db, _temp) = create_test_db().await;
+   db.execute("CREATE TABLE t (id INTEGER PRIMARY KEY)".into(), vec![])
+      .await
+      .unwrap();
+
+   let slow = |n: i64| {
+      (
+         "WITH RECURSIVE c(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM c WHERE i < 100000) \
+          SELECT ? AS n, COUNT(*) AS steps FROM c"
+            .to_string(),
+         vec![json!(n)],
+      )
+   };
+   let queries: Vec<_> = (0..4).map(slow).collect();
+
+   let start = Instant::now();
+   let results = db.fetch_all_parallel(queries).await.unwrap();
+   let elapsed = start.elapsed();
+
+   let order: Vec<_> = results.iter().map(|rows| rows[0]["n"].clone()).collect();
+   assert_eq!(order, vec![json!(0), json!(1), json!(2), json!(3)]);
+
+   // Overlapping queries each needed their own read connection. Wall-clock
+   // speedup depends on available cores, so only sanity-check the duration.
+   assert!(db.inner().pool_snapshot().unwrap().read_size >= 4);
+   assert!(elapsed < std::time::Duration::from_secs(10));
+
+   let err = db
+      .fetch_all_parallel(vec![slow(0), ("SELECT * FROM missing".into(), vec![])])
+      .await
+      .unwrap_err();
+   assert!(err.to_string().contains("missing"));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_connect_readonly() {
+   let (db, temp) = create_test_db().await;
+   db.execute("CREATE TABLE t (id INTEGER PRIMARY KEY)".into(), vec![])
+      .await
+      .unwrap();
+   db.execute("INSERT INTO t (id) VALUES (1)".into(), vec![])
+      .await
+      .unwrap();
+   db.close().await.unwrap();
+
+   let path = temp.path().join("test.db");
+   let db = DatabaseWrapper::connect_readonly(&path, None)
+      .await
+      .unwrap();
+   let rows = db
+      .fetch_all("SELECT id FROM t".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+
+   let err = db
+      .execute("INSERT INTO t (id) VALUES (2)".into(), vec![])
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "READ_ONLY_DATABASE");
+
+   db.close().await.unwrap();
+}
+
 #[tokio::test]
 async fn test_column_order_preserved() {
    let (db, _temp) = create_test_db().await;
@@ -320,3 +694,1895 @@ async fn test_close() {
 
    db.close().await.expect("close should succeed");
 }
+
+#[tokio::test]
+async fn test_materialize_query() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, score REAL, active INT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   for (name, score, active) in [("Alice", 9.5, 1), ("Bob", 4.0, 0), ("Carol", 7.25, 1)] {
+      db.execute(
+         "INSERT INTO users (name, score, active) VALUES ($1, $2, $3)".into(),
+         vec![json!(name), json!(score), json!(active)],
+      )
+      .await
+      .unwrap();
+   }
+
+   let subset = db
+      .materialize_query(
+         "SELECT id, name, score FROM users WHERE active = $1".into(),
+         vec![json!(1)],
+         "active_users",
+      )
+      .await
+      .unwrap();
+
+   // The copy holds only the filtered rows and can be queried independently
+   let rows = subset
+      .fetch_all(
+         "SELECT name, score FROM active_users WHERE score > $1 ORDER BY id".into(),
+         vec![json!(8)],
+      )
+      .await
+      .unwrap();
+
+   assert_eq!(rows.len(), 1);
+   assert_eq!(rows[0].get("name"), Some(&json!("Alice")));
+   assert_eq!(rows[0].get("score"), Some(&json!(9.5)));
+
+   let count = subset
+      .fetch_one("SELECT COUNT(*) AS n FROM active_users".into(), vec![])
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(count.get("n"), Some(&json!(2)));
+
+   // Column types were inferred from the first row
+   let types = subset
+      .fetch_all(
+         "SELECT name, type FROM pragma_table_info('active_users') ORDER BY cid".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+   let types: Vec<(&JsonValue, &JsonValue)> = types
+      .iter()
+      .map(|row| (row.get("name").unwrap(), row.get("type").unwrap()))
+      .collect();
+   assert_eq!(
+      types,
+      vec![
+         (&json!("id"), &json!("INTEGER")),
+         (&json!("name"), &json!("TEXT")),
+         (&json!("score"), &json!("REAL")),
+      ]
+   );
+
+   // The main database is untouched
+   let main_tables = db
+      .fetch_all(
+         "SELECT name FROM sqlite_master WHERE name = 'active_users'".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+   assert!(main_tables.is_empty());
+
+   subset.close().await.unwrap();
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_materialize_ignores_wrapper_decode_options() {
+   let (mut db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE t (user_id INTEGER, display_name TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute("INSERT INTO t VALUES (7, 'Ana')".into(), vec![])
+      .await
+      .unwrap();
+   db.set_decode_options(DecodeOptions {
+      key_case: KeyCase::CamelCase,
+      mode: DecodeMode::TaggedTypes,
+      ..Default::default()
+   });
+
+   let copy = db
+      .materialize_query("SELECT user_id, display_name FROM t".into(), vec![], "copy")
+      .await
+      .unwrap();
+
+   // Plain values under the original column names, not tagged JSON text
+   let rows = copy
+      .fetch_all(
+         "SELECT user_id, display_name, typeof(user_id) AS kind FROM copy".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+   assert_eq!(rows[0]["user_id"], json!(7));
+   assert_eq!(rows[0]["display_name"], json!("Ana"));
+   assert_eq!(rows[0]["kind"], json!("integer"));
+
+   copy.close().await.unwrap();
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_materialize_empty_query() {
+   let (db, _temp) = create_test_db().await;
+   db.execute("CREATE TABLE t (id INTEGER, name TEXT)".into(), vec![])
+      .await
+      .unwrap();
+
+   let copy = db
+      .materialize_query("SELECT id, name FROM t".into(), vec![], "copy")
+      .await
+      .unwrap();
+
+   // Schema is still created from the statement's columns
+   let rows = copy
+      .fetch_all("SELECT id, name FROM copy".into(), vec![])
+      .await
+      .unwrap();
+   assert!(rows.is_empty());
+
+   copy.close().await.unwrap();
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_disk_full_error_classified() {
+   use sqlx_sqlite_toolkit::Error;
+
+   let (db, _temp) = create_test_db().await;
+   db.execute("CREATE TABLE t (data BLOB)".into(), vec![])
+      .await
+      .unwrap();
+
+   // Cap the file at its current size so the next large write cannot grow it
+   let page_count = db
+      .fetch_one("SELECT page_count FROM pragma_page_count()".into(), vec![])
+      .await
+      .unwrap()
+      .unwrap();
+   db.execute(
+      format!("PRAGMA max_page_count = {}", page_count["page_count"]),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let err = db
+      .execute(
+         "INSERT INTO t (data) VALUES (zeroblob(1048576))".into(),
+         vec![],
+      )
+      .await
+      .unwrap_err();
+
+   assert!(matches!(err, Error::DiskFull(_)), "got {err:?}");
+   assert_eq!(err.error_code(), "SQLITE_FULL");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_max_result_rows() {
+   use sqlx_sqlite_toolkit::Error;
+
+   let (mut db, _temp) = create_test_db().await;
+   db.execute("CREATE TABLE t (id INTEGER PRIMARY KEY)".into(), vec![])
+      .await
+      .unwrap();
+   db.execute(
+      "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 200) \
+       INSERT INTO t (id) SELECT i FROM n"
+         .into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   db.set_max_result_rows(Some(100));
+   assert_eq!(db.max_result_rows(), Some(100));
+
+   let err = db
+      .fetch_all("SELECT * FROM t".into(), vec![])
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::ResultSetTooLarge { limit: 100 }));
+   assert_eq!(err.error_code(), "RESULT_SET_TOO_LARGE");
+
+   // Exactly at the limit is allowed
+   let rows = db
+      .fetch_all("SELECT * FROM t WHERE id <= 100".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 100);
+
+   // Per-query override lifts the limit
+   let rows = db
+      .fetch_all("SELECT * FROM t".into(), vec![])
+      .max_rows(None)
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 200);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_all_with_types() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE readings (id INTEGER PRIMARY KEY, value REAL, label TEXT, raw BLOB)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "INSERT INTO readings (value, label, raw) VALUES (2, 'two', x'ff'), (NULL, NULL, NULL)"
+         .into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let rows = db
+      .fetch_all_with_types(
+         "SELECT id, value, label, raw, id * 2 AS doubled FROM readings ORDER BY id".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 2);
+
+   let first = &rows[0];
+   let names: Vec<&str> = first.iter().map(|cell| cell.name.as_str()).collect();
+   assert_eq!(names, ["id", "value", "label", "raw", "doubled"]);
+
+   // REAL affinity stores the 2 as 2.0
+   assert_eq!(first[1].value, json!(2.0));
+   assert_eq!(first[1].sqlite_type, "REAL");
+   assert_eq!(first[1].declared_type.as_deref(), Some("REAL"));
+   assert_eq!(first[0].sqlite_type, "INTEGER");
+   assert_eq!(first[2].sqlite_type, "TEXT");
+   assert_eq!(first[3].sqlite_type, "BLOB");
+   assert_eq!(first[4].sqlite_type, "INTEGER");
+
+   let second = &rows[1];
+   assert_eq!(second[1].value, JsonValue::Null);
+   assert_eq!(second[1].sqlite_type, "NULL");
+   assert_eq!(second[1].declared_type.as_deref(), Some("REAL"));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_as_from_row() {
+   use sqlx_sqlite_toolkit::Error;
+
+   #[derive(Debug, PartialEq, sqlx::FromRow)]
+   struct User {
+      id: i64,
+      name: String,
+      score: Option<f64>,
+      avatar: Option<Vec<u8>>,
+   }
+
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, score REAL, avatar BLOB)"
+         .into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "INSERT INTO users (name, score, avatar) VALUES ('Alice', 9.5, x'0102'), ('Bob', NULL, NULL)"
+         .into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let users: Vec<User> = db
+      .fetch_all_as("SELECT * FROM users ORDER BY id".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(
+      users,
+      vec![
+         User {
+            id: 1,
+            name: "Alice".into(),
+            score: Some(9.5),
+            avatar: Some(vec![1, 2]),
+         },
+         User {
+            id: 2,
+            name: "Bob".into(),
+            score: None,
+            avatar: None,
+         },
+      ]
+   );
+
+   let bob: Option<User> = db
+      .fetch_one_as(
+         "SELECT * FROM users WHERE name = ?".into(),
+         vec![json!("Bob")],
+      )
+      .await
+      .unwrap();
+   assert_eq!(bob.map(|u| u.id), Some(2));
+
+   let nobody: Option<User> = db
+      .fetch_one_as("SELECT * FROM users WHERE id = 99".into(), vec![])
+      .await
+      .unwrap();
+   assert!(nobody.is_none());
+
+   let err = db
+      .fetch_one_as::<User>("SELECT * FROM users".into(), vec![])
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::MultipleRowsReturned(2)));
+
+   db.remove().await.unwrap();
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn test_chrono_datetime_round_trip() {
+   use chrono::{DateTime, TimeZone, Utc};
+
+   #[derive(sqlx::FromRow)]
+   struct Event {
+      id: i64,
+      at: DateTime<Utc>,
+   }
+
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE events (id INTEGER PRIMARY KEY, at TEXT NOT NULL)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   // Strings bind unchanged by default, offset included
+   db.execute(
+      "INSERT INTO events (id, at) VALUES (2, ?)".into(),
+      vec![json!("2024-03-01T14:30:00+02:00")],
+   )
+   .await
+   .unwrap();
+   let stored = db
+      .fetch_scalar("SELECT at FROM events WHERE id = 2".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(stored, Some(json!("2024-03-01T14:30:00+02:00")));
+   db.execute("DELETE FROM events".into(), vec![])
+      .await
+      .unwrap();
+
+   // With normalize_datetimes, an offset timestamp is stored normalized to UTC
+   let at = Utc.with_ymd_and_hms(2024, 3, 1, 12, 30, 0).unwrap();
+   db.execute(
+      "INSERT INTO events (id, at) VALUES (1, ?)".into(),
+      vec![json!("2024-03-01T14:30:00+02:00")],
+   )
+   .bind_options(BindOptions {
+      normalize_datetimes: true,
+      ..Default::default()
+   })
+   .await
+   .unwrap();
+   let stored = db
+      .fetch_scalar("SELECT at FROM events".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(stored, Some(json!("2024-03-01T12:30:00+00:00")));
+
+   let events: Vec<Event> = db
+      .fetch_all_as("SELECT id, at FROM events".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(events.len(), 1);
+   assert_eq!(events[0].id, 1);
+   assert_eq!(events[0].at, at);
+
+   // datetime_columns normalizes SQLite's own datetime() output on the JSON path
+   let rows = db
+      .fetch_all("SELECT datetime(at) AS at FROM events".into(), vec![])
+      .decode_options(DecodeOptions {
+         datetime_columns: vec!["at".into()],
+         ..Default::default()
+      })
+      .await
+      .unwrap();
+   let decoded: DateTime<Utc> = serde_json::from_value(rows[0]["at"].clone()).unwrap();
+   assert_eq!(rows[0].get("at"), Some(&json!("2024-03-01T12:30:00+00:00")));
+   assert_eq!(decoded, at);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_execute_script_collecting() {
+   let (db, _temp) = create_test_db().await;
+
+   let results = db
+      .execute_script_collecting(
+         "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT);
+          -- seed; with a comment
+          INSERT INTO notes (body) VALUES ('a;b'), ('c') RETURNING id;
+          UPDATE notes SET body = upper(body);
+          SELECT id, body FROM notes WHERE id > 5;
+          SELECT body FROM notes ORDER BY id",
+      )
+      .await
+      .unwrap();
+
+   assert_eq!(results.len(), 5);
+   assert!(results[0].rows.is_none());
+   assert_eq!(results[1].rows_affected, 2);
+   assert_eq!(results[1].last_insert_id, 2);
+   let ids: Vec<_> = results[1]
+      .rows
+      .as_ref()
+      .unwrap()
+      .iter()
+      .map(|r| r["id"].clone())
+      .collect();
+   assert_eq!(ids, [json!(1), json!(2)]);
+   assert_eq!(results[2].rows_affected, 2);
+   assert!(results[2].rows.is_none());
+   // A SELECT with no matches still reports an (empty) result set
+   assert_eq!(results[3].rows.as_ref().map(Vec::len), Some(0));
+   let bodies: Vec<_> = results[4]
+      .rows
+      .as_ref()
+      .unwrap()
+      .iter()
+      .map(|r| r["body"].clone())
+      .collect();
+   assert_eq!(bodies, [json!("A;B"), json!("C")]);
+
+   // A failing statement rolls back the statements before it
+   let err = db
+      .execute_script_collecting(
+         "INSERT INTO notes (body) VALUES ('d'); INSERT INTO missing VALUES (1)",
+      )
+      .await;
+   assert!(err.is_err());
+   let count = db
+      .fetch_scalar("SELECT COUNT(*) FROM notes".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(count, Some(json!(2)));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_max_transaction_statements() {
+   use sqlx_sqlite_toolkit::Error;
+
+   let (mut db, _temp) = create_test_db().await;
+   db.execute("CREATE TABLE t (id INTEGER PRIMARY KEY)".into(), vec![])
+      .await
+      .unwrap();
+
+   db.set_max_transaction_statements(Some(2));
+   assert_eq!(db.max_transaction_statements(), Some(2));
+
+   let insert = "INSERT INTO t (id) VALUES (?)";
+   let statements = |n: i64| {
+      (1..=n)
+         .map(|i| (insert, vec![json!(i)]))
+         .collect::<Vec<_>>()
+   };
+
+   let err = db.execute_transaction(statements(3)).await.unwrap_err();
+   assert!(matches!(err, Error::TooManyStatements { limit: 2 }));
+   assert_eq!(err.error_code(), "TOO_MANY_STATEMENTS");
+
+   let err = db
+      .begin_interruptible_transaction()
+      .execute(statements(3))
+      .await
+      .err()
+      .unwrap();
+   assert!(matches!(err, Error::TooManyStatements { limit: 2 }));
+
+   // Rejected before any statement ran
+   let count = db
+      .fetch_scalar("SELECT COUNT(*) FROM t".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(count, Some(json!(0)));
+
+   // Exactly at the limit is allowed
+   let results = db.execute_transaction(statements(2)).await.unwrap();
+   assert_eq!(results.len(), 2);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_transaction_with_retry_commits_after_busy() {
+   use sqlx::ConnectOptions;
+   use sqlx::sqlite::SqliteConnectOptions;
+   use std::time::Duration;
+
+   let (db, temp) = create_test_db().await;
+   db.execute("CREATE TABLE t (id INTEGER PRIMARY KEY)".into(), vec![])
+      .await
+      .unwrap();
+
+   // Fail with SQLITE_BUSY at once instead of waiting out the busy timeout
+   db.inner().set_busy_handler(|_| false);
+
+   // Hold the write lock from a connection outside the wrapper
+   let mut other = SqliteConnectOptions::new()
+      .filename(temp.path().join("test.db"))
+      .connect()
+      .await
+      .unwrap();
+   sqlx::query("BEGIN IMMEDIATE")
+      .execute(&mut other)
+      .await
+      .unwrap();
+
+   let insert = vec![("INSERT INTO t DEFAULT VALUES", vec![])];
+
+   // A single attempt surfaces the contention
+   let err = db.execute_transaction(insert.clone()).await.unwrap_err();
+   assert!(err.is_retryable(), "unexpected error: {err}");
+
+   let release = tokio::spawn(async move {
+      tokio::time::sleep(Duration::from_millis(100)).await;
+      sqlx::query("ROLLBACK").execute(&mut other).await.unwrap();
+   });
+
+   let results = db
+      .execute_transaction(insert)
+      .with_retry(10, Duration::from_millis(20))
+      .await
+      .unwrap();
+   assert_eq!(results.len(), 1);
+   release.await.unwrap();
+
+   let count = db
+      .fetch_scalar("SELECT COUNT(*) FROM t".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(count, Some(json!(1)));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_execute_named_binds_by_name() {
+   use indexmap::IndexMap;
+
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE t (id INTEGER PRIMARY KEY, a TEXT, b TEXT, c INTEGER)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   // `:v` appears twice but is one parameter; map order differs from query order
+   let params = IndexMap::from([
+      ("n".to_string(), json!(7)),
+      ("v".to_string(), json!("x")),
+      ("unused".to_string(), json!(true)),
+   ]);
+   let result = db
+      .execute_named("INSERT INTO t (a, b, c) VALUES (:v, :v, @n)".into(), params)
+      .await
+      .unwrap();
+   assert_eq!(result.rows_affected, 1);
+
+   let rows = db
+      .fetch_all("SELECT a, b, c FROM t".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows[0]["a"], json!("x"));
+   assert_eq!(rows[0]["b"], json!("x"));
+   assert_eq!(rows[0]["c"], json!(7));
+
+   let err = db
+      .execute_named(
+         "UPDATE t SET a = :v WHERE id = ?".into(),
+         IndexMap::from([("v".to_string(), json!("y"))]),
+      )
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "MIXED_PARAMETER_STYLES");
+
+   let err = db
+      .execute_named("UPDATE t SET a = :v".into(), IndexMap::new())
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "MISSING_NAMED_PARAMETER");
+
+   // Rejected queries ran nothing
+   let count = db
+      .fetch_scalar("SELECT COUNT(*) FROM t WHERE a = 'x'".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(count, Some(json!(1)));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_search_like_matches_literally() {
+   use sqlx_sqlite_toolkit::{Error, SqliteDatabaseConfig};
+
+   // One read connection, so the pragma reset is observable by the next query
+   let temp = TempDir::new().unwrap();
+   let config = SqliteDatabaseConfig {
+      max_read_connections: 1,
+      ..Default::default()
+   };
+   let db = DatabaseWrapper::connect(&temp.path().join("search.db"), Some(config))
+      .await
+      .unwrap();
+   db.execute(
+      "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   for name in [
+      "100% cotton",
+      "100 cotton",
+      "1000 cotton",
+      "a_b",
+      "axb",
+      r"C:\temp",
+      "Case Test",
+   ] {
+      db.execute(
+         "INSERT INTO products (name) VALUES (?)".into(),
+         vec![json!(name)],
+      )
+      .await
+      .unwrap();
+   }
+   let names = |rows: Vec<indexmap::IndexMap<String, JsonValue>>| -> Vec<JsonValue> {
+      rows.into_iter().map(|row| row["name"].clone()).collect()
+   };
+
+   let rows = db
+      .search_like("products", "name", "100%", false)
+      .await
+      .unwrap();
+   assert_eq!(names(rows), vec![json!("100% cotton")]);
+   let rows = db
+      .search_like("products", "name", "a_b", false)
+      .await
+      .unwrap();
+   assert_eq!(names(rows), vec![json!("a_b")]);
+   let rows = db
+      .search_like("products", "name", r":\t", false)
+      .await
+      .unwrap();
+   assert_eq!(names(rows), vec![json!(r"C:\temp")]);
+
+   let rows = db
+      .search_like("products", "name", "case", false)
+      .await
+      .unwrap();
+   assert_eq!(names(rows), vec![json!("Case Test")]);
+   let rows = db
+      .search_like("products", "name", "case", true)
+      .await
+      .unwrap();
+   assert!(rows.is_empty());
+   let rows = db
+      .search_like("products", "name", "Case", true)
+      .await
+      .unwrap();
+   assert_eq!(names(rows), vec![json!("Case Test")]);
+
+   // The connection is back to case-insensitive LIKE
+   let rows = db
+      .fetch_all(
+         "SELECT name FROM products WHERE name LIKE 'case%'".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+
+   let err = db
+      .search_like("products", "nope", "x", false)
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::UnknownColumn { ref column, .. } if column == "nope"));
+   let err = db
+      .search_like("missing", "name", "x", false)
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "TABLE_NOT_FOUND");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_all_json_path() {
+   let (db, _temp) = create_test_db().await;
+
+   db.execute(
+      "CREATE TABLE users (id INTEGER PRIMARY KEY, profile TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   for profile in [
+      json!({"address": {"city": "Oslo"}, "tags": ["admin"]}),
+      json!({"address": {"city": "Bergen"}, "tags": ["guest"]}),
+   ] {
+      db.execute(
+         "INSERT INTO users (profile) VALUES (?)".into(),
+         vec![json!(profile.to_string())],
+      )
+      .await
+      .unwrap();
+   }
+
+   let rows = db
+      .fetch_all_json_path("users", "profile", "address.city", json!("Oslo"))
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+   assert_eq!(rows[0]["id"], json!(1));
+
+   let rows = db
+      .fetch_all_json_path("users", "profile", "tags[0]", json!("guest"))
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+   assert_eq!(rows[0]["id"], json!(2));
+
+   let rows = db
+      .fetch_all_json_path("users", "profile", "address.zip", json!("Oslo"))
+      .await
+      .unwrap();
+   assert!(rows.is_empty());
+
+   for path in ["address.city') OR 1=1 --", "a\"b", "", "tags[x]", "1st"] {
+      let err = db
+         .fetch_all_json_path("users", "profile", path, json!("Oslo"))
+         .await
+         .unwrap_err();
+      assert_eq!(err.error_code(), "INVALID_JSON_PATH", "{path}");
+   }
+
+   let err = db
+      .fetch_all_json_path("users", "nope", "address.city", json!("Oslo"))
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "UNKNOWN_COLUMN");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_tree() {
+   use sqlx_sqlite_toolkit::Error;
+
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE categories (id INTEGER PRIMARY KEY, parent_id INTEGER, name TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   // Ids are deliberately not in depth-first order
+   db.execute(
+      "INSERT INTO categories (id, parent_id, name) VALUES
+         (1, NULL, 'Electronics'),
+         (2, NULL, 'Books'),
+         (3, 1, 'Phones'),
+         (4, 1, 'Laptops'),
+         (5, 3, 'Android'),
+         (6, 2, 'Fiction'),
+         (7, 3, 'iOS')"
+         .into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let names_and_depths = |rows: Vec<indexmap::IndexMap<String, JsonValue>>| {
+      rows
+         .into_iter()
+         .map(|row| (row["name"].clone(), row["_depth"].clone()))
+         .collect::<Vec<_>>()
+   };
+
+   let forest = db
+      .fetch_tree("categories", "id", "parent_id", None)
+      .await
+      .unwrap();
+   assert_eq!(
+      names_and_depths(forest),
+      [
+         (json!("Electronics"), json!(0)),
+         (json!("Phones"), json!(1)),
+         (json!("Android"), json!(2)),
+         (json!("iOS"), json!(2)),
+         (json!("Laptops"), json!(1)),
+         (json!("Books"), json!(0)),
+         (json!("Fiction"), json!(1)),
+      ]
+   );
+
+   let subtree = db
+      .fetch_tree("categories", "id", "parent_id", Some(json!(3)))
+      .await
+      .unwrap();
+   assert_eq!(
+      names_and_depths(subtree),
+      [
+         (json!("Phones"), json!(0)),
+         (json!("Android"), json!(1)),
+         (json!("iOS"), json!(1)),
+      ]
+   );
+
+   let err = db
+      .fetch_tree("categories", "id", "parent", None)
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::UnknownColumn { column, .. } if column == "parent"));
+   let err = db
+      .fetch_tree("missing", "id", "parent_id", None)
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::TableNotFound(_)));
+}
+
+#[tokio::test]
+async fn test_empty_string_as_null() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE t (id INTEGER PRIMARY KEY, v TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   // `..Default::default()` covers fields that only exist with the `chrono` feature
+   #[allow(clippy::needless_update)]
+   let as_null = BindOptions {
+      empty_string_as_null: true,
+      ..Default::default()
+   };
+
+   // Bound as empty TEXT by default
+   db.execute(
+      "INSERT INTO t (id, v) VALUES (1, ?)".into(),
+      vec![json!("")],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "INSERT INTO t (id, v) VALUES (2, ?)".into(),
+      vec![json!("")],
+   )
+   .bind_options(as_null)
+   .await
+   .unwrap();
+
+   let rows = db
+      .fetch_all("SELECT v, typeof(v) AS t FROM t ORDER BY id".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows[0]["v"], json!(""));
+   assert_eq!(rows[0]["t"], json!("text"));
+   assert_eq!(rows[1]["v"], JsonValue::Null);
+   assert_eq!(rows[1]["t"], json!("null"));
+
+   // Read parameters are converted too; non-empty strings are left alone
+   let row = db
+      .fetch_one(
+         "SELECT ? IS NULL AS is_null, ? AS kept".into(),
+         vec![json!(""), json!("x")],
+      )
+      .bind_options(as_null)
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(row["is_null"], json!(1));
+   assert_eq!(row["kept"], json!("x"));
+
+   db.execute_transaction(vec![("UPDATE t SET v = ? WHERE id = 1", vec![json!("")])])
+      .bind_options(as_null)
+      .await
+      .unwrap();
+   let nulls = db
+      .fetch_scalar("SELECT COUNT(*) FROM t WHERE v IS NULL".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(nulls, Some(json!(2)));
+}
+
+#[tokio::test]
+async fn test_insert_struct() {
+   use sqlx_sqlite_toolkit::Error;
+
+   #[derive(serde::Serialize)]
+   struct NewUser {
+      name: String,
+      active: bool,
+      tags: Vec<String>,
+   }
+
+   #[derive(serde::Serialize)]
+   struct WithTypo {
+      nmae: String,
+   }
+
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, active BOOLEAN, tags TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let user = NewUser {
+      name: "Alice".into(),
+      active: true,
+      tags: vec!["admin".into()],
+   };
+   let result = db.insert("users", &user).await.unwrap();
+   assert_eq!(result.rows_affected, 1);
+   assert_eq!(result.last_insert_id, 1);
+
+   let row = db
+      .fetch_one(
+         "SELECT name, active, tags FROM users WHERE id = 1".into(),
+         vec![],
+      )
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(row.get("name"), Some(&json!("Alice")));
+   assert_eq!(row.get("active"), Some(&json!(1)));
+   assert_eq!(row.get("tags"), Some(&json!(r#"["admin"]"#)));
+
+   let err = db
+      .insert("users", &WithTypo { nmae: "Bob".into() })
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::UnknownColumn { ref column, .. } if column == "nmae"));
+
+   let err = db.insert("missing", &user).await.unwrap_err();
+   assert!(matches!(err, Error::TableNotFound(_)));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_timeout_interrupts_query() {
+   let (db, _temp) = create_test_db().await;
+   // Never terminates on its own
+   let endless = "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) \
+                  SELECT count(*) AS n FROM c";
+
+   let started = Instant::now();
+   let err = db
+      .fetch_all(endless.into(), vec![])
+      .timeout(std::time::Duration::from_millis(100))
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "QUERY_TIMEOUT");
+   assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+   let err = db
+      .fetch_one(endless.into(), vec![])
+      .timeout(std::time::Duration::from_millis(50))
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "QUERY_TIMEOUT");
+
+   // The interrupted connections went back to the pool in working order
+   for _ in 0..8 {
+      let rows = db
+         .fetch_all("SELECT 1 AS one".into(), vec![])
+         .timeout(std::time::Duration::from_secs(5))
+         .await
+         .unwrap();
+      assert_eq!(rows[0]["one"], json!(1));
+   }
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_with_pragma_restores_previous_value() {
+   let (db, _temp) = create_test_db().await;
+   db.execute("CREATE TABLE users (id INTEGER PRIMARY KEY)".into(), vec![])
+      .await
+      .unwrap();
+   db.execute(
+      "CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id))".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   // The orphan order is only accepted while foreign keys are off
+   let enforced_inside = db
+      .with_pragma("foreign_keys", "OFF", async |conn| {
+         sqlx::query("INSERT INTO orders (user_id) VALUES (42)")
+            .execute(&mut *conn)
+            .await?;
+         let enforced: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+            .fetch_one(&mut *conn)
+            .await?;
+         Ok(enforced)
+      })
+      .await
+      .unwrap();
+   assert_eq!(enforced_inside, 0);
+
+   let row = db
+      .fetch_one("PRAGMA foreign_keys".into(), vec![])
+      .on_writer()
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(row["foreign_keys"], json!(1));
+   let err = db
+      .execute("INSERT INTO orders (user_id) VALUES (43)".into(), vec![])
+      .await
+      .unwrap_err();
+   assert!(err.to_string().contains("FOREIGN KEY"), "{err}");
+
+   // Restored even when the scope fails
+   let err = db
+      .with_pragma("foreign_keys", "OFF", async |_conn| -> Result<(), _> {
+         Err(sqlx_sqlite_toolkit::Error::Other("boom".into()))
+      })
+      .await
+      .unwrap_err();
+   assert_eq!(err.to_string(), "boom");
+   let row = db
+      .fetch_one("PRAGMA foreign_keys".into(), vec![])
+      .on_writer()
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(row["foreign_keys"], json!(1));
+
+   // A cancelled scope must not hand the writer back with foreign keys off
+   let cancelled = tokio::time::timeout(
+      std::time::Duration::from_millis(50),
+      db.with_pragma("foreign_keys", "OFF", async |_conn| {
+         tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+         Ok(())
+      }),
+   )
+   .await;
+   assert!(cancelled.is_err());
+   let row = db
+      .fetch_one("PRAGMA foreign_keys".into(), vec![])
+      .on_writer()
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(row["foreign_keys"], json!(1));
+
+   for (pragma, value) in [("key", "x"), ("foreign_keys", "OFF; DROP TABLE users")] {
+      let err = db
+         .with_pragma(pragma, value, async |_conn| Ok(()))
+         .await
+         .unwrap_err();
+      assert_eq!(err.error_code(), "INVALID_PRAGMA");
+   }
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_all_json() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE t (id INTEGER PRIMARY KEY, zeta TEXT, alpha REAL)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "INSERT INTO t (zeta, alpha) VALUES ('a', 1.5), ('b', NULL)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let rows = db
+      .fetch_all_json("SELECT id, zeta, alpha FROM t ORDER BY id".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(
+      rows,
+      json!([
+         {"id": 1, "zeta": "a", "alpha": 1.5},
+         {"id": 2, "zeta": "b", "alpha": null},
+      ])
+   );
+
+   // Keys keep the query's column order, not alphabetical order
+   #[cfg(feature = "preserve_order")]
+   {
+      let keys: Vec<&str> = rows[0]
+         .as_object()
+         .unwrap()
+         .keys()
+         .map(String::as_str)
+         .collect();
+      assert_eq!(keys, ["id", "zeta", "alpha"]);
+      assert_eq!(
+         serde_json::to_string(&rows[1]).unwrap(),
+         r#"{"id":2,"zeta":"b","alpha":null}"#
+      );
+   }
+
+   let empty = db
+      .fetch_all_json("SELECT * FROM t WHERE id > 10".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(empty, json!([]));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_execute_returning() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT, qty INTEGER)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let inserted = db
+      .execute(
+         "INSERT INTO items (name, qty) VALUES (?, ?), (?, ?) RETURNING id, name".into(),
+         vec![json!("apple"), json!(3), json!("pear"), json!(5)],
+      )
+      .execute_returning()
+      .await
+      .unwrap();
+   assert_eq!(inserted.len(), 2);
+   assert_eq!(inserted[0]["id"], json!(1));
+   assert_eq!(inserted[1]["name"], json!("pear"));
+
+   let updated = db
+      .execute(
+         "UPDATE items SET qty = qty + 1 WHERE name = ? RETURNING *".into(),
+         vec![json!("apple")],
+      )
+      .execute_returning()
+      .await
+      .unwrap();
+   assert_eq!(updated.len(), 1);
+   assert_eq!(updated[0]["qty"], json!(4));
+
+   let deleted = db
+      .execute(
+         "DELETE FROM items WHERE qty > 4 RETURNING id".into(),
+         vec![],
+      )
+      .execute_returning()
+      .await
+      .unwrap();
+   let keys: Vec<&str> = deleted[0].keys().map(String::as_str).collect();
+   assert_eq!(keys, ["id"]);
+   assert_eq!(deleted[0]["id"], json!(2));
+
+   // Without RETURNING the statement still runs
+   let rows = db
+      .execute("DELETE FROM items".into(), vec![])
+      .execute_returning()
+      .await
+      .unwrap();
+   assert!(rows.is_empty());
+
+   // Attached database
+   let (other, _other_temp) = create_test_db().await;
+   other
+      .execute(
+         "CREATE TABLE counters (id INTEGER PRIMARY KEY, hits INTEGER)".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+   let spec = sqlx_sqlite_conn_mgr::AttachedSpec {
+      database: std::sync::Arc::clone(other.inner_for_testing()),
+      schema_name: "stats".to_string(),
+      mode: sqlx_sqlite_conn_mgr::AttachedMode::ReadWrite,
+   };
+   let rows = db
+      .execute(
+         "INSERT INTO stats.counters (hits) VALUES (?) RETURNING id, hits".into(),
+         vec![json!(7)],
+      )
+      .attach(vec![spec])
+      .execute_returning()
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+   assert_eq!(rows[0]["hits"], json!(7));
+   let stored = other
+      .fetch_all("SELECT hits FROM counters".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(stored[0]["hits"], json!(7));
+
+   other.remove().await.unwrap();
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_as_attached_cross_database_join() {
+   let (db, _temp) = create_test_db().await;
+   let (other, _other_temp) = create_test_db().await;
+
+   db.execute(
+      "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "INSERT INTO users (id, name) VALUES (1, 'Alice')".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   other
+      .execute(
+         "CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER, total INTEGER)".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+   other
+      .execute(
+         "INSERT INTO orders (user_id, total) VALUES (1, 30), (1, 12)".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+
+   let rows = db
+      .fetch_all(
+         "SELECT u.name, SUM(o.total) AS spent FROM users u \
+          JOIN shop.orders o ON o.user_id = u.id GROUP BY u.id"
+            .into(),
+         vec![],
+      )
+      .attach(vec![
+         other
+            .as_attached("shop", sqlx_sqlite_conn_mgr::AttachedMode::ReadOnly)
+            .unwrap(),
+      ])
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+   assert_eq!(rows[0]["name"], json!("Alice"));
+   assert_eq!(rows[0]["spent"], json!(42));
+
+   for name in ["shop; DETACH main", "1shop", ""] {
+      let err = other
+         .as_attached(name, sqlx_sqlite_conn_mgr::AttachedMode::ReadOnly)
+         .err()
+         .expect("invalid schema name accepted");
+      assert!(matches!(
+         err,
+         sqlx_sqlite_toolkit::Error::ConnectionManager(
+            sqlx_sqlite_conn_mgr::Error::InvalidSchemaName(_)
+         )
+      ));
+   }
+
+   other.remove().await.unwrap();
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_execute_and_read_sees_own_write_under_load() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE events (id INTEGER PRIMARY KEY, kind TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   // Background readers keep the read pool busy
+   let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+   let readers: Vec<_> = (0..4)
+      .map(|_| {
+         let db = db.clone();
+         let stop = std::sync::Arc::clone(&stop);
+         tokio::spawn(async move {
+            while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+               db.fetch_all("SELECT COUNT(*) FROM events".into(), vec![])
+                  .await
+                  .unwrap();
+            }
+         })
+      })
+      .collect();
+
+   for i in 0..50 {
+      let (result, rows) = db
+         .execute(
+            "INSERT INTO events (kind) VALUES (?)".into(),
+            vec![json!(format!("e{i}"))],
+         )
+         .execute_and_read(
+            "SELECT id, kind FROM events WHERE id = ?".into(),
+            vec![json!(i + 1)],
+         )
+         .await
+         .unwrap();
+      assert_eq!(result.last_insert_id, i + 1);
+      assert_eq!(rows.len(), 1, "row {} not visible", i + 1);
+      assert_eq!(rows[0]["kind"], json!(format!("e{i}")));
+   }
+
+   stop.store(true, std::sync::atomic::Ordering::Relaxed);
+   for reader in readers {
+      reader.await.unwrap();
+   }
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_vacuum_shrinks_file_after_delete() {
+   let (db, temp) = create_test_db().await;
+   let db_path = temp.path().join("test.db");
+   db.execute(
+      "CREATE TABLE blobs (id INTEGER PRIMARY KEY, data BLOB)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 500) \
+       INSERT INTO blobs (data) SELECT randomblob(4096) FROM n"
+         .into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute("DELETE FROM blobs".into(), vec![])
+      .await
+      .unwrap();
+
+   // Checkpoint first so the size compared is the main file's, not the WAL's
+   db.execute("PRAGMA wal_checkpoint(TRUNCATE)".into(), vec![])
+      .await
+      .unwrap();
+   let before = std::fs::metadata(&db_path).unwrap().len();
+
+   db.vacuum().await.unwrap();
+
+   let after = std::fs::metadata(&db_path).unwrap().len();
+   assert!(after < before / 10, "{after} should be well below {before}");
+   assert!(db.table_exists("blobs").await.unwrap());
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_run_migrations_with_progress() {
+   use sqlx_sqlite_toolkit::{MigrationStatementProgress, Migrator};
+
+   let migrations = TempDir::new().unwrap();
+   std::fs::write(
+      migrations.path().join("0001_create_notes.sql"),
+      "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT);
+       CREATE INDEX notes_body ON notes (body);",
+   )
+   .unwrap();
+   std::fs::write(
+      migrations.path().join("0002_seed_notes.sql"),
+      "-- seed data; split on statements only
+       INSERT INTO notes (body) VALUES ('first; not a boundary');
+       /* a comment; with a semicolon */
+       INSERT INTO notes (body) VALUES ('second');
+       UPDATE notes SET body = body || '!';",
+   )
+   .unwrap();
+   let migrator = Migrator::new(migrations.path()).await.unwrap();
+
+   let (db, _temp) = create_test_db().await;
+   let mut events = Vec::new();
+   db.run_migrations_with_progress(&migrator, |progress| events.push(progress))
+      .await
+      .unwrap();
+
+   let progress = |version, done, total| MigrationStatementProgress {
+      version,
+      done,
+      total,
+   };
+   assert_eq!(
+      events,
+      vec![
+         progress(1, 1, 2),
+         progress(1, 2, 2),
+         progress(2, 1, 3),
+         progress(2, 2, 3),
+         progress(2, 3, 3),
+      ]
+   );
+
+   let rows = db
+      .fetch_all("SELECT body FROM notes ORDER BY id".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows[0]["body"], json!("first; not a boundary!"));
+   assert_eq!(rows[1]["body"], json!("second!"));
+
+   // Applied migrations are recorded as sqlx records them
+   let mut events = Vec::new();
+   db.run_migrations_with_progress(&migrator, |progress| events.push(progress))
+      .await
+      .unwrap();
+   assert!(events.is_empty());
+   db.run_migrations(&migrator).await.unwrap();
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_truncate_all_preserves_schema_and_migrations() {
+   use sqlx_sqlite_toolkit::Migrator;
+
+   let migrations = TempDir::new().unwrap();
+   std::fs::write(
+      migrations.path().join("0001_schema.sql"),
+      "CREATE TABLE authors (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT);
+       CREATE TABLE books (
+          id INTEGER PRIMARY KEY,
+          author_id INTEGER NOT NULL REFERENCES authors(id),
+          title TEXT
+       );
+       CREATE TABLE settings (key TEXT PRIMARY KEY, value TEXT);",
+   )
+   .unwrap();
+   let migrator = Migrator::new(migrations.path()).await.unwrap();
+
+   let (db, _temp) = create_test_db().await;
+   db.run_migrations(&migrator).await.unwrap();
+   db.execute_script_collecting(
+      "INSERT INTO authors (name) VALUES ('Ann'), ('Bo');
+       INSERT INTO books (author_id, title) VALUES (1, 'A'), (1, 'B'), (2, 'C');
+       INSERT INTO settings VALUES ('theme', 'dark');",
+   )
+   .await
+   .unwrap();
+
+   let deleted = db.truncate_all(&["settings"]).await.unwrap();
+   assert_eq!(deleted, 5);
+
+   for table in ["authors", "books"] {
+      assert!(db.table_exists(table).await.unwrap());
+      let count = db
+         .fetch_one(format!("SELECT COUNT(*) AS n FROM {table}"), vec![])
+         .await
+         .unwrap()
+         .unwrap();
+      assert_eq!(count["n"], json!(0));
+   }
+   let settings = db
+      .fetch_all("SELECT value FROM settings".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(settings.len(), 1);
+
+   let applied = db
+      .fetch_one("SELECT COUNT(*) AS n FROM _sqlx_migrations".into(), vec![])
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(applied["n"], json!(1));
+   db.run_migrations(&migrator).await.unwrap();
+
+   // AUTOINCREMENT restarts
+   let result = db
+      .execute("INSERT INTO authors (name) VALUES ('Cy')".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(result.last_insert_id, 1);
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_table_exists() {
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE people (id INTEGER PRIMARY KEY)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   db.execute(
+      "CREATE VIEW people_view AS SELECT * FROM people".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   assert!(db.table_exists("people").await.unwrap());
+   assert!(!db.table_exists("missing").await.unwrap());
+   assert!(!db.table_exists("people_view").await.unwrap());
+   // Bound, not interpolated
+   assert!(!db.table_exists("people' OR '1'='1").await.unwrap());
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_import_jsonl() {
+   use sqlx_sqlite_toolkit::Error;
+
+   let (db, temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE people (id INTEGER PRIMARY KEY, name TEXT NOT NULL, age INTEGER, active INTEGER DEFAULT 1)"
+         .into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let src = temp.path().join("people.jsonl");
+   std::fs::write(
+      &src,
+      "{\"name\": \"Alice\", \"age\": 30, \"active\": false}\n\n{\"name\": \"Bob\"}\n",
+   )
+   .unwrap();
+
+   let inserted = db.import_jsonl("people", &src).await.unwrap();
+   assert_eq!(inserted, 2);
+
+   let rows = db
+      .fetch_all(
+         "SELECT name, age, active FROM people ORDER BY id".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 2);
+   assert_eq!(rows[0]["name"], json!("Alice"));
+   assert_eq!(rows[0]["age"], json!(30));
+   assert_eq!(rows[0]["active"], json!(0));
+   assert_eq!(rows[1]["age"], JsonValue::Null);
+   assert_eq!(rows[1]["active"], json!(1));
+
+   // Unknown columns are rejected before anything is inserted
+   let bad = temp.path().join("bad.jsonl");
+   std::fs::write(&bad, "{\"name\": \"Carol\"}\n{\"nickname\": \"C\"}\n").unwrap();
+   let err = db.import_jsonl("people", &bad).await.unwrap_err();
+   assert!(matches!(err, Error::UnknownColumn { ref column, .. } if column == "nickname"));
+
+   let err = db.import_jsonl("missing", &src).await.unwrap_err();
+   assert_eq!(err.error_code(), "TABLE_NOT_FOUND");
+
+   let count = db
+      .fetch_one("SELECT COUNT(*) AS n FROM people".into(), vec![])
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(count["n"], json!(2));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_import_jsonl_batches_rows() {
+   let (db, temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT, qty INTEGER)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   // More rows than fit in one 999-parameter statement, with keys in varying
+   // order and some rows using only defaults
+   let mut contents = String::new();
+   for i in 0..1200 {
+      match i % 3 {
+         0 => contents.push_str(&format!("{{\"name\": \"n{i}\", \"qty\": {i}}}\n")),
+         1 => contents.push_str(&format!("{{\"qty\": {i}, \"name\": \"n{i}\"}}\n")),
+         _ => contents.push_str("{}\n"),
+      }
+   }
+   let src = temp.path().join("items.jsonl");
+   std::fs::write(&src, contents).unwrap();
+
+   let inserted = db.import_jsonl("items", &src).await.unwrap();
+   assert_eq!(inserted, 1200);
+
+   let counts = db
+      .fetch_one(
+         "SELECT COUNT(*) AS n, COUNT(name) AS named, SUM(qty) AS total FROM items".into(),
+         vec![],
+      )
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(counts["n"], json!(1200));
+   assert_eq!(counts["named"], json!(800));
+   let expected: i64 = (0..1200).filter(|i| i % 3 != 2).sum();
+   assert_eq!(counts["total"], json!(expected));
+
+   let err = db
+      .import_jsonl("items", temp.path().join("missing.jsonl"))
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "IO_ERROR");
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_bulk_insert() {
+   use sqlx_sqlite_toolkit::Error;
+
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE points (id INTEGER PRIMARY KEY, x INTEGER NOT NULL, y INTEGER, flag INTEGER)"
+         .into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   let columns = ["x", "y", "flag"];
+   let rows = |range: std::ops::Range<i64>| -> Vec<Vec<JsonValue>> {
+      range
+         .map(|i| vec![json!(i), json!(i * 2), json!(i % 2 == 0)])
+         .collect()
+   };
+
+   let result = db
+      .bulk_insert("points", &columns, Vec::new())
+      .await
+      .unwrap();
+   assert_eq!(result.rows_affected, 0);
+
+   // 3 columns fit 333 rows per statement: exactly one chunk, one row over,
+   // and several chunks
+   let mut total = 0;
+   for count in [333, 334, 1000] {
+      let result = db
+         .bulk_insert("points", &columns, rows(total..total + count))
+         .await
+         .unwrap();
+      assert_eq!(result.rows_affected, count as u64);
+      total += count;
+      assert_eq!(result.last_insert_id, total);
+   }
+
+   let stats = db
+      .fetch_one(
+         "SELECT COUNT(*) AS n, SUM(y) AS y, SUM(flag) AS flags FROM points".into(),
+         vec![],
+      )
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(stats["n"], json!(total));
+   assert_eq!(stats["y"], json!(total * (total - 1)));
+   assert_eq!(stats["flags"], json!((total + 1) / 2));
+
+   let err = db
+      .bulk_insert(
+         "points",
+         &columns,
+         vec![vec![json!(1), json!(2), json!(3)], vec![json!(1)]],
+      )
+      .await
+      .unwrap_err();
+   assert!(matches!(
+      err,
+      Error::RowWidthMismatch {
+         row: 1,
+         expected: 3,
+         actual: 1
+      }
+   ));
+
+   let err = db
+      .bulk_insert("points", &["x", "z"], vec![vec![json!(1), json!(2)]])
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::UnknownColumn { ref column, .. } if column == "z"));
+
+   // A failing chunk rolls back the chunks before it
+   let mut bad = rows(0..500);
+   bad[400][0] = JsonValue::Null;
+   let err = db.bulk_insert("points", &columns, bad).await.unwrap_err();
+   assert!(err.error_code().starts_with("SQLITE_"));
+   let count = db
+      .fetch_one("SELECT COUNT(*) AS n FROM points".into(), vec![])
+      .await
+      .unwrap()
+      .unwrap();
+   assert_eq!(count["n"], json!(total));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_with_migrations_refuses_queries_until_complete() {
+   use sqlx_sqlite_toolkit::{Error, Migrator};
+
+   let migrations = TempDir::new().unwrap();
+   std::fs::write(
+      migrations.path().join("0001_create_notes.sql"),
+      "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT);",
+   )
+   .unwrap();
+   let migrator = Migrator::new(migrations.path()).await.unwrap();
+
+   let (db, _temp) = create_test_db().await;
+   let db = db.with_migrations(migrator);
+
+   // The migration task has not run yet on this single-threaded runtime
+   let err = db
+      .fetch_all("SELECT * FROM notes".into(), vec![])
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::MigrationsPending));
+   let err = db
+      .execute("INSERT INTO notes (body) VALUES ('early')".into(), vec![])
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "MIGRATIONS_PENDING");
+
+   db.wait_for_migrations().await.unwrap();
+
+   db.execute("INSERT INTO notes (body) VALUES ('hi')".into(), vec![])
+      .await
+      .unwrap();
+   let rows = db
+      .fetch_all("SELECT body FROM notes".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+   assert_eq!(rows[0]["body"], json!("hi"));
+
+   // A failed run keeps refusing queries
+   let broken_migrations = TempDir::new().unwrap();
+   std::fs::write(broken_migrations.path().join("0001_broken.sql"), "NOT SQL;").unwrap();
+   let broken = Migrator::new(broken_migrations.path()).await.unwrap();
+   let (other, _other_temp) = create_test_db().await;
+   let other = other.with_migrations(broken);
+   let err = other.wait_for_migrations().await.unwrap_err();
+   assert_eq!(err.error_code(), "MIGRATIONS_FAILED");
+   let err = other
+      .fetch_one("SELECT 1".into(), vec![])
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::MigrationsFailed(_)));
+
+   db.remove().await.unwrap();
+   other.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_multiple_statements_rejected() {
+   use sqlx_sqlite_toolkit::Error;
+
+   let (db, _temp) = create_test_db().await;
+   db.execute("CREATE TABLE notes (body TEXT);".into(), vec![])
+      .await
+      .unwrap();
+
+   // Semicolons inside literals and comments are part of a single statement
+   db.execute(
+      "INSERT INTO notes (body) VALUES ('a; DROP TABLE notes') -- trailing; comment".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+
+   let err = db
+      .execute(
+         "INSERT INTO notes (body) VALUES ('x'); DROP TABLE notes".into(),
+         vec![],
+      )
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::MultipleStatements));
+
+   let err = db
+      .fetch_all("SELECT * FROM notes; SELECT 1".into(), vec![])
+      .await
+      .unwrap_err();
+   assert_eq!(err.error_code(), "MULTIPLE_STATEMENTS");
+
+   // Rejected input never ran: the table and its single row survive
+   let rows = db
+      .fetch_all("SELECT body FROM notes".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(rows.len(), 1);
+   assert_eq!(rows[0]["body"], json!("a; DROP TABLE notes"));
+
+   db.remove().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_scalar() {
+   use sqlx_sqlite_toolkit::Error;
+
+   let (db, _temp) = create_test_db().await;
+   db.execute(
+      "CREATE TABLE t (id INTEGER PRIMARY KEY, name TEXT)".into(),
+      vec![],
+   )
+   .await
+   .unwrap();
+   for name in ["a", "b", "c"] {
+      db.execute("INSERT INTO t (name) VALUES (?)".into(), vec![json!(name)])
+         .await
+         .unwrap();
+   }
+
+   let count = db
+      .fetch_scalar("SELECT COUNT(*) FROM t".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(count, Some(json!(3)));
+
+   let name = db
+      .fetch_scalar("SELECT name, id FROM t WHERE id = ?".into(), vec![json!(2)])
+      .await
+      .unwrap();
+   assert_eq!(name, Some(json!("b")));
+
+   let none = db
+      .fetch_scalar("SELECT id FROM t WHERE id = 99".into(), vec![])
+      .await
+      .unwrap();
+   assert_eq!(none, None);
+
+   let err = db
+      .fetch_scalar("SELECT id FROM t".into(), vec![])
+      .await
+      .unwrap_err();
+   assert!(matches!(err, Error::MultipleRowsReturned(3)));
+
+   db.remove().await.unwrap();
+}
@@ -10,6 +10,7 @@ import Database, {
    ColumnValue,
    TableChange,
    TableChangeEvent,
+   SubscribeOptions,
    KeysetColumn,
 } from './index';
 
@@ -52,6 +53,9 @@ beforeEach(() => {
       if (cmd === 'plugin:sqlite|fetch_one') {
          return null;
       }
+      if (cmd === 'plugin:sqlite|fetch_scalar') {
+         return 3;
+      }
       if (cmd === 'plugin:sqlite|close') {
          return true;
       }
@@ -73,6 +77,9 @@ beforeEach(() => {
       if (cmd === 'plugin:sqlite|unsubscribe') {
          return true;
       }
+      if (cmd === 'plugin:sqlite|unsubscribe_all') {
+         return 2;
+      }
       if (cmd === 'plugin:sqlite|unobserve') {
          return undefined;
       }
@@ -180,6 +187,13 @@ describe('Database commands', () => {
       expect(lastArgs).toMatchObject({ db: 't.db', values: [ 1 ], attached: null });
    });
 
+   it('fetch_scalar', async () => {
+      const count = await Database.get('t.db').fetchScalar<number>('SELECT COUNT(*) FROM t');
+      expect(lastCmd).toBe('plugin:sqlite|fetch_scalar');
+      expect(lastArgs).toMatchObject({ db: 't.db', values: [], attached: null });
+      expect(count).toBe(3);
+   });
+
    it('fetch_one with attached databases', async () => {
       await Database.get('main.db')
          .fetchOne('SELECT COUNT(*) as total FROM users u JOIN orders.orders o ON u.id = o.user_id', [])
@@ -321,6 +335,29 @@ describe('Database commands', () => {
       expect(lastArgs.pageSize).toBe(50);
    });
 
+   it('importJsonl', async () => {
+      await Database.get('t.db').importJsonl('users', '/tmp/users.jsonl');
+      expect(lastCmd).toBe('plugin:sqlite|import_jsonl');
+      expect(lastArgs.db).toBe('t.db');
+      expect(lastArgs.table).toBe('users');
+      expect(lastArgs.srcPath).toBe('/tmp/users.jsonl');
+   });
+
+   it('tableExists', async () => {
+      await Database.get('t.db').tableExists('users');
+      expect(lastCmd).toBe('plugin:sqlite|table_exists');
+      expect(lastArgs.db).toBe('t.db');
+      expect(lastArgs.table).toBe('users');
+   });
+
+   it('backupDatabase', async () => {
+      await Database.get('t.db').backupDatabase('backups/t.db', () => { return; });
+      expect(lastCmd).toBe('plugin:sqlite|backup_database');
+      expect(lastArgs.db).toBe('t.db');
+      expect(lastArgs.destPath).toBe('backups/t.db');
+      expect(lastArgs.onProgress).toBeDefined();
+   });
+
    it('close', async () => {
       await Database.get('t.db').close();
       expect(lastCmd).toBe('plugin:sqlite|close');
@@ -507,6 +544,20 @@ describe('Observer commands', () => {
       expect(sub.id).toBe('sub-123');
    });
 
+   it('subscribe with msgpack format', async () => {
+      const options: SubscribeOptions = {
+         format: 'msgpack',
+         decode: () => { return { event: 'lagged', data: { count: 1 } }; },
+      };
+
+      const sub = await Database.get('t.db').subscribe([ 'users' ], () => { return; }, options);
+
+      expect(lastCmd).toBe('plugin:sqlite|subscribe');
+      expect(lastArgs.options).toEqual({ format: 'msgpack' });
+      expect(lastArgs.onEvent).toBeDefined();
+      expect(sub.id).toBe('sub-123');
+   });
+
    it('unsubscribe', async () => {
       const sub = new Subscription('sub-456');
 
@@ -519,6 +570,14 @@ describe('Observer commands', () => {
       expect(result).toBe(true);
    });
 
+   it('unsubscribeAll', async () => {
+      const result = await Database.get('t.db').unsubscribeAll();
+
+      expect(lastCmd).toBe('plugin:sqlite|unsubscribe_all');
+      expect(lastArgs.db).toBe('t.db');
+      expect(result).toBe(2);
+   });
+
    it('unobserve', async () => {
       await Database.get('t.db').unobserve();
       expect(lastCmd).toBe('plugin:sqlite|unobserve');
@@ -581,6 +640,7 @@ describe('Observer types', () => {
    it('TableChange structure', () => {
       const change: TableChange = {
          table: 'users',
+         schema: 'main',
          operation: 'insert',
          rowid: 1,
          primaryKey: [ { type: 'integer', value: 1 } ],
@@ -588,21 +648,30 @@ describe('Observer types', () => {
             { type: 'integer', value: 1 },
             { type: 'text', value: 'Alice' },
          ],
+         columnNames: [ 'id', 'name' ],
+         schemaStale: false,
+         capturedAt: 1700000000123,
       };
 
       expect(change.table).toBe('users');
+      expect(change.schema).toBe('main');
       expect(change.operation).toBe('insert');
       expect(change.rowid).toBe(1);
       expect(change.primaryKey).toHaveLength(1);
       expect(change.oldValues).toBeUndefined();
       expect(change.newValues).toHaveLength(2);
+      expect(change.columnNames).toEqual([ 'id', 'name' ]);
    });
 
    it('TableChange without rowid', () => {
       const change: TableChange = {
          table: 'kv_store',
+         schema: 'main',
          operation: 'update',
          primaryKey: [ { type: 'text', value: 'my-key' } ],
+         columnNames: [],
+         schemaStale: false,
+         capturedAt: 1700000000123,
       };
 
       expect(change.rowid).toBeUndefined();
@@ -614,8 +683,12 @@ describe('Observer types', () => {
          event: 'change',
          data: {
             table: 'users',
+            schema: 'main',
             operation: 'delete',
             primaryKey: [ { type: 'integer', value: 5 } ],
+            columnNames: [],
+            schemaStale: false,
+            capturedAt: 1700000000123,
          },
       };
 
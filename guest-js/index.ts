@@ -264,6 +264,37 @@ export interface MigrationEvent {
    error?: string;
 }
 
+/**
+ * Payload of the `sqlite:migration-progress` event, emitted after each statement
+ * of a migration when the plugin is built with `migration_statement_progress()`.
+ */
+export interface MigrationProgressEvent {
+
+   /** Database path (relative, as registered with the plugin) */
+   dbPath: string;
+
+   /** Version of the migration being applied */
+   version: number;
+
+   /** Statements of the migration executed so far */
+   done: number;
+
+   /** Statements in the migration */
+   total: number;
+}
+
+/**
+ * Progress of {@link Database.backupDatabase}, reported after each batch of pages.
+ */
+export interface BackupProgress {
+
+   /** Pages still to be copied */
+   remaining: number;
+
+   /** Total pages in the source database */
+   total: number;
+}
+
 // ─── Pagination Types ───
 
 /**
@@ -323,6 +354,16 @@ export interface ObserverConfig {
     * reducing memory usage per notification.
     */
    captureValues?: boolean;
+
+   /**
+    * Whether to fill in `VIRTUAL` generated column values in `newValues`.
+    *
+    * SQLite does not compute virtual generated columns when capturing a change,
+    * so they are reported as `null`. When `true`, each changed row of a table with
+    * such columns is re-read after commit, at the cost of one extra read per row.
+    * Default: false.
+    */
+   resolveGeneratedColumns?: boolean;
 }
 
 /**
@@ -350,6 +391,9 @@ export interface TableChange {
    /** Name of the table that was changed */
    table: string;
 
+   /** Schema the table belongs to: `main`, or the name of an attached database */
+   schema: string;
+
    /** The type of change operation (insert, update, delete) */
    operation?: ChangeOperation;
 
@@ -364,6 +408,18 @@ export interface TableChange {
 
    /** Column values after the change (for insert and update) */
    newValues?: ColumnValue[];
+
+   /** Column names, in the same order as `oldValues`/`newValues` (empty when unknown) */
+   columnNames: string[];
+
+   /**
+    * Whether the table's schema changed since it was observed, leaving
+    * `primaryKey` and `columnNames` empty; re-read the row to get its state
+    */
+   schemaStale: boolean;
+
+   /** When the change was captured, in milliseconds since the Unix epoch */
+   capturedAt: number;
 }
 
 /**
@@ -377,6 +433,19 @@ export type TableChangeEvent =
    | { event: 'change'; data: TableChange }
    | { event: 'lagged'; data: { count: number } };
 
+/**
+ * Options for a change subscription.
+ *
+ * `json` (the default) sends each event as JSON text. `msgpack` sends each event
+ * as a MessagePack-encoded `ArrayBuffer` with the same structure as the JSON
+ * event, which is cheaper for high-frequency change streams. The plugin does
+ * not bundle a MessagePack decoder: supply one as `decode`, e.g. `decode` from
+ * `@msgpack/msgpack`.
+ */
+export type SubscribeOptions =
+   | { format?: 'json' }
+   | { format: 'msgpack'; decode: (bytes: ArrayBuffer) => TableChangeEvent };
+
 /**
  * Represents an active subscription to table change notifications.
  *
@@ -506,6 +575,55 @@ class FetchOneBuilder<T> implements PromiseLike<T | undefined> {
    }
 }
 
+/**
+ * Builder for SELECT queries returning a single value
+ */
+class FetchScalarBuilder<T> implements PromiseLike<T | undefined> {
+   private readonly _db: Database;
+   private readonly _query: string;
+   private readonly _bindValues: SqlValue[];
+   private _attached: AttachedDatabaseSpec[];
+
+   public constructor(
+      db: Database,
+      query: string,
+      bindValues: SqlValue[],
+      attached: AttachedDatabaseSpec[] = []
+   ) {
+      this._db = db;
+      this._query = query;
+      this._bindValues = bindValues;
+      this._attached = attached;
+   }
+
+   /**
+    * Attach databases for cross-database queries
+    */
+   public attach(specs: AttachedDatabaseSpec[]): this {
+      this._attached = specs;
+      return this;
+   }
+
+   /**
+    * Make the builder directly awaitable
+    */
+   public then<TResult1 = T | undefined, TResult2 = never>(
+      onfulfilled?: ((value: T | undefined) => TResult1 | PromiseLike<TResult1>) | null,
+      onrejected?: ((reason: unknown) => TResult2 | PromiseLike<TResult2>) | null
+   ): PromiseLike<TResult1 | TResult2> {
+      return this._execute().then(onfulfilled, onrejected);
+   }
+
+   private async _execute(): Promise<T | undefined> {
+      return await invoke<T | undefined>('plugin:sqlite|fetch_scalar', {
+         db: this._db.path,
+         query: this._query,
+         values: this._bindValues,
+         attached: this._attached.length > 0 ? this._attached : null,
+      });
+   }
+}
+
 /**
  * Builder for paginated SELECT queries using keyset (cursor-based) pagination
  */
@@ -928,6 +1046,109 @@ export default class Database {
       return new TransactionBuilder(this, statements);
    }
 
+   /**
+    * **fetchScalar**
+    *
+    * Creates a builder for SELECT queries returning a single value: the first column
+    * of zero or one row. Returns `undefined` if no rows match, and rejects if more
+    * than one row matches.
+    * Returns a builder that can optionally attach databases before executing.
+    *
+    * @param query - SQL SELECT query
+    * @param bindValues - Optional parameter values
+    *
+    * @example
+    * ```ts
+    * const count = await db.fetchScalar<number>('SELECT COUNT(*) FROM todos');
+    * ```
+    */
+   public fetchScalar<T = SqlValue>(query: string, bindValues?: SqlValue[]): FetchScalarBuilder<T> {
+      return new FetchScalarBuilder<T>(this, query, bindValues ?? []);
+   }
+
+   /**
+    * **importJsonl**
+    *
+    * Bulk-loads a JSON Lines file into an existing table. Each line must be a JSON
+    * object whose keys are column names of `table`. All rows are inserted in a single
+    * transaction, so any invalid line or unknown column leaves the table unchanged.
+    *
+    * @param table - Name of the table to insert into
+    * @param srcPath - Path to the `.jsonl` file, relative to the app config directory.
+    *   Requires the `sqlite:allow-import-jsonl` permission, which is not in `sqlite:default`.
+    * @returns Number of rows inserted
+    *
+    * @example
+    * ```ts
+    * const inserted = await db.importJsonl('users', 'seed/users.jsonl');
+    * ```
+    */
+   public async importJsonl(table: string, srcPath: string): Promise<number> {
+      return await invoke<number>('plugin:sqlite|import_jsonl', {
+         db: this.path,
+         table,
+         srcPath,
+      });
+   }
+
+   /**
+    * **tableExists**
+    *
+    * Checks whether a table exists, e.g. before creating it. Views and indexes of the
+    * same name do not count. The name is bound as a parameter, never interpolated.
+    *
+    * @param table - Name of the table to look for
+    * @returns `true` if the table exists
+    *
+    * @example
+    * ```ts
+    * if (!(await db.tableExists('todos'))) {
+    *    await db.execute('CREATE TABLE todos (id INTEGER PRIMARY KEY, title TEXT)');
+    * }
+    * ```
+    */
+   public async tableExists(table: string): Promise<boolean> {
+      return await invoke<boolean>('plugin:sqlite|table_exists', {
+         db: this.path,
+         table,
+      });
+   }
+
+   /**
+    * **backupDatabase**
+    *
+    * Copies the database into a new file using SQLite's online backup API. Pages are
+    * copied in batches without blocking readers, and `onProgress` is called after each
+    * batch. The destination is resolved relative to the app config directory, like
+    * database paths, and must not already exist.
+    *
+    * @param destPath - Path of the backup file to create
+    * @param onProgress - Optional callback receiving `{ remaining, total }` page counts
+    *
+    * @example
+    * ```ts
+    * await db.backupDatabase('backups/mydb.db', ({ remaining, total }) => {
+    *    console.log(`Backup ${Math.round(100 * (total - remaining) / total)}% done`);
+    * });
+    * ```
+    */
+   public async backupDatabase(
+      destPath: string,
+      onProgress?: (progress: BackupProgress) => void
+   ): Promise<void> {
+      const channel = new Channel<BackupProgress>();
+
+      if (onProgress) {
+         channel.onmessage = onProgress;
+      }
+
+      await invoke<void>('plugin:sqlite|backup_database', {
+         db: this.path,
+         destPath,
+         onProgress: channel,
+      });
+   }
+
    /**
     * **fetchAll**
     *
@@ -1125,6 +1346,7 @@ export default class Database {
     *
     * @param tables - Table names to receive notifications for
     * @param onEvent - Callback invoked for each change event
+    * @param options - Optional payload format; see `SubscribeOptions`
     * @returns A Subscription that can be used to stop receiving notifications
     *
     * @example
@@ -1142,25 +1364,75 @@ export default class Database {
     *
     * // Later, stop receiving notifications
     * await subscription.unsubscribe();
+    *
+    * // Compact binary payloads for high-frequency streams
+    * import { decode } from '@msgpack/msgpack';
+    *
+    * await db.subscribe(['users'], handleEvent, {
+    *    format: 'msgpack',
+    *    decode: (bytes) => { return decode(bytes) as TableChangeEvent; },
+    * });
     * ```
     */
    public async subscribe(
       tables: string[],
-      onEvent: (event: TableChangeEvent) => void
+      onEvent: (event: TableChangeEvent) => void,
+      options?: SubscribeOptions
    ): Promise<Subscription> {
-      const channel = new Channel<TableChangeEvent>();
+      let subscriptionId: string;
 
-      channel.onmessage = onEvent;
+      if (options?.format === 'msgpack') {
+         const decode = options.decode;
 
-      const subscriptionId = await invoke<string>('plugin:sqlite|subscribe', {
-         db: this.path,
-         tables,
-         onEvent: channel,
-      });
+         const channel = new Channel<ArrayBuffer>();
+
+         channel.onmessage = (bytes) => { onEvent(decode(bytes)); };
+
+         subscriptionId = await invoke<string>('plugin:sqlite|subscribe', {
+            db: this.path,
+            tables,
+            onEvent: channel,
+            options: { format: 'msgpack' },
+         });
+      } else {
+         const channel = new Channel<TableChangeEvent>();
+
+         channel.onmessage = onEvent;
+
+         subscriptionId = await invoke<string>('plugin:sqlite|subscribe', {
+            db: this.path,
+            tables,
+            onEvent: channel,
+            options: null,
+         });
+      }
 
       return new Subscription(subscriptionId);
    }
 
+   /**
+    * **unsubscribeAll**
+    *
+    * Cancel every subscription to this database, e.g. when a view that
+    * subscribed to several tables unmounts. Observation stays enabled, so new
+    * subscriptions can be made afterwards.
+    *
+    * @returns The number of subscriptions cancelled
+    *
+    * @example
+    * ```ts
+    * await db.subscribe(['users'], onUsers);
+    * await db.subscribe(['posts'], onPosts);
+    *
+    * const cancelled = await db.unsubscribeAll(); // 2
+    * ```
+    */
+   public async unsubscribeAll(): Promise<number> {
+      return await invoke<number>('plugin:sqlite|unsubscribe_all', {
+         db: this.path,
+      });
+   }
+
    /**
     * **unobserve**
     *
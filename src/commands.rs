@@ -10,7 +10,7 @@ use serde_json::Value as JsonValue;
 use sqlx_sqlite_conn_mgr::SqliteDatabaseConfig;
 use sqlx_sqlite_toolkit::{
    ActiveInterruptibleTransaction, ActiveInterruptibleTransactions, ActiveRegularTransactions,
-   DatabaseWrapper, Statement, TransactionWriter, WriteQueryResult,
+   BackupProgress, DatabaseWrapper, Statement, TransactionWriter, WriteQueryResult,
 };
 use std::sync::Arc;
 use tauri::ipc::Channel;
@@ -20,9 +20,7 @@ use uuid::Uuid;
 
 use crate::{
    DbInstances, Error, MigrationEvent, MigrationStates, MigrationStatus, Result,
-   subscriptions::{
-      ActiveSubscriptions, ObserverConfigParams, TableChangePayload, event_to_payload,
-   },
+   subscriptions::{ActiveSubscriptions, ObserverConfigParams, SubscribeOptions, event_to_payload},
 };
 
 /// Token representing an active interruptible transaction
@@ -140,7 +138,8 @@ pub async fn load<R: Runtime>(
       }
       Entry::Vacant(entry) => {
          // We won the race, create and insert the wrapper
-         let wrapper = crate::resolve::connect(&db, &app, custom_config).await?;
+         let mut wrapper = crate::resolve::connect(&db, &app, custom_config).await?;
+         wrapper.set_max_transaction_statements(db_instances.max_transaction_statements);
          entry.insert(wrapper);
          Ok(db)
       }
@@ -154,7 +153,10 @@ pub async fn load<R: Runtime>(
 /// - Migrations completed successfully
 ///
 /// Returns Err if migrations failed.
-async fn await_migrations(migration_states: &State<'_, MigrationStates>, db: &str) -> Result<()> {
+pub(crate) async fn await_migrations(
+   migration_states: &State<'_, MigrationStates>,
+   db: &str,
+) -> Result<()> {
    loop {
       // Get notify handle before checking status
       let notify = {
@@ -290,6 +292,94 @@ pub async fn execute_transaction(
    }
 }
 
+/// Bulk-load a JSON Lines file into an existing table.
+///
+/// `src_path` is resolved relative to the app config directory, like database paths.
+/// Each line is a JSON object mapping column names to values. All rows are inserted in a
+/// single transaction; returns the number of rows inserted.
+#[tauri::command]
+pub async fn import_jsonl<R: Runtime>(
+   app: AppHandle<R>,
+   db_instances: State<'_, DbInstances>,
+   db: String,
+   table: String,
+   src_path: String,
+) -> Result<u64> {
+   if crate::resolve::is_memory_path(&src_path) {
+      return Err(Error::InvalidPath(format!(
+         "import source must be a file: {src_path}"
+      )));
+   }
+   let src = crate::resolve::resolve_database_path(&src_path, &app)?;
+
+   let instances = db_instances.inner.read().await;
+
+   let wrapper = instances
+      .get(&db)
+      .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?;
+
+   let inserted = wrapper.import_jsonl(&table, &src).await?;
+
+   Ok(inserted)
+}
+
+/// Check whether a table exists in a loaded database.
+///
+/// The table name is bound as a query parameter, never interpolated into SQL.
+#[tauri::command]
+pub async fn table_exists(
+   db_instances: State<'_, DbInstances>,
+   db: String,
+   table: String,
+) -> Result<bool> {
+   let instances = db_instances.inner.read().await;
+
+   let wrapper = instances
+      .get(&db)
+      .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?;
+
+   let exists = wrapper.table_exists(&table).await?;
+
+   Ok(exists)
+}
+
+/// Back up a database into a new file using SQLite's online backup API.
+///
+/// `dest_path` is resolved relative to the app config directory, like database paths.
+/// Pages are copied in batches without blocking readers, and `{ remaining, total }`
+/// page counts are sent on `on_progress` after each batch. Fails if the destination
+/// already exists.
+#[tauri::command]
+pub async fn backup_database<R: Runtime>(
+   app: AppHandle<R>,
+   db_instances: State<'_, DbInstances>,
+   db: String,
+   dest_path: String,
+   on_progress: Channel<BackupProgress>,
+) -> Result<()> {
+   if crate::resolve::is_memory_path(&dest_path) {
+      return Err(Error::InvalidPath(format!(
+         "backup destination must be a file: {dest_path}"
+      )));
+   }
+   let dest = crate::resolve::resolve_database_path(&dest_path, &app)?;
+
+   let instances = db_instances.inner.read().await;
+
+   let wrapper = instances
+      .get(&db)
+      .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?;
+
+   wrapper
+      .backup(&dest, |progress| {
+         // A closed channel only means the frontend stopped listening
+         let _ = on_progress.send(progress);
+      })
+      .await?;
+
+   Ok(())
+}
+
 /// Execute a SELECT query returning all matching rows.
 ///
 /// Returns the entire result set in a single response. For large or unbounded queries,
@@ -347,6 +437,35 @@ pub async fn fetch_one(
    Ok(result)
 }
 
+/// Execute a SELECT query returning the first column of zero or one row
+///
+/// Avoids sending a full row map over IPC for single-value queries such as counts.
+#[tauri::command]
+pub async fn fetch_scalar(
+   db_instances: State<'_, DbInstances>,
+   db: String,
+   query: String,
+   values: Vec<JsonValue>,
+   attached: Option<Vec<AttachedDatabaseSpec>>,
+) -> Result<Option<JsonValue>> {
+   let instances = db_instances.inner.read().await;
+
+   let wrapper = instances
+      .get(&db)
+      .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?;
+
+   let mut builder = wrapper.fetch_scalar(query, values);
+
+   if let Some(specs) = attached {
+      let resolved_specs = resolve_attached_specs(specs, &instances)?;
+      builder = builder.attach(resolved_specs);
+   }
+
+   let result = builder.execute().await?;
+
+   Ok(result)
+}
+
 /// Execute a paginated SELECT query using keyset (cursor-based) pagination
 #[allow(clippy::too_many_arguments)]
 #[tauri::command]
@@ -505,6 +624,8 @@ pub async fn begin_interruptible_transaction(
       .get(&db)
       .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?;
 
+   wrapper.check_transaction_statements(initial_statements.len())?;
+
    // Generate unique transaction ID
    let transaction_id = Uuid::new_v4().to_string();
 
@@ -542,12 +663,18 @@ pub async fn begin_interruptible_transaction(
 /// Returns a new token if continuing with more statements, or None if committed/rolled back.
 #[tauri::command]
 pub async fn transaction_continue(
+   db_instances: State<'_, DbInstances>,
    active_txs: State<'_, ActiveInterruptibleTransactions>,
    token: TransactionToken,
    action: TransactionAction,
 ) -> Result<Option<TransactionToken>> {
    match action {
       TransactionAction::Continue { statements } => {
+         // Reject oversized batches up front, leaving the transaction intact
+         if let Some(wrapper) = db_instances.inner.read().await.get(&token.db_path) {
+            wrapper.check_transaction_statements(statements.len())?;
+         }
+
          // Remove transaction to get mutable access
          let mut tx = active_txs
             .remove(&token.db_path, &token.transaction_id)
@@ -681,6 +808,9 @@ pub async fn observe(
       if let Some(capture) = params.capture_values {
          observer_config = observer_config.with_capture_values(capture);
       }
+      if let Some(resolve) = params.resolve_generated_columns {
+         observer_config = observer_config.with_resolve_generated_columns(resolve);
+      }
    }
 
    wrapper.enable_observation(observer_config);
@@ -690,7 +820,8 @@ pub async fn observe(
 /// Subscribe to change notifications for specific tables.
 ///
 /// Returns a subscription ID that can be used to unsubscribe later.
-/// Change events are streamed to the frontend via Tauri Channel.
+/// Change events are streamed to the frontend via Tauri Channel, encoded in
+/// the format selected by `options` (JSON by default).
 ///
 /// Requires `observe()` to have been called first.
 #[tauri::command]
@@ -699,7 +830,8 @@ pub async fn subscribe(
    active_subs: State<'_, ActiveSubscriptions>,
    db: String,
    tables: Vec<String>,
-   on_event: Channel<TableChangePayload>,
+   on_event: Channel,
+   options: Option<SubscribeOptions>,
 ) -> Result<String> {
    const MAX_SUBSCRIPTIONS_PER_DATABASE: usize = 100;
 
@@ -727,11 +859,18 @@ pub async fn subscribe(
    // Spawn task to forward stream events to the Tauri Channel
    let sub_id = subscription_id.clone();
    let db_path = db.clone();
+   let format = options.unwrap_or_default().format;
 
    let handle = tokio::spawn(async move {
       while let Some(event) = stream.next().await {
-         let payload = event_to_payload(event);
-         if on_event.send(payload).is_err() {
+         let body = match format.encode(&event_to_payload(event)) {
+            Ok(body) => body,
+            Err(e) => {
+               tracing::error!("Subscription {} failed to encode event: {}", sub_id, e);
+               continue;
+            }
+         };
+         if on_event.send(body).is_err() {
             // Channel closed (frontend disconnected)
             debug!("Subscription {} channel closed, stopping", sub_id);
             break;
@@ -760,6 +899,18 @@ pub async fn unsubscribe(
    Ok(active_subs.remove(&subscription_id).await)
 }
 
+/// Cancel every subscription to a database, e.g. when a view unmounts.
+///
+/// Observation stays enabled, so new subscriptions can be made. Returns the
+/// number of subscriptions cancelled.
+#[tauri::command]
+pub async fn unsubscribe_all(
+   active_subs: State<'_, ActiveSubscriptions>,
+   db: String,
+) -> Result<usize> {
+   Ok(active_subs.remove_for_db(&db).await)
+}
+
 /// Disable observation on a database.
 ///
 /// Stops tracking changes and aborts all subscriptions for this database.
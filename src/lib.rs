@@ -14,6 +14,7 @@ mod resolve;
 mod subscriptions;
 
 pub use error::{Error, Result};
+pub use resolve::{connect_in_app_config, connect_in_app_data, connect_in_temp};
 pub use sqlx_sqlite_conn_mgr::{
    AttachedMode, AttachedSpec, Migrator as SqliteMigrator, SqliteDatabaseConfig,
 };
@@ -52,6 +53,8 @@ impl<R: Runtime> Drop for ExitGuard<R> {
 pub struct DbInstances {
    pub(crate) inner: Arc<RwLock<HashMap<String, DatabaseWrapper>>>,
    pub(crate) max: usize,
+   /// Statement limit applied to each transaction call on loaded databases.
+   pub(crate) max_transaction_statements: Option<usize>,
 }
 
 impl Default for DbInstances {
@@ -59,6 +62,7 @@ impl Default for DbInstances {
       Self {
          inner: Arc::new(RwLock::new(HashMap::new())),
          max: DEFAULT_MAX_DATABASES,
+         max_transaction_statements: None,
       }
    }
 }
@@ -69,8 +73,15 @@ impl DbInstances {
       Self {
          inner: Arc::new(RwLock::new(HashMap::new())),
          max,
+         max_transaction_statements: None,
       }
    }
+
+   /// Limit the number of statements accepted by each transaction call.
+   pub fn with_max_transaction_statements(mut self, max: Option<usize>) -> Self {
+      self.max_transaction_statements = max;
+      self
+   }
 }
 
 /// Migration status for a database.
@@ -178,10 +189,18 @@ pub struct MigrationEvent {
 pub struct Builder {
    /// Migrations registered per database path
    migrations: HashMap<String, Arc<Migrator>>,
-   /// Timeout for interruptible transactions. Defaults to 5 minutes.
+   /// Idle timeout for interruptible transactions. Defaults to 5 minutes.
    transaction_timeout: Option<std::time::Duration>,
+   /// Maximum age of interruptible transactions. Defaults to none.
+   transaction_total_timeout: Option<std::time::Duration>,
    /// Maximum number of concurrently loaded databases. Defaults to 50.
    max_databases: Option<usize>,
+   /// Maximum statements per transaction call. Defaults to none.
+   max_transaction_statements: Option<usize>,
+   /// Emit per-statement migration progress events. Defaults to false.
+   migration_statement_progress: bool,
+   /// Database paths opened at plugin setup, in registration order
+   preload: Vec<String>,
 }
 
 impl Builder {
@@ -190,7 +209,11 @@ impl Builder {
       Self {
          migrations: HashMap::new(),
          transaction_timeout: None,
+         transaction_total_timeout: None,
          max_databases: None,
+         max_transaction_statements: None,
+         migration_statement_progress: false,
+         preload: Vec::new(),
       }
    }
 
@@ -220,10 +243,12 @@ impl Builder {
       self
    }
 
-   /// Set the timeout for interruptible transactions.
+   /// Set the idle timeout for interruptible transactions.
    ///
-   /// If an interruptible transaction exceeds this duration, it will be automatically
-   /// rolled back on the next access attempt. Defaults to 5 minutes.
+   /// If an interruptible transaction goes this long without a `continue` or
+   /// `read` call, it will be automatically rolled back on the next access
+   /// attempt. Each call resets the timer, so long-running but active
+   /// transactions are not affected. Defaults to 5 minutes.
    ///
    /// Returns `Err(Error::InvalidConfig)` if `timeout` is zero.
    pub fn transaction_timeout(mut self, timeout: std::time::Duration) -> Result<Self> {
@@ -236,6 +261,23 @@ impl Builder {
       Ok(self)
    }
 
+   /// Set the maximum age of interruptible transactions.
+   ///
+   /// Unlike [`transaction_timeout`](Self::transaction_timeout), this is measured
+   /// from when the transaction began, so it also ends active transactions. Use it
+   /// to bound how long the write lock can be held. Defaults to no limit.
+   ///
+   /// Returns `Err(Error::InvalidConfig)` if `timeout` is zero.
+   pub fn transaction_total_timeout(mut self, timeout: std::time::Duration) -> Result<Self> {
+      if timeout.is_zero() {
+         return Err(Error::InvalidConfig(
+            "transaction_total_timeout must be greater than zero".to_string(),
+         ));
+      }
+      self.transaction_total_timeout = Some(timeout);
+      Ok(self)
+   }
+
    /// Set the maximum number of databases that can be loaded simultaneously.
    ///
    /// Prevents unbounded memory growth from connection pool proliferation.
@@ -252,22 +294,77 @@ impl Builder {
       Ok(self)
    }
 
+   /// Set the maximum number of statements in a single transaction call.
+   ///
+   /// Applies to `executeTransaction()`, the initial statements of
+   /// `beginInterruptibleTransaction()` and each `continueWith()` call. Larger
+   /// requests fail with `TOO_MANY_STATEMENTS` before any statement executes,
+   /// so an oversized statement list from the frontend cannot tie up the
+   /// writer. Defaults to no limit.
+   ///
+   /// Returns `Err(Error::InvalidConfig)` if `max` is zero.
+   pub fn max_transaction_statements(mut self, max: usize) -> Result<Self> {
+      if max == 0 {
+         return Err(Error::InvalidConfig(
+            "max_transaction_statements must be greater than zero".to_string(),
+         ));
+      }
+      self.max_transaction_statements = Some(max);
+      Ok(self)
+   }
+
+   /// Report progress through each migration's statements.
+   ///
+   /// Registered migrations are split into statements and executed one at a
+   /// time, emitting a `sqlite:migration-progress` event with the migration
+   /// version and statements done and total after each. Every migration still
+   /// runs in one transaction. Progress events are not cached for
+   /// `getMigrationEvents()`.
+   pub fn migration_statement_progress(mut self) -> Self {
+      self.migration_statement_progress = true;
+      self
+   }
+
+   /// Open a database at plugin setup, before the frontend calls `load`.
+   ///
+   /// The database is connected in the background (after its migrations, if
+   /// any) and added to the loaded databases, so the first `load` of `path`
+   /// returns it without connecting. A failure is logged and left for `load`
+   /// to report. Preloaded databases count towards `max_databases`.
+   ///
+   /// * `path` - Database path (relative to app config directory), as passed
+   ///   to `load`
+   pub fn preload(mut self, path: &str) -> Self {
+      if !self.preload.iter().any(|p| p == path) {
+         self.preload.push(path.to_string());
+      }
+      self
+   }
+
    /// Build the plugin with command registration and state management.
    pub fn build<R: Runtime>(self) -> tauri::plugin::TauriPlugin<R> {
       let migrations = Arc::new(self.migrations);
       let transaction_timeout = self.transaction_timeout;
+      let transaction_total_timeout = self.transaction_total_timeout;
       let max_databases = self.max_databases;
+      let max_transaction_statements = self.max_transaction_statements;
+      let migration_statement_progress = self.migration_statement_progress;
+      let preload = self.preload;
 
       PluginBuilder::<R>::new("sqlite")
          .invoke_handler(tauri::generate_handler![
             commands::load,
             commands::execute,
             commands::execute_transaction,
+            commands::import_jsonl,
+            commands::table_exists,
+            commands::backup_database,
             commands::begin_interruptible_transaction,
             commands::transaction_continue,
             commands::transaction_read,
             commands::fetch_all,
             commands::fetch_one,
+            commands::fetch_scalar,
             commands::fetch_page,
             commands::close,
             commands::close_all,
@@ -276,18 +373,26 @@ impl Builder {
             commands::observe,
             commands::subscribe,
             commands::unsubscribe,
+            commands::unsubscribe_all,
             commands::unobserve,
          ])
          .setup(move |app, _api| {
-            app.manage(match max_databases {
-               Some(max) => DbInstances::new(max),
-               None => DbInstances::default(),
-            });
+            app.manage(
+               match max_databases {
+                  Some(max) => DbInstances::new(max),
+                  None => DbInstances::default(),
+               }
+               .with_max_transaction_statements(max_transaction_statements),
+            );
             app.manage(MigrationStates::default());
-            app.manage(match transaction_timeout {
+            let mut active_txs = match transaction_timeout {
                Some(timeout) => ActiveInterruptibleTransactions::new(timeout),
                None => ActiveInterruptibleTransactions::default(),
-            });
+            };
+            if let Some(timeout) = transaction_total_timeout {
+               active_txs = active_txs.with_total_timeout(timeout);
+            }
+            app.manage(active_txs);
             app.manage(ActiveRegularTransactions::default());
             app.manage(subscriptions::ActiveSubscriptions::default());
 
@@ -310,11 +415,25 @@ impl Builder {
                   let migrator = Arc::clone(migrator);
 
                   tauri::async_runtime::spawn(async move {
-                     run_migrations_for_database(app_handle, path, migrator).await;
+                     run_migrations_for_database(
+                        app_handle,
+                        path,
+                        migrator,
+                        migration_statement_progress,
+                     )
+                     .await;
                   });
                }
             }
 
+            for path in preload.iter() {
+               let app_handle = app.clone();
+               let path = path.clone();
+               tauri::async_runtime::spawn(async move {
+                  preload_database(app_handle, path).await;
+               });
+            }
+
             debug!("SQLite plugin initialized");
             Ok(())
          })
@@ -359,46 +478,24 @@ impl Builder {
                   tauri::async_runtime::spawn(async move {
                      let _guard = ExitGuard { app_handle };
 
-                     // Scope block: drops the RwLock write guard (from instances_clone)
-                     // before _guard fires exit(), whose RunEvent::Exit handler calls
-                     // try_read() on the same lock.
-                     {
-                        let timeout_result = tokio::time::timeout(
-                           std::time::Duration::from_secs(5),
-                           async {
-                              // First, abort all subscriptions and transactions
-                              debug!("Aborting active subscriptions and transactions");
-                              active_subs_clone.abort_all().await;
-                              sqlx_sqlite_toolkit::cleanup_all_transactions(&interruptible_txs_clone, &regular_txs_clone).await;
-
-                              // Close databases (each wrapper's close() disables its own
-                              // observer at the crate level, unregistering SQLite hooks)
-                              let mut guard = instances_clone.inner.write().await;
-                              let wrappers: Vec<DatabaseWrapper> =
-                                 guard.drain().map(|(_, v)| v).collect();
-
-                              // Close databases in parallel
-                              let mut set = tokio::task::JoinSet::new();
-                              for wrapper in wrappers {
-                                 set.spawn(async move { wrapper.close().await });
-                              }
-
-                              while let Some(result) = set.join_next().await {
-                                 match result {
-                                    Ok(Err(e)) => warn!("Error closing database: {:?}", e),
-                                    Err(e) => warn!("Database close task panicked: {:?}", e),
-                                    Ok(Ok(())) => {}
-                                 }
-                              }
-                           },
-                        )
-                        .await;
-
-                        if timeout_result.is_err() {
-                           warn!("Database cleanup timed out after 5 seconds");
-                        } else {
-                           debug!("Database cleanup complete");
-                        }
+                     // close_all_databases releases the DbInstances write lock before
+                     // returning, so the RunEvent::Exit handler fired by _guard can
+                     // try_read() it.
+                     let timeout_result = tokio::time::timeout(
+                        std::time::Duration::from_secs(5),
+                        close_all_databases(
+                           &instances_clone,
+                           &interruptible_txs_clone,
+                           &regular_txs_clone,
+                           &active_subs_clone,
+                        ),
+                     )
+                     .await;
+
+                     if timeout_result.is_err() {
+                        warn!("Database cleanup timed out after 5 seconds");
+                     } else {
+                        debug!("Database cleanup complete");
                      }
                   });
                }
@@ -431,11 +528,80 @@ impl Builder {
    }
 }
 
+/// Abort subscriptions and transactions, then close every loaded database.
+///
+/// Each wrapper's `close()` disables its observer and checkpoints the WAL, so
+/// no `-wal` data is left behind once this returns. Databases are closed in
+/// parallel; failures are logged and do not stop the others from closing.
+async fn close_all_databases(
+   instances: &DbInstances,
+   interruptible_txs: &ActiveInterruptibleTransactions,
+   regular_txs: &ActiveRegularTransactions,
+   active_subs: &subscriptions::ActiveSubscriptions,
+) {
+   debug!("Aborting active subscriptions and transactions");
+   active_subs.abort_all().await;
+   sqlx_sqlite_toolkit::cleanup_all_transactions(interruptible_txs, regular_txs).await;
+
+   let wrappers: Vec<DatabaseWrapper> = {
+      let mut guard = instances.inner.write().await;
+      guard.drain().map(|(_, v)| v).collect()
+   };
+
+   let mut set = tokio::task::JoinSet::new();
+   for wrapper in wrappers {
+      set.spawn(async move { wrapper.close().await });
+   }
+
+   while let Some(result) = set.join_next().await {
+      match result {
+         Ok(Err(e)) => warn!("Error closing database: {:?}", e),
+         Err(e) => warn!("Database close task panicked: {:?}", e),
+         Ok(Ok(())) => {}
+      }
+   }
+}
+
 /// Initializes the plugin with default configuration.
 pub fn init<R: Runtime>() -> tauri::plugin::TauriPlugin<R> {
    Builder::new().build()
 }
 
+/// Open a preloaded database and add it to the loaded databases.
+///
+/// Waits for the database's migrations like `load` does, then connects unless
+/// a `load` call got there first.
+async fn preload_database<R: Runtime>(app: tauri::AppHandle<R>, path: String) {
+   let migration_states = app.state::<MigrationStates>();
+   if let Err(e) = commands::await_migrations(&migration_states, &path).await {
+      warn!("Skipping preload of {}: {}", path, e);
+      return;
+   }
+
+   let db_instances = app.state::<DbInstances>();
+   let mut instances = db_instances.inner.write().await;
+   if instances.contains_key(&path) {
+      return;
+   }
+   if instances.len() >= db_instances.max {
+      warn!(
+         "Skipping preload of {}: {}",
+         path,
+         Error::TooManyDatabases(db_instances.max)
+      );
+      return;
+   }
+
+   match resolve::connect(&path, &app, None).await {
+      Ok(mut wrapper) => {
+         wrapper.set_max_transaction_statements(db_instances.max_transaction_statements);
+         instances.insert(path.clone(), wrapper);
+         debug!("Preloaded database {}", path);
+      }
+      Err(e) => warn!("Failed to preload database {}: {}", path, e),
+   }
+}
+
 /// Run migrations for a single database and emit events.
 ///
 /// This function is spawned as a task for each database with registered migrations.
@@ -455,6 +621,7 @@ async fn run_migrations_for_database<R: Runtime>(
    app: tauri::AppHandle<R>,
    path: String,
    migrator: Arc<Migrator>,
+   statement_progress: bool,
 ) {
    let migration_states = app.state::<MigrationStates>();
 
@@ -507,12 +674,20 @@ async fn run_migrations_for_database<R: Runtime>(
    };
 
    // Run migrations
-   // Note: SQLx's migrator.run() doesn't provide per-migration callbacks,
-   // so we can only report start and finish. For detailed per-migration events,
-   // we would need to iterate migrations manually.
+   // Note: SQLx's migrator.run() doesn't provide per-migration callbacks, so
+   // without statement progress we can only report start and finish.
    trace!("Running migrations for {}", path);
 
-   match db.run_migrations(&migrator).await {
+   let result = if statement_progress {
+      db.run_migrations_with_progress(&migrator, |progress| {
+         emit_migration_progress(&app, &path, progress)
+      })
+      .await
+   } else {
+      db.run_migrations(&migrator).await
+   };
+
+   match result {
       Ok(()) => {
          info!("Migrations completed successfully for {}", path);
 
@@ -566,6 +741,39 @@ fn emit_migration_event<R: Runtime>(
    }
 }
 
+/// Event payload emitted after each statement of a migration, when enabled via
+/// [`Builder::migration_statement_progress`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationProgressEvent {
+   /// Database path (relative, as registered)
+   pub db_path: String,
+   /// Version of the migration being applied
+   pub version: i64,
+   /// Statements of the migration executed so far
+   pub done: usize,
+   /// Statements in the migration
+   pub total: usize,
+}
+
+/// Emit a per-statement migration progress event to the frontend.
+fn emit_migration_progress<R: Runtime>(
+   app: &tauri::AppHandle<R>,
+   db_path: &str,
+   progress: sqlx_sqlite_toolkit::MigrationStatementProgress,
+) {
+   let event = MigrationProgressEvent {
+      db_path: db_path.to_string(),
+      version: progress.version,
+      done: progress.done,
+      total: progress.total,
+   };
+
+   if let Err(e) = app.emit("sqlite:migration-progress", &event) {
+      warn!("Failed to emit migration progress event: {}", e);
+   }
+}
+
 /// Resolve database path for migrations.
 ///
 /// Delegates to `resolve::resolve_database_path` to ensure consistent path validation
@@ -593,6 +801,12 @@ mod tests {
       assert_eq!(builder.max_databases, Some(1));
    }
 
+   #[test]
+   fn test_max_transaction_statements_rejects_zero() {
+      let err = Builder::new().max_transaction_statements(0).unwrap_err();
+      assert!(matches!(err, Error::InvalidConfig(_)));
+   }
+
    #[test]
    fn test_transaction_timeout_rejects_zero() {
       let err = Builder::new()
@@ -601,6 +815,78 @@ mod tests {
       assert!(matches!(err, Error::InvalidConfig(_)));
    }
 
+   #[test]
+   fn test_transaction_total_timeout_rejects_zero() {
+      let err = Builder::new()
+         .transaction_total_timeout(std::time::Duration::ZERO)
+         .unwrap_err();
+      assert!(matches!(err, Error::InvalidConfig(_)));
+   }
+
+   #[test]
+   fn test_preload_keeps_order_without_duplicates() {
+      let builder = Builder::new()
+         .preload("main.db")
+         .preload("cache.db")
+         .preload("main.db");
+      assert_eq!(builder.preload, ["main.db", "cache.db"]);
+   }
+
+   #[test]
+   fn test_setup_preloads_database() {
+      const PATH: &str = "file:preload_test?mode=memory&cache=shared";
+
+      let app = tauri::test::mock_builder()
+         .plugin(Builder::new().preload(PATH).build())
+         .build(tauri::test::mock_context(tauri::test::noop_assets()))
+         .unwrap();
+
+      // Setup spawns the preload on the async runtime instead of waiting for it
+      let instances = app.state::<DbInstances>();
+      let loaded = tauri::async_runtime::block_on(async {
+         for _ in 0..200 {
+            if instances.inner.read().await.contains_key(PATH) {
+               return true;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+         }
+         false
+      });
+      assert!(loaded, "{PATH} was not preloaded");
+   }
+
+   #[tokio::test]
+   async fn test_close_all_databases_checkpoints_wal() {
+      let temp_dir = tempfile::TempDir::new().unwrap();
+      let path = temp_dir.path().join("test.db");
+      let db = DatabaseWrapper::connect(&path, None).await.unwrap();
+      db.execute("CREATE TABLE t (id INTEGER PRIMARY KEY)".into(), vec![])
+         .await
+         .unwrap();
+      db.execute("INSERT INTO t (id) VALUES (1)".into(), vec![])
+         .await
+         .unwrap();
+
+      let instances = DbInstances::default();
+      instances
+         .inner
+         .write()
+         .await
+         .insert("test.db".to_string(), db);
+
+      close_all_databases(
+         &instances,
+         &ActiveInterruptibleTransactions::default(),
+         &ActiveRegularTransactions::default(),
+         &subscriptions::ActiveSubscriptions::default(),
+      )
+      .await;
+
+      assert!(instances.inner.read().await.is_empty());
+      let wal = temp_dir.path().join("test.db-wal");
+      assert!(!wal.exists() || std::fs::metadata(&wal).unwrap().len() == 0);
+   }
+
    #[test]
    fn test_transaction_timeout_accepts_positive() {
       let builder = Builder::new()
@@ -17,7 +17,68 @@ pub async fn connect<R: Runtime>(
    app: &AppHandle<R>,
    custom_config: Option<SqliteDatabaseConfig>,
 ) -> Result<DatabaseWrapper, Error> {
-   let abs_path = resolve_database_path(path, app)?;
+   connect_in_app_config(path, app, custom_config).await
+}
+
+/// Connect to a database in the app config directory, creating the directory if needed.
+///
+/// | Platform | Location |
+/// | -------- | -------- |
+/// | Linux    | `$XDG_CONFIG_HOME/{bundleIdentifier}` or `$HOME/.config/{bundleIdentifier}` |
+/// | macOS    | `$HOME/Library/Application Support/{bundleIdentifier}` |
+/// | Windows  | `{FOLDERID_RoamingAppData}/{bundleIdentifier}` |
+/// | iOS      | `Library/Application Support/{bundleIdentifier}` in the app sandbox |
+/// | Android  | The app's internal files directory |
+///
+/// `name` may include subdirectories (e.g. `"data/app.db"`) but cannot escape the
+/// directory. This is the location used by the `load` command.
+pub async fn connect_in_app_config<R: Runtime>(
+   name: &str,
+   app: &AppHandle<R>,
+   custom_config: Option<SqliteDatabaseConfig>,
+) -> Result<DatabaseWrapper, Error> {
+   let abs_path = resolve_database_path(name, app)?;
+   Ok(DatabaseWrapper::connect(&abs_path, custom_config).await?)
+}
+
+/// Connect to a database in the app data directory, creating the directory if needed.
+///
+/// | Platform | Location |
+/// | -------- | -------- |
+/// | Linux    | `$XDG_DATA_HOME/{bundleIdentifier}` or `$HOME/.local/share/{bundleIdentifier}` |
+/// | macOS    | `$HOME/Library/Application Support/{bundleIdentifier}` |
+/// | Windows  | `{FOLDERID_RoamingAppData}/{bundleIdentifier}` |
+/// | iOS      | `Library/Application Support/{bundleIdentifier}` in the app sandbox |
+/// | Android  | The app's internal files directory |
+///
+/// On desktop Linux this keeps databases out of the config directory, which users
+/// often back up or sync. Path rules are the same as [`connect_in_app_config`].
+pub async fn connect_in_app_data<R: Runtime>(
+   name: &str,
+   app: &AppHandle<R>,
+   custom_config: Option<SqliteDatabaseConfig>,
+) -> Result<DatabaseWrapper, Error> {
+   let app_path = app
+      .path()
+      .app_data_dir()
+      .map_err(|_| Error::InvalidPath("No app data path found".to_string()))?;
+
+   let abs_path = resolve_in_dir(name, &app_path)?;
+   Ok(DatabaseWrapper::connect(&abs_path, custom_config).await?)
+}
+
+/// Connect to a database in the system temporary directory.
+///
+/// Uses [`std::env::temp_dir`] (`$TMPDIR` or `/tmp` on Unix, `%TEMP%` on Windows).
+/// The OS may delete these files at any time, so use this only for scratch
+/// databases. On Android the default temp directory is not writable by apps;
+/// prefer [`connect_in_app_data`] there. Path rules are the same as
+/// [`connect_in_app_config`].
+pub async fn connect_in_temp(
+   name: &str,
+   custom_config: Option<SqliteDatabaseConfig>,
+) -> Result<DatabaseWrapper, Error> {
+   let abs_path = resolve_in_dir(name, &std::env::temp_dir())?;
    Ok(DatabaseWrapper::connect(&abs_path, custom_config).await?)
 }
 
@@ -34,9 +95,14 @@ pub fn resolve_database_path<R: Runtime>(path: &str, app: &AppHandle<R>) -> Resu
       .app_config_dir()
       .map_err(|_| Error::InvalidPath("No app config path found".to_string()))?;
 
-   create_dir_all(&app_path)?;
+   resolve_in_dir(path, &app_path)
+}
+
+/// Create `dir` if needed and resolve `path` inside it.
+fn resolve_in_dir(path: &str, dir: &Path) -> Result<PathBuf, Error> {
+   create_dir_all(dir)?;
 
-   validate_and_resolve(path, &app_path)
+   validate_and_resolve(path, dir)
 }
 
 /// Validate a user-supplied path and resolve it against a base directory.
@@ -113,7 +179,7 @@ fn validate_and_resolve(path: &str, base: &Path) -> Result<PathBuf, Error> {
 ///
 /// Matches the same patterns as `is_memory_database` in `sqlx-sqlite-conn-mgr`:
 /// `:memory:`, `file::memory:*` URIs, and `mode=memory` query parameters.
-fn is_memory_path(path: &str) -> bool {
+pub(crate) fn is_memory_path(path: &str) -> bool {
    path == ":memory:"
       || path.starts_with("file::memory:")
       || (path.starts_with("file:") && path.contains("mode=memory"))
@@ -131,6 +197,19 @@ mod tests {
       dir
    }
 
+   #[test]
+   fn test_resolve_in_dir_creates_directory() {
+      let dir = make_temp_base().join("app_data_not_yet_created");
+      let _ = fs::remove_dir_all(&dir);
+
+      let result = resolve_in_dir("mydb.db", &dir).unwrap();
+      assert_eq!(result, dir.join("mydb.db"));
+      assert!(dir.is_dir());
+
+      let err = resolve_in_dir("../mydb.db", &dir).unwrap_err();
+      assert!(matches!(err, Error::PathTraversal(_)));
+   }
+
    #[test]
    fn test_simple_filename() {
       let base = make_temp_base();
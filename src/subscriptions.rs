@@ -1,104 +1,72 @@
 //! Observer integration for the Tauri plugin.
 //!
 //! This module provides the bridge between the sqlx-sqlite-observer crate and
-//! Tauri's IPC layer, converting observer types to serializable payloads and
-//! managing active subscription state.
+//! Tauri's IPC layer, wrapping observer changes (serialized via the observer's
+//! `serde` feature) in channel payloads and managing active subscription state.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use tauri::ipc::InvokeResponseBody;
 use tokio::sync::RwLock;
 use tracing::debug;
 
-use sqlx_sqlite_observer::{ChangeOperation, ColumnValue, TableChange, TableChangeEvent};
+use sqlx_sqlite_observer::{TableChange, TableChangeEvent};
 
-/// Serializable column value for IPC transport.
-///
-/// Maps observer's `ColumnValue` to a tagged enum that can be sent to the frontend.
+/// Serializable event payload sent to the frontend via Tauri Channel.
 #[derive(Debug, Clone, Serialize)]
-#[serde(tag = "type", content = "value")]
+#[serde(tag = "event", content = "data")]
 #[serde(rename_all = "camelCase")]
-pub enum ColumnValuePayload {
-   Null,
-   Integer(i64),
-   Real(f64),
-   Text(String),
-   Blob(String), // base64-encoded
+pub enum TableChangePayload {
+   Change(TableChange),
+   Lagged { count: u64 },
 }
 
-impl From<&ColumnValue> for ColumnValuePayload {
-   fn from(value: &ColumnValue) -> Self {
-      match value {
-         ColumnValue::Null => ColumnValuePayload::Null,
-         ColumnValue::Integer(i) => ColumnValuePayload::Integer(*i),
-         ColumnValue::Real(r) => ColumnValuePayload::Real(*r),
-         ColumnValue::Text(s) => ColumnValuePayload::Text(s.clone()),
-         ColumnValue::Blob(b) => {
-            use base64::Engine;
-            ColumnValuePayload::Blob(base64::engine::general_purpose::STANDARD.encode(b))
-         }
+/// Wire format for subscription payloads sent over the Tauri Channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PayloadFormat {
+   /// JSON text. Readable in devtools; the default.
+   #[default]
+   Json,
+   /// MessagePack binary with the same structure as the JSON payload. Strings
+   /// and arrays are length-prefixed, so there is no text escaping or number
+   /// formatting; the frontend receives an `ArrayBuffer` and must decode it.
+   Msgpack,
+}
+
+impl PayloadFormat {
+   /// Encode a payload as a Channel message body in this format.
+   pub fn encode(self, payload: &TableChangePayload) -> crate::Result<InvokeResponseBody> {
+      match self {
+         PayloadFormat::Json => serde_json::to_string(payload)
+            .map(InvokeResponseBody::Json)
+            .map_err(|e| crate::Error::Other(e.to_string())),
+         PayloadFormat::Msgpack => rmp_serde::to_vec_named(payload)
+            .map(InvokeResponseBody::Raw)
+            .map_err(|e| crate::Error::Other(e.to_string())),
       }
    }
 }
 
-/// Serializable change data for a single table change.
-#[derive(Debug, Clone, Serialize)]
+/// Subscription options from the frontend.
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct TableChangeData {
-   pub table: String,
-   pub operation: Option<String>,
-   pub rowid: Option<i64>,
-   pub primary_key: Vec<ColumnValuePayload>,
-   #[serde(skip_serializing_if = "Option::is_none")]
-   pub old_values: Option<Vec<ColumnValuePayload>>,
-   #[serde(skip_serializing_if = "Option::is_none")]
-   pub new_values: Option<Vec<ColumnValuePayload>>,
-}
-
-/// Serializable event payload sent to the frontend via Tauri Channel.
-#[derive(Debug, Clone, Serialize)]
-#[serde(tag = "event", content = "data")]
-#[serde(rename_all = "camelCase")]
-pub enum TableChangePayload {
-   Change(TableChangeData),
-   Lagged { count: u64 },
+pub struct SubscribeOptions {
+   /// Payload wire format. Default: JSON.
+   #[serde(default)]
+   pub format: PayloadFormat,
 }
 
 /// Convert an observer `TableChangeEvent` to a serializable payload.
 pub fn event_to_payload(event: TableChangeEvent) -> TableChangePayload {
    match event {
-      TableChangeEvent::Change(change) => TableChangePayload::Change(change_to_data(&change)),
+      TableChangeEvent::Change(change) => TableChangePayload::Change(change),
       TableChangeEvent::Lagged(count) => TableChangePayload::Lagged { count },
    }
 }
 
-/// Convert an observer `TableChange` to serializable data.
-fn change_to_data(change: &TableChange) -> TableChangeData {
-   TableChangeData {
-      table: change.table.clone(),
-      operation: change.operation.map(|op| match op {
-         ChangeOperation::Insert => "insert".to_string(),
-         ChangeOperation::Update => "update".to_string(),
-         ChangeOperation::Delete => "delete".to_string(),
-      }),
-      rowid: change.rowid,
-      primary_key: change
-         .primary_key
-         .iter()
-         .map(ColumnValuePayload::from)
-         .collect(),
-      old_values: change
-         .old_values
-         .as_ref()
-         .map(|vals| vals.iter().map(ColumnValuePayload::from).collect()),
-      new_values: change
-         .new_values
-         .as_ref()
-         .map(|vals| vals.iter().map(ColumnValuePayload::from).collect()),
-   }
-}
-
 /// Observer config params from the frontend.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -107,6 +75,8 @@ pub struct ObserverConfigParams {
    pub channel_capacity: Option<usize>,
    /// Whether to capture column values in change notifications. Default: true.
    pub capture_values: Option<bool>,
+   /// Whether to re-read changed rows to fill in virtual generated columns. Default: false.
+   pub resolve_generated_columns: Option<bool>,
 }
 
 /// Tracks an active subscription's abort handle.
@@ -145,8 +115,9 @@ impl ActiveSubscriptions {
       }
    }
 
-   /// Remove and abort all subscriptions for a specific database.
-   pub async fn remove_for_db(&self, db_path: &str) {
+   /// Remove and abort all subscriptions for a specific database. Returns how
+   /// many were removed.
+   pub async fn remove_for_db(&self, db_path: &str) -> usize {
       let mut subs = self.0.write().await;
       let keys_to_remove: Vec<String> = subs
          .iter()
@@ -154,11 +125,12 @@ impl ActiveSubscriptions {
          .map(|(k, _)| k.clone())
          .collect();
 
-      for key in keys_to_remove {
-         if let Some(sub) = subs.remove(&key) {
+      for key in &keys_to_remove {
+         if let Some(sub) = subs.remove(key) {
             sub.abort_handle.abort();
          }
       }
+      keys_to_remove.len()
    }
 
    /// Count active subscriptions for a specific database.
@@ -176,3 +148,139 @@ impl ActiveSubscriptions {
       }
    }
 }
+
+#[cfg(test)]
+mod tests {
+   use std::time::{Instant, SystemTime};
+
+   use sqlx_sqlite_observer::{ChangeOperation, ColumnValue};
+
+   use super::*;
+
+   fn sample_payload() -> TableChangePayload {
+      TableChangePayload::Change(TableChange {
+         table: "users".into(),
+         schema: "main".into(),
+         operation: Some(ChangeOperation::Update),
+         rowid: Some(7),
+         primary_key: vec![ColumnValue::Integer(7)],
+         old_values: Some(vec![
+            ColumnValue::Integer(7),
+            ColumnValue::Text("Alice".into()),
+            ColumnValue::Null,
+         ]),
+         new_values: Some(vec![
+            ColumnValue::Integer(7),
+            ColumnValue::Real(1.5),
+            ColumnValue::Blob(vec![1, 2]),
+         ]),
+         column_names: vec!["id".into(), "name".into(), "avatar".into()],
+         schema_stale: false,
+         timestamp: Instant::now(),
+         captured_at: SystemTime::now(),
+      })
+   }
+
+   #[test]
+   fn test_msgpack_round_trips_to_json_structure() {
+      let payload = sample_payload();
+
+      let InvokeResponseBody::Raw(bytes) = PayloadFormat::Msgpack.encode(&payload).unwrap() else {
+         panic!("msgpack must encode as a raw body");
+      };
+      let decoded: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+
+      assert_eq!(decoded, serde_json::to_value(&payload).unwrap());
+   }
+
+   #[tokio::test]
+   async fn test_remove_for_db_aborts_only_that_database() {
+      let subs = ActiveSubscriptions::default();
+      let mut handles = Vec::new();
+      for (id, db) in [
+         ("a1", "a.db"),
+         ("a2", "a.db"),
+         ("a3", "a.db"),
+         ("b1", "b.db"),
+      ] {
+         let handle = tokio::spawn(std::future::pending::<()>());
+         subs
+            .insert(id.into(), db.into(), handle.abort_handle())
+            .await;
+         handles.push(handle);
+      }
+
+      assert_eq!(subs.remove_for_db("a.db").await, 3);
+      assert_eq!(subs.count_for_db("a.db").await, 0);
+      assert_eq!(subs.count_for_db("b.db").await, 1);
+
+      let b = handles.pop().unwrap();
+      for handle in handles {
+         assert!(handle.await.unwrap_err().is_cancelled());
+      }
+      assert!(!b.is_finished());
+      assert_eq!(subs.remove_for_db("a.db").await, 0);
+      subs.abort_all().await;
+   }
+
+   #[tokio::test]
+   async fn test_execute_produces_payload_for_subscribed_table() {
+      use futures::StreamExt;
+      use sqlx_sqlite_observer::ObserverConfig;
+      use sqlx_sqlite_toolkit::DatabaseWrapper;
+
+      let temp_dir = tempfile::TempDir::new().unwrap();
+      let mut db = DatabaseWrapper::connect(&temp_dir.path().join("test.db"), None)
+         .await
+         .unwrap();
+      for sql in [
+         "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)",
+         "CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT)",
+      ] {
+         db.execute(sql.into(), vec![]).await.unwrap();
+      }
+
+      // Same path as the `observe` and `subscribe` commands
+      db.enable_observation(ObserverConfig::new().with_tables(["users", "posts"]));
+      let mut stream = db.observable().unwrap().subscribe_stream(["users"]);
+
+      db.execute(
+         "INSERT INTO posts (title) VALUES ('ignored')".into(),
+         vec![],
+      )
+      .await
+      .unwrap();
+      db.execute("INSERT INTO users (name) VALUES ('Alice')".into(), vec![])
+         .await
+         .unwrap();
+
+      let event = tokio::time::timeout(std::time::Duration::from_secs(1), stream.next())
+         .await
+         .unwrap()
+         .unwrap();
+      let InvokeResponseBody::Json(body) = PayloadFormat::Json
+         .encode(&event_to_payload(event))
+         .unwrap()
+      else {
+         panic!("json must encode as a json body");
+      };
+      let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+      assert_eq!(payload["event"], "change");
+      assert_eq!(payload["data"]["table"], "users");
+      assert_eq!(payload["data"]["operation"], "insert");
+      assert_eq!(
+         payload["data"]["newValues"][1],
+         serde_json::json!({ "type": "text", "value": "Alice" })
+      );
+   }
+
+   #[test]
+   fn test_json_is_default_format() {
+      let options: SubscribeOptions = serde_json::from_str("{}").unwrap();
+      assert_eq!(options.format, PayloadFormat::Json);
+
+      let body = options.format.encode(&sample_payload()).unwrap();
+      assert!(matches!(body, InvokeResponseBody::Json(_)));
+   }
+}